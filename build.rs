@@ -1,3 +1,9 @@
+use std::fs;
+use std::path::Path;
+
+#[path = "clippy_dev/src/lint_docs.rs"]
+mod lint_docs;
+
 fn main() {
     // Forward the profile to the main compilation
     println!("cargo:rustc-env=PROFILE={}", std::env::var("PROFILE").unwrap());
@@ -16,4 +22,28 @@ fn main() {
         "cargo:rustc-env=RUSTC_RELEASE_CHANNEL={}",
         rustc_tools_util::get_channel().unwrap_or_default()
     );
+    embed_lint_docs();
+}
+
+/// Writes the documentation of all lints to `$OUT_DIR/lint_docs.rs`, so that `cargo clippy
+/// --explain` works without access to the lint list website. The docs are empty if the sources
+/// of `clippy_lints` are not next to this package.
+fn embed_lint_docs() {
+    let lints_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("clippy_lints/src");
+    let files = lint_docs::rust_files(&lints_dir);
+    let lints = files
+        .iter()
+        .filter_map(|file| fs::read_to_string(file).ok())
+        .flat_map(|content| lint_docs::parse_lint_decls(&content))
+        .collect::<Vec<_>>();
+    let configs = fs::read_to_string(lints_dir.join("utils/conf.rs"))
+        .map(|content| lint_docs::parse_config(&content))
+        .unwrap_or_default();
+    let out_file = Path::new(&std::env::var("OUT_DIR").unwrap()).join("lint_docs.rs");
+    fs::write(out_file, lint_docs::gen_lint_docs(&lints, &configs)).unwrap();
+
+    println!("cargo:rerun-if-changed=clippy_dev/src/lint_docs.rs");
+    for file in files {
+        println!("cargo:rerun-if-changed={}", file.display());
+    }
 }
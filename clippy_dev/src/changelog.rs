@@ -0,0 +1,194 @@
+//! `cargo dev changelog`: generates the "New lints" and "Moves and Deprecations" sections of the
+//! changelog by comparing the lints declared at two git revisions.
+
+use crate::metadata::parse_renames;
+use crate::{clippy_project_root, module_name, parse_contents, Lint};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// The lints and renames of a single revision.
+#[derive(Default)]
+struct Registry {
+    lints: HashMap<String, Lint>,
+    /// The renamed lints as `(old name, new name)`
+    renames: Vec<(String, String)>,
+}
+
+/// Prints the changelog sections for the changes between the revisions `from` and `to`.
+pub fn run(from: &str, to: &str) {
+    print!("{}", changelog(&registry_at(from), &registry_at(to)));
+}
+
+fn git(args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(clippy_project_root())
+        .output()
+        .unwrap_or_else(|e| panic!("Cannot run git: {}", e));
+    if !output.status.success() {
+        eprintln!("error: `git {}` failed", args.join(" "));
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        std::process::exit(1);
+    }
+    String::from_utf8(output.stdout).expect("git output is not valid UTF-8")
+}
+
+/// Gathers the lints declared in `clippy_lints/src` at the revision `rev`.
+fn registry_at(rev: &str) -> Registry {
+    let mut registry = Registry::default();
+    for file in git(&["ls-tree", "-r", "--name-only", rev, "clippy_lints/src"]).lines() {
+        if !file.ends_with(".rs") {
+            continue;
+        }
+        let content = git(&["show", &format!("{}:{}", rev, file)]);
+        let module = module_name(
+            Path::new(file)
+                .strip_prefix("clippy_lints/src")
+                .expect("`git ls-tree` only lists files in `clippy_lints/src`"),
+        );
+        if file == "clippy_lints/src/lib.rs" || file == "clippy_lints/src/renamed_lints.rs" {
+            registry.renames.extend(parse_renames(&content));
+        }
+        registry.lints.extend(
+            parse_contents(&content, &module)
+                .filter(|(lint, _)| !lint.group.starts_with("internal"))
+                .map(|(lint, _)| (lint.name.clone(), lint)),
+        );
+    }
+    registry
+}
+
+/// Lint groups ordered by their default level, from `allow` to `deny`.
+fn level_rank(group: &str) -> u8 {
+    match group {
+        "correctness" => 2,
+        "style" | "complexity" | "perf" => 1,
+        _ => 0,
+    }
+}
+
+fn changelog(old: &Registry, new: &Registry) -> String {
+    let renames = new
+        .renames
+        .iter()
+        .filter(|rename| !old.renames.contains(rename))
+        .collect::<Vec<_>>();
+
+    let mut new_lints = new
+        .lints
+        .values()
+        .filter(|lint| {
+            lint.deprecation.is_none()
+                && !old.lints.contains_key(&lint.name)
+                && !renames.iter().any(|(_, new_name)| *new_name == lint.name)
+        })
+        .map(|lint| &lint.name)
+        .collect::<Vec<_>>();
+    new_lints.sort();
+    let new_lints = new_lints
+        .into_iter()
+        .map(|name| format!("* [`{}`]", name))
+        .collect::<Vec<_>>();
+
+    let mut deprecations = Vec::new();
+    let mut moves = Vec::new();
+    for lint in new.lints.values() {
+        let old_lint = match old.lints.get(&lint.name) {
+            Some(old_lint) => old_lint,
+            None if lint.deprecation.is_some() => {
+                deprecations.push(format!("* Deprecate [`{}`] lint", lint.name));
+                continue;
+            },
+            None => continue,
+        };
+        if lint.deprecation.is_some() {
+            if old_lint.deprecation.is_none() {
+                deprecations.push(format!("* Deprecate [`{}`] lint", lint.name));
+            }
+        } else if lint.group != old_lint.group {
+            let verb = if lint.group == "nursery" {
+                "Move"
+            } else if level_rank(&lint.group) < level_rank(&old_lint.group) {
+                "Downgrade"
+            } else if level_rank(&lint.group) > level_rank(&old_lint.group) {
+                "Upgrade"
+            } else {
+                "Move"
+            };
+            moves.push(format!("* {} [`{}`] to {}", verb, lint.name, lint.group));
+        }
+    }
+    deprecations.sort();
+    moves.sort();
+    let renames = renames
+        .iter()
+        .map(|(old_name, new_name)| format!("* Rename `{}` to [`{}`]", old_name, new_name));
+
+    let moves_and_deprecations = deprecations.into_iter().chain(moves).chain(renames).collect::<Vec<_>>();
+
+    let mut out = String::new();
+    for (title, entries) in &[
+        ("New lints", new_lints),
+        ("Moves and Deprecations", moves_and_deprecations),
+    ] {
+        if !entries.is_empty() {
+            out.push_str(&format!("### {}\n\n{}\n\n", title, entries.join("\n")));
+        }
+    }
+    out
+}
+
+#[test]
+fn test_changelog() {
+    fn registry(lints: &[(&str, &str, bool)], renames: &[(&str, &str)]) -> Registry {
+        Registry {
+            lints: lints
+                .iter()
+                .map(|&(name, group, deprecated)| {
+                    let deprecation = if deprecated { Some("reason") } else { None };
+                    (name.to_string(), Lint::new(name, group, "desc", deprecation, "module"))
+                })
+                .collect(),
+            renames: renames
+                .iter()
+                .map(|&(old, new)| (old.to_string(), new.to_string()))
+                .collect(),
+        }
+    }
+
+    let old = registry(
+        &[
+            ("unchanged", "style", false),
+            ("to_pedantic", "style", false),
+            ("to_correctness", "style", false),
+            ("to_nursery", "perf", false),
+            ("to_deprecated", "style", false),
+            ("old_name", "style", false),
+        ],
+        &[("older_name", "unchanged")],
+    );
+    let new = registry(
+        &[
+            ("unchanged", "style", false),
+            ("to_pedantic", "pedantic", false),
+            ("to_correctness", "correctness", false),
+            ("to_nursery", "nursery", false),
+            ("to_deprecated", "Deprecated", true),
+            ("new_name", "style", false),
+            ("a_new_lint", "complexity", false),
+            ("a_new", "complexity", false),
+        ],
+        &[("older_name", "unchanged"), ("old_name", "new_name")],
+    );
+    let expected = "### New lints\n\n\
+                    * [`a_new`]\n\
+                    * [`a_new_lint`]\n\n\
+                    ### Moves and Deprecations\n\n\
+                    * Deprecate [`to_deprecated`] lint\n\
+                    * Downgrade [`to_pedantic`] to pedantic\n\
+                    * Move [`to_nursery`] to nursery\n\
+                    * Upgrade [`to_correctness`] to correctness\n\
+                    * Rename `old_name` to [`new_name`]\n\n";
+    assert_eq!(expected, changelog(&old, &new));
+}
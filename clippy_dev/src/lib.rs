@@ -9,31 +9,17 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+pub mod changelog;
 pub mod fmt;
+pub mod lint_docs;
+pub mod metadata;
 pub mod new_lint;
 pub mod stderr_length_check;
 pub mod update_lints;
 
+pub use lint_docs::ConfigKey;
+
 lazy_static! {
-    static ref DEC_CLIPPY_LINT_RE: Regex = Regex::new(
-        r#"(?x)
-        declare_clippy_lint!\s*[\{(]
-        (?:\s+///.*)*
-        \s+pub\s+(?P<name>[A-Z_][A-Z_0-9]*)\s*,\s*
-        (?P<cat>[a-z_]+)\s*,\s*
-        "(?P<desc>(?:[^"\\]+|\\(?s).(?-s))*)"\s*[})]
-    "#
-    )
-    .unwrap();
-    static ref DEC_DEPRECATED_LINT_RE: Regex = Regex::new(
-        r#"(?x)
-        declare_deprecated_lint!\s*[{(]\s*
-        (?:\s+///.*)*
-        \s+pub\s+(?P<name>[A-Z_][A-Z_0-9]*)\s*,\s*
-        "(?P<desc>(?:[^"\\]+|\\(?s).(?-s))*)"\s*[})]
-    "#
-    )
-    .unwrap();
     static ref NL_ESCAPE_RE: Regex = Regex::new(r#"\\\n\s*"#).unwrap();
 }
 
@@ -152,47 +138,83 @@ pub fn gen_register_lint_list<'a>(lints: impl Iterator<Item = &'a Lint>) -> Vec<
     inner
 }
 
+/// The parts of a lint declaration that are only needed for the documentation of the lint.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LintDocs {
+    /// The doc comment, without the `///`
+    pub text: String,
+    /// The value of the `#[clippy::version]` attribute: the version the lint was added in, or
+    /// for deprecated lints, the version it was deprecated in
+    pub version: Option<String>,
+}
+
 /// Gathers all files in `src/clippy_lints` and gathers all lints inside
 pub fn gather_all() -> impl Iterator<Item = Lint> {
+    gather_all_with_docs().map(|(lint, _)| lint)
+}
+
+/// Like `gather_all`, but also returns the documentation of each lint
+pub fn gather_all_with_docs() -> impl Iterator<Item = (Lint, LintDocs)> {
     lint_files().flat_map(|f| gather_from_file(&f))
 }
 
-fn gather_from_file(dir_entry: &walkdir::DirEntry) -> impl Iterator<Item = Lint> {
+fn gather_from_file(dir_entry: &walkdir::DirEntry) -> impl Iterator<Item = (Lint, LintDocs)> {
     let content = fs::read_to_string(dir_entry.path()).unwrap();
-    let path = dir_entry.path();
-    let filename = path.file_stem().unwrap();
-    let path_buf = path.with_file_name(filename);
-    let mut rel_path = path_buf
+    let rel_path = dir_entry
+        .path()
         .strip_prefix(clippy_project_root().join("clippy_lints/src"))
         .expect("only files in `clippy_lints/src` should be looked at");
+
+    parse_contents(&content, &module_name(rel_path))
+}
+
+/// Returns the name of the module of a file, given its path relative to `clippy_lints/src`.
+fn module_name(rel_path: &Path) -> String {
+    let filename = rel_path.file_stem().unwrap();
+    let path_buf = rel_path.with_file_name(filename);
+    let mut rel_path = path_buf.as_path();
     // If the lints are stored in mod.rs, we get the module name from
     // the containing directory:
     if filename == "mod" {
         rel_path = rel_path.parent().unwrap();
     }
 
-    let module = rel_path
+    rel_path
         .components()
         .map(|c| c.as_os_str().to_str().unwrap())
         .collect::<Vec<_>>()
-        .join("::");
+        .join("::")
+}
 
-    parse_contents(&content, &module)
+fn parse_contents(content: &str, module: &str) -> impl Iterator<Item = (Lint, LintDocs)> {
+    let module = module.to_string();
+    lint_docs::parse_lint_decls(content).into_iter().map(move |decl| {
+        (
+            Lint::new(
+                &decl.name,
+                &decl.group,
+                &decl.desc,
+                decl.deprecation.as_deref(),
+                &module,
+            ),
+            LintDocs {
+                text: decl.docs,
+                version: decl.version,
+            },
+        )
+    })
 }
 
-fn parse_contents(content: &str, module: &str) -> impl Iterator<Item = Lint> {
-    let lints = DEC_CLIPPY_LINT_RE
-        .captures_iter(content)
-        .map(|m| Lint::new(&m["name"], &m["cat"], &m["desc"], None, module));
-    let deprecated = DEC_DEPRECATED_LINT_RE
-        .captures_iter(content)
-        .map(|m| Lint::new(&m["name"], "Deprecated", &m["desc"], Some(&m["desc"]), module));
-    // Removing the `.collect::<Vec<Lint>>().into_iter()` causes some lifetime issues due to the map
-    lints.chain(deprecated).collect::<Vec<Lint>>().into_iter()
+/// Gathers the configuration keys of all lints
+#[must_use]
+pub fn gather_config() -> Vec<ConfigKey> {
+    let path = clippy_project_root().join("clippy_lints/src/utils/conf.rs");
+    let content = fs::read_to_string(&path).unwrap_or_else(|e| panic!("Cannot read from {}: {}", path.display(), e));
+    lint_docs::parse_config(&content)
 }
 
 /// Collects all .rs files in the `clippy_lints/src` directory
-fn lint_files() -> impl Iterator<Item = walkdir::DirEntry> {
+pub fn lint_files() -> impl Iterator<Item = walkdir::DirEntry> {
     // We use `WalkDir` instead of `fs::read_dir` here in order to recurse into subdirectories.
     // Otherwise we would not collect all the lints, for example in `clippy_lints/src/methods/`.
     let path = clippy_project_root().join("clippy_lints/src");
@@ -351,6 +373,7 @@ declare_deprecated_lint! {
     "#,
         "module_name",
     )
+    .map(|(lint, _)| lint)
     .collect();
 
     let expected = vec![
@@ -367,6 +390,93 @@ declare_deprecated_lint! {
     assert_eq!(expected, result);
 }
 
+#[test]
+fn test_parse_docs() {
+    let result: Vec<LintDocs> = parse_contents(
+        r#"
+declare_clippy_lint! {
+    /// **What it does:** Checks for things.
+    ///
+    /// **Example:**
+    /// ```rust
+    ///# let x = 1;
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub SOME_LINT,
+    style,
+    "some lint"
+}
+
+declare_deprecated_lint! {
+    /// **What it does:** Nothing.
+    #[clippy::version = "pre 1.29.0"]
+    pub OLD_LINT,
+    "not needed anymore"
+}
+    "#,
+        "module_name",
+    )
+    .map(|(_, docs)| docs)
+    .collect();
+
+    let expected = vec![
+        LintDocs {
+            text: "**What it does:** Checks for things.\n\n**Example:**\n```rust\n# let x = 1;\n```".to_string(),
+            version: Some("1.45.0".to_string()),
+        },
+        LintDocs {
+            text: "**What it does:** Nothing.".to_string(),
+            version: Some("pre 1.29.0".to_string()),
+        },
+    ];
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn test_parse_config() {
+    let result = lint_docs::parse_config(
+        r#"
+define_Conf! {
+    /// Lint: SOME_LINT, OTHER_LINT. The names to lint about
+    (names, "names": Vec<String>, [
+        "foo", "bar",
+    ].iter().map(ToString::to_string).collect()),
+    /// DEPRECATED LINT: OLD_LINT. Use `SOME_LINT` instead.
+    (old_threshold, "old_threshold": Option<u64>, None),
+    /// Lint: OTHER_LINT. The maximum size
+    (size_threshold, "size_threshold": u64, 200),
+    /// Lint: OTHER_LINT. The weights
+    (weights, "weights": crate::utils::conf::Weights, crate::utils::conf::Weights::default()),
+}
+    "#,
+    );
+
+    let expected = vec![
+        ConfigKey {
+            name: "names".to_string(),
+            ty: "Vec<String>".to_string(),
+            default: r#"["foo", "bar"]"#.to_string(),
+            doc: "The names to lint about".to_string(),
+            lints: vec!["some_lint".to_string(), "other_lint".to_string()],
+        },
+        ConfigKey {
+            name: "size-threshold".to_string(),
+            ty: "u64".to_string(),
+            default: "200".to_string(),
+            doc: "The maximum size".to_string(),
+            lints: vec!["other_lint".to_string()],
+        },
+        ConfigKey {
+            name: "weights".to_string(),
+            ty: "Weights".to_string(),
+            default: "{}".to_string(),
+            doc: "The weights".to_string(),
+            lints: vec!["other_lint".to_string()],
+        },
+    ];
+    assert_eq!(expected, result);
+}
+
 #[test]
 fn test_replace_region() {
     let text = "\nabc\n123\n789\ndef\nghi";
@@ -0,0 +1,286 @@
+//! Parses the lint declarations in `clippy_lints/src` and the configuration keys in
+//! `define_Conf!`, including their documentation.
+//!
+//! This module only depends on `std`: the build script of `cargo-clippy` includes it with
+//! `#[path]` to embed the lint docs for `cargo clippy --explain`.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A lint declared with `declare_clippy_lint!` or `declare_deprecated_lint!`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LintDecl {
+    /// The name in lowercase
+    pub name: String,
+    /// The group, `Deprecated` for deprecated lints
+    pub group: String,
+    pub desc: String,
+    pub deprecation: Option<String>,
+    /// The doc comment, without the `///`
+    pub docs: String,
+    /// The value of the `#[clippy::version]` attribute: the version the lint was added in, or
+    /// for deprecated lints, the version it was deprecated in
+    pub version: Option<String>,
+}
+
+/// A configuration key of `clippy.toml`, parsed from `clippy_lints/src/utils/conf.rs`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConfigKey {
+    /// The name as written in `clippy.toml`, e.g. `too-many-lines-threshold`
+    pub name: String,
+    pub ty: String,
+    /// The default value in `clippy.toml` syntax
+    pub default: String,
+    pub doc: String,
+    /// The names of the lints that use this key
+    pub lints: Vec<String>,
+}
+
+/// The path of the configuration types that are tables in `clippy.toml`
+const CONF_MOD_PATH: &str = "crate::utils::conf::";
+
+/// Parses the lint declarations in the source of a module. Declarations that don't have the
+/// expected shape are skipped.
+#[must_use]
+pub fn parse_lint_decls(content: &str) -> Vec<LintDecl> {
+    let mut lints = Vec::new();
+    for &(mac, deprecated) in &[("declare_clippy_lint!", false), ("declare_deprecated_lint!", true)] {
+        let mut rest = content;
+        while let Some(pos) = rest.find(mac) {
+            rest = &rest[pos + mac.len()..];
+            // the docs may contain examples of declarations
+            if let Some((lint, after)) = parse_lint_decl(rest, deprecated) {
+                lints.push(lint);
+                rest = after;
+            }
+        }
+    }
+    lints
+}
+
+/// Parses the body of a lint declaration macro, starting at its opening brace, and returns the
+/// input after it.
+fn parse_lint_decl(input: &str, deprecated: bool) -> Option<(LintDecl, &str)> {
+    let mut rest = input.trim_start();
+    if !rest.starts_with('{') && !rest.starts_with('(') {
+        return None;
+    }
+    rest = &rest[1..];
+
+    let mut docs = Vec::new();
+    while let Some(line) = rest.trim_start().strip_prefix("///") {
+        let end = line.find('\n').unwrap_or(line.len());
+        docs.push(line[..end].strip_prefix(' ').unwrap_or(&line[..end]));
+        rest = &line[end..];
+    }
+    let mut version = None;
+    if let Some(attr) = rest.trim_start().strip_prefix("#[clippy::version") {
+        let attr = attr.trim_start().strip_prefix('=')?.trim_start().strip_prefix('"')?;
+        let end = attr.find('"')?;
+        version = Some(attr[..end].to_string());
+        rest = attr[end + 1..].strip_prefix(']')?;
+    }
+
+    rest = rest.trim_start().strip_prefix("pub")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (name, after) = ident(rest.trim_start())?;
+    rest = after.trim_start().strip_prefix(',')?;
+    let group = if deprecated {
+        "Deprecated".to_string()
+    } else {
+        let (group, after) = ident(rest.trim_start())?;
+        rest = after.trim_start().strip_prefix(',')?;
+        group.to_string()
+    };
+    let (desc, after) = string_lit(rest.trim_start())?;
+    let after = after.trim_start();
+    if !after.starts_with('}') && !after.starts_with(')') {
+        return None;
+    }
+
+    let lint = LintDecl {
+        name: name.to_lowercase(),
+        group,
+        deprecation: if deprecated { Some(desc.clone()) } else { None },
+        desc,
+        docs: docs.join("\n"),
+        version,
+    };
+    Some((lint, &after[1..]))
+}
+
+/// Splits an identifier off the start of `input`.
+fn ident(input: &str) -> Option<(&str, &str)> {
+    let end = input
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(input.len());
+    if end == 0 {
+        None
+    } else {
+        Some((&input[..end], &input[end..]))
+    }
+}
+
+/// Splits a string literal off the start of `input` and returns its value. Escaped quotes are
+/// unescaped and escaped line breaks are removed along with the indentation that follows them,
+/// other escapes are kept as written.
+fn string_lit(input: &str) -> Option<(String, &str)> {
+    let mut rest = input.strip_prefix('"')?;
+    let mut value = String::new();
+    loop {
+        let end = rest.find(|c| c == '"' || c == '\\')?;
+        value.push_str(&rest[..end]);
+        if rest[end..].starts_with('"') {
+            return Some((value, &rest[end + 1..]));
+        }
+        let escaped = rest[end + 1..].chars().next()?;
+        rest = &rest[end + 1 + escaped.len_utf8()..];
+        match escaped {
+            '"' => value.push('"'),
+            '\n' => rest = rest.trim_start(),
+            c => {
+                value.push('\\');
+                value.push(c);
+            },
+        }
+    }
+}
+
+/// Parses the configuration keys in `define_Conf!`. Only the keys whose doc comment starts with
+/// `Lint: LINT_NAME, OTHER_LINT.` are returned.
+#[must_use]
+pub fn parse_config(content: &str) -> Vec<ConfigKey> {
+    let mut keys = Vec::new();
+    let mut rest = content;
+    while !rest.is_empty() {
+        let (line, next) = rest.split_at(rest.find('\n').map_or(rest.len(), |i| i + 1));
+        rest = next;
+        keys.extend(parse_config_key(line, rest));
+    }
+    keys
+}
+
+/// Parses a configuration key from its doc comment `line` and the tuple that follows it, like
+///
+/// ```text
+/// /// Lint: TOO_MANY_LINES. The maximum number of lines a function or method can have
+/// (too_many_lines_threshold, "too_many_lines_threshold": u64, 100),
+/// ```
+fn parse_config_key(line: &str, rest: &str) -> Option<ConfigKey> {
+    let doc = line.trim().strip_prefix("///")?;
+    if !doc.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let doc = doc.trim_start().strip_prefix("Lint:")?;
+    let (lints, doc) = doc.split_at(doc.find('.')?);
+    let doc = &doc[1..];
+    let is_lint_list =
+        |c: char| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == ',' || c.is_whitespace();
+    if !lints.starts_with(char::is_whitespace)
+        || !lints.chars().all(is_lint_list)
+        || !doc.starts_with(char::is_whitespace)
+    {
+        return None;
+    }
+
+    let tuple = ident(rest.trim_start().strip_prefix('(')?)?.1;
+    let tuple = tuple.strip_prefix(',')?.trim_start().strip_prefix('"')?;
+    let (name, tuple) = ident(tuple)?;
+    let tuple = tuple.strip_prefix("\":")?.trim_start();
+    let (ty, tuple) = tuple.split_at(tuple.find(',')?);
+    let tuple = tuple[1..].trim_start();
+    // the default ends with the first `),` at the end of a line
+    let mut end = 0;
+    let default = loop {
+        end += tuple[end..].find("),")?;
+        if tuple[end + 2..]
+            .lines()
+            .next()
+            .map_or(true, |line| line.trim().is_empty())
+        {
+            break &tuple[..end];
+        }
+        end += 2;
+    };
+
+    Some(ConfigKey {
+        name: name.replace('_', "-"),
+        ty: ty.trim().trim_start_matches(CONF_MOD_PATH).to_string(),
+        default: config_default(default),
+        doc: doc.trim().to_string(),
+        lints: lints.split(',').map(|lint| lint.trim().to_lowercase()).collect(),
+    })
+}
+
+/// Turns the Rust expression of a default value into the value in `clippy.toml`.
+fn config_default(default: &str) -> String {
+    if default.trim().starts_with(CONF_MOD_PATH) {
+        // The default of every key of the table
+        return "{}".to_string();
+    }
+    let default = default
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("[ ", "[")
+        .replace(", ]", "]");
+    match default.trim_end_matches(".iter().map(ToString::to_string).collect()") {
+        "Vec::new()" => "[]".to_string(),
+        default => default.to_string(),
+    }
+}
+
+/// Generates the lint documentation that the build script of `cargo-clippy` embeds for
+/// `cargo clippy --explain` and `cargo clippy --list-lints`, an expression of type
+/// `&[LintDoc]`.
+#[must_use]
+pub fn gen_lint_docs(lints: &[LintDecl], configs: &[ConfigKey]) -> String {
+    let mut lints = lints
+        .iter()
+        .filter(|lint| !lint.group.starts_with("internal"))
+        .collect::<Vec<_>>();
+    lints.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut out = String::from("&[\n");
+    for lint in lints {
+        out.push_str("    LintDoc {\n");
+        let _ = writeln!(out, "        name: {:?},", lint.name);
+        let _ = writeln!(out, "        group: {:?},", lint.group);
+        let _ = writeln!(out, "        desc: {:?},", lint.desc);
+        let _ = writeln!(out, "        deprecation: {:?},", lint.deprecation);
+        let _ = writeln!(out, "        docs: {:?},", lint.docs);
+        let _ = writeln!(out, "        version: {:?},", lint.version);
+        out.push_str("        config: &[\n");
+        for conf in configs.iter().filter(|conf| conf.lints.contains(&lint.name)) {
+            let _ = writeln!(
+                out,
+                "            ConfigKey {{ name: {:?}, ty: {:?}, default: {:?}, doc: {:?} }},",
+                conf.name, conf.ty, conf.default, conf.doc
+            );
+        }
+        out.push_str("        ],\n");
+        out.push_str("    },\n");
+    }
+    out.push(']');
+    out
+}
+
+/// Collects the `.rs` files in `dir` and its subdirectories, in a stable order.
+#[must_use]
+pub fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            if path.is_dir() {
+                files.extend(rust_files(&path));
+            } else if path.extension().map_or(false, |ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
@@ -1,7 +1,8 @@
 #![cfg_attr(feature = "deny-warnings", deny(warnings))]
 
 use clap::{App, Arg, SubCommand};
-use clippy_dev::{fmt, new_lint, stderr_length_check, update_lints};
+use clippy_dev::{changelog, fmt, metadata, new_lint, stderr_length_check, update_lints};
+use std::path::Path;
 
 fn main() {
     let matches = App::new("Clippy developer tooling")
@@ -52,7 +53,7 @@ fn main() {
                         .help("Specify whether the lint runs during the early or late pass")
                         .takes_value(true)
                         .possible_values(&["early", "late"])
-                        .required(true),
+                        .required_unless("module"),
                 )
                 .arg(
                     Arg::with_name("name")
@@ -81,6 +82,62 @@ fn main() {
                             "internal_warn",
                         ])
                         .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("module")
+                        .short("m")
+                        .long("module")
+                        .help("Add the lint to an existing module and its lint pass, ex: methods")
+                        .takes_value(true)
+                        .conflicts_with("pass"),
+                )
+                .arg(
+                    Arg::with_name("config")
+                        .long("config")
+                        .help(
+                            "Add a configuration option as `key:type=default;description`, \
+                             ex: 'max-depth:u64=5;The maximum nesting depth', \
+                             or `key` for an existing option. Can be given several times",
+                        )
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                )
+                .arg(
+                    Arg::with_name("fix")
+                        .long("fix")
+                        .help("Create a `.fixed` test file for a lint with suggestions"),
+                )
+                .arg(
+                    Arg::with_name("msrv")
+                        .long("msrv")
+                        .help("Make the lint respect the `msrv` configuration"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Writes the metadata of all lints to a JSON file for the lint list website")
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .help("The file to write to")
+                        .takes_value(true)
+                        .default_value("util/gh-pages/lints.json"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("changelog")
+                .about("Prints the changelog sections for the lints added, moved or deprecated between two revisions")
+                .arg(
+                    Arg::with_name("from")
+                        .help("The revision of the previous release")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("to")
+                        .help("The revision of the new release")
+                        .default_value("HEAD"),
                 ),
         )
         .subcommand(
@@ -103,15 +160,28 @@ fn main() {
             }
         },
         ("new_lint", Some(matches)) => {
-            match new_lint::create(
-                matches.value_of("pass"),
-                matches.value_of("name"),
-                matches.value_of("category"),
-            ) {
+            let lint = new_lint::LintData {
+                pass: matches.value_of("pass"),
+                name: matches.value_of("name").expect("`name` argument is validated by clap"),
+                category: matches
+                    .value_of("category")
+                    .expect("`category` argument is validated by clap"),
+                module: matches.value_of("module"),
+                config: matches.values_of("config").map_or_else(Vec::new, Iterator::collect),
+                fix: matches.is_present("fix"),
+                msrv: matches.is_present("msrv"),
+            };
+            match new_lint::create(&lint) {
                 Ok(_) => update_lints::run(update_lints::UpdateMode::Change),
                 Err(e) => eprintln!("Unable to create lint: {}", e),
             }
         },
+        ("export", Some(matches)) => {
+            metadata::export(Path::new(matches.value_of("output").unwrap()));
+        },
+        ("changelog", Some(matches)) => {
+            changelog::run(matches.value_of("from").unwrap(), matches.value_of("to").unwrap());
+        },
         ("limit_stderr_length", _) => {
             stderr_length_check::check();
         },
@@ -0,0 +1,214 @@
+//! Collects the metadata of all lints into `lints.json`, which is used by the lint list website.
+
+use crate::{clippy_project_root, gather_all_with_docs, gather_config, ConfigKey, Lint};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Serialize, Serializer};
+use std::fs;
+use std::path::Path;
+
+lazy_static! {
+    static ref DOC_SECTION_RE: Regex = Regex::new(r#"^\*\*([\w\s]+?)[:?.!]?\*\*(.*)"#).unwrap();
+    static ref RENAMED_RE: Regex =
+        Regex::new(r#"\(\s*"clippy::(?P<old>\w+)",\s*"clippy::(?P<new>\w+)",?\s*\)"#).unwrap();
+}
+
+#[derive(Debug, Serialize)]
+pub struct LintMetadata {
+    pub id: String,
+    pub group: String,
+    pub level: &'static str,
+    /// The sections of the documentation, like "What it does", in the order they are written
+    #[serde(serialize_with = "serialize_sections")]
+    pub docs: Vec<(String, String)>,
+    #[serde(serialize_with = "serialize_config")]
+    pub config: Vec<ConfigKey>,
+    pub deprecation: Option<String>,
+    /// The former names of the lint
+    pub renamed_from: Vec<String>,
+    /// The version the lint was added in, or for deprecated lints, the version it was deprecated in
+    pub version: Option<String>,
+}
+
+/// Collects the metadata of all lints, except for the internal ones.
+#[must_use]
+pub fn collect() -> Vec<LintMetadata> {
+    let root = clippy_project_root();
+    let configs = gather_config();
+    let renames = parse_renames(&read(&root.join("clippy_lints/src/renamed_lints.rs")));
+
+    let mut lints = gather_all_with_docs()
+        .filter_map(|(lint, docs)| {
+            let level = default_level(&lint)?;
+            let config = configs
+                .iter()
+                .filter(|conf| conf.lints.contains(&lint.name))
+                .cloned()
+                .collect::<Vec<_>>();
+            let mut sections = doc_sections(&docs.text);
+            if !config.is_empty() {
+                sections.push(("Configuration".to_string(), config_section(&config)));
+            }
+            let renamed_from = renames
+                .iter()
+                .filter(|(_, new)| *new == lint.name)
+                .map(|(old, _)| old.clone())
+                .collect::<Vec<_>>();
+            Some(LintMetadata {
+                group: if lint.deprecation.is_some() {
+                    "deprecated".to_string()
+                } else {
+                    lint.group.clone()
+                },
+                level,
+                docs: sections,
+                config,
+                deprecation: lint.deprecation.clone(),
+                renamed_from,
+                version: docs.version,
+                id: lint.name,
+            })
+        })
+        .collect::<Vec<_>>();
+    lints.sort_by(|a, b| a.id.cmp(&b.id));
+    lints
+}
+
+/// Writes the metadata of all lints to `path` as JSON.
+pub fn export(path: &Path) {
+    let json = serde_json::to_string_pretty(&collect()).expect("lint metadata can be serialized");
+    if let Err(e) = fs::write(path, json) {
+        panic!("Cannot write to {}: {}", path.display(), e);
+    }
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("Cannot read from {}: {}", path.display(), e))
+}
+
+fn default_level(lint: &Lint) -> Option<&'static str> {
+    if lint.deprecation.is_some() {
+        return Some("Deprecated");
+    }
+    match lint.group.as_str() {
+        "correctness" => Some("Deny"),
+        "style" | "complexity" | "perf" => Some("Warn"),
+        "restriction" | "pedantic" | "nursery" | "cargo" => Some("Allow"),
+        _ => None,
+    }
+}
+
+/// Splits the docs into their `**Section:**`s and removes the lines that are hidden in Rust code
+/// examples.
+fn doc_sections(docs: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let mut in_rust_code = false;
+    for line in docs.lines() {
+        let trimmed = line.trim_start();
+        let mut text = line;
+        if let Some(attrs) = trimmed.strip_prefix("```") {
+            in_rust_code = !in_rust_code
+                && attrs.split(',').all(|attr| {
+                    ["", "rust", "ignore", "no_run", "should_panic", "compile_fail"].contains(&attr.trim())
+                });
+        } else if in_rust_code && (trimmed == "#" || trimmed.starts_with("# ")) {
+            continue;
+        } else if let Some(caps) = DOC_SECTION_RE.captures(line) {
+            sections.push((caps[1].to_string(), String::new()));
+            text = caps.get(2).map_or("", |m| m.as_str()).trim_start();
+        }
+        match sections.last_mut() {
+            Some((_, section)) => {
+                section.push_str(text);
+                section.push('\n');
+            },
+            None => eprintln!(
+                "warning: skipping a doc comment line that is not in a section: `{}`",
+                line
+            ),
+        }
+    }
+    for (_, section) in &mut sections {
+        *section = section.trim().to_string();
+    }
+    sections
+}
+
+fn config_section(config: &[ConfigKey]) -> String {
+    let mut section = String::from("This lint has the following configuration variables:\n");
+    for conf in config {
+        section.push_str(&format!(
+            "\n* `{}: {}`: {} (defaults to `{}`).",
+            conf.name, conf.ty, conf.doc, conf.default
+        ));
+    }
+    section
+}
+
+fn serialize_sections<S: Serializer>(sections: &[(String, String)], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(sections.iter().map(|(title, text)| (title, text)))
+}
+
+fn serialize_config<S: Serializer>(config: &[ConfigKey], serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Key<'a> {
+        name: &'a str,
+        #[serde(rename = "type")]
+        ty: &'a str,
+        default: &'a str,
+        doc: &'a str,
+    }
+
+    serializer.collect_seq(config.iter().map(|conf| Key {
+        name: &conf.name,
+        ty: &conf.ty,
+        default: &conf.default,
+        doc: &conf.doc,
+    }))
+}
+
+/// Returns the renamed lints as `(old name, new name)`, from the `RENAMED_LINTS` table or, in
+/// older revisions, from the `register_renamed` calls in `lib.rs`.
+pub(crate) fn parse_renames(content: &str) -> Vec<(String, String)> {
+    RENAMED_RE
+        .captures_iter(content)
+        .map(|m| (m["old"].to_string(), m["new"].to_string()))
+        .collect()
+}
+
+#[test]
+fn test_parse_renames() {
+    let table = r#"
+pub static RENAMED_LINTS: &[(&str, &str)] = &[
+    ("clippy::stutter", "clippy::module_name_repetitions"),
+    (
+        "clippy::new_without_default_derive",
+        "clippy::new_without_default",
+    ),
+];
+"#;
+    let calls = r#"
+    ls.register_renamed("clippy::stutter", "clippy::module_name_repetitions");
+    ls.register_renamed("clippy::new_without_default_derive", "clippy::new_without_default");
+    store.register_removed("clippy::unstable_as_slice", "`Vec::as_slice` has been stabilized in 1.7");
+"#;
+    let expected = vec![
+        ("stutter".to_string(), "module_name_repetitions".to_string()),
+        (
+            "new_without_default_derive".to_string(),
+            "new_without_default".to_string(),
+        ),
+    ];
+    assert_eq!(expected, parse_renames(table));
+    assert_eq!(expected, parse_renames(calls));
+}
+
+#[test]
+fn test_doc_sections() {
+    let docs = "**What it does:** Checks for things\nin code.\n\n**Example:**\n```rust\n# let x = 1;\nlet y = x;\n```";
+    let expected = vec![
+        ("What it does".to_string(), "Checks for things\nin code.".to_string()),
+        ("Example".to_string(), "```rust\nlet y = x;\n```".to_string()),
+    ];
+    assert_eq!(expected, doc_sections(docs));
+}
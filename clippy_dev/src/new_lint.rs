@@ -1,91 +1,409 @@
-use crate::clippy_project_root;
-use std::fs::{File, OpenOptions};
+use crate::{clippy_project_root, gather_config};
+use std::fs::{self, OpenOptions};
 use std::io;
 use std::io::prelude::*;
 use std::io::ErrorKind;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The options of `cargo dev new_lint`.
+pub struct LintData<'a> {
+    /// `early` or `late`, only used for lints in a new module
+    pub pass: Option<&'a str>,
+    pub name: &'a str,
+    pub category: &'a str,
+    /// An existing module, like `methods` or `utils::internal_lints`, to add the lint to instead of
+    /// creating a new one
+    pub module: Option<&'a str>,
+    /// The configuration options of the lint, as `key:type=default;description` for new keys or
+    /// `key` for existing ones
+    pub config: Vec<&'a str>,
+    /// Whether the lint has suggestions, which are checked with a `.fixed` test file
+    pub fix: bool,
+    /// Whether the lint respects the `msrv` configuration
+    pub msrv: bool,
+}
+
+/// A configuration option of the new lint.
+#[derive(Debug, PartialEq)]
+struct ConfigOption {
+    /// The name of the `Conf` field, e.g. `too_many_lines_threshold`
+    name: String,
+    ty: String,
+    /// The default value as a Rust expression, or `None` if the key already exists
+    default: Option<String>,
+    /// The description in the doc comment of the key, or `None` if the key already exists
+    doc: Option<String>,
+}
+
+impl ConfigOption {
+    fn toml_name(&self) -> String {
+        self.name.replace('_', "-")
+    }
+
+    fn is_copy(&self) -> bool {
+        [
+            "bool", "char", "f32", "f64", "u8", "u16", "u32", "u64", "usize", "i32", "i64", "isize",
+        ]
+        .contains(&self.ty.as_str())
+    }
+}
 
 /// Creates files required to implement and test a new lint and runs `update_lints`.
 ///
 /// # Errors
 ///
-/// This function errors, if the files couldn't be created
-pub fn create(pass: Option<&str>, lint_name: Option<&str>, category: Option<&str>) -> Result<(), io::Error> {
-    let pass = pass.expect("`pass` argument is validated by clap");
-    let lint_name = lint_name.expect("`name` argument is validated by clap");
-    let category = category.expect("`category` argument is validated by clap");
-
-    match open_files(lint_name) {
-        Ok((mut test_file, mut lint_file)) => {
-            let (pass_type, pass_lifetimes, pass_import, context_import) = match pass {
-                "early" => ("EarlyLintPass", "", "use rustc_ast::ast::*;", "EarlyContext"),
-                "late" => ("LateLintPass", "<'_, '_>", "use rustc_hir::*;", "LateContext"),
-                _ => {
-                    unreachable!("`pass_type` should only ever be `early` or `late`!");
-                },
-            };
-
-            let camel_case_name = to_camel_case(lint_name);
-
-            if let Err(e) = test_file.write_all(get_test_file_contents(lint_name).as_bytes()) {
-                return Err(io::Error::new(
-                    ErrorKind::Other,
-                    format!("Could not write to test file: {}", e),
-                ));
-            };
-
-            if let Err(e) = lint_file.write_all(
-                get_lint_file_contents(
-                    pass_type,
-                    pass_lifetimes,
-                    lint_name,
-                    &camel_case_name,
-                    category,
-                    pass_import,
-                    context_import,
-                )
-                .as_bytes(),
-            ) {
-                return Err(io::Error::new(
-                    ErrorKind::Other,
-                    format!("Could not write to lint file: {}", e),
-                ));
-            }
-            Ok(())
+/// This function errors, if the files couldn't be created or the options are invalid
+pub fn create(lint: &LintData<'_>) -> Result<(), io::Error> {
+    let project_root = clippy_project_root();
+    let existing_config = gather_config()
+        .into_iter()
+        .map(|conf| (conf.name.replace('-', "_"), conf.ty))
+        .collect::<Vec<_>>();
+    let config = lint
+        .config
+        .iter()
+        .map(|option| parse_config_option(option, &existing_config))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| io::Error::new(ErrorKind::InvalidInput, e))?;
+
+    if lint.module.is_some() && (!config.is_empty() || lint.msrv) {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "`--config` and `--msrv` can't be combined with `--module`, \
+             add the configuration to the lint pass of the module by hand",
+        ));
+    }
+
+    let test_contents = get_test_file_contents(lint.name, lint.fix);
+    let mut new_files = vec![(
+        project_root.join("tests/ui").join(format!("{}.rs", lint.name)),
+        test_contents.clone(),
+    )];
+    if lint.fix {
+        new_files.push((
+            project_root.join("tests/ui").join(format!("{}.fixed", lint.name)),
+            test_contents,
+        ));
+    }
+    if !config.is_empty() {
+        let dir = project_root.join("tests/ui-toml").join(lint.name);
+        new_files.push((dir.join("clippy.toml"), get_clippy_toml_contents(&config)));
+        new_files.push((
+            dir.join(format!("{}.rs", lint.name)),
+            get_test_file_contents(lint.name, false),
+        ));
+    }
+
+    match lint.module {
+        Some(module) => {
+            let path = module_path(&project_root, module)?;
+            let contents = read(&path)?;
+            let contents = add_lint_to_module(&contents, &get_lint_declaration(lint.name, lint.category))
+                .map_err(|e| io::Error::new(ErrorKind::InvalidInput, format!("{}: {}", path.display(), e)))?;
+            write_new_files(&new_files)?;
+            write(&path, &contents)?;
         },
-        Err(e) => Err(io::Error::new(
-            ErrorKind::Other,
-            format!("Unable to create lint: {}", e),
-        )),
+        None => {
+            let pass = lint.pass.expect("`pass` argument is validated by clap");
+            new_files.push((
+                project_root.join("clippy_lints/src").join(format!("{}.rs", lint.name)),
+                get_lint_file_contents(pass, lint.name, lint.category, &config, lint.msrv),
+            ));
+            write_new_files(&new_files)?;
+
+            let lib_rs = project_root.join("clippy_lints/src/lib.rs");
+            let registration = get_pass_registration(pass, lint.name, &config, lint.msrv);
+            write(&lib_rs, &add_pass_registration(&read(&lib_rs)?, &registration))?;
+        },
+    }
+
+    if !config.is_empty() || lint.msrv {
+        let conf_rs = project_root.join("clippy_lints/src/utils/conf.rs");
+        let mut contents = read(&conf_rs)?;
+        for option in config
+            .iter()
+            .chain(if lint.msrv { Some(msrv_option()) } else { None }.as_ref())
+        {
+            contents = add_config_option(&contents, option, lint.name);
+        }
+        write(&conf_rs, &contents)?;
+
+        let unknown_key = project_root.join("tests/ui-toml/toml_unknown_key/conf_unknown_key.stderr");
+        let mut contents = read(&unknown_key)?;
+        // The keys are listed in the order of `define_Conf!`, where `msrv` is the last one
+        for option in config.iter().filter(|option| option.default.is_some()) {
+            contents = contents.replacen("`msrv`", &format!("`{}`, `msrv`", option.toml_name()), 1);
+        }
+        write(&unknown_key, &contents)?;
     }
+
+    Ok(())
 }
 
-fn open_files(lint_name: &str) -> Result<(File, File), io::Error> {
-    let project_root = clippy_project_root();
+fn read(path: &Path) -> Result<String, io::Error> {
+    fs::read_to_string(path)
+        .map_err(|e| io::Error::new(ErrorKind::Other, format!("Could not read {}: {}", path.display(), e)))
+}
 
-    let test_file_path = project_root.join("tests").join("ui").join(format!("{}.rs", lint_name));
-    let lint_file_path = project_root
-        .join("clippy_lints")
-        .join("src")
-        .join(format!("{}.rs", lint_name));
+fn write(path: &Path, contents: &str) -> Result<(), io::Error> {
+    fs::write(path, contents).map_err(|e| {
+        io::Error::new(
+            ErrorKind::Other,
+            format!("Could not write to {}: {}", path.display(), e),
+        )
+    })
+}
 
-    if Path::new(&test_file_path).exists() {
+/// Creates all files, or none of them if one already exists.
+fn write_new_files(files: &[(PathBuf, String)]) -> Result<(), io::Error> {
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
         return Err(io::Error::new(
             ErrorKind::AlreadyExists,
-            format!("test file {:?} already exists", test_file_path),
+            format!("file {:?} already exists", path),
         ));
     }
-    if Path::new(&lint_file_path).exists() {
-        return Err(io::Error::new(
-            ErrorKind::AlreadyExists,
-            format!("lint file {:?} already exists", lint_file_path),
-        ));
+    for (path, contents) in files {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        if let Err(e) = file.write_all(contents.as_bytes()) {
+            return Err(io::Error::new(
+                ErrorKind::Other,
+                format!("Could not write to {}: {}", path.display(), e),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the file of a module like `methods` or `utils::internal_lints`.
+fn module_path(project_root: &Path, module: &str) -> Result<PathBuf, io::Error> {
+    let base = project_root.join("clippy_lints/src").join(module.replace("::", "/"));
+    let candidates = [base.with_extension("rs"), base.join("mod.rs")];
+    candidates.iter().find(|path| path.exists()).cloned().ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::NotFound,
+            format!("the module `{}` doesn't exist in `clippy_lints/src`", module),
+        )
+    })
+}
+
+fn msrv_option() -> ConfigOption {
+    ConfigOption {
+        name: "msrv".to_string(),
+        ty: "Option<RustcVersion>".to_string(),
+        default: None,
+        doc: None,
+    }
+}
+
+/// Parses `key:type=default;description`, or `key` for a key that already exists in `existing` (a
+/// list of `(name, type)`).
+fn parse_config_option(option: &str, existing: &[(String, String)]) -> Result<ConfigOption, String> {
+    let (name, ty_and_default) = match option.find(':') {
+        Some(pos) => (&option[..pos], Some(&option[pos + 1..])),
+        None => (option, None),
+    };
+    let name = name.trim().replace('-', "_");
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(format!("`{}` is not a valid configuration key", name));
+    }
+    if name == "msrv" {
+        return Err("use `--msrv` to make the lint respect the `msrv` configuration".to_string());
+    }
+
+    let existing_ty = existing.iter().find(|(key, _)| *key == name).map(|(_, ty)| ty);
+    match (ty_and_default, existing_ty) {
+        (None, Some(ty)) => Ok(ConfigOption {
+            name,
+            ty: ty.clone(),
+            default: None,
+            doc: None,
+        }),
+        (None, None) => Err(format!(
+            "the configuration key `{}` doesn't exist yet, specify it as `{}:<type>=<default>;<description>`",
+            name, name
+        )),
+        (Some(ty_and_default), existing_ty) => {
+            let pos = ty_and_default.find('=').ok_or_else(|| {
+                format!(
+                    "`{}` has no default value, expected `key:type=default;description`",
+                    option
+                )
+            })?;
+            let ty = ty_and_default[..pos].trim().to_string();
+            let mut default_and_doc = ty_and_default[pos + 1..].splitn(2, ';');
+            let default = default_and_doc.next().unwrap_or_default().trim().to_string();
+            let doc = default_and_doc.next().unwrap_or_default().trim().to_string();
+            if ty.is_empty() || default.is_empty() {
+                return Err(format!(
+                    "`{}` is not of the form `key:type=default;description`",
+                    option
+                ));
+            }
+            if existing_ty.is_none() && doc.is_empty() {
+                return Err(format!(
+                    "`{}` has no description, expected `key:type=default;description`",
+                    option
+                ));
+            }
+            match existing_ty {
+                Some(existing_ty) if existing_ty.replace(' ', "") != ty.replace(' ', "") => Err(format!(
+                    "the configuration key `{}` already exists with the type `{}`",
+                    name, existing_ty
+                )),
+                Some(_) => Ok(ConfigOption {
+                    name,
+                    ty,
+                    default: None,
+                    doc: None,
+                }),
+                None => Ok(ConfigOption {
+                    name,
+                    ty,
+                    default: Some(default),
+                    doc: Some(doc),
+                }),
+            }
+        },
+    }
+}
+
+/// Adds `option` to the `define_Conf!` invocation in `conf_rs`, or adds `lint_name` to the lints
+/// of the option if it already exists.
+fn add_config_option(conf_rs: &str, option: &ConfigOption, lint_name: &str) -> String {
+    let lint_upper = lint_name.to_uppercase();
+    let mut lines = conf_rs.lines().map(ToString::to_string).collect::<Vec<_>>();
+    let key = format!("({}, \"{}\":", option.name, option.name);
+    if let Some(pos) = lines.iter().position(|line| line.trim_start().starts_with(&key)) {
+        if let Some(doc) = pos.checked_sub(1).and_then(|doc_pos| lines.get_mut(doc_pos)) {
+            if let Some(end) = doc.find(". ") {
+                doc.insert_str(end, &format!(", {}", lint_upper));
+            }
+        }
+    } else if let Some(pos) = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("(msrv, \"msrv\":"))
+    {
+        // New keys go before `msrv`, which stays the last one
+        lines.insert(
+            pos - 1,
+            format!(
+                "    /// Lint: {}. {}",
+                lint_upper,
+                option.doc.as_deref().unwrap_or_default()
+            ),
+        );
+        lines.insert(
+            pos,
+            format!(
+                "    ({name}, \"{name}\": {ty}, {default}),",
+                name = option.name,
+                ty = option.ty,
+                default = option.default.as_deref().unwrap_or_default()
+            ),
+        );
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Inserts the lint declaration before the lint pass of `module` and adds the lint to the pass.
+fn add_lint_to_module(module: &str, declaration: &str) -> Result<String, String> {
+    let passes = module
+        .match_indices("declare_lint_pass!(")
+        .chain(module.match_indices("impl_lint_pass!("))
+        .map(|(pos, _)| pos)
+        .collect::<Vec<_>>();
+    let pass_start = match passes.as_slice() {
+        [pos] => *pos,
+        [] => return Err("the module doesn't declare a lint pass".to_string()),
+        _ => return Err("the module declares several lint passes, add the lint to one of them by hand".to_string()),
+    };
+    let list_end = module[pass_start..]
+        .find("]);")
+        .map(|pos| pass_start + pos)
+        .ok_or_else(|| "the lint pass has no list of lints".to_string())?;
+    let name_upper = declaration
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("pub "))
+        .map(|name| name.trim_end_matches(','))
+        .expect("the declaration contains the lint name");
+
+    let list = module[pass_start..list_end].trim_end();
+    let lint_entry = if list.ends_with(',') {
+        // A multiline list ends with a trailing comma before the closing bracket
+        format!("\n    {},\n", name_upper)
+    } else {
+        format!(", {}", name_upper)
+    };
+
+    let mut out = String::with_capacity(module.len() + declaration.len() + lint_entry.len() + 1);
+    out.push_str(&module[..pass_start]);
+    out.push_str(declaration);
+    out.push('\n');
+    out.push_str(list);
+    out.push_str(&lint_entry);
+    out.push_str(&module[list_end..]);
+    Ok(out)
+}
+
+/// Inserts the lint pass registration at the end of the late and early passes in `lib.rs`.
+fn add_pass_registration(lib_rs: &str, registration: &str) -> String {
+    // `UnusedClippyAllow` has to stay the last pass, so new passes go before it
+    let marker = "\n    // Registered after the other Clippy passes";
+    let fallback_marker = "\n\n    store.register_group(true, \"clippy::restriction\"";
+    match lib_rs.find(marker).or_else(|| lib_rs.find(fallback_marker)) {
+        Some(pos) => format!("{}\n{}{}", &lib_rs[..pos], registration, &lib_rs[pos..]),
+        None => {
+            eprintln!(
+                "warning: couldn't find the lint pass registrations in `clippy_lints/src/lib.rs`, \
+                 register the lint pass by hand:\n{}",
+                registration
+            );
+            lib_rs.to_string()
+        },
     }
+}
 
-    let test_file = OpenOptions::new().write(true).create_new(true).open(test_file_path)?;
-    let lint_file = OpenOptions::new().write(true).create_new(true).open(lint_file_path)?;
+fn get_pass_registration(pass: &str, lint_name: &str, config: &[ConfigOption], msrv: bool) -> String {
+    let camel_case_name = to_camel_case(lint_name);
+    if config.is_empty() && !msrv {
+        return format!(
+            "    store.register_{}_pass(|| box {}::{});",
+            pass, lint_name, camel_case_name
+        );
+    }
 
-    Ok((test_file, lint_file))
+    let mut out = String::new();
+    let mut args = Vec::new();
+    for option in config {
+        if option.is_copy() {
+            out.push_str(&format!("    let {name} = conf.{name};\n", name = option.name));
+            args.push(option.name.clone());
+        } else {
+            out.push_str(&format!("    let {name} = conf.{name}.clone();\n", name = option.name));
+            args.push(format!("{}.clone()", option.name));
+        }
+    }
+    if msrv {
+        args.push("msrv".to_string());
+    }
+    out.push_str(&format!(
+        "    store.register_{}_pass(move || box {}::{}::new({}));",
+        pass,
+        lint_name,
+        camel_case_name,
+        args.join(", ")
+    ));
+    out
 }
 
 fn to_camel_case(name: &str) -> String {
@@ -100,33 +418,72 @@ fn to_camel_case(name: &str) -> String {
         .collect()
 }
 
-fn get_test_file_contents(lint_name: &str) -> String {
+/// Returns the version of the current nightly Rust, which ships the new lint first, e.g. `1.45.0`
+/// for `rustc 1.45.0-nightly`.
+fn get_stabilization_version() -> String {
+    Command::new("rustc")
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| parse_rustc_version(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_else(|| "<version>".to_string())
+}
+
+fn parse_rustc_version(version: &str) -> Option<String> {
+    let version = version.split_whitespace().nth(1)?;
+    let version = version.split('-').next()?;
+    if version.split('.').count() == 3 {
+        Some(version.to_string())
+    } else {
+        None
+    }
+}
+
+fn get_test_file_contents(lint_name: &str, fix: bool) -> String {
     format!(
-        "#![warn(clippy::{})]
+        "{}#![warn(clippy::{})]
 
 fn main() {{
     // test code goes here
 }}
 ",
+        if fix { "// run-rustfix\n\n" } else { "" },
         lint_name
     )
 }
 
-fn get_lint_file_contents(
-    pass_type: &str,
-    pass_lifetimes: &str,
-    lint_name: &str,
-    camel_case_name: &str,
-    category: &str,
-    pass_import: &str,
-    context_import: &str,
-) -> String {
-    format!(
-        "use rustc_lint::{{{type}, {context_import}}};
-use rustc_session::{{declare_lint_pass, declare_tool_lint}};
-{pass_import}
+/// Returns a `clippy.toml` that sets the new configuration keys to their default values, so that
+/// the test can be adjusted to other values.
+fn get_clippy_toml_contents(config: &[ConfigOption]) -> String {
+    let mut out = String::new();
+    for option in config {
+        match option.default.as_deref().map(toml_literal) {
+            Some(Some(value)) => out.push_str(&format!("{} = {}\n", option.toml_name(), value)),
+            Some(None) => out.push_str(&format!("# {} = <value>\n", option.toml_name())),
+            None => {},
+        }
+    }
+    out
+}
+
+/// Returns the Rust expression `default` as a value in `clippy.toml`, if it is a simple literal.
+fn toml_literal(default: &str) -> Option<String> {
+    let number = default.replace('_', "");
+    if default == "true"
+        || default == "false"
+        || (default.len() >= 2 && default.starts_with('"') && default.ends_with('"'))
+    {
+        Some(default.to_string())
+    } else if number.parse::<f64>().is_ok() {
+        Some(number)
+    } else {
+        None
+    }
+}
 
-declare_clippy_lint! {{
+fn get_lint_declaration(lint_name: &str, category: &str) -> String {
+    format!(
+        "declare_clippy_lint! {{
     /// **What it does:**
     ///
     /// **Why is this bad?**
@@ -142,23 +499,104 @@ declare_clippy_lint! {{
     /// ```rust
     /// // example code which does not raise clippy warning
     /// ```
+    #[clippy::version = \"{version}\"]
     pub {name_upper},
     {category},
     \"default lint description\"
 }}
+",
+        version = get_stabilization_version(),
+        name_upper = lint_name.to_uppercase(),
+        category = category,
+    )
+}
 
-declare_lint_pass!({name_camel} => [{name_upper}]);
+fn get_lint_file_contents(pass: &str, lint_name: &str, category: &str, config: &[ConfigOption], msrv: bool) -> String {
+    let (pass_type, pass_lifetimes, pass_import, context_import) = match pass {
+        "early" => ("EarlyLintPass", "", "use rustc_ast::ast::*;", "EarlyContext"),
+        "late" => ("LateLintPass", "<'_, '_>", "use rustc_hir::*;", "LateContext"),
+        _ => {
+            unreachable!("`pass_type` should only ever be `early` or `late`!");
+        },
+    };
+    let name_camel = to_camel_case(lint_name);
+    let name_upper = lint_name.to_uppercase();
+
+    let mut fields = config
+        .iter()
+        .map(|option| (option.name.clone(), option.ty.clone(), option.is_copy()))
+        .collect::<Vec<_>>();
+    if msrv {
+        fields.push(("msrv".to_string(), "Option<RustcVersion>".to_string(), true));
+    }
 
-impl {type}{lifetimes} for {name_camel} {{}}
+    let mut out = format!(
+        "use rustc_lint::{{{pass_type}, {context_import}}};
+use rustc_session::{{{pass_macro}, declare_tool_lint}};
+{pass_import}
 ",
-        type=pass_type,
-        lifetimes=pass_lifetimes,
-        name_upper=lint_name.to_uppercase(),
-        name_camel=camel_case_name,
-        category=category,
-        pass_import=pass_import,
-        context_import=context_import
-    )
+        pass_type = pass_type,
+        context_import = context_import,
+        pass_macro = if fields.is_empty() {
+            "declare_lint_pass"
+        } else {
+            "impl_lint_pass"
+        },
+        pass_import = pass_import,
+    );
+    if msrv {
+        out.push_str("use crate::utils::msrvs::RustcVersion;\n");
+    }
+    out.push('\n');
+    out.push_str(&get_lint_declaration(lint_name, category));
+    out.push('\n');
+
+    if fields.is_empty() {
+        out.push_str(&format!("declare_lint_pass!({} => [{}]);\n", name_camel, name_upper));
+    } else {
+        let derive = if fields.iter().all(|(_, _, is_copy)| *is_copy) {
+            "Copy, Clone"
+        } else {
+            "Clone"
+        };
+        let params = fields
+            .iter()
+            .map(|(name, ty, _)| format!("{}: {}", name, ty))
+            .collect::<Vec<_>>();
+        let names = fields.iter().map(|(name, _, _)| name.as_str()).collect::<Vec<_>>();
+        out.push_str(&format!(
+            "#[derive({derive})]
+pub struct {name_camel} {{
+{fields}
+}}
+
+impl {name_camel} {{
+    #[must_use]
+    pub fn new({params}) -> Self {{
+        Self {{ {names} }}
+    }}
+}}
+
+impl_lint_pass!({name_camel} => [{name_upper}]);
+",
+            derive = derive,
+            name_camel = name_camel,
+            name_upper = name_upper,
+            fields = params
+                .iter()
+                .map(|param| format!("    {},", param))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            params = params.join(", "),
+            names = names.join(", "),
+        ));
+    }
+
+    out.push_str(&format!(
+        "\nimpl {}{} for {} {{}}\n",
+        pass_type, pass_lifetimes, name_camel
+    ));
+    out
 }
 
 #[test]
@@ -175,3 +613,170 @@ fn test_camel_case() {
     let name4 = to_camel_case(name3);
     assert_eq!(name4, "LintName");
 }
+
+#[test]
+fn test_parse_rustc_version() {
+    assert_eq!(
+        Some("1.45.0".to_string()),
+        parse_rustc_version("rustc 1.45.0-nightly (7ced01a73 2020-05-21)\n")
+    );
+    assert_eq!(
+        Some("1.44.0".to_string()),
+        parse_rustc_version("rustc 1.44.0 (49cae5576 2020-06-01)")
+    );
+    assert_eq!(None, parse_rustc_version("error"));
+}
+
+#[test]
+fn test_parse_config_option() {
+    let existing = vec![("max_struct_bools".to_string(), "u64".to_string())];
+    assert_eq!(
+        Ok(ConfigOption {
+            name: "max_depth".to_string(),
+            ty: "u64".to_string(),
+            default: Some("5".to_string()),
+            doc: Some("The maximum nesting depth".to_string()),
+        }),
+        parse_config_option("max-depth:u64=5;The maximum nesting depth", &existing)
+    );
+    assert_eq!(
+        Ok(ConfigOption {
+            name: "max_struct_bools".to_string(),
+            ty: "u64".to_string(),
+            default: None,
+            doc: None,
+        }),
+        parse_config_option("max_struct_bools", &existing)
+    );
+    assert!(parse_config_option("max_struct_bools:bool=true", &existing).is_err());
+    assert!(parse_config_option("max_depth", &existing).is_err());
+    assert!(parse_config_option("max_depth:u64", &existing).is_err());
+    assert!(parse_config_option("max_depth:u64=5", &existing).is_err());
+    assert!(parse_config_option("max_depth:u64=5; ", &existing).is_err());
+    assert!(parse_config_option("msrv", &existing).is_err());
+}
+
+#[test]
+fn test_get_clippy_toml_contents() {
+    let option = |name: &str, default: Option<&str>| ConfigOption {
+        name: name.to_string(),
+        ty: "_".to_string(),
+        default: default.map(ToString::to_string),
+        doc: None,
+    };
+    let config = vec![
+        option("max_size", Some("16_384")),
+        option("prefix", Some("\"my_\"")),
+        option("names", Some("Vec::new()")),
+        option("blocking_functions", None),
+    ];
+    assert_eq!(
+        "max-size = 16384\nprefix = \"my_\"\n# names = <value>\n",
+        get_clippy_toml_contents(&config)
+    );
+}
+
+#[test]
+fn test_add_config_option() {
+    let conf_rs = "define_Conf! {
+    /// Lint: TOO_MANY_LINES. The maximum number of lines a function or method can have
+    (too_many_lines_threshold, \"too_many_lines_threshold\": u64, 100),
+    /// Lint: MANUAL_STRIP. The minimum Rust version that the crate supports
+    (msrv, \"msrv\": Option<String>, None),
+}
+";
+    let new_option = ConfigOption {
+        name: "max_depth".to_string(),
+        ty: "u64".to_string(),
+        default: Some("5".to_string()),
+        doc: Some("The maximum nesting depth".to_string()),
+    };
+    let expected = "define_Conf! {
+    /// Lint: TOO_MANY_LINES. The maximum number of lines a function or method can have
+    (too_many_lines_threshold, \"too_many_lines_threshold\": u64, 100),
+    /// Lint: FOO_BAR. The maximum nesting depth
+    (max_depth, \"max_depth\": u64, 5),
+    /// Lint: MANUAL_STRIP, FOO_BAR. The minimum Rust version that the crate supports
+    (msrv, \"msrv\": Option<String>, None),
+}
+";
+    let result = add_config_option(conf_rs, &new_option, "foo_bar");
+    assert_eq!(expected, add_config_option(&result, &msrv_option(), "foo_bar"));
+}
+
+#[test]
+fn test_add_lint_to_module() {
+    let declaration = "declare_clippy_lint! {\n    pub NEW_LINT,\n    style,\n    \"desc\"\n}\n";
+
+    let module = "declare_clippy_lint! {\n    pub OLD_LINT,\n    style,\n    \"desc\"\n}\n\n\
+                  declare_lint_pass!(Pass => [OLD_LINT]);\n";
+    let expected = "declare_clippy_lint! {\n    pub OLD_LINT,\n    style,\n    \"desc\"\n}\n\n\
+                    declare_clippy_lint! {\n    pub NEW_LINT,\n    style,\n    \"desc\"\n}\n\n\
+                    declare_lint_pass!(Pass => [OLD_LINT, NEW_LINT]);\n";
+    assert_eq!(Ok(expected.to_string()), add_lint_to_module(module, declaration));
+
+    let module = "impl_lint_pass!(Pass => [\n    OLD_LINT,\n]);\n";
+    let expected = format!(
+        "{}\nimpl_lint_pass!(Pass => [\n    OLD_LINT,\n    NEW_LINT,\n]);\n",
+        declaration
+    );
+    assert_eq!(Ok(expected), add_lint_to_module(module, declaration));
+
+    assert!(add_lint_to_module(
+        "declare_lint_pass!(A => [X]);\ndeclare_lint_pass!(B => [Y]);\n",
+        declaration
+    )
+    .is_err());
+}
+
+#[test]
+fn test_get_pass_registration() {
+    let config = vec![
+        ConfigOption {
+            name: "max_depth".to_string(),
+            ty: "u64".to_string(),
+            default: Some("5".to_string()),
+            doc: Some("The maximum nesting depth".to_string()),
+        },
+        ConfigOption {
+            name: "names".to_string(),
+            ty: "Vec<String>".to_string(),
+            default: None,
+            doc: None,
+        },
+    ];
+    assert_eq!(
+        "    store.register_early_pass(|| box foo_bar::FooBar);",
+        get_pass_registration("early", "foo_bar", &[], false)
+    );
+    assert_eq!(
+        "    let max_depth = conf.max_depth;\n    \
+         let names = conf.names.clone();\n    \
+         store.register_late_pass(move || box foo_bar::FooBar::new(max_depth, names.clone(), msrv));",
+        get_pass_registration("late", "foo_bar", &config, true)
+    );
+}
+
+#[test]
+fn test_add_pass_registration() {
+    let lib_rs = "    store.register_late_pass(|| box a::A);\n    \
+                  // Registered after the other Clippy passes, so that it runs after all of their lints were\n    \
+                  // emitted.\n    \
+                  store.register_late_pass(|| box z::Z::default());\n\n    \
+                  store.register_group(true, \"clippy::restriction\", Some(\"clippy_restriction\"), vec![";
+    let expected = "    store.register_late_pass(|| box a::A);\n    \
+                    store.register_late_pass(|| box b::B);\n    \
+                    // Registered after the other Clippy passes, so that it runs after all of their lints were\n    \
+                    // emitted.\n    \
+                    store.register_late_pass(|| box z::Z::default());\n\n    \
+                    store.register_group(true, \"clippy::restriction\", Some(\"clippy_restriction\"), vec![";
+    let registration = "    store.register_late_pass(|| box b::B);";
+    assert_eq!(expected, add_pass_registration(lib_rs, registration));
+
+    let lib_rs = "    store.register_late_pass(|| box a::A);\n\n    \
+                  store.register_group(true, \"clippy::restriction\", Some(\"clippy_restriction\"), vec![";
+    let expected = "    store.register_late_pass(|| box a::A);\n    \
+                    store.register_late_pass(|| box b::B);\n\n    \
+                    store.register_group(true, \"clippy::restriction\", Some(\"clippy_restriction\"), vec![";
+    assert_eq!(expected, add_pass_registration(lib_rs, registration));
+}
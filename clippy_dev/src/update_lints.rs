@@ -1,7 +1,9 @@
+use crate::metadata::parse_renames;
 use crate::{
-    gather_all, gen_changelog_lint_list, gen_deprecated, gen_lint_group_list, gen_modules_list, gen_register_lint_list,
-    replace_region_in_file, Lint, DOCS_LINK,
+    clippy_project_root, gather_all, gather_all_with_docs, gen_changelog_lint_list, gen_deprecated,
+    gen_lint_group_list, gen_modules_list, gen_register_lint_list, replace_region_in_file, Lint, DOCS_LINK,
 };
+use std::fs;
 use std::path::Path;
 
 #[derive(Clone, Copy, PartialEq)]
@@ -126,6 +128,41 @@ pub fn run(update_mode: UpdateMode) {
         .changed;
     }
 
+    // `deprecated_lints.rs` is not compiled, so the internal lint that checks the
+    // `#[clippy::version]` attributes doesn't see the deprecated lints
+    let unversioned = gather_all_with_docs()
+        .filter(|(lint, docs)| lint.deprecation.is_some() && docs.version.is_none())
+        .map(|(lint, _)| lint.name)
+        .collect::<Vec<_>>();
+    if update_mode == UpdateMode::Check && !unversioned.is_empty() {
+        println!(
+            "The following deprecated lints have no `#[clippy::version]` attribute: {}",
+            unversioned.join(", ")
+        );
+        std::process::exit(1);
+    }
+
+    // The table in `renamed_lints.rs` is written by hand, so check that the renames point to
+    // existing lints and that the old names are free
+    let renamed_lints_path = clippy_project_root().join("clippy_lints/src/renamed_lints.rs");
+    let renamed_lints = fs::read_to_string(&renamed_lints_path)
+        .unwrap_or_else(|e| panic!("Cannot read {}: {}", renamed_lints_path.display(), e));
+    let invalid_renames = parse_renames(&renamed_lints)
+        .into_iter()
+        .filter(|(old_name, new_name)| {
+            lint_list.iter().any(|lint| lint.name == *old_name) || !lint_list.iter().any(|lint| lint.name == *new_name)
+        })
+        .map(|(old_name, new_name)| format!("`{}` -> `{}`", old_name, new_name))
+        .collect::<Vec<_>>();
+    if !invalid_renames.is_empty() {
+        println!(
+            "The following renames in `clippy_lints/src/renamed_lints.rs` are invalid, because the new name \
+             doesn't exist or the old name is still in use: {}",
+            invalid_renames.join(", ")
+        );
+        std::process::exit(1);
+    }
+
     if update_mode == UpdateMode::Check && file_change {
         println!(
             "Not all lints defined properly. \
@@ -0,0 +1,472 @@
+use crate::utils::sugg::Fix;
+use crate::utils::usage::local_use_count;
+use crate::utils::{
+    async_body_kind, get_enclosing_block, in_macro, is_async_block, is_def_path, match_def_path, paths, snippet_block,
+    snippet_opt, span_lint_and_fix, span_lint_and_help, span_lint_and_note,
+};
+use if_chain::if_chain;
+use rustc_errors::Applicability;
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{walk_expr, FnKind, NestedVisitorMap, Visitor};
+use rustc_hir::{
+    AsyncGeneratorKind, Body, Constness, Expr, ExprKind, FnDecl, FnRetTy, GenericBound, HirId, IsAsync, ItemKind,
+    Local, MatchSource, Node, PatKind, TraitBoundModifier, Ty, TyKind, TypeBindingKind, Unsafety,
+};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::hir::map::Map;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{BytePos, Span};
+use rustc_target::spec::abi::Abi;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `async fn`s that never `.await` anything.
+    ///
+    /// **Why is this bad?** The function doesn't need to be `async`, and every
+    /// caller has to poll a future just to get the return value.
+    ///
+    /// **Known problems:** The function may have to be `async` to fit an
+    /// interface, e.g. when it is passed to a function that expects a closure
+    /// returning a future.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// async fn get_answer() -> u32 {
+    ///     42
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// fn get_answer() -> u32 {
+    ///     42
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub UNUSED_ASYNC,
+    pedantic,
+    "`async fn`s that never `.await`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `async` blocks that are dropped right away,
+    /// either by binding them to `_`, to an unused underscore-prefixed local,
+    /// or by passing them to `drop` or `forget`.
+    ///
+    /// **Why is this bad?** Futures do nothing unless they are polled, so the
+    /// code in the block is never run.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust,ignore
+    /// let _ = async {
+    ///     send_report().await;
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// send_report().await;
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub UNPOLLED_ASYNC_BLOCK,
+    correctness,
+    "`async` blocks that are dropped without being polled"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for calls to blocking functions of `std`, like
+    /// the functions in `std::fs`, `std::thread::sleep` or `Mutex::lock`, in
+    /// `async fn`s and `async` blocks. Additional functions can be configured
+    /// with `blocking-functions`.
+    ///
+    /// **Why is this bad?** The executor thread is blocked until the call
+    /// returns, so no other future can make progress on it in the meantime.
+    ///
+    /// **Known problems:** Calls in closures inside the async body are not
+    /// checked, since they are often passed to `spawn_blocking`. Blocking trait
+    /// methods like `Read::read` are not detected.
+    ///
+    /// **Example:**
+    /// ```rust,ignore
+    /// async fn load(path: &Path) -> io::Result<String> {
+    ///     std::fs::read_to_string(path)
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// async fn load(path: &Path) -> io::Result<String> {
+    ///     tokio::fs::read_to_string(path).await
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub BLOCKING_IN_ASYNC,
+    pedantic,
+    "blocking calls inside of `async` bodies"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for functions that return `impl Future` and
+    /// whose body is a single `async` block.
+    ///
+    /// **Why is this bad?** The same function can be written more concisely
+    /// with the `async fn` syntax.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use std::future::Future;
+    ///
+    /// fn foo() -> impl Future<Output = i32> {
+    ///     async { 42 }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// async fn foo() -> i32 {
+    ///     42
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_ASYNC_FN,
+    style,
+    "manual implementations of `async` functions can be simplified using the dedicated syntax"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `async fn`s and `async` blocks whose future
+    /// is larger than `future-size-threshold` (default: 16384 bytes).
+    ///
+    /// **Why is this bad?** A future stores all values that are held across an
+    /// `.await` point. Large futures are expensive to move around and can
+    /// overflow the stack when they are nested.
+    ///
+    /// **Known problems:** Only futures whose size doesn't depend on generic
+    /// parameters are checked.
+    ///
+    /// **Example:**
+    /// ```rust,ignore
+    /// async fn process() {
+    ///     let buffer = [0_u8; 32 * 1024];
+    ///     fill(&buffer).await;
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// async fn process() {
+    ///     let buffer = vec![0_u8; 32 * 1024];
+    ///     fill(&buffer).await;
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub LARGE_FUTURES,
+    pedantic,
+    "`async fn`s and `async` blocks with large futures"
+}
+
+const BLOCKING_FUNCTIONS: [&[&str]; 8] = [
+    &paths::JOIN_HANDLE_JOIN,
+    &paths::MUTEX_LOCK,
+    &paths::OPEN_OPTIONS_OPEN,
+    &paths::RECEIVER_RECV,
+    &paths::RWLOCK_READ,
+    &paths::RWLOCK_WRITE,
+    &paths::STDIN_READ_LINE,
+    &paths::THREAD_SLEEP,
+];
+
+pub struct AsyncBodies {
+    /// The paths of the configured blocking functions, split into their segments.
+    blocking_functions: Vec<Vec<String>>,
+    future_size_threshold: u64,
+}
+
+impl AsyncBodies {
+    pub fn new(blocking_functions: &[String], future_size_threshold: u64) -> Self {
+        Self {
+            blocking_functions: blocking_functions
+                .iter()
+                .map(|path| path.split("::").map(ToString::to_string).collect())
+                .collect(),
+            future_size_threshold,
+        }
+    }
+
+    fn is_blocking(&self, cx: &LateContext<'_, '_>, did: DefId) -> bool {
+        let def_path = cx.get_def_path(did);
+        let in_fs = |len| def_path.len() == len && def_path[0].as_str() == "std" && def_path[1].as_str() == "fs";
+        // the free functions of `std::fs` and all methods of `File`
+        in_fs(3)
+            || (in_fs(4) && def_path[2].as_str() == "File")
+            || BLOCKING_FUNCTIONS.iter().any(|&path| is_def_path(cx, did, path))
+            || self.blocking_functions.iter().any(|path| {
+                let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+                match_def_path(cx, did, &path)
+            })
+    }
+
+    fn check_future_size(&self, cx: &LateContext<'_, '_>, owner: HirId, span: Span) {
+        let ty = cx.tcx.type_of(cx.tcx.hir().local_def_id(owner));
+        if let Ok(layout) = cx.tcx.layout_of(cx.param_env.and(ty)) {
+            let size = layout.size.bytes();
+            if size > self.future_size_threshold {
+                span_lint_and_help(
+                    cx,
+                    LARGE_FUTURES,
+                    span,
+                    &format!("large future with a size of {} bytes", size),
+                    None,
+                    "consider moving the large values that are held across `.await` points to the heap",
+                );
+            }
+        }
+    }
+}
+
+impl_lint_pass!(AsyncBodies => [UNUSED_ASYNC, UNPOLLED_ASYNC_BLOCK, BLOCKING_IN_ASYNC, MANUAL_ASYNC_FN, LARGE_FUTURES]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for AsyncBodies {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'a, 'tcx>,
+        kind: FnKind<'tcx>,
+        decl: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        span: Span,
+        _: HirId,
+    ) {
+        check_manual_async_fn(cx, kind, decl, body, span);
+    }
+
+    fn check_body(&mut self, cx: &LateContext<'a, 'tcx>, body: &'tcx Body<'_>) {
+        let kind = match async_body_kind(body) {
+            Some(kind) => kind,
+            None => return,
+        };
+        let owner = cx.tcx.hir().body_owner(body.id());
+        let span = if kind == AsyncGeneratorKind::Fn {
+            let fn_id = cx.tcx.hir().get_parent_item(owner);
+            cx.tcx.def_span(cx.tcx.hir().local_def_id(fn_id).to_def_id())
+        } else {
+            cx.tcx.hir().span(owner)
+        };
+        if in_macro(span) {
+            return;
+        }
+
+        let mut visitor = AsyncBodyVisitor {
+            cx,
+            pass: self,
+            has_await: false,
+            blocking_calls: Vec::new(),
+        };
+        visitor.visit_expr(&body.value);
+
+        if kind == AsyncGeneratorKind::Fn && !visitor.has_await {
+            span_lint_and_help(
+                cx,
+                UNUSED_ASYNC,
+                span,
+                "unused `async` for function with no await statements",
+                None,
+                "consider removing the `async` from this function",
+            );
+        }
+        for call in visitor.blocking_calls {
+            span_lint_and_help(
+                cx,
+                BLOCKING_IN_ASYNC,
+                call,
+                "this call blocks the thread of the executor",
+                None,
+                "consider using an async alternative, or the `spawn_blocking` function of your runtime",
+            );
+        }
+        self.check_future_size(cx, owner, span);
+    }
+
+    fn check_local(&mut self, cx: &LateContext<'a, 'tcx>, local: &'tcx Local<'_>) {
+        if_chain! {
+            if let Some(init) = local.init;
+            if is_async_block(cx, init);
+            if !in_macro(local.span);
+            then {
+                let dropped = match local.pat.kind {
+                    PatKind::Wild => true,
+                    PatKind::Binding(_, id, ident, None) => {
+                        ident.as_str().starts_with('_') && is_unused_local(cx, local.hir_id, id)
+                    },
+                    _ => false,
+                };
+                if dropped {
+                    lint_unpolled(cx, init.span);
+                }
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr<'_>) {
+        if_chain! {
+            if let ExprKind::Call(func, [arg]) = expr.kind;
+            if let ExprKind::Path(ref qpath) = func.kind;
+            if let Some(did) = cx.tables.qpath_res(qpath, func.hir_id).opt_def_id();
+            if is_def_path(cx, did, &paths::DROP) || is_def_path(cx, did, &paths::MEM_FORGET);
+            if is_async_block(cx, arg);
+            if !in_macro(expr.span);
+            then {
+                lint_unpolled(cx, arg.span);
+            }
+        }
+    }
+}
+
+fn lint_unpolled(cx: &LateContext<'_, '_>, span: Span) {
+    span_lint_and_note(
+        cx,
+        UNPOLLED_ASYNC_BLOCK,
+        span,
+        "this `async` block is dropped without being polled",
+        None,
+        "futures do nothing unless you `.await` or poll them",
+    );
+}
+
+/// Checks if the local `id` declared by `local` is never used in its block.
+fn is_unused_local(cx: &LateContext<'_, '_>, local: HirId, id: HirId) -> bool {
+    let map = cx.tcx.hir();
+    if_chain! {
+        if let Some(block) = get_enclosing_block(cx, local);
+        if let Some(Node::Expr(block_expr)) = map.find(map.get_parent_node(block.hir_id));
+        then {
+            local_use_count(cx, id, block_expr) == 0
+        } else {
+            false
+        }
+    }
+}
+
+/// Looks for `.await`s and blocking calls in an async body, without entering closures or nested
+/// async blocks.
+struct AsyncBodyVisitor<'a, 'b, 'tcx> {
+    cx: &'a LateContext<'b, 'tcx>,
+    pass: &'a AsyncBodies,
+    has_await: bool,
+    blocking_calls: Vec<Span>,
+}
+
+impl<'a, 'b, 'tcx> Visitor<'tcx> for AsyncBodyVisitor<'a, 'b, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        let callee = match expr.kind {
+            ExprKind::Match(_, _, MatchSource::AwaitDesugar) => {
+                self.has_await = true;
+                None
+            },
+            ExprKind::Call(func, _) => {
+                if let ExprKind::Path(ref qpath) = func.kind {
+                    self.cx.tables.qpath_res(qpath, func.hir_id).opt_def_id()
+                } else {
+                    None
+                }
+            },
+            ExprKind::MethodCall(..) => self.cx.tables.type_dependent_def_id(expr.hir_id),
+            _ => None,
+        };
+        if let Some(did) = callee {
+            if !in_macro(expr.span) && self.pass.is_blocking(self.cx, did) {
+                self.blocking_calls.push(expr.span);
+            }
+        }
+        walk_expr(self, expr);
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+}
+
+fn check_manual_async_fn<'tcx>(
+    cx: &LateContext<'_, 'tcx>,
+    kind: FnKind<'tcx>,
+    decl: &'tcx FnDecl<'_>,
+    body: &'tcx Body<'_>,
+    span: Span,
+) {
+    let ident = match kind {
+        FnKind::ItemFn(ident, ..) | FnKind::Method(ident, ..) => ident,
+        FnKind::Closure(_) => return,
+    };
+    if_chain! {
+        if let Some(header) = kind.header();
+        if let IsAsync::NotAsync = header.asyncness;
+        if header.constness == Constness::NotConst && header.unsafety == Unsafety::Normal && header.abi == Abi::Rust;
+        if !in_macro(span);
+        if let FnRetTy::Return(ret_ty) = decl.output;
+        if let Some(output) = future_output_ty(cx, ret_ty);
+        if let ExprKind::Block(block, _) = body.value.kind;
+        if block.stmts.is_empty();
+        if let Some(closure) = block.expr;
+        if is_async_block(cx, closure);
+        if let ExprKind::Closure(_, _, async_body_id, ..) = closure.kind;
+        if let Some(head) = snippet_opt(cx, span.until(ident.span));
+        if let Some(fn_pos) = head.rfind("fn");
+        if let Some(sig) = snippet_opt(cx, span.until(ret_ty.span));
+        if let Some(arrow_pos) = sig.rfind("->");
+        if let Some(output_snippet) = snippet_opt(cx, output.span);
+        then {
+            let fn_lo = span.lo() + BytePos(fn_pos as u32);
+            let fn_kw = span.with_lo(fn_lo).with_hi(fn_lo + BytePos(2));
+            let (ret_span, ret) = if let TyKind::Tup([]) = output.kind {
+                // `-> ()` is left out
+                let arrow = sig[..arrow_pos].trim_end().len();
+                (ret_ty.span.with_lo(span.lo() + BytePos(arrow as u32)), String::new())
+            } else {
+                (ret_ty.span, output_snippet)
+            };
+            let async_body = cx.tcx.hir().body(async_body_id);
+            let body_snippet = snippet_block(cx, async_body.value.span, "{ .. }", Some(span));
+            let fix = Fix::new(
+                "make the function `async` and return the output of the future directly",
+                Applicability::MachineApplicable,
+            )
+            .insert_before(fn_kw, "async ")
+            .replace(ret_span, ret)
+            .replace(body.value.span, body_snippet);
+            span_lint_and_fix(
+                cx,
+                MANUAL_ASYNC_FN,
+                span.with_hi(ret_ty.span.hi()),
+                "this function can be simplified using the `async fn` syntax",
+                fix,
+            );
+        }
+    }
+}
+
+/// Returns `T` if `ty` is `impl Future<Output = T>`, possibly with additional lifetime bounds.
+fn future_output_ty<'tcx>(cx: &LateContext<'_, 'tcx>, ty: &'tcx Ty<'tcx>) -> Option<&'tcx Ty<'tcx>> {
+    if_chain! {
+        if let TyKind::Def(item_id, _) = ty.kind;
+        if let ItemKind::OpaqueTy(ref opaque) = cx.tcx.hir().expect_item(item_id.id).kind;
+        let trait_bounds = opaque
+            .bounds
+            .iter()
+            .filter_map(|bound| match bound {
+                GenericBound::Trait(poly_trait_ref, TraitBoundModifier::None) => Some(poly_trait_ref),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if let [future] = trait_bounds.as_slice();
+        if future.trait_ref.path.res.opt_def_id() == cx.tcx.lang_items().future_trait();
+        if let Some(segment) = future.trait_ref.path.segments.last();
+        if let Some(args) = segment.args;
+        if let [binding] = args.bindings;
+        if binding.ident.as_str() == "Output";
+        if let TypeBindingKind::Equality { ty: output } = binding.kind;
+        then {
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
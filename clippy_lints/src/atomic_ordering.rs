@@ -30,6 +30,7 @@ declare_clippy_lint! {
     /// atomic::fence(Ordering::Relaxed);
     /// atomic::compiler_fence(Ordering::Relaxed);
     /// ```
+    #[clippy::version = "1.42.0"]
     pub INVALID_ATOMIC_ORDERING,
     correctness,
     "usage of invalid atomic ordering in atomic loads/stores and memory fences"
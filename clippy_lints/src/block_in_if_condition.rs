@@ -20,6 +20,7 @@ declare_clippy_lint! {
     /// ```rust
     /// if { true } { /* ... */ }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub BLOCK_IN_IF_CONDITION_EXPR,
     style,
     "braces that can be eliminated in conditions, e.g., `if { true } ...`"
@@ -39,6 +40,7 @@ declare_clippy_lint! {
     /// // or
     /// if somefunc(|x| { x == 47 }) {}
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub BLOCK_IN_IF_CONDITION_STMT,
     style,
     "complex blocks in conditions, e.g., `if { let x = true; x } ...`"
@@ -1,15 +1,21 @@
 //! calculate cognitive complexity and warn about overly complex functions
 
 use rustc_ast::ast::Attribute;
+use rustc_hir::def::Res;
+use rustc_hir::def_id::DefId;
 use rustc_hir::intravisit::{walk_expr, FnKind, NestedVisitorMap, Visitor};
-use rustc_hir::{Body, Expr, ExprKind, FnDecl, HirId};
+use rustc_hir::{
+    Arm, BinOpKind, Block, Body, Destination, Expr, ExprKind, FnDecl, Guard, HirId, LoopSource, MatchSource,
+};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_middle::hir::map::Map;
+use rustc_middle::ty;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::source_map::Span;
 use rustc_span::BytePos;
 
-use crate::utils::{is_type_diagnostic_item, snippet_opt, span_lint_and_help, LimitStack};
+use crate::utils::conf::CognitiveComplexityWeights;
+use crate::utils::{higher, in_macro, qpath_res, snippet_opt, span_lint_and_then, walk_ptrs_ty, LimitStack};
 
 declare_clippy_lint! {
     /// **What it does:** Checks for methods with high cognitive complexity.
@@ -17,24 +23,48 @@ declare_clippy_lint! {
     /// **Why is this bad?** Methods of high cognitive complexity tend to be hard to
     /// both read and maintain. Also LLVM will tend to optimize small methods better.
     ///
+    /// The complexity is the sum of the points of the constructs that make code harder
+    /// to follow: `if`, `else`, `match`, loops, sequences of `&&` or `||`, recursive calls
+    /// and `break`/`continue` to a label. `if`, `match` and loops add more points the
+    /// deeper they are nested in other conditionals, loops and closures. The points of
+    /// each construct can be changed with the `cognitive-complexity-weights` option of
+    /// `clippy.toml`, and the warning shows the constructs that add the most.
+    ///
+    /// This scoring replaced a simpler one that didn't account for nesting, so nested
+    /// code scores higher than with earlier versions of Clippy while the default
+    /// `cognitive-complexity-threshold` of 25 stayed the same.
+    ///
     /// **Known problems:** Sometimes it's hard to find a way to reduce the
     /// complexity.
     ///
+    /// A `match` on an enum that is nested in a `match` on another enum is
+    /// often easy to follow, but adds points for the nesting. Set
+    /// `cognitive-complexity-flat-enum-matches` to `true` to not count the arms of
+    /// those `match`es as nesting.
+    ///
     /// **Example:** No. You'll see it when you get the warning.
+    #[clippy::version = "pre 1.29.0"]
     pub COGNITIVE_COMPLEXITY,
     nursery,
     "functions that should be split up into multiple functions"
 }
 
+/// The number of constructs listed in the warning
+const MAX_NOTES: usize = 3;
+
 pub struct CognitiveComplexity {
     limit: LimitStack,
+    weights: CognitiveComplexityWeights,
+    flat_enum_matches: bool,
 }
 
 impl CognitiveComplexity {
     #[must_use]
-    pub fn new(limit: u64) -> Self {
+    pub fn new(limit: u64, weights: CognitiveComplexityWeights, flat_enum_matches: bool) -> Self {
         Self {
             limit: LimitStack::new(limit),
+            weights,
+            flat_enum_matches,
         }
     }
 }
@@ -50,31 +80,27 @@ impl CognitiveComplexity {
         decl: &'tcx FnDecl<'_>,
         body: &'tcx Body<'_>,
         body_span: Span,
+        def_id: DefId,
     ) {
         if body_span.from_expansion() {
             return;
         }
 
-        let expr = &body.value;
-
-        let mut helper = CCHelper { cc: 1, returns: 0 };
-        helper.visit_expr(expr);
-        let CCHelper { cc, returns } = helper;
-        let ret_ty = cx.tables.node_type(expr.hir_id);
-        let ret_adjust = if is_type_diagnostic_item(cx, ret_ty, sym!(result_type)) {
-            returns
-        } else {
-            #[allow(clippy::integer_division)]
-            (returns / 2)
+        let mut helper = CCHelper {
+            cx,
+            weights: self.weights,
+            flat_enum_matches: self.flat_enum_matches,
+            fn_def_id: def_id,
+            nesting: 0,
+            bool_op: None,
+            for_loop_head: None,
+            increments: Vec::new(),
         };
+        helper.visit_expr(&body.value);
+        let mut increments = helper.increments;
+        let cc = increments.iter().map(|increment| increment.points).sum::<u64>();
 
-        let mut rust_cc = cc;
-        // prevent degenerate cases where unreachable code contains `return` statements
-        if rust_cc >= ret_adjust {
-            rust_cc -= ret_adjust;
-        }
-
-        if rust_cc > self.limit.limit() {
+        if cc > self.limit.limit() {
             let fn_span = match kind {
                 FnKind::ItemFn(ident, _, _, _, _) | FnKind::Method(ident, _, _, _) => ident.span,
                 FnKind::Closure(_) => {
@@ -96,17 +122,23 @@ impl CognitiveComplexity {
                 },
             };
 
-            span_lint_and_help(
+            increments.retain(|increment| increment.points > 0);
+            increments.sort_by_key(|increment| (std::cmp::Reverse(increment.points), increment.span.lo()));
+            span_lint_and_then(
                 cx,
                 COGNITIVE_COMPLEXITY,
                 fn_span,
                 &format!(
                     "the function has a cognitive complexity of ({}/{})",
-                    rust_cc,
+                    cc,
                     self.limit.limit()
                 ),
-                None,
-                "you could split it up into multiple smaller functions",
+                |diag| {
+                    diag.help("you could split it up into multiple smaller functions");
+                    for increment in increments.iter().take(MAX_NOTES) {
+                        diag.span_note(increment.span, &increment.to_string());
+                    }
+                },
             );
         }
     }
@@ -122,9 +154,9 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for CognitiveComplexity {
         span: Span,
         hir_id: HirId,
     ) {
-        let def_id = cx.tcx.hir().local_def_id(hir_id);
-        if !cx.tcx.has_attr(def_id.to_def_id(), sym!(test)) {
-            self.check(cx, kind, decl, body, span);
+        let def_id = cx.tcx.hir().local_def_id(hir_id).to_def_id();
+        if !cx.tcx.has_attr(def_id, sym!(test)) {
+            self.check(cx, kind, decl, body, span, def_id);
         }
     }
 
@@ -136,28 +168,248 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for CognitiveComplexity {
     }
 }
 
-struct CCHelper {
-    cc: u64,
-    returns: u64,
+/// A construct that adds to the complexity of a function.
+struct Increment {
+    span: Span,
+    /// What the construct is, e.g. "`for` loop"
+    kind: &'static str,
+    points: u64,
+    /// How many levels deep the construct is nested, if its points depend on that
+    nesting: u64,
+}
+
+impl std::fmt::Display for Increment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "this {} adds {}", self.kind, self.points)?;
+        match self.nesting {
+            0 => Ok(()),
+            1 => write!(f, " (nested 1 level deep)"),
+            nesting => write!(f, " (nested {} levels deep)", nesting),
+        }
+    }
+}
+
+struct CCHelper<'a, 'tcx> {
+    cx: &'a LateContext<'a, 'tcx>,
+    weights: CognitiveComplexityWeights,
+    flat_enum_matches: bool,
+    /// The function that is checked, calls to it are recursive
+    fn_def_id: DefId,
+    nesting: u64,
+    /// The operator of the `&&`/`||` sequence the current expression is an operand of
+    bool_op: Option<BinOpKind>,
+    /// The span of `for pat in iter` of the `for` loop that is visited next
+    for_loop_head: Option<Span>,
+    increments: Vec<Increment>,
+}
+
+impl<'a, 'tcx> CCHelper<'a, 'tcx> {
+    fn add(&mut self, span: Span, kind: &'static str, points: u64) {
+        self.increments.push(Increment {
+            span,
+            kind,
+            points,
+            nesting: 0,
+        });
+    }
+
+    /// Adds a construct whose points grow with the nesting level.
+    fn add_nested(&mut self, span: Span, kind: &'static str, points: u64) {
+        self.increments.push(Increment {
+            span,
+            kind,
+            points: points + self.nesting * self.weights.nesting,
+            nesting: self.nesting,
+        });
+    }
+
+    /// Visits `expr` one nesting level deeper.
+    fn visit_nested(&mut self, expr: &'tcx Expr<'tcx>) {
+        self.nesting += 1;
+        self.visit_expr(expr);
+        self.nesting -= 1;
+    }
+
+    /// Visits an `if` or `if let`. `is_else_if` is true if it directly follows an `else`. The
+    /// arms are the `then` block and the `else` block, which is empty if there is none.
+    fn visit_if(
+        &mut self,
+        expr: &'tcx Expr<'tcx>,
+        scrutinee: &'tcx Expr<'tcx>,
+        arms: &'tcx [Arm<'tcx>],
+        is_else_if: bool,
+    ) {
+        let if_span = head_span(expr, scrutinee);
+        if is_else_if {
+            // `else if` continues the chain of conditions, it isn't nested in the `if` before it
+            self.add(if_span, "`else if`", self.weights.if_);
+        } else {
+            self.add_nested(if_span, "`if`", self.weights.if_);
+        }
+        self.visit_expr(scrutinee);
+        let (then, els) = match arms {
+            [then, els] => (then, els),
+            _ => return walk_arms(self, arms),
+        };
+        self.visit_nested(then.body);
+        if !has_else_clause(expr) {
+            return;
+        }
+        match els.body.kind {
+            ExprKind::Match(scrutinee, arms, MatchSource::IfDesugar { .. })
+            | ExprKind::Match(scrutinee, arms, MatchSource::IfLetDesugar { .. })
+                if !in_macro(els.body.span) =>
+            {
+                self.visit_if(els.body, scrutinee, arms, true);
+            },
+            _ => {
+                self.add(els.body.span, "`else`", self.weights.else_);
+                self.visit_nested(els.body);
+            },
+        }
+    }
+
+    fn visit_match(&mut self, expr: &'tcx Expr<'tcx>, scrutinee: &'tcx Expr<'tcx>, arms: &'tcx [Arm<'tcx>]) {
+        self.add_nested(head_span(expr, scrutinee), "`match`", self.weights.match_);
+        self.visit_expr(scrutinee);
+        let flat = self.flat_enum_matches
+            && match walk_ptrs_ty(self.cx.tables.expr_ty(scrutinee)).kind {
+                ty::Adt(def, _) => def.is_enum(),
+                _ => false,
+            };
+        if flat {
+            walk_arms(self, arms);
+        } else {
+            self.nesting += 1;
+            walk_arms(self, arms);
+            self.nesting -= 1;
+        }
+    }
+
+    fn is_recursive_call(&self, expr: &'tcx Expr<'tcx>) -> bool {
+        match expr.kind {
+            ExprKind::Call(func, _) => match func.kind {
+                ExprKind::Path(ref qpath) => match qpath_res(self.cx, qpath, func.hir_id) {
+                    Res::Def(_, def_id) => def_id == self.fn_def_id,
+                    _ => false,
+                },
+                _ => false,
+            },
+            ExprKind::MethodCall(..) => self.cx.tables.type_dependent_def_id(expr.hir_id) == Some(self.fn_def_id),
+            _ => false,
+        }
+    }
 }
 
-impl<'tcx> Visitor<'tcx> for CCHelper {
+impl<'a, 'tcx> Visitor<'tcx> for CCHelper<'a, 'tcx> {
     type Map = Map<'tcx>;
 
-    fn visit_expr(&mut self, e: &'tcx Expr<'_>) {
-        walk_expr(self, e);
-        match e.kind {
-            ExprKind::Match(_, ref arms, _) => {
-                if arms.len() > 1 {
-                    self.cc += 1;
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        let bool_op = self.bool_op.take();
+        if in_macro(expr.span) {
+            return walk_expr(self, expr);
+        }
+        match expr.kind {
+            ExprKind::Binary(op, left, right) if op.node == BinOpKind::And || op.node == BinOpKind::Or => {
+                if bool_op != Some(op.node) {
+                    let kind = if op.node == BinOpKind::And {
+                        "sequence of `&&`"
+                    } else {
+                        "sequence of `||`"
+                    };
+                    self.add(expr.span, kind, self.weights.bool_ops);
+                }
+                self.bool_op = Some(op.node);
+                self.visit_expr(left);
+                self.bool_op = Some(op.node);
+                self.visit_expr(right);
+                self.bool_op = None;
+            },
+            ExprKind::Match(scrutinee, arms, MatchSource::IfDesugar { .. })
+            | ExprKind::Match(scrutinee, arms, MatchSource::IfLetDesugar { .. }) => {
+                self.visit_if(expr, scrutinee, arms, false);
+            },
+            ExprKind::Match(scrutinee, arms, MatchSource::Normal) => self.visit_match(expr, scrutinee, arms),
+            ExprKind::Loop(block, _, source) => {
+                let (span, kind) = match source {
+                    LoopSource::Loop => (expr.span.until(block.span), "`loop`"),
+                    LoopSource::While | LoopSource::WhileLet => (loop_head_span(expr, block), "`while` loop"),
+                    LoopSource::ForLoop => (self.for_loop_head.take().unwrap_or(expr.span), "`for` loop"),
+                };
+                self.add_nested(span, kind, self.weights.loop_);
+                self.nesting += 1;
+                self.visit_block(block);
+                self.nesting -= 1;
+            },
+            ExprKind::Match(iter, arms, MatchSource::ForLoopDesugar) => {
+                self.visit_expr(iter);
+                if let Some((_, arg, _)) = higher::for_loop(expr) {
+                    // the loop is the body of the only arm
+                    self.for_loop_head = Some(expr.span.with_hi(arg.span.hi()));
+                }
+                walk_arms(self, arms);
+            },
+            ExprKind::Closure(_, _, body_id, _, movability) => {
+                let body = self.cx.tcx.hir().body(body_id);
+                // `async` blocks are generators, they don't make the code harder to follow
+                if movability.is_none() {
+                    self.visit_nested(&body.value);
+                } else {
+                    self.visit_expr(&body.value);
                 }
-                self.cc += arms.iter().filter(|arm| arm.guard.is_some()).count() as u64;
             },
-            ExprKind::Ret(_) => self.returns += 1,
-            _ => {},
+            ExprKind::Break(Destination { label: Some(_), .. }, _) => {
+                self.add(expr.span, "`break` to a label", self.weights.labeled_jumps);
+                walk_expr(self, expr);
+            },
+            ExprKind::Continue(Destination { label: Some(_), .. }) => {
+                self.add(expr.span, "`continue` to a label", self.weights.labeled_jumps);
+            },
+            // `x.await` is a loop that isn't written by the user
+            ExprKind::Match(scrutinee, _, MatchSource::AwaitDesugar) => self.visit_expr(scrutinee),
+            _ => {
+                if self.is_recursive_call(expr) {
+                    self.add(expr.span, "recursive call", self.weights.recursion);
+                }
+                walk_expr(self, expr);
+            },
         }
     }
+
     fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
         NestedVisitorMap::None
     }
 }
+
+fn walk_arms<'tcx>(helper: &mut CCHelper<'_, 'tcx>, arms: &'tcx [Arm<'tcx>]) {
+    for arm in arms {
+        if let Some(Guard::If(ref guard)) = arm.guard {
+            helper.visit_expr(guard);
+        }
+        helper.visit_expr(arm.body);
+    }
+}
+
+fn has_else_clause(expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Match(_, _, MatchSource::IfDesugar { contains_else_clause })
+        | ExprKind::Match(_, _, MatchSource::IfLetDesugar { contains_else_clause }) => contains_else_clause,
+        _ => false,
+    }
+}
+
+/// Returns the span of the head of an `if`, `if let` or `match`, e.g. `if x > 0`.
+fn head_span(expr: &Expr<'_>, scrutinee: &Expr<'_>) -> Span {
+    expr.span.with_hi(scrutinee.span.hi())
+}
+
+/// Returns the span of the head of a `while` or `while let` loop, e.g. `while x > 0`.
+fn loop_head_span(expr: &Expr<'_>, block: &Block<'_>) -> Span {
+    match block.expr {
+        Some(&Expr {
+            kind: ExprKind::Match(scrutinee, ..),
+            ..
+        }) => head_span(expr, scrutinee),
+        _ => expr.span.until(block.span),
+    }
+}
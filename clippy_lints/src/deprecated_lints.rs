@@ -1,6 +1,11 @@
 macro_rules! declare_deprecated_lint {
-    (pub $name: ident, $_reason: expr) => {
-        declare_lint!(pub $name, Allow, "deprecated lint")
+    { $(#[$attr:meta])* pub $name: ident, $_reason: expr } => {
+        declare_lint! {
+            $(#[$attr])*
+            pub $name,
+            Allow,
+            "deprecated lint"
+        }
     }
 }
 
@@ -9,6 +14,7 @@ declare_deprecated_lint! {
     ///
     /// **Deprecation reason:** This used to check for `assert!(a == b)` and recommend
     /// replacement with `assert_eq!(a, b)`, but this is no longer needed after RFC 2011.
+    #[clippy::version = "pre 1.29.0"]
     pub SHOULD_ASSERT_EQ,
     "`assert!()` will be more flexible with RFC 2011"
 }
@@ -18,6 +24,7 @@ declare_deprecated_lint! {
     ///
     /// **Deprecation reason:** This used to check for `Vec::extend`, which was slower than
     /// `Vec::extend_from_slice`. Thanks to specialization, this is no longer true.
+    #[clippy::version = "pre 1.29.0"]
     pub EXTEND_FROM_SLICE,
     "`.extend_from_slice(_)` is a faster way to extend a Vec by a slice"
 }
@@ -29,6 +36,7 @@ declare_deprecated_lint! {
     /// an infinite iterator, which is better expressed by `iter::repeat`,
     /// but the method has been removed for `Iterator::step_by` which panics
     /// if given a zero
+    #[clippy::version = "pre 1.29.0"]
     pub RANGE_STEP_BY_ZERO,
     "`iterator.step_by(0)` panics nowadays"
 }
@@ -38,6 +46,7 @@ declare_deprecated_lint! {
     ///
     /// **Deprecation reason:** This used to check for `Vec::as_slice`, which was unstable with good
     /// stable alternatives. `Vec::as_slice` has now been stabilized.
+    #[clippy::version = "pre 1.29.0"]
     pub UNSTABLE_AS_SLICE,
     "`Vec::as_slice` has been stabilized in 1.7"
 }
@@ -47,6 +56,7 @@ declare_deprecated_lint! {
     ///
     /// **Deprecation reason:** This used to check for `Vec::as_mut_slice`, which was unstable with good
     /// stable alternatives. `Vec::as_mut_slice` has now been stabilized.
+    #[clippy::version = "pre 1.29.0"]
     pub UNSTABLE_AS_MUT_SLICE,
     "`Vec::as_mut_slice` has been stabilized in 1.7"
 }
@@ -57,6 +67,7 @@ declare_deprecated_lint! {
     /// **Deprecation reason:** This used to check for `.to_string()` method calls on values
     /// of type `&str`. This is not unidiomatic and with specialization coming, `to_string` could be
     /// specialized to be as efficient as `to_owned`.
+    #[clippy::version = "pre 1.29.0"]
     pub STR_TO_STRING,
     "using `str::to_string` is common even today and specialization will likely happen soon"
 }
@@ -67,6 +78,7 @@ declare_deprecated_lint! {
     /// **Deprecation reason:** This used to check for `.to_string()` method calls on values
     /// of type `String`. This is not unidiomatic and with specialization coming, `to_string` could be
     /// specialized to be as efficient as `clone`.
+    #[clippy::version = "pre 1.29.0"]
     pub STRING_TO_STRING,
     "using `string::to_string` is common even today and specialization will likely happen soon"
 }
@@ -78,6 +90,7 @@ declare_deprecated_lint! {
     /// between non-pointer types of differing alignment is well-defined behavior (it's semantically
     /// equivalent to a memcpy). This lint has thus been refactored into two separate lints:
     /// cast_ptr_alignment and transmute_ptr_to_ptr.
+    #[clippy::version = "pre 1.29.0"]
     pub MISALIGNED_TRANSMUTE,
     "this lint has been split into cast_ptr_alignment and transmute_ptr_to_ptr"
 }
@@ -88,6 +101,7 @@ declare_deprecated_lint! {
     /// **Deprecation reason:** This lint is too subjective, not having a good reason for being in clippy.
     /// Additionally, compound assignment operators may be overloaded separately from their non-assigning
     /// counterparts, so this lint may suggest a change in behavior or the code may not compile.
+    #[clippy::version = "pre 1.29.0"]
     pub ASSIGN_OPS,
     "using compound assignment operators (e.g., `+=`) is harmless"
 }
@@ -98,6 +112,7 @@ declare_deprecated_lint! {
     /// **Deprecation reason:** The original rule will only lint for `if let`. After
     /// making it support to lint `match`, naming as `if let` is not suitable for it.
     /// So, this lint is deprecated.
+    #[clippy::version = "pre 1.29.0"]
     pub IF_LET_REDUNDANT_PATTERN_MATCHING,
     "this lint has been changed to redundant_pattern_matching"
 }
@@ -109,6 +124,7 @@ declare_deprecated_lint! {
     /// Vec::with_capacity(n); vec.set_len(n);` with `let vec = vec![0; n];`. The
     /// replacement has very different performance characteristics so the lint is
     /// deprecated.
+    #[clippy::version = "pre 1.29.0"]
     pub UNSAFE_VECTOR_INITIALIZATION,
     "the replacement suggested by this lint had substantially different behavior"
 }
@@ -118,6 +134,7 @@ declare_deprecated_lint! {
     ///
     /// **Deprecation reason:** This lint has been superseded by the warn-by-default
     /// `invalid_value` rustc lint.
+    #[clippy::version = "pre 1.29.0"]
     pub INVALID_REF,
     "superseded by rustc lint `invalid_value`"
 }
@@ -126,6 +143,7 @@ declare_deprecated_lint! {
     /// **What it does:** Nothing. This lint has been deprecated.
     ///
     /// **Deprecation reason:** This lint has been superseded by #[must_use] in rustc.
+    #[clippy::version = "pre 1.29.0"]
     pub UNUSED_COLLECT,
     "`collect` has been marked as #[must_use] in rustc and that covers all cases of this lint"
 }
@@ -135,6 +153,7 @@ declare_deprecated_lint! {
     ///
     /// **Deprecation reason:** This lint has been uplifted to rustc and is now called
     /// `array_into_iter`.
+    #[clippy::version = "1.41.0"]
     pub INTO_ITER_ON_ARRAY,
     "this lint has been uplifted to rustc and is now called `array_into_iter`"
 }
@@ -144,6 +163,7 @@ declare_deprecated_lint! {
     ///
     /// **Deprecation reason:** This lint has been uplifted to rustc and is now called
     /// `unused_labels`.
+    #[clippy::version = "1.42.0"]
     pub UNUSED_LABEL,
     "this lint has been uplifted to rustc and is now called `unused_labels`"
 }
@@ -152,6 +172,7 @@ declare_deprecated_lint! {
     /// **What it does:** Nothing. This lint has been deprecated.
     ///
     /// **Deprecation reason:** Associated-constants are now preferred.
+    #[clippy::version = "1.44.0"]
     pub REPLACE_CONSTS,
     "associated-constants `MIN`/`MAX` of integers are prefer to `{min,max}_value()` and module constants"
 }
@@ -1,4 +1,4 @@
-use crate::utils::{get_parent_expr, implements_trait, snippet, span_lint_and_sugg};
+use crate::utils::{get_parent_expr, implements_trait, snippet_with_applicability, span_lint_and_sugg};
 use if_chain::if_chain;
 use rustc_ast::util::parser::{ExprPrecedence, PREC_POSTFIX, PREC_PREFIX};
 use rustc_errors::Applicability;
@@ -29,6 +29,7 @@ declare_clippy_lint! {
     /// ```rust,ignore
     /// let _ = d.unwrap().deref();
     /// ```
+    #[clippy::version = "1.44.0"]
     pub EXPLICIT_DEREF_METHODS,
     pedantic,
     "Explicit use of deref or deref_mut method while not in a method chain."
@@ -79,14 +80,18 @@ fn lint_deref(cx: &LateContext<'_, '_>, method_name: &str, call_expr: &Expr<'_>,
                 .deref_trait()
                 .map_or(false, |id| implements_trait(cx, cx.tables.expr_ty(&call_expr), id, &[]))
             {
+                let mut applicability = Applicability::MachineApplicable;
                 span_lint_and_sugg(
                     cx,
                     EXPLICIT_DEREF_METHODS,
                     expr_span,
                     "explicit deref method call",
                     "try this",
-                    format!("&*{}", &snippet(cx, var_span, "..")),
-                    Applicability::MachineApplicable,
+                    format!(
+                        "&*{}",
+                        &snippet_with_applicability(cx, var_span, "..", &mut applicability)
+                    ),
+                    applicability,
                 );
             }
         },
@@ -97,14 +102,18 @@ fn lint_deref(cx: &LateContext<'_, '_>, method_name: &str, call_expr: &Expr<'_>,
                 .deref_mut_trait()
                 .map_or(false, |id| implements_trait(cx, cx.tables.expr_ty(&call_expr), id, &[]))
             {
+                let mut applicability = Applicability::MachineApplicable;
                 span_lint_and_sugg(
                     cx,
                     EXPLICIT_DEREF_METHODS,
                     expr_span,
                     "explicit deref_mut method call",
                     "try this",
-                    format!("&mut *{}", &snippet(cx, var_span, "..")),
-                    Applicability::MachineApplicable,
+                    format!(
+                        "&mut *{}",
+                        &snippet_with_applicability(cx, var_span, "..", &mut applicability)
+                    ),
+                    applicability,
                 );
             }
         },
@@ -18,6 +18,7 @@ declare_clippy_lint! {
     /// foo((0));
     /// ((1, 2));
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub DOUBLE_PARENS,
     complexity,
     "Warn on unnecessary double parentheses"
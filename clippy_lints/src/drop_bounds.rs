@@ -27,6 +27,7 @@ declare_clippy_lint! {
     /// ```rust
     /// fn foo<T: Drop>() {}
     /// ```
+    #[clippy::version = "1.35.0"]
     pub DROP_BOUNDS,
     correctness,
     "Bounds of the form `T: Drop` are useless"
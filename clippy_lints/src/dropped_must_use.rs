@@ -0,0 +1,381 @@
+use crate::utils::{
+    get_trait_def_id, implements_trait, in_macro, is_must_use_func_call, is_must_use_ty, match_def_path, must_use_attr,
+    paths, span_lint_and_help, span_lint_and_note,
+};
+use rustc_hir::def::Res;
+use rustc_hir::intravisit::{walk_expr, NestedVisitorMap, Visitor};
+use rustc_hir::{Block, Expr, ExprKind, HirId, Local, MatchSource, PatKind, QPath, Stmt, StmtKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::hir::map::Map;
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::subst::GenericArgKind;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::source_map::Span;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `#[must_use]` values, and values of the types
+    /// listed in `must-use-types`, that are stored in a local but go out of scope
+    /// without being used. This includes values that are overwritten before they
+    /// are read, values that are not read on the way to a `return`, `?`, `break`
+    /// or `continue` that leaves their block, and statements like `builder.option(x);`
+    /// that drop a value of one of the configured types.
+    ///
+    /// **Why is this bad?** Binding a value to an underscore-prefixed name silences
+    /// both the `unused_must_use` and the `unused_variables` lint, but the value is
+    /// still ignored. Builder APIs that return `Self` by value are easy to misuse
+    /// the same way: calling a setter as a statement configures a copy that is
+    /// dropped right away.
+    ///
+    /// **Known problems:** The analysis walks the HIR of the block the local is
+    /// declared in instead of its control flow graph. The arms of an `if` or `match`
+    /// are checked separately, but a read in one arm counts as a use for the code
+    /// after it, and a read in a loop counts for all of its iterations. Guards and
+    /// other values whose type implements `Drop` are held for their side effects
+    /// and are ignored, and so are lazy values like iterators and futures.
+    ///
+    /// **Example:**
+    /// ```rust,ignore
+    /// let _res = file.write_all(b"data");
+    ///
+    /// let res = file.sync_all();
+    /// check_state()?;
+    /// res?;
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// file.write_all(b"data")?;
+    ///
+    /// let res = file.sync_all();
+    /// res?;
+    /// check_state()?;
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub DROPPED_MUST_USE,
+    pedantic,
+    "a value that must be used goes out of scope without being used"
+}
+
+/// Why a value must be used.
+#[derive(Clone, Copy, PartialEq)]
+enum Reason {
+    /// The type of the value is `#[must_use]`.
+    Type,
+    /// The value is the result of a `#[must_use]` function.
+    Fn,
+    /// The type of the value is listed in `must-use-types`.
+    Config,
+}
+
+/// What happens to the value stored in a local after its declaration.
+enum Fate {
+    Used,
+    /// Dropped at an early exit, or at the end of the scope.
+    Dropped(Option<Span>),
+    /// Overwritten by the given assignment before being read.
+    Overwritten(Span),
+}
+
+pub struct DroppedMustUse {
+    /// The paths of the configured types, split into their segments.
+    types: Vec<Vec<String>>,
+}
+
+impl DroppedMustUse {
+    pub fn new(types: &[String]) -> Self {
+        Self {
+            types: types
+                .iter()
+                .map(|path| path.split("::").map(ToString::to_string).collect())
+                .collect(),
+        }
+    }
+
+    fn is_configured(&self, cx: &LateContext<'_, '_>, ty: Ty<'_>) -> bool {
+        if let ty::Adt(adt, _) = ty.kind {
+            self.types.iter().any(|path| {
+                let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+                match_def_path(cx, adt.did, &path)
+            })
+        } else {
+            false
+        }
+    }
+
+    fn reason<'tcx>(&self, cx: &LateContext<'_, 'tcx>, init: &Expr<'_>, ty: Ty<'tcx>) -> Option<Reason> {
+        if self.is_configured(cx, ty) {
+            Some(Reason::Config)
+        } else if is_lazy(cx, ty) || holds_resource(cx, ty) {
+            None
+        } else if is_must_use_ty(cx, ty) {
+            Some(Reason::Type)
+        } else if is_must_use_func_call(cx, init) {
+            Some(Reason::Fn)
+        } else {
+            None
+        }
+    }
+
+    fn check_local<'tcx>(
+        &self,
+        cx: &LateContext<'_, 'tcx>,
+        local: &Local<'_>,
+        rest: &'tcx [Stmt<'_>],
+        tail: Option<&'tcx Expr<'_>>,
+    ) {
+        let (id, name, init) = match (&local.pat.kind, local.init) {
+            (PatKind::Binding(_, id, name, None), Some(init)) => (*id, name.as_str(), init),
+            _ => return,
+        };
+        let ty = cx.tables.expr_ty(init);
+        let reason = match self.reason(cx, init, ty) {
+            Some(reason) => reason,
+            None => return,
+        };
+        let what = if reason == Reason::Config {
+            format!("value of type `{}`", ty)
+        } else {
+            "`#[must_use]` value".to_string()
+        };
+        // Unused variables and assignments that are never read are already linted by rustc,
+        // unless the name starts with an underscore.
+        let silenced = name.starts_with('_');
+        match fate(cx, id, rest, tail) {
+            Fate::Used => {},
+            Fate::Dropped(None) if silenced => span_lint_and_help(
+                cx,
+                DROPPED_MUST_USE,
+                local.pat.span,
+                &format!("the {} stored in `{}` is never used", what, name),
+                None,
+                "use the value, or discard it explicitly with `let _ = ...`",
+            ),
+            // Values computed by `#[must_use]` functions may simply be pure, dropping them on an
+            // early exit is fine.
+            Fate::Dropped(Some(exit)) if reason != Reason::Fn => span_lint_and_note(
+                cx,
+                DROPPED_MUST_USE,
+                local.pat.span,
+                &format!("the {} stored in `{}` may be dropped without being used", what, name),
+                Some(exit),
+                "the value goes out of scope here",
+            ),
+            Fate::Overwritten(assign) if silenced => span_lint_and_note(
+                cx,
+                DROPPED_MUST_USE,
+                local.pat.span,
+                &format!("the {} stored in `{}` is overwritten before it is used", what, name),
+                Some(assign),
+                "the value is overwritten here",
+            ),
+            Fate::Dropped(_) | Fate::Overwritten(_) => {},
+        }
+    }
+
+    fn check_discarded(&self, cx: &LateContext<'_, '_>, expr: &Expr<'_>) {
+        let ty = cx.tables.expr_ty(expr);
+        // rustc already warns about discarded `#[must_use]` values
+        if self.is_configured(cx, ty) && !is_must_use_ty(cx, ty) && !is_must_use_func_call(cx, expr) {
+            span_lint_and_help(
+                cx,
+                DROPPED_MUST_USE,
+                expr.span,
+                &format!("this value of type `{}` is dropped without being used", ty),
+                None,
+                "use the returned value, or discard it explicitly with `let _ = ...`",
+            );
+        }
+    }
+}
+
+impl_lint_pass!(DroppedMustUse => [DROPPED_MUST_USE]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for DroppedMustUse {
+    fn check_block(&mut self, cx: &LateContext<'a, 'tcx>, block: &'tcx Block<'_>) {
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            if in_macro(stmt.span) || in_external_macro(cx.sess(), stmt.span) {
+                continue;
+            }
+            match stmt.kind {
+                StmtKind::Local(ref local) => self.check_local(cx, local, &block.stmts[i + 1..], block.expr),
+                StmtKind::Semi(ref expr) => self.check_discarded(cx, expr),
+                StmtKind::Expr(_) | StmtKind::Item(_) => {},
+            }
+        }
+    }
+}
+
+/// Iterators and futures do nothing until they are used, dropping them early is harmless.
+fn is_lazy<'tcx>(cx: &LateContext<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
+    let is_iterator = get_trait_def_id(cx, &paths::ITERATOR).map_or(false, |id| implements_trait(cx, ty, id, &[]));
+    let is_future = cx
+        .tcx
+        .lang_items()
+        .future_trait()
+        .map_or(false, |id| implements_trait(cx, ty, id, &[]));
+    is_iterator || is_future
+}
+
+/// Is the value held for the side effects of its `Drop` impl, like a lock guard?
+fn holds_resource<'tcx>(cx: &LateContext<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
+    let has_dtor = |ty: Ty<'_>| matches!(ty.kind, ty::Adt(adt, _) if adt.has_dtor(cx.tcx));
+    has_dtor(ty)
+        || ty.walk().any(|arg| match arg.unpack() {
+            GenericArgKind::Type(inner) => {
+                has_dtor(inner)
+                    && matches!(inner.kind, ty::Adt(adt, _) if must_use_attr(&cx.tcx.get_attrs(adt.did)).is_some())
+            },
+            GenericArgKind::Lifetime(_) | GenericArgKind::Const(_) => false,
+        })
+}
+
+/// Follows the statements after the declaration of the local `id` until it is read, overwritten
+/// or goes out of scope.
+fn fate<'tcx>(cx: &LateContext<'_, 'tcx>, id: HirId, rest: &'tcx [Stmt<'_>], tail: Option<&'tcx Expr<'_>>) -> Fate {
+    for stmt in rest {
+        let mut visitor = UseVisitor::new(cx, id);
+        match stmt.kind {
+            StmtKind::Semi(ref expr) | StmtKind::Expr(ref expr) => {
+                if let ExprKind::Assign(ref lhs, ref rhs, _) = expr.kind {
+                    if is_local(lhs, id) {
+                        visitor.visit_expr(rhs);
+                        return visitor.fate().unwrap_or(Fate::Overwritten(expr.span));
+                    }
+                }
+                visitor.visit_expr(expr);
+            },
+            StmtKind::Local(ref local) => visitor.visit_local(local),
+            StmtKind::Item(_) => {},
+        }
+        if let Some(fate) = visitor.fate() {
+            return fate;
+        }
+    }
+    if let Some(tail) = tail {
+        let mut visitor = UseVisitor::new(cx, id);
+        visitor.visit_expr(tail);
+        if let Some(fate) = visitor.fate() {
+            return fate;
+        }
+    }
+    Fate::Dropped(None)
+}
+
+fn is_local(expr: &Expr<'_>, id: HirId) -> bool {
+    matches!(expr.kind, ExprKind::Path(QPath::Resolved(None, ref path)) if path.res == Res::Local(id))
+}
+
+/// Looks for reads of a local and for expressions that leave the enclosing block before the local
+/// is read.
+struct UseVisitor<'a, 'b, 'tcx> {
+    cx: &'a LateContext<'b, 'tcx>,
+    id: HirId,
+    /// Whether the local is read on the current path.
+    used: bool,
+    /// The first exit that is reached without reading the local.
+    exit: Option<Span>,
+    /// The loops and labeled blocks inside of the visited code, a `break` or `continue` to these
+    /// doesn't leave the block.
+    targets: Vec<HirId>,
+    closure_depth: u32,
+}
+
+impl<'a, 'b, 'tcx> UseVisitor<'a, 'b, 'tcx> {
+    fn new(cx: &'a LateContext<'b, 'tcx>, id: HirId) -> Self {
+        Self {
+            cx,
+            id,
+            used: false,
+            exit: None,
+            targets: Vec::new(),
+            closure_depth: 0,
+        }
+    }
+
+    fn fate(&self) -> Option<Fate> {
+        match self.exit {
+            Some(exit) => Some(Fate::Dropped(Some(exit))),
+            None if self.used => Some(Fate::Used),
+            None => None,
+        }
+    }
+
+    fn exit(&mut self, span: Span) {
+        if self.closure_depth == 0 && !self.used && self.exit.is_none() {
+            self.exit = Some(span);
+        }
+    }
+
+    fn visit_target(&mut self, target: HirId, expr: &'tcx Expr<'_>) {
+        self.targets.push(target);
+        walk_expr(self, expr);
+        self.targets.pop();
+    }
+}
+
+impl<'a, 'b, 'tcx> Visitor<'tcx> for UseVisitor<'a, 'b, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        // the rest of this path is irrelevant
+        if self.used || self.exit.is_some() {
+            return;
+        }
+        if is_local(expr, self.id) {
+            self.used = true;
+            return;
+        }
+        match expr.kind {
+            ExprKind::Assign(ref lhs, ref rhs, _) if is_local(lhs, self.id) => self.visit_expr(rhs),
+            ExprKind::Match(ref scrutinee, _, MatchSource::TryDesugar) => {
+                self.visit_expr(scrutinee);
+                self.exit(expr.span);
+            },
+            ExprKind::Match(ref scrutinee, arms, _) => {
+                self.visit_expr(scrutinee);
+                if self.used {
+                    return;
+                }
+                let mut used = false;
+                for arm in arms {
+                    self.used = false;
+                    self.visit_arm(arm);
+                    used |= self.used;
+                }
+                self.used = used;
+            },
+            ExprKind::Ret(value) => {
+                if let Some(value) = value {
+                    self.visit_expr(value);
+                }
+                self.exit(expr.span);
+            },
+            ExprKind::Break(destination, value) => {
+                if let Some(value) = value {
+                    self.visit_expr(value);
+                }
+                if matches!(destination.target_id, Ok(target) if !self.targets.contains(&target)) {
+                    self.exit(expr.span);
+                }
+            },
+            ExprKind::Continue(destination) => {
+                if matches!(destination.target_id, Ok(target) if !self.targets.contains(&target)) {
+                    self.exit(expr.span);
+                }
+            },
+            ExprKind::Loop(..) => self.visit_target(expr.hir_id, expr),
+            // `break` refers to the block rather than the expression
+            ExprKind::Block(block, Some(_)) => self.visit_target(block.hir_id, expr),
+            ExprKind::Closure(..) => {
+                self.closure_depth += 1;
+                walk_expr(self, expr);
+                self.closure_depth -= 1;
+            },
+            _ => walk_expr(self, expr),
+        }
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.cx.tcx.hir())
+    }
+}
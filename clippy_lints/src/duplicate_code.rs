@@ -0,0 +1,247 @@
+use crate::utils::{in_macro, is_automatically_derived, span_lint_hir_and_then, SpanlessEq, SpanlessHash};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::intravisit::{walk_expr, FnKind, NestedVisitorMap, Visitor};
+use rustc_hir::{Block, Body, BodyId, Crate, Expr, ExprKind, FnDecl, HirId, Node};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::hir::map::Map;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::source_map::{MultiSpan, Span};
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for functions and large blocks whose bodies are
+    /// identical up to the names of their local bindings. The types of all
+    /// expressions have to be the same as well.
+    ///
+    /// **Why is this bad?** Copy-pasted code has to be kept in sync by hand. It
+    /// usually belongs in a helper function that all copies call.
+    ///
+    /// **Known problems:** Copies that were edited afterwards, even slightly, are
+    /// not found. Only bodies with at least `duplicate-code-threshold`
+    /// expressions (default: 30) are considered.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// fn sum_even(v: &[u32]) -> u32 {
+    ///     let mut sum = 0;
+    ///     for x in v {
+    ///         if x % 2 == 0 {
+    ///             sum += x;
+    ///         }
+    ///     }
+    ///     sum
+    /// }
+    ///
+    /// fn add_even(values: &[u32]) -> u32 {
+    ///     let mut total = 0;
+    ///     for value in values {
+    ///         if value % 2 == 0 {
+    ///             total += value;
+    ///         }
+    ///     }
+    ///     total
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub DUPLICATE_CODE,
+    pedantic,
+    "functions or blocks that are copies of each other up to the names of local bindings"
+}
+
+/// A function body or block that is large enough to be compared.
+struct Candidate {
+    hir_id: HirId,
+    /// The body of the function, or the body the block is part of.
+    body: BodyId,
+    /// The span of the whole function or block.
+    span: Span,
+    /// The span the lint is emitted on, e.g. the signature of a function.
+    lint_span: Span,
+    is_fn: bool,
+}
+
+pub struct DuplicateCode {
+    threshold: u64,
+    /// Candidates grouped by their spanless hash and size. Candidates in the same group are only
+    /// likely to be copies, see `is_copy`.
+    candidates: FxHashMap<(u64, u64), Vec<Candidate>>,
+    /// Blocks that are the body of a function, those are already checked as functions.
+    fn_body_blocks: FxHashSet<HirId>,
+}
+
+impl DuplicateCode {
+    pub fn new(threshold: u64) -> Self {
+        Self {
+            threshold,
+            candidates: FxHashMap::default(),
+            fn_body_blocks: FxHashSet::default(),
+        }
+    }
+}
+
+impl_lint_pass!(DuplicateCode => [DUPLICATE_CODE]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for DuplicateCode {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'a, 'tcx>,
+        kind: FnKind<'tcx>,
+        decl: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        span: Span,
+        hir_id: HirId,
+    ) {
+        if let FnKind::Closure(_) = kind {
+            return;
+        }
+        if in_macro(span) || is_automatically_derived(kind.attrs()) {
+            return;
+        }
+        if let ExprKind::Block(block, _) = body.value.kind {
+            self.fn_body_blocks.insert(block.hir_id);
+        }
+
+        let size = count_exprs(cx, &body.value);
+        if size < self.threshold {
+            return;
+        }
+
+        let mut hasher = SpanlessHash::new(cx, cx.tables).ignore_local_names();
+        for param in body.params {
+            hasher.hash_pat(&param.pat);
+        }
+        hasher.hash_expr(&body.value);
+
+        self.candidates
+            .entry((hasher.finish(), size))
+            .or_default()
+            .push(Candidate {
+                hir_id,
+                body: body.id(),
+                span,
+                lint_span: span.with_hi(decl.output.span().hi()),
+                is_fn: true,
+            });
+    }
+
+    fn check_block(&mut self, cx: &LateContext<'a, 'tcx>, block: &'tcx Block<'_>) {
+        if in_macro(block.span) || self.fn_body_blocks.contains(&block.hir_id) {
+            return;
+        }
+
+        let mut counter = ExprCounter { cx, count: 0 };
+        counter.visit_block(block);
+        if counter.count < self.threshold {
+            return;
+        }
+
+        let mut hasher = SpanlessHash::new(cx, cx.tables).ignore_local_names();
+        hasher.hash_block(block);
+
+        self.candidates
+            .entry((hasher.finish(), counter.count))
+            .or_default()
+            .push(Candidate {
+                hir_id: block.hir_id,
+                body: cx.tcx.hir().body_owned_by(cx.tcx.hir().enclosing_body_owner(block.hir_id)),
+                span: block.span,
+                lint_span: block.span,
+                is_fn: false,
+            });
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'a, 'tcx>, _: &'tcx Crate<'_>) {
+        // Equal hashes only make copies likely, so the candidates are compared with each other
+        let mut clusters: Vec<Vec<&Candidate>> = Vec::new();
+        for candidates in self.candidates.values().filter(|candidates| candidates.len() > 1) {
+            let mut confirmed: Vec<Vec<&Candidate>> = Vec::new();
+            for candidate in candidates {
+                match confirmed.iter_mut().find(|copies| is_copy(cx, copies[0], candidate)) {
+                    Some(copies) => copies.push(candidate),
+                    None => confirmed.push(vec![candidate]),
+                }
+            }
+            clusters.extend(confirmed.into_iter().filter(|copies| copies.len() > 1));
+        }
+        clusters.sort_by_key(|copies| copies[0].span.lo());
+
+        for (i, copies) in clusters.iter().enumerate() {
+            // Blocks inside of copied functions are copies as well, only report the outermost copy.
+            let nested = clusters.iter().enumerate().any(|(j, other)| {
+                i != j
+                    && copies
+                        .iter()
+                        .all(|c| other.iter().any(|o| o.span != c.span && o.span.contains(c.span)))
+            });
+            if nested {
+                continue;
+            }
+
+            let (first, rest) = copies.split_first().expect("clusters have at least two copies");
+            let what = if first.is_fn { "function" } else { "block" };
+            let msg = if rest.len() == 1 {
+                format!("this {} has a copy", what)
+            } else {
+                format!("this {} has {} copies", what, rest.len())
+            };
+            let note = format!("copies of this {} are found here", what);
+            span_lint_hir_and_then(cx, DUPLICATE_CODE, first.hir_id, first.lint_span, &msg, |diag| {
+                diag.span_note(
+                    MultiSpan::from_spans(rest.iter().map(|c| c.lint_span).collect()),
+                    &note,
+                );
+                diag.help("consider moving the common code into a helper function");
+            });
+        }
+    }
+}
+
+/// Checks whether two candidates with the same hash are actually equal up to the names of their
+/// local bindings, including the types of all expressions.
+fn is_copy(cx: &LateContext<'_, '_>, left: &Candidate, right: &Candidate) -> bool {
+    if left.is_fn != right.is_fn {
+        return false;
+    }
+    let map = cx.tcx.hir();
+    let mut eq = SpanlessEq::new(cx)
+        .ignore_local_names()
+        .with_body_tables(cx.tcx.body_tables(left.body), cx.tcx.body_tables(right.body));
+    if left.is_fn {
+        let (left, right) = (map.body(left.body), map.body(right.body));
+        left.params.len() == right.params.len()
+            && left
+                .params
+                .iter()
+                .zip(right.params)
+                .all(|(l, r)| eq.eq_pat(&l.pat, &r.pat))
+            && eq.eq_expr(&left.value, &right.value)
+    } else {
+        match (map.find(left.hir_id), map.find(right.hir_id)) {
+            (Some(Node::Block(left)), Some(Node::Block(right))) => eq.eq_block(left, right),
+            _ => false,
+        }
+    }
+}
+
+fn count_exprs<'a, 'tcx>(cx: &'a LateContext<'a, 'tcx>, expr: &'tcx Expr<'_>) -> u64 {
+    let mut counter = ExprCounter { cx, count: 0 };
+    counter.visit_expr(expr);
+    counter.count
+}
+
+struct ExprCounter<'a, 'tcx> {
+    cx: &'a LateContext<'a, 'tcx>,
+    count: u64,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for ExprCounter<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        self.count += 1;
+        walk_expr(self, expr);
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.cx.tcx.hir())
+    }
+}
@@ -25,6 +25,7 @@ declare_clippy_lint! {
     ///     BattenbergCake,
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub ENUM_VARIANT_NAMES,
     style,
     "enums where all variants share a prefix/postfix"
@@ -47,6 +48,7 @@ declare_clippy_lint! {
     ///     BattenbergCake,
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub PUB_ENUM_VARIANT_NAMES,
     pedantic,
     "enums where all variants share a prefix/postfix"
@@ -66,6 +68,7 @@ declare_clippy_lint! {
     ///     struct BlackForestCake;
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MODULE_NAME_REPETITIONS,
     pedantic,
     "type names prefixed/postfixed with their containing module's name"
@@ -96,6 +99,7 @@ declare_clippy_lint! {
     ///     ...
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MODULE_INCEPTION,
     style,
     "modules that have the same name as their parent module"
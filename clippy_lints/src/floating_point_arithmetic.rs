@@ -44,6 +44,7 @@ declare_clippy_lint! {
     /// let _ = a.ln_1p();
     /// let _ = a.exp_m1();
     /// ```
+    #[clippy::version = "1.43.0"]
     pub IMPRECISE_FLOPS,
     nursery,
     "usage of imprecise floating point operations"
@@ -101,6 +102,7 @@ declare_clippy_lint! {
     /// let _ = a.abs();
     /// let _ = -a.abs();
     /// ```
+    #[clippy::version = "1.43.0"]
     pub SUBOPTIMAL_FLOPS,
     nursery,
     "usage of sub-optimal floating point operations"
@@ -167,14 +169,19 @@ fn prepare_receiver_sugg<'a>(cx: &LateContext<'_, '_>, mut expr: &'a Expr<'a>) -
 
 fn check_log_base(cx: &LateContext<'_, '_>, expr: &Expr<'_>, args: &[Expr<'_>]) {
     if let Some(method) = get_specialized_log_method(cx, &args[1]) {
+        let mut applicability = Applicability::MachineApplicable;
         span_lint_and_sugg(
             cx,
             SUBOPTIMAL_FLOPS,
             expr.span,
             "logarithm for bases 2, 10 and e can be computed more accurately",
             "consider using",
-            format!("{}.{}()", Sugg::hir(cx, &args[0], ".."), method),
-            Applicability::MachineApplicable,
+            format!(
+                "{}.{}()",
+                Sugg::hir_with_applicability(cx, &args[0], "..", &mut applicability),
+                method
+            ),
+            applicability,
         );
     }
 }
@@ -307,6 +314,7 @@ fn check_expm1(cx: &LateContext<'_, '_>, expr: &Expr<'_>) {
         if cx.tables.expr_ty(&method_args[0]).is_floating_point();
         if path.ident.name.as_str() == "exp";
         then {
+            let mut applicability = Applicability::MachineApplicable;
             span_lint_and_sugg(
                 cx,
                 IMPRECISE_FLOPS,
@@ -315,9 +323,9 @@ fn check_expm1(cx: &LateContext<'_, '_>, expr: &Expr<'_>) {
                 "consider using",
                 format!(
                     "{}.exp_m1()",
-                    Sugg::hir(cx, &method_args[0], "..")
+                    Sugg::hir_with_applicability(cx, &method_args[0], "..", &mut applicability)
                 ),
-                Applicability::MachineApplicable,
+                applicability,
             );
         }
     }
@@ -354,6 +362,7 @@ fn check_mul_add(cx: &LateContext<'_, '_>, expr: &Expr<'_>) {
             return;
         };
 
+        let mut applicability = Applicability::MachineApplicable;
         span_lint_and_sugg(
             cx,
             SUBOPTIMAL_FLOPS,
@@ -363,10 +372,10 @@ fn check_mul_add(cx: &LateContext<'_, '_>, expr: &Expr<'_>) {
             format!(
                 "{}.mul_add({}, {})",
                 prepare_receiver_sugg(cx, recv),
-                Sugg::hir(cx, arg1, ".."),
-                Sugg::hir(cx, arg2, ".."),
+                Sugg::hir_with_applicability(cx, arg1, "..", &mut applicability),
+                Sugg::hir_with_applicability(cx, arg2, "..", &mut applicability),
             ),
-            Applicability::MachineApplicable,
+            applicability,
         );
     }
 }
@@ -3,7 +3,7 @@ use rustc_hir::intravisit::FnKind;
 use rustc_hir::{Body, FnDecl, HirId};
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_lint::{LateContext, LateLintPass};
-use rustc_middle::ty::{Opaque, Predicate::Trait, ToPolyTraitRef};
+use rustc_middle::ty::{Predicate::Trait, ToPolyTraitRef};
 use rustc_session::{declare_lint_pass, declare_tool_lint};
 use rustc_span::{sym, Span};
 use rustc_trait_selection::traits::error_reporting::suggestions::InferCtxtExt;
@@ -40,6 +40,7 @@ declare_clippy_lint! {
     /// ```rust
     /// async fn is_send(bytes: std::sync::Arc<[u8]>) {}
     /// ```
+    #[clippy::version = "1.44.0"]
     pub FUTURE_NOT_SEND,
     nursery,
     "public Futures must be Send"
@@ -61,49 +62,37 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for FutureNotSend {
             return;
         }
         let ret_ty = utils::return_ty(cx, hir_id);
-        if let Opaque(id, subst) = ret_ty.kind {
-            let preds = cx.tcx.predicates_of(id).instantiate(cx.tcx, subst);
-            let mut is_future = false;
-            for p in preds.predicates {
-                if let Some(trait_ref) = p.to_opt_poly_trait_ref() {
-                    if Some(trait_ref.def_id()) == cx.tcx.lang_items().future_trait() {
-                        is_future = true;
-                        break;
-                    }
-                }
-            }
-            if is_future {
-                let send_trait = cx.tcx.get_diagnostic_item(sym::send_trait).unwrap();
-                let span = decl.output.span();
-                let send_result = cx.tcx.infer_ctxt().enter(|infcx| {
-                    let cause = traits::ObligationCause::misc(span, hir_id);
-                    let mut fulfillment_cx = traits::FulfillmentContext::new();
-                    fulfillment_cx.register_bound(&infcx, cx.param_env, ret_ty, send_trait, cause);
-                    fulfillment_cx.select_all_or_error(&infcx)
-                });
-                if let Err(send_errors) = send_result {
-                    utils::span_lint_and_then(
-                        cx,
-                        FUTURE_NOT_SEND,
-                        span,
-                        "future cannot be sent between threads safely",
-                        |db| {
-                            cx.tcx.infer_ctxt().enter(|infcx| {
-                                for FulfillmentError { obligation, .. } in send_errors {
-                                    infcx.maybe_note_obligation_cause_for_async_await(db, &obligation);
-                                    if let Trait(trait_pred, _) = obligation.predicate {
-                                        let trait_ref = trait_pred.to_poly_trait_ref();
-                                        db.note(&*format!(
-                                            "`{}` doesn't implement `{}`",
-                                            trait_ref.self_ty(),
-                                            trait_ref.print_only_trait_path(),
-                                        ));
-                                    }
+        if utils::is_future_opaque(cx, ret_ty) {
+            let send_trait = cx.tcx.get_diagnostic_item(sym::send_trait).unwrap();
+            let span = decl.output.span();
+            let send_result = cx.tcx.infer_ctxt().enter(|infcx| {
+                let cause = traits::ObligationCause::misc(span, hir_id);
+                let mut fulfillment_cx = traits::FulfillmentContext::new();
+                fulfillment_cx.register_bound(&infcx, cx.param_env, ret_ty, send_trait, cause);
+                fulfillment_cx.select_all_or_error(&infcx)
+            });
+            if let Err(send_errors) = send_result {
+                utils::span_lint_and_then(
+                    cx,
+                    FUTURE_NOT_SEND,
+                    span,
+                    "future cannot be sent between threads safely",
+                    |db| {
+                        cx.tcx.infer_ctxt().enter(|infcx| {
+                            for FulfillmentError { obligation, .. } in send_errors {
+                                infcx.maybe_note_obligation_cause_for_async_await(db, &obligation);
+                                if let Trait(trait_pred, _) = obligation.predicate {
+                                    let trait_ref = trait_pred.to_poly_trait_ref();
+                                    db.note(&*format!(
+                                        "`{}` doesn't implement `{}`",
+                                        trait_ref.self_ty(),
+                                        trait_ref.print_only_trait_path(),
+                                    ));
                                 }
-                            })
-                        },
-                    );
-                }
+                            }
+                        })
+                    },
+                );
             }
         }
     }
@@ -18,6 +18,7 @@ declare_clippy_lint! {
     /// // format!() returns a `String`
     /// let s: String = format!("hello").into();
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub IDENTITY_CONVERSION,
     complexity,
     "using always-identical `Into`/`From`/`IntoIter` conversions"
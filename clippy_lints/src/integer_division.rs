@@ -20,6 +20,7 @@ declare_clippy_lint! {
     ///     println!("{}", x);
     /// }
     /// ```
+    #[clippy::version = "1.37.0"]
     pub INTEGER_DIVISION,
     restriction,
     "integer division may cause loss of precision"
@@ -38,6 +38,8 @@ extern crate rustc_index;
 #[allow(unused_extern_crates)]
 extern crate rustc_infer;
 #[allow(unused_extern_crates)]
+extern crate rustc_interface;
+#[allow(unused_extern_crates)]
 extern crate rustc_lexer;
 #[allow(unused_extern_crates)]
 extern crate rustc_lint;
@@ -64,13 +66,15 @@ use rustc_session::Session;
 
 /// Macro used to declare a Clippy lint.
 ///
-/// Every lint declaration consists of 4 parts:
+/// Every lint declaration consists of 5 parts:
 ///
 /// 1. The documentation, which is used for the website
-/// 2. The `LINT_NAME`. See [lint naming][lint_naming] on lint naming conventions.
-/// 3. The `lint_level`, which is a mapping from *one* of our lint groups to `Allow`, `Warn` or
+/// 2. The `#[clippy::version]` attribute with the Clippy version the lint is added in, which is
+///    the version of the current nightly Rust. Lints older than Rust 1.29 use `"pre 1.29.0"`.
+/// 3. The `LINT_NAME`. See [lint naming][lint_naming] on lint naming conventions.
+/// 4. The `lint_level`, which is a mapping from *one* of our lint groups to `Allow`, `Warn` or
 ///    `Deny`. The lint level here has nothing to do with what lint groups the lint is a part of.
-/// 4. The `description` that contains a short explanation on what's wrong with code where the
+/// 5. The `description` that contains a short explanation on what's wrong with code where the
 ///    lint is triggered.
 ///
 /// Currently the categories `style`, `correctness`, `complexity` and `perf` are enabled by default.
@@ -105,6 +109,7 @@ use rustc_session::Session;
 ///     /// // Good
 ///     /// Insert a short example of improved code that doesn't trigger the lint
 ///     /// ```
+///     #[clippy::version = "1.45.0"]
 ///     pub LINT_NAME,
 ///     pedantic,
 ///     "description"
@@ -166,8 +171,12 @@ macro_rules! declare_clippy_lint {
 }
 
 mod consts;
+mod renamed_lints;
+// Public so that the `clippy_utils` crate can re-export it for third-party lint libraries. Lints
+// outside of this crate should use `clippy_utils` instead.
+#[doc(hidden)]
 #[macro_use]
-mod utils;
+pub mod utils;
 
 // begin lints modules, do not remove this comment, it’s used in `update_lints`
 mod approx_const;
@@ -175,6 +184,7 @@ mod arithmetic;
 mod as_conversions;
 mod assertions_on_constants;
 mod assign_ops;
+mod async_bodies;
 mod atomic_ordering;
 mod attrs;
 mod await_holding_lock;
@@ -199,6 +209,8 @@ mod double_comparison;
 mod double_parens;
 mod drop_bounds;
 mod drop_forget_ref;
+mod dropped_must_use;
+mod duplicate_code;
 mod duration_subsec;
 mod else_if_without_else;
 mod empty_enum;
@@ -247,6 +259,7 @@ mod literal_representation;
 mod loops;
 mod macro_use;
 mod main_recursion;
+mod manual_combinators;
 mod map_clone;
 mod map_unit_fn;
 mod match_on_vec_items;
@@ -318,6 +331,7 @@ mod types;
 mod unicode;
 mod unnamed_address;
 mod unsafe_removed_from_name;
+mod unused_clippy_allow;
 mod unused_io_amount;
 mod unused_self;
 mod unwrap;
@@ -416,6 +430,17 @@ pub fn read_conf(args: &[rustc_ast::ast::NestedMetaItem], sess: &Session) -> Con
 pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf: &Conf) {
     register_removed_non_tool_lints(store);
 
+    let msrv = conf.msrv.as_ref().and_then(|msrv| {
+        let version = utils::msrvs::RustcVersion::parse(msrv);
+        if version.is_none() {
+            sess.err(&format!(
+                "error reading Clippy's configuration file. `{}` is not a valid Rust version",
+                msrv
+            ));
+        }
+        version
+    });
+
     // begin deprecated lints, do not remove this comment, it’s used in `update_lints`
     store.register_removed(
         "clippy::should_assert_eq",
@@ -492,6 +517,11 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &assertions_on_constants::ASSERTIONS_ON_CONSTANTS,
         &assign_ops::ASSIGN_OP_PATTERN,
         &assign_ops::MISREFACTORED_ASSIGN_OP,
+        &async_bodies::BLOCKING_IN_ASYNC,
+        &async_bodies::LARGE_FUTURES,
+        &async_bodies::MANUAL_ASYNC_FN,
+        &async_bodies::UNPOLLED_ASYNC_BLOCK,
+        &async_bodies::UNUSED_ASYNC,
         &atomic_ordering::INVALID_ATOMIC_ORDERING,
         &attrs::DEPRECATED_CFG_ATTR,
         &attrs::DEPRECATED_SEMVER,
@@ -537,6 +567,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &drop_forget_ref::DROP_REF,
         &drop_forget_ref::FORGET_COPY,
         &drop_forget_ref::FORGET_REF,
+        &dropped_must_use::DROPPED_MUST_USE,
+        &duplicate_code::DUPLICATE_CODE,
         &duration_subsec::DURATION_SUBSEC,
         &else_if_without_else::ELSE_IF_WITHOUT_ELSE,
         &empty_enum::EMPTY_ENUM,
@@ -617,6 +649,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &loops::FOR_LOOP_OVER_OPTION,
         &loops::FOR_LOOP_OVER_RESULT,
         &loops::ITER_NEXT_LOOP,
+        &loops::MANUAL_ITERATOR_LOOP,
         &loops::MANUAL_MEMCPY,
         &loops::MUT_RANGE_BOUND,
         &loops::NEEDLESS_COLLECT,
@@ -628,6 +661,14 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &loops::WHILE_LET_ON_ITERATOR,
         &macro_use::MACRO_USE_IMPORTS,
         &main_recursion::MAIN_RECURSION,
+        &manual_combinators::MANUAL_FILTER,
+        &manual_combinators::MANUAL_FLATTEN,
+        &manual_combinators::MANUAL_MAP,
+        &manual_combinators::MANUAL_OK,
+        &manual_combinators::MANUAL_OK_OR,
+        &manual_combinators::MANUAL_STRIP,
+        &manual_combinators::MANUAL_UNWRAP_OR,
+        &manual_combinators::MANUAL_UNWRAP_OR_DEFAULT,
         &map_clone::MAP_CLONE,
         &map_unit_fn::OPTION_MAP_UNIT_FN,
         &map_unit_fn::RESULT_MAP_UNIT_FN,
@@ -635,13 +676,17 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &matches::INFALLIBLE_DESTRUCTURING_MATCH,
         &matches::MATCH_AS_REF,
         &matches::MATCH_BOOL,
+        &matches::MATCH_COLLAPSIBLE_ARMS,
         &matches::MATCH_OVERLAPPING_ARM,
         &matches::MATCH_REF_PATS,
         &matches::MATCH_SINGLE_BINDING,
+        &matches::MATCH_UNREACHABLE_ARM,
         &matches::MATCH_WILD_ERR_ARM,
+        &matches::REDUNDANT_GUARD,
         &matches::REST_PAT_IN_FULLY_BOUND_STRUCTS,
         &matches::SINGLE_MATCH,
         &matches::SINGLE_MATCH_ELSE,
+        &matches::UNNESTED_OR_PATTERNS,
         &matches::WILDCARD_ENUM_MATCH_ARM,
         &matches::WILDCARD_IN_OR_PATTERNS,
         &mem_discriminant::MEM_DISCRIMINANT_NON_ENUM,
@@ -744,6 +789,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &no_effect::UNNECESSARY_OPERATION,
         &non_copy_const::BORROW_INTERIOR_MUTABLE_CONST,
         &non_copy_const::DECLARE_INTERIOR_MUTABLE_CONST,
+        &non_copy_const::UNSAFE_SYNC_STATIC,
         &non_expressive_names::JUST_UNDERSCORES_AND_DIGITS,
         &non_expressive_names::MANY_SINGLE_CHAR_NAMES,
         &non_expressive_names::SIMILAR_NAMES,
@@ -768,6 +814,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &ranges::RANGE_ZIP_WITH_LEN,
         &redundant_clone::REDUNDANT_CLONE,
         &redundant_field_names::REDUNDANT_FIELD_NAMES,
+        &redundant_pattern_matching::MATCH_LIKE_MATCHES_MACRO,
         &redundant_pattern_matching::REDUNDANT_PATTERN_MATCHING,
         &redundant_pub_crate::REDUNDANT_PUB_CRATE,
         &redundant_static_lifetimes::REDUNDANT_STATIC_LIFETIMES,
@@ -840,6 +887,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &unnamed_address::FN_ADDRESS_COMPARISONS,
         &unnamed_address::VTABLE_ADDRESS_COMPARISONS,
         &unsafe_removed_from_name::UNSAFE_REMOVED_FROM_NAME,
+        &unused_clippy_allow::UNUSED_CLIPPY_ALLOW,
         &unused_io_amount::UNUSED_IO_AMOUNT,
         &unused_self::UNUSED_SELF,
         &unwrap::PANICKING_UNWRAP,
@@ -849,7 +897,11 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         &utils::internal_lints::COLLAPSIBLE_SPAN_LINT_CALLS,
         &utils::internal_lints::COMPILER_LINT_FUNCTIONS,
         &utils::internal_lints::DEFAULT_LINT,
+        &utils::internal_lints::INVALID_CLIPPY_VERSION_ATTRIBUTE,
         &utils::internal_lints::LINT_WITHOUT_LINT_PASS,
+        &utils::internal_lints::MACHINE_APPLICABLE_SNIPPET,
+        &utils::internal_lints::MATCH_TYPE_ON_DIAGNOSTIC_ITEM,
+        &utils::internal_lints::MISSING_CLIPPY_VERSION_ATTRIBUTE,
         &utils::internal_lints::OUTER_EXPN_EXPN_DATA,
         &utils::internal_lints::PRODUCE_ICE,
         &vec::USELESS_VEC,
@@ -869,6 +921,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     ]);
     // end register lints, do not remove this comment, it’s used in `update_lints`
 
+    // clears the cache of the path helpers, so it has to run before any other late pass
+    store.register_late_pass(|| box utils::def_path::ClearResolvedPaths);
     store.register_late_pass(|| box await_holding_lock::AwaitHoldingLock);
     store.register_late_pass(|| box serde_api::SerdeAPI);
     store.register_late_pass(|| box utils::internal_lints::CompilerLintFunctions::new());
@@ -876,6 +930,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|| box utils::internal_lints::OuterExpnDataPass);
     store.register_late_pass(|| box utils::inspector::DeepCodeInspector);
     store.register_late_pass(|| box utils::author::Author);
+    store.register_early_pass(|| box utils::author::EarlyAuthor);
     let vec_box_size_threshold = conf.vec_box_size_threshold;
     store.register_late_pass(move || box types::Types::new(vec_box_size_threshold));
     store.register_late_pass(|| box booleans::NonminimalBool);
@@ -926,7 +981,15 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|| box temporary_assignment::TemporaryAssignment);
     store.register_late_pass(|| box transmute::Transmute);
     let cognitive_complexity_threshold = conf.cognitive_complexity_threshold;
-    store.register_late_pass(move || box cognitive_complexity::CognitiveComplexity::new(cognitive_complexity_threshold));
+    let cognitive_complexity_weights = conf.cognitive_complexity_weights;
+    let cognitive_complexity_flat_enum_matches = conf.cognitive_complexity_flat_enum_matches;
+    store.register_late_pass(move || {
+        box cognitive_complexity::CognitiveComplexity::new(
+            cognitive_complexity_threshold,
+            cognitive_complexity_weights,
+            cognitive_complexity_flat_enum_matches,
+        )
+    });
     let too_large_for_stack = conf.too_large_for_stack;
     store.register_late_pass(move || box escape::BoxedLocal{too_large_for_stack});
     store.register_late_pass(|| box panic_unimplemented::PanicUnimplemented);
@@ -996,7 +1059,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|| box duration_subsec::DurationSubsec);
     store.register_late_pass(|| box default_trait_access::DefaultTraitAccess);
     store.register_late_pass(|| box indexing_slicing::IndexingSlicing);
-    store.register_late_pass(|| box non_copy_const::NonCopyConst);
+    let ignore_interior_mutability = conf.ignore_interior_mutability.clone();
+    store.register_late_pass(move || box non_copy_const::NonCopyConst::new(&ignore_interior_mutability));
     store.register_late_pass(|| box ptr_offset_with_cast::PtrOffsetWithCast);
     store.register_late_pass(|| box redundant_clone::RedundantClone);
     store.register_late_pass(|| box slow_vector_initialization::SlowVectorInit);
@@ -1010,7 +1074,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|| box inherent_to_string::InherentToString);
     store.register_late_pass(|| box trait_bounds::TraitBounds);
     store.register_late_pass(|| box comparison_chain::ComparisonChain);
-    store.register_late_pass(|| box mut_key::MutableKeyType);
+    let ignore_interior_mutability = conf.ignore_interior_mutability.clone();
+    store.register_late_pass(move || box mut_key::MutableKeyType::new(&ignore_interior_mutability));
     store.register_late_pass(|| box modulo_arithmetic::ModuloArithmetic);
     store.register_early_pass(|| box reference::DerefAddrOf);
     store.register_early_pass(|| box reference::RefInDeref);
@@ -1062,8 +1127,27 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|| box dereference::Dereferencing);
     store.register_late_pass(|| box future_not_send::FutureNotSend);
     store.register_late_pass(|| box utils::internal_lints::CollapsibleCalls);
+    store.register_late_pass(|| box utils::internal_lints::MatchTypeOnDiagItem);
+    store.register_late_pass(|| box utils::internal_lints::MachineApplicableSnippet);
     store.register_late_pass(|| box if_let_mutex::IfLetMutex);
     store.register_late_pass(|| box match_on_vec_items::MatchOnVecItems);
+    let duplicate_code_threshold = conf.duplicate_code_threshold;
+    store.register_late_pass(move || box duplicate_code::DuplicateCode::new(duplicate_code_threshold));
+    let must_use_types = conf.must_use_types.clone();
+    store.register_late_pass(move || box dropped_must_use::DroppedMustUse::new(&must_use_types));
+    let blocking_functions = conf.blocking_functions.clone();
+    let future_size_threshold = conf.future_size_threshold;
+    store.register_late_pass(move || box async_bodies::AsyncBodies::new(&blocking_functions, future_size_threshold));
+    store.register_late_pass(move || box manual_combinators::ManualCombinators::new(msrv));
+    // Registered after the other Clippy passes, so that it runs after all of their lints were
+    // emitted. Passes of `--lint-lib`s come later, their lints are ignored.
+    let clippy_lint_names = store
+        .get_lints()
+        .iter()
+        .map(|lint| lint.name_lower())
+        .filter(|name| name.starts_with("clippy::"))
+        .collect::<FxHashSet<_>>();
+    store.register_late_pass(move || box unused_clippy_allow::UnusedClippyAllow::new(clippy_lint_names.clone()));
 
     store.register_group(true, "clippy::restriction", Some("clippy_restriction"), vec![
         LintId::of(&arithmetic::FLOAT_ARITHMETIC),
@@ -1102,12 +1186,16 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&shadow::SHADOW_REUSE),
         LintId::of(&shadow::SHADOW_SAME),
         LintId::of(&strings::STRING_ADD),
+        LintId::of(&unused_clippy_allow::UNUSED_CLIPPY_ALLOW),
         LintId::of(&verbose_file_reads::VERBOSE_FILE_READS),
         LintId::of(&write::PRINT_STDOUT),
         LintId::of(&write::USE_DEBUG),
     ]);
 
     store.register_group(true, "clippy::pedantic", Some("clippy_pedantic"), vec![
+        LintId::of(&async_bodies::BLOCKING_IN_ASYNC),
+        LintId::of(&async_bodies::LARGE_FUTURES),
+        LintId::of(&async_bodies::UNUSED_ASYNC),
         LintId::of(&attrs::INLINE_ALWAYS),
         LintId::of(&await_holding_lock::AWAIT_HOLDING_LOCK),
         LintId::of(&checked_conversions::CHECKED_CONVERSIONS),
@@ -1120,6 +1208,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&derive::UNSAFE_DERIVE_DESERIALIZE),
         LintId::of(&doc::DOC_MARKDOWN),
         LintId::of(&doc::MISSING_ERRORS_DOC),
+        LintId::of(&dropped_must_use::DROPPED_MUST_USE),
+        LintId::of(&duplicate_code::DUPLICATE_CODE),
         LintId::of(&empty_enum::EMPTY_ENUM),
         LintId::of(&enum_variants::MODULE_NAME_REPETITIONS),
         LintId::of(&enum_variants::PUB_ENUM_VARIANT_NAMES),
@@ -1140,6 +1230,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&macro_use::MACRO_USE_IMPORTS),
         LintId::of(&matches::MATCH_BOOL),
         LintId::of(&matches::SINGLE_MATCH_ELSE),
+        LintId::of(&matches::UNNESTED_OR_PATTERNS),
         LintId::of(&methods::FILTER_MAP),
         LintId::of(&methods::FILTER_MAP_NEXT),
         LintId::of(&methods::FIND_MAP),
@@ -1153,8 +1244,10 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&mut_mut::MUT_MUT),
         LintId::of(&needless_continue::NEEDLESS_CONTINUE),
         LintId::of(&needless_pass_by_value::NEEDLESS_PASS_BY_VALUE),
+        LintId::of(&non_copy_const::UNSAFE_SYNC_STATIC),
         LintId::of(&non_expressive_names::SIMILAR_NAMES),
         LintId::of(&ranges::RANGE_PLUS_ONE),
+        LintId::of(&redundant_pattern_matching::MATCH_LIKE_MATCHES_MACRO),
         LintId::of(&shadow::SHADOW_UNRELATED),
         LintId::of(&strings::STRING_ADD_ASSIGN),
         LintId::of(&trait_bounds::TYPE_REPETITION_IN_BOUNDS),
@@ -1181,7 +1274,11 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&utils::internal_lints::COLLAPSIBLE_SPAN_LINT_CALLS),
         LintId::of(&utils::internal_lints::COMPILER_LINT_FUNCTIONS),
         LintId::of(&utils::internal_lints::DEFAULT_LINT),
+        LintId::of(&utils::internal_lints::INVALID_CLIPPY_VERSION_ATTRIBUTE),
         LintId::of(&utils::internal_lints::LINT_WITHOUT_LINT_PASS),
+        LintId::of(&utils::internal_lints::MACHINE_APPLICABLE_SNIPPET),
+        LintId::of(&utils::internal_lints::MATCH_TYPE_ON_DIAGNOSTIC_ITEM),
+        LintId::of(&utils::internal_lints::MISSING_CLIPPY_VERSION_ATTRIBUTE),
         LintId::of(&utils::internal_lints::OUTER_EXPN_EXPN_DATA),
         LintId::of(&utils::internal_lints::PRODUCE_ICE),
     ]);
@@ -1191,6 +1288,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&assertions_on_constants::ASSERTIONS_ON_CONSTANTS),
         LintId::of(&assign_ops::ASSIGN_OP_PATTERN),
         LintId::of(&assign_ops::MISREFACTORED_ASSIGN_OP),
+        LintId::of(&async_bodies::MANUAL_ASYNC_FN),
+        LintId::of(&async_bodies::UNPOLLED_ASYNC_BLOCK),
         LintId::of(&atomic_ordering::INVALID_ATOMIC_ORDERING),
         LintId::of(&attrs::DEPRECATED_CFG_ATTR),
         LintId::of(&attrs::DEPRECATED_SEMVER),
@@ -1270,6 +1369,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&loops::FOR_LOOP_OVER_OPTION),
         LintId::of(&loops::FOR_LOOP_OVER_RESULT),
         LintId::of(&loops::ITER_NEXT_LOOP),
+        LintId::of(&loops::MANUAL_ITERATOR_LOOP),
         LintId::of(&loops::MANUAL_MEMCPY),
         LintId::of(&loops::MUT_RANGE_BOUND),
         LintId::of(&loops::NEEDLESS_COLLECT),
@@ -1280,16 +1380,27 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&loops::WHILE_LET_LOOP),
         LintId::of(&loops::WHILE_LET_ON_ITERATOR),
         LintId::of(&main_recursion::MAIN_RECURSION),
+        LintId::of(&manual_combinators::MANUAL_FILTER),
+        LintId::of(&manual_combinators::MANUAL_FLATTEN),
+        LintId::of(&manual_combinators::MANUAL_MAP),
+        LintId::of(&manual_combinators::MANUAL_OK),
+        LintId::of(&manual_combinators::MANUAL_OK_OR),
+        LintId::of(&manual_combinators::MANUAL_STRIP),
+        LintId::of(&manual_combinators::MANUAL_UNWRAP_OR),
+        LintId::of(&manual_combinators::MANUAL_UNWRAP_OR_DEFAULT),
         LintId::of(&map_clone::MAP_CLONE),
         LintId::of(&map_unit_fn::OPTION_MAP_UNIT_FN),
         LintId::of(&map_unit_fn::RESULT_MAP_UNIT_FN),
         LintId::of(&match_on_vec_items::MATCH_ON_VEC_ITEMS),
         LintId::of(&matches::INFALLIBLE_DESTRUCTURING_MATCH),
         LintId::of(&matches::MATCH_AS_REF),
+        LintId::of(&matches::MATCH_COLLAPSIBLE_ARMS),
         LintId::of(&matches::MATCH_OVERLAPPING_ARM),
         LintId::of(&matches::MATCH_REF_PATS),
         LintId::of(&matches::MATCH_SINGLE_BINDING),
+        LintId::of(&matches::MATCH_UNREACHABLE_ARM),
         LintId::of(&matches::MATCH_WILD_ERR_ARM),
+        LintId::of(&matches::REDUNDANT_GUARD),
         LintId::of(&matches::SINGLE_MATCH),
         LintId::of(&matches::WILDCARD_IN_OR_PATTERNS),
         LintId::of(&mem_discriminant::MEM_DISCRIMINANT_NON_ENUM),
@@ -1444,6 +1555,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_group(true, "clippy::style", Some("clippy_style"), vec![
         LintId::of(&assertions_on_constants::ASSERTIONS_ON_CONSTANTS),
         LintId::of(&assign_ops::ASSIGN_OP_PATTERN),
+        LintId::of(&async_bodies::MANUAL_ASYNC_FN),
         LintId::of(&attrs::UNKNOWN_CLIPPY_LINTS),
         LintId::of(&bit_mask::VERBOSE_BIT_MASK),
         LintId::of(&blacklisted_name::BLACKLISTED_NAME),
@@ -1478,6 +1590,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&matches::INFALLIBLE_DESTRUCTURING_MATCH),
         LintId::of(&matches::MATCH_OVERLAPPING_ARM),
         LintId::of(&matches::MATCH_REF_PATS),
+        LintId::of(&matches::MATCH_UNREACHABLE_ARM),
         LintId::of(&matches::MATCH_WILD_ERR_ARM),
         LintId::of(&matches::SINGLE_MATCH),
         LintId::of(&mem_replace::MEM_REPLACE_OPTION_WITH_NONE),
@@ -1556,12 +1669,23 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         LintId::of(&lifetimes::EXTRA_UNUSED_LIFETIMES),
         LintId::of(&lifetimes::NEEDLESS_LIFETIMES),
         LintId::of(&loops::EXPLICIT_COUNTER_LOOP),
+        LintId::of(&loops::MANUAL_ITERATOR_LOOP),
         LintId::of(&loops::MUT_RANGE_BOUND),
         LintId::of(&loops::WHILE_LET_LOOP),
+        LintId::of(&manual_combinators::MANUAL_FILTER),
+        LintId::of(&manual_combinators::MANUAL_FLATTEN),
+        LintId::of(&manual_combinators::MANUAL_MAP),
+        LintId::of(&manual_combinators::MANUAL_OK),
+        LintId::of(&manual_combinators::MANUAL_OK_OR),
+        LintId::of(&manual_combinators::MANUAL_STRIP),
+        LintId::of(&manual_combinators::MANUAL_UNWRAP_OR),
+        LintId::of(&manual_combinators::MANUAL_UNWRAP_OR_DEFAULT),
         LintId::of(&map_unit_fn::OPTION_MAP_UNIT_FN),
         LintId::of(&map_unit_fn::RESULT_MAP_UNIT_FN),
         LintId::of(&matches::MATCH_AS_REF),
+        LintId::of(&matches::MATCH_COLLAPSIBLE_ARMS),
         LintId::of(&matches::MATCH_SINGLE_BINDING),
+        LintId::of(&matches::REDUNDANT_GUARD),
         LintId::of(&matches::WILDCARD_IN_OR_PATTERNS),
         LintId::of(&methods::CLONE_ON_COPY),
         LintId::of(&methods::FILTER_NEXT),
@@ -1614,6 +1738,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
 
     store.register_group(true, "clippy::correctness", Some("clippy_correctness"), vec![
         LintId::of(&approx_const::APPROX_CONSTANT),
+        LintId::of(&async_bodies::UNPOLLED_ASYNC_BLOCK),
         LintId::of(&atomic_ordering::INVALID_ATOMIC_ORDERING),
         LintId::of(&attrs::DEPRECATED_SEMVER),
         LintId::of(&attrs::MISMATCHED_TARGET_OS),
@@ -1777,16 +1902,53 @@ fn register_removed_non_tool_lints(store: &mut rustc_lint::LintStore) {
     );
 }
 
-/// Register renamed lints.
+/// Register renamed lints, which are listed in `renamed_lints.rs`.
 ///
 /// Used in `./src/driver.rs`.
 pub fn register_renamed(ls: &mut rustc_lint::LintStore) {
-    ls.register_renamed("clippy::stutter", "clippy::module_name_repetitions");
-    ls.register_renamed("clippy::new_without_default_derive", "clippy::new_without_default");
-    ls.register_renamed("clippy::cyclomatic_complexity", "clippy::cognitive_complexity");
-    ls.register_renamed("clippy::const_static_lifetime", "clippy::redundant_static_lifetimes");
+    for (old_name, new_name) in renamed_lints::RENAMED_LINTS {
+        ls.register_renamed(old_name, new_name);
+    }
 }
 
+/// The signature of the function a third-party lint library exports to register its lints.
+///
+/// Libraries are passed to `clippy-driver` with `--lint-lib <path>`. They have to be built with
+/// the same toolchain and `clippy_utils` version as the driver that loads them. The function is
+/// looked up under the name `REGISTER_LINTS_SYMBOL` and called after Clippy's own lints are
+/// registered:
+///
+/// ```rust,ignore
+/// #[no_mangle]
+/// pub fn clippy_register_lints(store: &mut LintStore, sess: &Session, conf: &Conf) {
+///     // reads the `[third-party.my-lints]` table of `clippy.toml`
+///     let my_conf: MyConf = match conf.third_party_conf("my-lints") {
+///         Ok(my_conf) => my_conf.unwrap_or_default(),
+///         Err(e) => {
+///             sess.err(&format!("error reading the configuration of `my-lints`: {}", e));
+///             MyConf::default()
+///         },
+///     };
+///     store.register_lints(&[&MY_LINT]);
+///     store.register_late_pass(move || box MyLint::new(my_conf.clone()));
+/// }
+/// ```
+///
+/// Used in `./src/driver.rs`.
+pub type RegisterLintsFn = fn(store: &mut rustc_lint::LintStore, sess: &Session, conf: &Conf);
+
+/// The symbol name of the `RegisterLintsFn` of a third-party lint library.
+pub const REGISTER_LINTS_SYMBOL: &str = "clippy_register_lints";
+
+/// The version of `clippy_lints` and of the compiler it was built with, NUL-terminated. The driver
+/// only loads lint libraries that were built against the same version, since `RegisterLintsFn`
+/// has no stable ABI.
+pub const LINT_LIB_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), " (", env!("CLIPPY_RUSTC_VERSION"), ")\0");
+
+/// The symbol name of the function that returns the `LINT_LIB_VERSION` a third-party lint library
+/// was built against, see `clippy_utils::export_lint_lib_version!`.
+pub const LINT_LIB_VERSION_SYMBOL: &str = "clippy_lint_lib_version";
+
 // only exists to let the dogfood integration test works.
 // Don't run clippy as an executable directly
 #[allow(dead_code)]
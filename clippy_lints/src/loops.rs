@@ -1,12 +1,13 @@
 use crate::consts::{constant, Constant};
 use crate::reexport::Name;
 use crate::utils::paths;
-use crate::utils::usage::{is_unused, mutated_variables};
+use crate::utils::usage::{has_control_flow, is_unused, local_use_count, mutated_variables};
 use crate::utils::{
-    get_enclosing_block, get_parent_expr, get_trait_def_id, has_iter_method, higher, implements_trait,
-    is_integer_const, is_no_std_crate, is_refutable, last_path_segment, match_trait_method, match_type, match_var,
-    multispan_sugg, snippet, snippet_opt, snippet_with_applicability, span_lint, span_lint_and_help,
-    span_lint_and_sugg, span_lint_and_then, SpanlessEq,
+    get_enclosing_block, get_parent_expr, get_trait_def_id, has_iter_method, higher, implements_trait, in_macro,
+    indent_of, is_copy, is_expn_of, is_integer_const, is_no_std_crate, is_refutable, last_path_segment, match_def_path,
+    match_qpath, match_trait_method, match_type, match_var, multispan_sugg, remove_blocks, snippet, snippet_opt,
+    snippet_with_applicability, span_lint, span_lint_and_fix, span_lint_and_help, span_lint_and_sugg,
+    span_lint_and_then, walk_ptrs_ty, SpanlessEq,
 };
 use crate::utils::{is_type_diagnostic_item, qpath_res, same_tys, sext, sugg};
 use if_chain::if_chain;
@@ -17,8 +18,8 @@ use rustc_errors::Applicability;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::intravisit::{walk_block, walk_expr, walk_pat, walk_stmt, NestedVisitorMap, Visitor};
 use rustc_hir::{
-    def_id, BinOpKind, BindingAnnotation, Block, BorrowKind, Expr, ExprKind, GenericArg, HirId, LoopSource,
-    MatchSource, Mutability, Node, Pat, PatKind, QPath, Stmt, StmtKind,
+    def_id, BinOpKind, BindingAnnotation, Block, BorrowKind, Expr, ExprKind, GenericArg, HirId, Local, LoopSource,
+    MatchSource, Mutability, Node, Pat, PatKind, QPath, Stmt, StmtKind, UnOp,
 };
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_lint::{LateContext, LateLintPass, LintContext};
@@ -55,6 +56,7 @@ declare_clippy_lint! {
     /// # let mut dst = vec![0; 65];
     /// dst[64..(src.len() + 64)].clone_from_slice(&src[..]);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MANUAL_MEMCPY,
     perf,
     "manually copying items between slices"
@@ -83,6 +85,7 @@ declare_clippy_lint! {
     ///     println!("{}", i);
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub NEEDLESS_RANGE_LOOP,
     style,
     "for-looping over a range of indices where an iterator over items would do"
@@ -112,6 +115,7 @@ declare_clippy_lint! {
     ///     // ..
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub EXPLICIT_ITER_LOOP,
     pedantic,
     "for-looping over `_.iter()` or `_.iter_mut()` when `&_` or `&mut _` would do"
@@ -140,6 +144,7 @@ declare_clippy_lint! {
     ///     // ..
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub EXPLICIT_INTO_ITER_LOOP,
     pedantic,
     "for-looping over `_.into_iter()` when `_` would do"
@@ -163,6 +168,7 @@ declare_clippy_lint! {
     ///     ..
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub ITER_NEXT_LOOP,
     correctness,
     "for-looping over `_.next()` which is probably not intended"
@@ -189,6 +195,7 @@ declare_clippy_lint! {
     ///     ..
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub FOR_LOOP_OVER_OPTION,
     correctness,
     "for-looping over an `Option`, which is more clearly expressed as an `if let`"
@@ -215,6 +222,7 @@ declare_clippy_lint! {
     ///     ..
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub FOR_LOOP_OVER_RESULT,
     correctness,
     "for-looping over a `Result`, which is more clearly expressed as an `if let`"
@@ -244,6 +252,7 @@ declare_clippy_lint! {
     ///     // .. do something with x
     /// };
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub WHILE_LET_LOOP,
     complexity,
     "`loop { if let { ... } else break }`, which can be written as a `while let` loop"
@@ -251,13 +260,18 @@ declare_clippy_lint! {
 
 declare_clippy_lint! {
     /// **What it does:** Checks for functions collecting an iterator when collect
-    /// is not needed.
+    /// is not needed. This also follows a collected `Vec`, `VecDeque` or `LinkedList`
+    /// that is bound to a local: if its only use is a `for` loop over it, its `len()`,
+    /// `.iter().any(..)` on it or passing it to a function taking an `IntoIterator`,
+    /// the iterator can be used directly.
     ///
     /// **Why is this bad?** `collect` causes the allocation of a new data structure,
     /// when this allocation may not be needed.
     ///
-    /// **Known problems:**
-    /// None
+    /// **Known problems:** When the collected value is bound to a local, using the
+    /// iterator instead moves its evaluation to where the local is used. This
+    /// changes the program if the iterator has side effects or borrows something
+    /// that is modified in between.
     ///
     /// **Example:**
     /// ```rust
@@ -266,6 +280,22 @@ declare_clippy_lint! {
     /// // should be
     /// let len = iterator.count();
     /// ```
+    ///
+    /// ```rust
+    /// # let iterator = vec![1].into_iter();
+    /// let v: Vec<_> = iterator.collect();
+    /// for x in v {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    /// could be written as
+    /// ```rust
+    /// # let iterator = vec![1].into_iter();
+    /// for x in iterator {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    #[clippy::version = "1.30.0"]
     pub NEEDLESS_COLLECT,
     perf,
     "collecting an iterator when collect is not needed"
@@ -290,6 +320,7 @@ declare_clippy_lint! {
     ///     ..
     /// } // oops, stray `-`
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub REVERSE_RANGE_LOOP,
     correctness,
     "iteration over an empty range, such as `10..0` or `5..5`"
@@ -320,6 +351,7 @@ declare_clippy_lint! {
     /// # fn bar(bar: usize, baz: usize) {}
     /// for (i, item) in v.iter().enumerate() { bar(i, *item); }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub EXPLICIT_COUNTER_LOOP,
     complexity,
     "for-looping with an explicit counter when `_.enumerate()` would do"
@@ -338,6 +370,7 @@ declare_clippy_lint! {
     /// ```no_run
     /// loop {}
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub EMPTY_LOOP,
     style,
     "empty `loop {}`, which should block or sleep"
@@ -357,6 +390,7 @@ declare_clippy_lint! {
     ///     ..
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub WHILE_LET_ON_ITERATOR,
     style,
     "using a while-let loop instead of a for loop on an iterator"
@@ -385,6 +419,7 @@ declare_clippy_lint! {
     ///     ..
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub FOR_KV_MAP,
     style,
     "looping on a map using `iter` when `keys` or `values` would do"
@@ -406,6 +441,7 @@ declare_clippy_lint! {
     ///     break;
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub NEVER_LOOP,
     correctness,
     "any loop that will always `break` or `return`"
@@ -426,6 +462,7 @@ declare_clippy_lint! {
     ///     println!("{}", i); // prints numbers from 0 to 42, not 0 to 21
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MUT_RANGE_BOUND,
     complexity,
     "for loop over a range where one of the bounds is a mutable variable"
@@ -449,11 +486,58 @@ declare_clippy_lint! {
     ///     println!("let me loop forever!");
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub WHILE_IMMUTABLE_CONDITION,
     correctness,
     "variables used within while expression are not mutated in the body"
 }
 
+declare_clippy_lint! {
+    /// **What it does:** Checks for loops that compute what a single iterator or `Vec`
+    /// method would: a sum, whether any element matches, the position of the first match,
+    /// the element with the greatest or smallest key, pushing each element onto a `Vec`
+    /// or removing elements from a `Vec` one by one.
+    ///
+    /// **Why is this bad?** The method says what the loop does and can't get the
+    /// bookkeeping wrong.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let v = vec![1, 2, 3];
+    /// # let mut out = Vec::new();
+    /// let mut total = 0;
+    /// for x in &v {
+    ///     total += x;
+    /// }
+    ///
+    /// let mut found = false;
+    /// for x in &v {
+    ///     if *x > 1 {
+    ///         found = true;
+    ///         break;
+    ///     }
+    /// }
+    ///
+    /// for x in &v {
+    ///     out.push(x * 2);
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let v = vec![1, 2, 3];
+    /// # let mut out = Vec::new();
+    /// let total: i32 = v.iter().sum();
+    /// let found = v.iter().any(|x| *x > 1);
+    /// out.extend(v.iter().map(|x| x * 2));
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_ITERATOR_LOOP,
+    complexity,
+    "loops that could be a call to an iterator method like `sum`, `any` or `position`"
+}
+
 declare_lint_pass!(Loops => [
     MANUAL_MEMCPY,
     NEEDLESS_RANGE_LOOP,
@@ -472,6 +556,7 @@ declare_lint_pass!(Loops => [
     NEVER_LOOP,
     MUT_RANGE_BOUND,
     WHILE_IMMUTABLE_CONDITION,
+    MANUAL_ITERATOR_LOOP,
 ]);
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Loops {
@@ -612,6 +697,10 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Loops {
         }
 
         check_needless_collect(expr, cx);
+
+        if let ExprKind::Block(ref block, _) = expr.kind {
+            check_manual_iterator_loops(cx, block);
+        }
     }
 }
 
@@ -1016,6 +1105,15 @@ fn detect_manual_memcpy<'a, 'tcx>(
             // indexed retrievals.
             let manual_copies = get_indexed_assignments(cx, body, canonical_id);
 
+            // for some reason this is the only way to get the `Span`
+            // of the entire `for` loop
+            let for_span = if let ExprKind::Match(_, arms, _) = &expr.kind {
+                arms[0].body.span
+            } else {
+                unreachable!()
+            };
+            let separator = format!(";\n{}", " ".repeat(indent_of(cx, for_span).unwrap_or(0)));
+
             let big_sugg = manual_copies
                 .into_iter()
                 .map(|(dst_var, src_var)| {
@@ -1035,23 +1133,34 @@ fn detect_manual_memcpy<'a, 'tcx>(
                         dst, src_var.var_name, src_offset, src_limit
                     )
                 })
-                .join("\n    ");
+                .join(&separator);
 
             if !big_sugg.is_empty() {
                 span_lint_and_sugg(
                     cx,
                     MANUAL_MEMCPY,
-                    expr.span,
+                    for_span,
                     "it looks like you're manually copying between slices",
                     "try replacing the loop by",
-                    big_sugg,
-                    Applicability::Unspecified,
+                    format!("{}{}", big_sugg, for_loop_terminator(cx, expr)),
+                    Applicability::MaybeIncorrect,
                 );
             }
         }
     }
 }
 
+/// Returns the `;` needed to replace the desugared `for` loop `expr` by an expression statement,
+/// or nothing if it is already followed by one.
+fn for_loop_terminator(cx: &LateContext<'_, '_>, expr: &Expr<'_>) -> &'static str {
+    let map = cx.tcx.hir();
+    let loop_id = map.get_parent_node(expr.hir_id);
+    match map.find(map.get_parent_node(loop_id)) {
+        Some(Node::Stmt(stmt)) if matches!(stmt.kind, StmtKind::Semi(_)) => "",
+        _ => ";",
+    }
+}
+
 /// Checks for looping over a range and then indexing a sequence with it.
 /// The iteratee must be a range literal.
 #[allow(clippy::too_many_lines)]
@@ -1076,6 +1185,7 @@ fn check_for_loop_range<'a, 'tcx>(
                 indexed_mut: FxHashSet::default(),
                 indexed_indirectly: FxHashMap::default(),
                 indexed_directly: FxHashMap::default(),
+                direct_index_exprs: FxHashMap::default(),
                 referenced: FxHashSet::default(),
                 nonindex: false,
                 prefer_mutable: false,
@@ -1170,55 +1280,99 @@ fn check_for_loop_range<'a, 'tcx>(
                     mem::swap(&mut method_1, &mut method_2);
                 }
 
-                if visitor.nonindex {
-                    span_lint_and_then(
-                        cx,
-                        NEEDLESS_RANGE_LOOP,
-                        expr.span,
-                        &format!("the loop variable `{}` is used to index `{}`", ident.name, indexed),
-                        |diag| {
-                            multispan_sugg(
-                                diag,
-                                "consider using an iterator".to_string(),
-                                vec![
-                                    (pat.span, format!("({}, <item>)", ident.name)),
-                                    (
-                                        arg.span,
-                                        format!("{}.{}().enumerate(){}{}", indexed, method, method_1, method_2),
-                                    ),
-                                ],
-                            );
-                        },
-                    );
+                let item = if is_name_free(cx, body, "item") {
+                    "item"
+                } else {
+                    "<item>"
+                };
+                let (msg, pat_repl, arg_repl) = if visitor.nonindex {
+                    (
+                        format!("the loop variable `{}` is used to index `{}`", ident.name, indexed),
+                        format!("({}, {})", ident.name, item),
+                        format!("{}.{}().enumerate(){}{}", indexed, method, method_1, method_2),
+                    )
                 } else {
                     let repl = if starts_at_zero && take_is_empty {
                         format!("&{}{}", ref_mut, indexed)
                     } else {
                         format!("{}.{}(){}{}", indexed, method, method_1, method_2)
                     };
-
-                    span_lint_and_then(
-                        cx,
-                        NEEDLESS_RANGE_LOOP,
-                        expr.span,
-                        &format!(
+                    (
+                        format!(
                             "the loop variable `{}` is only used to index `{}`.",
                             ident.name, indexed
                         ),
-                        |diag| {
-                            multispan_sugg(
-                                diag,
-                                "consider using an iterator".to_string(),
-                                vec![(pat.span, "<item>".to_string()), (arg.span, repl)],
-                            );
-                        },
-                    );
+                        item.to_string(),
+                        repl,
+                    )
+                };
+
+                let applicability = if item.starts_with('<') {
+                    Applicability::HasPlaceholders
+                } else {
+                    Applicability::MaybeIncorrect
+                };
+                let mut fix = sugg::Fix::new("consider using an iterator", applicability)
+                    .replace(pat.span, pat_repl)
+                    .replace(arg.span, arg_repl);
+                if let Some(index_exprs) = visitor.direct_index_exprs.get(&indexed) {
+                    let mutable = visitor.indexed_mut.contains(&indexed);
+                    for &hir_id in index_exprs {
+                        let (span, repl) = element_replacement(cx, hir_id, item, mutable);
+                        fix = fix.replace(span, repl);
+                    }
                 }
+                span_lint_and_fix(cx, NEEDLESS_RANGE_LOOP, expr.span, &msg, fix);
             }
         }
     }
 }
 
+/// Returns the span and the replacement that uses the element `item` of an iterator instead of
+/// the index expression `hir_id`, e.g. `*item` for `v[i]` or `item` for `&v[i]`.
+fn element_replacement(cx: &LateContext<'_, '_>, hir_id: HirId, item: &str, mutable: bool) -> (Span, String) {
+    let map = cx.tcx.hir();
+    let span = map.span(hir_id);
+    if let Some(Node::Expr(parent)) = map.find(map.get_parent_node(hir_id)) {
+        match parent.kind {
+            // auto-deref takes care of these
+            ExprKind::Field(ref base, _) | ExprKind::Index(ref base, _) if base.hir_id == hir_id => {
+                return (span, item.to_string());
+            },
+            ExprKind::MethodCall(_, _, ref args) if args[0].hir_id == hir_id => {
+                let takes_ref = cx.tables.type_dependent_def_id(parent.hir_id).map_or(false, |def_id| {
+                    matches!(cx.tcx.fn_sig(def_id).inputs().skip_binder()[0].kind, ty::Ref(..))
+                });
+                return if takes_ref {
+                    (span, item.to_string())
+                } else {
+                    (span, format!("(*{})", item))
+                };
+            },
+            ExprKind::AddrOf(BorrowKind::Ref, Mutability::Not, _) if !mutable && !parent.span.from_expansion() => {
+                return (parent.span, item.to_string());
+            },
+            // the arguments of `format_args!` are only used through `Display`, `Debug` and the
+            // like, which are implemented for references as well
+            ExprKind::AddrOf(BorrowKind::Ref, Mutability::Not, _)
+                if is_expn_of(parent.span, "format_args").is_some() =>
+            {
+                return (span, item.to_string());
+            },
+            _ => {},
+        }
+    }
+    (span, format!("*{}", item))
+}
+
+/// Is `name` unused in the source of `expr`, so that it can be introduced as a new binding?
+fn is_name_free(cx: &LateContext<'_, '_>, expr: &Expr<'_>, name: &str) -> bool {
+    snippet_opt(cx, expr.span).map_or(false, |snip| {
+        snip.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .all(|word| word != name)
+    })
+}
+
 fn is_len_call(expr: &Expr<'_>, var: Name) -> bool {
     if_chain! {
         if let ExprKind::MethodCall(ref method, _, ref len_args) = expr.kind;
@@ -1474,7 +1628,7 @@ fn check_for_loop_explicit_counter<'a, 'tcx>(
 
             if visitor2.state == VarState::Warn {
                 if let Some(name) = visitor2.name {
-                    let mut applicability = Applicability::MachineApplicable;
+                    let mut applicability = Applicability::MaybeIncorrect;
 
                     // for some reason this is the only way to get the `Span`
                     // of the entire `for` loop
@@ -1483,20 +1637,36 @@ fn check_for_loop_explicit_counter<'a, 'tcx>(
                     } else {
                         unreachable!()
                     };
+                    let header_span = for_span.with_hi(arg.span.hi());
+                    let header = format!(
+                        "for ({}, {}) in {}.enumerate()",
+                        name,
+                        snippet_with_applicability(cx, pat.span, "item", &mut applicability),
+                        make_iterator_snippet(cx, arg, &mut applicability),
+                    );
 
-                    span_lint_and_sugg(
+                    // the counter is replaced by the index of `enumerate`, so its declaration and
+                    // increment have to go as well
+                    let fix = match (
+                        counter_declaration(cx, block, expr, *id),
+                        counter_increment(cx, body, *id),
+                    ) {
+                        (Some(decl), Some(incr)) if counter_fits_index(cx, body, *id) => {
+                            sugg::Fix::new("consider using", applicability)
+                                .remove(decl)
+                                .replace(header_span, header)
+                                .remove(incr)
+                        },
+                        _ => {
+                            sugg::Fix::new("consider using", Applicability::MaybeIncorrect).replace(header_span, header)
+                        },
+                    };
+                    span_lint_and_fix(
                         cx,
                         EXPLICIT_COUNTER_LOOP,
-                        for_span.with_hi(arg.span.hi()),
+                        header_span,
                         &format!("the variable `{}` is used as a loop counter.", name),
-                        "consider using",
-                        format!(
-                            "for ({}, {}) in {}.enumerate()",
-                            name,
-                            snippet_with_applicability(cx, pat.span, "item", &mut applicability),
-                            make_iterator_snippet(cx, arg, &mut applicability),
-                        ),
-                        applicability,
+                        fix,
                     );
                 }
             }
@@ -1504,6 +1674,62 @@ fn check_for_loop_explicit_counter<'a, 'tcx>(
     }
 }
 
+/// Returns the span of the `let mut counter = 0;` statement, if it is in the same block as the
+/// `for` loop and the counter is not used in between.
+fn counter_declaration<'tcx>(
+    cx: &LateContext<'_, 'tcx>,
+    block: &'tcx Block<'tcx>,
+    for_expr: &Expr<'_>,
+    id: HirId,
+) -> Option<Span> {
+    let is_loop = |e: &Expr<'_>| matches!(e.kind, ExprKind::DropTemps(inner) if inner.hir_id == for_expr.hir_id);
+    let loop_pos = block
+        .stmts
+        .iter()
+        .position(|stmt| matches!(stmt.kind, StmtKind::Expr(e) | StmtKind::Semi(e) if is_loop(e)))
+        .or_else(|| block.expr.filter(|e| is_loop(e)).map(|_| block.stmts.len()))?;
+    let decl_pos = block.stmts[..loop_pos].iter().position(|stmt| {
+        matches!(stmt.kind, StmtKind::Local(local)
+            if local.pat.hir_id == id && local.init.map_or(false, |init| is_integer_const(cx, init, 0)))
+    })?;
+    let used_in_between = block.stmts[decl_pos + 1..loop_pos].iter().any(|stmt| match stmt.kind {
+        StmtKind::Local(local) => local.init.map_or(false, |init| local_use_count(cx, id, init) > 0),
+        StmtKind::Expr(e) | StmtKind::Semi(e) => local_use_count(cx, id, e) > 0,
+        StmtKind::Item(_) => false,
+    });
+    if used_in_between {
+        None
+    } else {
+        Some(block.stmts[decl_pos].span)
+    }
+}
+
+/// Returns the span of the `counter += 1` increment, if it is the last statement of the loop
+/// body (otherwise the code after it would see a different value).
+fn counter_increment(cx: &LateContext<'_, '_>, body: &Expr<'_>, id: HirId) -> Option<Span> {
+    let is_increment = |e: &Expr<'_>| {
+        matches!(e.kind, ExprKind::AssignOp(op, ref lhs, ref rhs)
+            if op.node == BinOpKind::Add && var_def_id(cx, lhs) == Some(id) && is_integer_const(cx, rhs, 1))
+    };
+    if let ExprKind::Block(ref block, _) = body.kind {
+        match (block.stmts.last(), block.expr) {
+            (_, Some(e)) if is_increment(e) => Some(e.span),
+            (Some(stmt), None) => match stmt.kind {
+                StmtKind::Semi(e) if is_increment(e) => Some(stmt.span),
+                _ => None,
+            },
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Can the counter be replaced by the `usize` index of `enumerate` without changing its type?
+fn counter_fits_index<'tcx>(cx: &LateContext<'_, 'tcx>, body: &'tcx Expr<'_>, id: HirId) -> bool {
+    local_use_count(cx, id, body) == 1 || matches!(cx.tables.node_type(id).kind, ty::Uint(ast::UintTy::Usize))
+}
+
 /// If `arg` was the argument to a `for` loop, return the "cleanest" way of writing the
 /// actual `Iterator` that the loop uses.
 fn make_iterator_snippet(cx: &LateContext<'_, '_>, arg: &Expr<'_>, applic_ref: &mut Applicability) -> String {
@@ -1530,7 +1756,7 @@ fn make_iterator_snippet(cx: &LateContext<'_, '_>, arg: &Expr<'_>, applic_ref: &
                     sugg::Sugg::hir_with_applicability(cx, &arg_inner, "_", applic_ref).maybe_par(),
                     meth_name,
                 )
-            }
+            },
             _ => format!(
                 "{}.into_iter()",
                 sugg::Sugg::hir_with_applicability(cx, arg, "_", applic_ref).maybe_par()
@@ -1743,6 +1969,9 @@ struct VarVisitor<'a, 'tcx> {
     /// subset of `indexed` of vars that are indexed directly: `v[i]`
     /// this will not contain cases like `v[calc_index(i)]` or `v[(i + 4) % N]`
     indexed_directly: FxHashMap<Name, (Option<region::Scope>, Ty<'tcx>)>,
+    /// the `v[i]` expressions of the directly indexed variables, used to rewrite them
+    /// when suggesting an iterator
+    direct_index_exprs: FxHashMap<Name, Vec<HirId>>,
     /// Any names that are used outside an index operation.
     /// Used to detect things like `&mut vec` used together with `vec[i]`
     referenced: FxHashSet<Name>,
@@ -1791,6 +2020,7 @@ impl<'a, 'tcx> VarVisitor<'a, 'tcx> {
                                     seqvar.segments[0].ident.name,
                                     (Some(extent), self.cx.tables.node_type(seqexpr.hir_id)),
                                 );
+                                self.add_direct_index_expr(seqvar.segments[0].ident.name, expr);
                             }
                             return false;  // no need to walk further *on the variable*
                         }
@@ -1803,6 +2033,7 @@ impl<'a, 'tcx> VarVisitor<'a, 'tcx> {
                                     seqvar.segments[0].ident.name,
                                     (None, self.cx.tables.node_type(seqexpr.hir_id)),
                                 );
+                                self.add_direct_index_expr(seqvar.segments[0].ident.name, expr);
                             }
                             return false;  // no need to walk further *on the variable*
                         }
@@ -1813,6 +2044,16 @@ impl<'a, 'tcx> VarVisitor<'a, 'tcx> {
         }
         true
     }
+
+    fn add_direct_index_expr(&mut self, name: Name, expr: &Expr<'_>) {
+        // `v.index(i)` calls can't be replaced by the element
+        if let ExprKind::Index(..) = expr.kind {
+            self.direct_index_exprs
+                .entry(name)
+                .or_insert_with(Vec::new)
+                .push(expr.hir_id);
+        }
+    }
 }
 
 impl<'a, 'tcx> Visitor<'tcx> for VarVisitor<'a, 'tcx> {
@@ -2206,17 +2447,11 @@ fn var_def_id(cx: &LateContext<'_, '_>, expr: &Expr<'_>) -> Option<HirId> {
 }
 
 fn is_loop(expr: &Expr<'_>) -> bool {
-    match expr.kind {
-        ExprKind::Loop(..) => true,
-        _ => false,
-    }
+    matches!(expr.kind, ExprKind::Loop(..))
 }
 
 fn is_conditional(expr: &Expr<'_>) -> bool {
-    match expr.kind {
-        ExprKind::Match(..) => true,
-        _ => false,
-    }
+    matches!(expr.kind, ExprKind::Match(..))
 }
 
 fn is_nested(cx: &LateContext<'_, '_>, match_expr: &Expr<'_>, iter_expr: &Expr<'_>) -> bool {
@@ -2471,6 +2706,11 @@ impl<'a, 'tcx> Visitor<'tcx> for VarCollectorVisitor<'a, 'tcx> {
 const NEEDLESS_COLLECT_MSG: &str = "avoid using `collect()` when not needed";
 
 fn check_needless_collect<'a, 'tcx>(expr: &'tcx Expr<'_>, cx: &LateContext<'a, 'tcx>) {
+    check_needless_collect_direct_usage(expr, cx);
+    check_needless_collect_indirect_usage(expr, cx);
+}
+
+fn check_needless_collect_direct_usage<'a, 'tcx>(expr: &'tcx Expr<'_>, cx: &LateContext<'a, 'tcx>) {
     if_chain! {
         if let ExprKind::MethodCall(ref method, _, ref args) = expr.kind;
         if let ExprKind::MethodCall(ref chain_method, _, _) = args[0].kind;
@@ -2538,6 +2778,206 @@ fn check_needless_collect<'a, 'tcx>(expr: &'tcx Expr<'_>, cx: &LateContext<'a, '
     }
 }
 
+/// Looks for `let v = iter.collect();` statements in a block where `v` is used exactly once in the
+/// rest of the block, in a way that works with `iter` as well.
+fn check_needless_collect_indirect_usage<'a, 'tcx>(expr: &'tcx Expr<'tcx>, cx: &LateContext<'a, 'tcx>) {
+    if let ExprKind::Block(ref block, _) = expr.kind {
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            if_chain! {
+                if let StmtKind::Local(ref local) = stmt.kind;
+                if let PatKind::Binding(annotation, id, _, None) = local.pat.kind;
+                if matches!(annotation, BindingAnnotation::Unannotated | BindingAnnotation::Mutable);
+                if let Some(ref init) = local.init;
+                if !init.span.from_expansion();
+                if let ExprKind::MethodCall(ref method, _, ref args) = init.kind;
+                if method.ident.name == sym!(collect) && match_trait_method(cx, init, &paths::ITERATOR);
+                let ty = cx.tables.expr_ty(init);
+                if is_type_diagnostic_item(cx, ty, sym!(vec_type))
+                    || is_type_diagnostic_item(cx, ty, sym!(vecdeque_type))
+                    || match_type(cx, ty, &paths::LINKED_LIST);
+                let rest: Vec<&'tcx Expr<'tcx>> = block.stmts[i + 1..]
+                    .iter()
+                    .filter_map(|stmt| match stmt.kind {
+                        StmtKind::Local(local) => local.init,
+                        StmtKind::Expr(expr) | StmtKind::Semi(expr) => Some(expr),
+                        StmtKind::Item(..) => None,
+                    })
+                    .chain(block.expr)
+                    .collect();
+                // the binding must not be used, moved or mutated anywhere else
+                if rest.iter().map(|expr| local_use_count(cx, id, expr)).sum::<usize>() == 1;
+                if let Some(usage) = find_indirect_collect_usage(cx, id, &rest);
+                then {
+                    let mut applicability = Applicability::MaybeIncorrect;
+                    let iter = sugg::Sugg::hir_with_applicability(cx, &args[0], "..", &mut applicability);
+                    let (span, replacement) = match usage {
+                        IndirectCollectUsage::Len(expr) => (expr.span, format!("{}.count()", iter.maybe_par())),
+                        IndirectCollectUsage::Iterator(expr) => (expr.span, iter.to_string()),
+                    };
+                    span_lint_and_then(cx, NEEDLESS_COLLECT, method.ident.span, NEEDLESS_COLLECT_MSG, |diag| {
+                        diag.span_label(span, "the iterator could be used here instead");
+                        diag.multipart_suggestion(
+                            "use the original iterator instead of collecting it",
+                            vec![(stmt.span, String::new()), (span, replacement)],
+                            applicability,
+                        );
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A use of a collected local that could use the original iterator instead.
+enum IndirectCollectUsage<'tcx> {
+    /// `v.len()`, which becomes `iter.count()`
+    Len(&'tcx Expr<'tcx>),
+    /// `for _ in v`, `v.iter()` in `v.iter().any(..)` or `v` passed as an `IntoIterator`, which
+    /// all become `iter`
+    Iterator(&'tcx Expr<'tcx>),
+}
+
+fn find_indirect_collect_usage<'a, 'tcx>(
+    cx: &'a LateContext<'a, 'tcx>,
+    id: HirId,
+    exprs: &[&'tcx Expr<'tcx>],
+) -> Option<IndirectCollectUsage<'tcx>> {
+    let mut visitor = IndirectCollectUsageVisitor {
+        cx,
+        id,
+        nested: 0,
+        usage: None,
+        repeated: false,
+    };
+    for expr in exprs {
+        visitor.visit_expr(expr);
+        if visitor.usage.is_some() {
+            break;
+        }
+    }
+    if visitor.repeated {
+        None
+    } else {
+        visitor.usage
+    }
+}
+
+/// Finds the first supported use of the local `id`, and whether it is inside of a loop or closure
+/// (where the iterator could be used more than once).
+struct IndirectCollectUsageVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'a, 'tcx>,
+    id: HirId,
+    nested: usize,
+    usage: Option<IndirectCollectUsage<'tcx>>,
+    repeated: bool,
+}
+
+impl<'a, 'tcx> IndirectCollectUsageVisitor<'a, 'tcx> {
+    fn is_local(&self, expr: &Expr<'_>) -> bool {
+        if let ExprKind::Path(QPath::Resolved(None, ref path)) = expr.kind {
+            path.res == Res::Local(self.id)
+        } else {
+            false
+        }
+    }
+
+    fn usage(&self, expr: &'tcx Expr<'tcx>) -> Option<IndirectCollectUsage<'tcx>> {
+        if let Some((_, arg, _)) = higher::for_loop(expr) {
+            if self.is_local(arg) {
+                return Some(IndirectCollectUsage::Iterator(arg));
+            }
+        }
+        match expr.kind {
+            ExprKind::MethodCall(ref method, _, ref args)
+                if method.ident.name == sym!(len) && self.is_local(&args[0]) =>
+            {
+                Some(IndirectCollectUsage::Len(expr))
+            },
+            ExprKind::MethodCall(ref method, _, ref args)
+                if method.ident.name == sym!(any) && match_trait_method(self.cx, expr, &paths::ITERATOR) =>
+            {
+                match args[0].kind {
+                    ExprKind::MethodCall(ref iter_method, _, ref iter_args)
+                        if iter_method.ident.name == sym!(iter) && self.is_local(&iter_args[0]) =>
+                    {
+                        Some(IndirectCollectUsage::Iterator(&args[0]))
+                    },
+                    _ => None,
+                }
+            },
+            ExprKind::MethodCall(_, _, ref args) => {
+                let def_id = self.cx.tables.type_dependent_def_id(expr.hir_id)?;
+                self.into_iterator_arg(def_id, args)
+            },
+            ExprKind::Call(ref func, ref args) => {
+                if let ExprKind::Path(ref qpath) = func.kind {
+                    let def_id = self.cx.tables.qpath_res(qpath, func.hir_id).opt_def_id()?;
+                    self.into_iterator_arg(def_id, args)
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the argument that is the local, if the function `def_id` takes it as a generic
+    /// `IntoIterator` parameter.
+    fn into_iterator_arg(&self, def_id: def_id::DefId, args: &'tcx [Expr<'tcx>]) -> Option<IndirectCollectUsage<'tcx>> {
+        let index = args.iter().position(|arg| self.is_local(arg))?;
+        let sig = self.cx.tcx.fn_sig(def_id);
+        let input = *sig.skip_binder().inputs().get(index)?;
+        if !matches!(input.kind, ty::Param(_)) {
+            return None;
+        }
+        let is_into_iterator = self
+            .cx
+            .tcx
+            .predicates_of(def_id)
+            .predicates
+            .iter()
+            .any(|(predicate, _)| {
+                if let ty::Predicate::Trait(ref poly_trait_predicate, _) = predicate {
+                    let trait_ref = poly_trait_predicate.skip_binder().trait_ref;
+                    trait_ref.self_ty() == input && match_def_path(self.cx, trait_ref.def_id, &paths::INTO_ITERATOR)
+                } else {
+                    false
+                }
+            });
+        if is_into_iterator {
+            Some(IndirectCollectUsage::Iterator(&args[index]))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for IndirectCollectUsageVisitor<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if self.usage.is_some() {
+            return;
+        }
+        if let Some(usage) = self.usage(expr) {
+            self.usage = Some(usage);
+            self.repeated = self.nested > 0;
+            return;
+        }
+        if let ExprKind::Loop(..) | ExprKind::Closure(..) = expr.kind {
+            self.nested += 1;
+            walk_expr(self, expr);
+            self.nested -= 1;
+        } else {
+            walk_expr(self, expr);
+        }
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.cx.tcx.hir())
+    }
+}
+
 fn shorten_needless_collect_span(expr: &Expr<'_>) -> Span {
     if_chain! {
         if let ExprKind::MethodCall(_, _, ref args) = expr.kind;
@@ -2548,3 +2988,620 @@ fn shorten_needless_collect_span(expr: &Expr<'_>) -> Span {
     }
     unreachable!()
 }
+
+/// Returns the expressions of the statements `stmts` and of the trailing expression `tail`.
+fn stmts_exprs<'tcx>(
+    stmts: &'tcx [Stmt<'tcx>],
+    tail: Option<&'tcx Expr<'tcx>>,
+) -> impl Iterator<Item = &'tcx Expr<'tcx>> {
+    stmts.iter().filter_map(stmt_to_expr).chain(tail)
+}
+
+/// Returns the only expression in the block `body`, with or without a semicolon.
+fn single_expr<'tcx>(body: &'tcx Expr<'tcx>) -> Option<&'tcx Expr<'tcx>> {
+    if let ExprKind::Block(block, _) = body.kind {
+        match (block.stmts, block.expr) {
+            ([], Some(expr)) => Some(expr),
+            ([stmt], None) => match stmt.kind {
+                StmtKind::Expr(expr) | StmtKind::Semi(expr) => Some(expr),
+                _ => None,
+            },
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Checks the loops in `block` for `MANUAL_ITERATOR_LOOP`. The accumulating loops need the
+/// declaration of the accumulator right before the loop, which is why this works on blocks.
+fn check_manual_iterator_loops<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, block: &'tcx Block<'tcx>) {
+    let loops = block
+        .stmts
+        .iter()
+        .map(|stmt| match stmt.kind {
+            StmtKind::Expr(expr) | StmtKind::Semi(expr) => Some((stmt.span, expr)),
+            StmtKind::Local(..) | StmtKind::Item(..) => None,
+        })
+        .chain(once(block.expr.map(|expr| (expr.span, expr))));
+
+    for (i, (span, loop_expr)) in loops.enumerate().filter_map(|(i, stmt)| Some((i, stmt?))) {
+        if in_macro(span) {
+            continue;
+        }
+        let rest = stmts_exprs(
+            block.stmts.get(i + 1..).unwrap_or(&[]),
+            block.expr.filter(|_| i < block.stmts.len()),
+        );
+        // the accumulator must be declared right before the loop, without anything in between
+        let decl = i
+            .checked_sub(1)
+            .map(|j| &block.stmts[j])
+            .and_then(|stmt| match stmt.kind {
+                StmtKind::Local(local) if !in_macro(stmt.span) => Some((stmt.span, local)),
+                _ => None,
+            })
+            .filter(|(decl_span, _)| {
+                snippet_opt(cx, decl_span.between(span)).map_or(false, |between| between.trim().is_empty())
+            });
+
+        if let ExprKind::DropTemps(inner) = loop_expr.kind {
+            if let Some((pat, arg, body)) = higher::for_loop(inner) {
+                if let Some((decl_span, local)) = decl {
+                    check_manual_accumulation(cx, decl_span.to(span), local, pat, arg, body, rest);
+                }
+                check_manual_extend(cx, span, pat, arg, body);
+            }
+        } else if let Some((cond, body)) = higher::while_loop(loop_expr) {
+            if let Some((decl_span, local)) = decl {
+                check_manual_retain(cx, decl_span.to(span), local, cond, body, rest);
+            }
+        }
+    }
+}
+
+/// The accumulator of a loop, declared as `let mut acc = init;` right before it.
+struct Accumulator<'tcx> {
+    id: HirId,
+    name: Name,
+    init: &'tcx Expr<'tcx>,
+    ty: Option<&'tcx rustc_hir::Ty<'tcx>>,
+}
+
+/// Checks for `let mut acc = ..; for pat in arg { .. }` loops that compute a sum, `any`,
+/// `position` or `max_by_key`/`min_by_key`.
+fn check_manual_accumulation<'a, 'tcx>(
+    cx: &LateContext<'a, 'tcx>,
+    span: Span,
+    local: &'tcx Local<'tcx>,
+    pat: &'tcx Pat<'tcx>,
+    arg: &'tcx Expr<'tcx>,
+    body: &'tcx Expr<'tcx>,
+    rest: impl Iterator<Item = &'tcx Expr<'tcx>>,
+) {
+    let acc = match (&local.pat.kind, local.init) {
+        (&PatKind::Binding(BindingAnnotation::Mutable, id, ident, None), Some(init)) => Accumulator {
+            id,
+            name: ident.name,
+            init,
+            ty: local.ty,
+        },
+        _ => return,
+    };
+    let expr = match single_expr(body) {
+        Some(expr) => expr,
+        None => return,
+    };
+
+    let mut applicability = Applicability::MaybeIncorrect;
+    let accumulation = manual_sum(cx, &acc, pat, arg, expr, &mut applicability)
+        .or_else(|| manual_any(cx, &acc, pat, arg, expr, &mut applicability))
+        .or_else(|| manual_position(cx, &acc, pat, arg, expr, &mut applicability))
+        .or_else(|| manual_max_by_key(cx, &acc, pat, arg, expr, &mut applicability));
+    if let Some((method, value)) = accumulation {
+        // the accumulator stays mutable if it is modified after the loop
+        let mutated_later = rest
+            .map(|expr| mutated_variables(expr, cx))
+            .any(|mutated| mutated.map_or(true, |mutated| mutated.contains(&acc.id)));
+        let ty = match acc.ty {
+            Some(ty) => format!(": {}", snippet_with_applicability(cx, ty.span, "_", &mut applicability)),
+            // `sum` needs to know the type to sum into
+            None if method == "Iterator::sum" => format!(": {}", cx.tables.node_type(acc.id)),
+            None => String::new(),
+        };
+        span_lint_and_sugg(
+            cx,
+            MANUAL_ITERATOR_LOOP,
+            span,
+            &format!("this loop can be replaced by a call to `{}`", method),
+            "replace it with",
+            format!(
+                "let {}{}{} = {};",
+                if mutated_later { "mut " } else { "" },
+                acc.name,
+                ty,
+                value
+            ),
+            applicability,
+        );
+    }
+}
+
+/// `let mut acc = 0; for x in iter { acc += f(x); }` => `iter.map(|x| f(x)).sum()`
+fn manual_sum<'a, 'tcx>(
+    cx: &LateContext<'a, 'tcx>,
+    acc: &Accumulator<'tcx>,
+    pat: &'tcx Pat<'tcx>,
+    arg: &'tcx Expr<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    applicability: &mut Applicability,
+) -> Option<(&'static str, String)> {
+    if_chain! {
+        let ty = cx.tables.node_type(acc.id);
+        if ty.is_integral() || ty.is_floating_point();
+        if let Some((init, _)) = constant(cx, cx.tables, acc.init);
+        if matches!(init, Constant::Int(0)) || init == Constant::F32(0.0) || init == Constant::F64(0.0);
+        if let ExprKind::AssignOp(op, ref lhs, ref value) = expr.kind;
+        if op.node == BinOpKind::Add && var_def_id(cx, lhs) == Some(acc.id);
+        if local_use_count(cx, acc.id, value) == 0 && !has_control_flow(cx, value);
+        // `acc += 1` counts the elements, which `explicit_counter_loop` deals with
+        if uses_pat_binding(cx, pat, value);
+        then {
+            let iter = make_iterator_snippet(cx, arg, applicability);
+            let value = if is_pat_binding(cx, pat, value) {
+                format!("{}.sum()", iter)
+            } else {
+                format!(
+                    "{}.map(|{}| {}).sum()",
+                    iter,
+                    snippet_with_applicability(cx, pat.span, "_", applicability),
+                    snippet_with_applicability(cx, value.span, "..", applicability),
+                )
+            };
+            return Some(("Iterator::sum", value));
+        }
+    }
+    None
+}
+
+/// `let mut acc = false; for x in iter { if p(x) { acc = true; break; } }` => `iter.any(|x| p(x))`
+fn manual_any<'a, 'tcx>(
+    cx: &LateContext<'a, 'tcx>,
+    acc: &Accumulator<'tcx>,
+    pat: &'tcx Pat<'tcx>,
+    arg: &'tcx Expr<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    applicability: &mut Applicability,
+) -> Option<(&'static str, String)> {
+    if_chain! {
+        if let ExprKind::Lit(ref lit) = acc.init.kind;
+        if let ast::LitKind::Bool(false) = lit.node;
+        if let Some((cond, then, None)) = higher::if_block(expr);
+        if let Some(value) = assign_and_break(cx, then, acc.id);
+        if let ExprKind::Lit(ref lit) = value.kind;
+        if let ast::LitKind::Bool(true) = lit.node;
+        if local_use_count(cx, acc.id, cond) == 0 && !has_control_flow(cx, cond);
+        then {
+            return Some((
+                "Iterator::any",
+                format!(
+                    "{}.any(|{}| {})",
+                    make_iterator_snippet(cx, arg, applicability),
+                    snippet_with_applicability(cx, pat.span, "_", applicability),
+                    snippet_with_applicability(cx, cond.span, "..", applicability),
+                ),
+            ));
+        }
+    }
+    None
+}
+
+/// `let mut acc = None; for (i, x) in iter.enumerate() { if p(x) { acc = Some(i); break; } }`
+/// => `iter.position(|x| p(x))`
+fn manual_position<'a, 'tcx>(
+    cx: &LateContext<'a, 'tcx>,
+    acc: &Accumulator<'tcx>,
+    pat: &'tcx Pat<'tcx>,
+    arg: &'tcx Expr<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    applicability: &mut Applicability,
+) -> Option<(&'static str, String)> {
+    if_chain! {
+        if let ExprKind::Path(ref qpath) = acc.init.kind;
+        if match_qpath(qpath, &paths::OPTION_NONE);
+        if let ExprKind::MethodCall(ref method, _, ref enumerate_args) = arg.kind;
+        if method.ident.name == sym!(enumerate) && match_trait_method(cx, arg, &paths::ITERATOR);
+        if let PatKind::Tuple(ref pats, None) = pat.kind;
+        if let [index_pat, item_pat] = pats;
+        if let PatKind::Binding(_, index_id, _, None) = index_pat.kind;
+        if let Some((cond, then, None)) = higher::if_block(expr);
+        if let Some(value) = assign_and_break(cx, then, acc.id);
+        if let ExprKind::Call(ref some, ref some_args) = value.kind;
+        if let ExprKind::Path(ref some_path) = some.kind;
+        if match_qpath(some_path, &paths::OPTION_SOME) && var_def_id(cx, &some_args[0]) == Some(index_id);
+        if local_use_count(cx, acc.id, cond) == 0 && local_use_count(cx, index_id, cond) == 0;
+        if !has_control_flow(cx, cond);
+        then {
+            return Some((
+                "Iterator::position",
+                format!(
+                    "{}.position(|{}| {})",
+                    sugg::Sugg::hir_with_applicability(cx, &enumerate_args[0], "..", applicability).maybe_par(),
+                    snippet_with_applicability(cx, item_pat.span, "_", applicability),
+                    snippet_with_applicability(cx, cond.span, "..", applicability),
+                ),
+            ));
+        }
+    }
+    None
+}
+
+/// `let mut acc = None; for x in iter { if acc.map_or(true, |a| key(x) >= key(a)) { acc = Some(x);
+/// } }` => `iter.max_by_key(|x| key(x))`
+///
+/// Only `>=` (which keeps the last maximum) and `<` (which keeps the first minimum) behave like
+/// `max_by_key` and `min_by_key`.
+fn manual_max_by_key<'a, 'tcx>(
+    cx: &LateContext<'a, 'tcx>,
+    acc: &Accumulator<'tcx>,
+    pat: &'tcx Pat<'tcx>,
+    arg: &'tcx Expr<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    applicability: &mut Applicability,
+) -> Option<(&'static str, String)> {
+    if_chain! {
+        if let ExprKind::Path(ref qpath) = acc.init.kind;
+        if match_qpath(qpath, &paths::OPTION_NONE);
+        if let PatKind::Binding(BindingAnnotation::Unannotated, item_id, _, None) = pat.kind;
+        if let Some((cond, then, None)) = higher::if_block(expr);
+        if let ExprKind::MethodCall(ref method, _, ref map_or_args) = cond.kind;
+        if method.ident.name == sym!(map_or) && var_def_id(cx, &map_or_args[0]) == Some(acc.id);
+        if let ExprKind::Lit(ref lit) = map_or_args[1].kind;
+        if let ast::LitKind::Bool(true) = lit.node;
+        if let ExprKind::Closure(_, _, body_id, _, _) = map_or_args[2].kind;
+        let closure = cx.tcx.hir().body(body_id);
+        if let [param] = closure.params;
+        if let PatKind::Binding(_, prev_id, _, None) = param.pat.kind;
+        if let ExprKind::Binary(op, ref left, ref right) = remove_blocks(&closure.value).kind;
+        let (method, key) = match op.node {
+            BinOpKind::Ge => ("Iterator::max_by_key", left),
+            BinOpKind::Le => ("Iterator::max_by_key", right),
+            BinOpKind::Lt => ("Iterator::min_by_key", left),
+            BinOpKind::Gt => ("Iterator::min_by_key", right),
+            _ => return None,
+        };
+        let other = if key.hir_id == left.hir_id { right } else { left };
+        if is_same_key(cx, key, other, item_id, prev_id);
+        if let Some(assign) = single_expr(then);
+        if let ExprKind::Assign(ref target, ref value, _) = assign.kind;
+        if var_def_id(cx, target) == Some(acc.id);
+        if let ExprKind::Call(ref some, ref some_args) = value.kind;
+        if let ExprKind::Path(ref some_path) = some.kind;
+        if match_qpath(some_path, &paths::OPTION_SOME) && var_def_id(cx, &some_args[0]) == Some(item_id);
+        then {
+            // the key function gets a reference to the element, so the key must not borrow from
+            // it, and functions called with the element itself would get a reference instead
+            let key_ty = cx.tables.expr_ty(key);
+            if !is_copy(cx, key_ty) || matches!(key_ty.kind, ty::Ref(..)) || matches!(key.kind, ExprKind::Call(..)) {
+                *applicability = Applicability::MaybeIncorrect;
+            }
+            let method_name = if method == "Iterator::max_by_key" { "max_by_key" } else { "min_by_key" };
+            return Some((
+                method,
+                format!(
+                    "{}.{}(|{}| {})",
+                    make_iterator_snippet(cx, arg, applicability),
+                    method_name,
+                    snippet_with_applicability(cx, pat.span, "_", applicability),
+                    snippet_with_applicability(cx, key.span, "..", applicability),
+                ),
+            ));
+        }
+    }
+    None
+}
+
+/// Are `left` and `right` the same key of the elements `left_id` and `right_id`, like `x.len()`
+/// and `y.len()`? The parts that don't depend on the elements are compared with `SpanlessEq`.
+fn is_same_key(cx: &LateContext<'_, '_>, left: &Expr<'_>, right: &Expr<'_>, left_id: HirId, right_id: HirId) -> bool {
+    match (&left.kind, &right.kind) {
+        (ExprKind::Field(l_base, l_ident), ExprKind::Field(r_base, r_ident)) => {
+            l_ident.name == r_ident.name && is_same_key_or_element(cx, l_base, r_base, left_id, right_id)
+        },
+        (ExprKind::MethodCall(l_method, _, l_args), ExprKind::MethodCall(r_method, _, r_args)) => {
+            l_method.ident.name == r_method.ident.name
+                && l_args.len() == r_args.len()
+                && is_same_key_or_element(cx, &l_args[0], &r_args[0], left_id, right_id)
+                && SpanlessEq::new(cx).eq_exprs(&l_args[1..], &r_args[1..])
+        },
+        (ExprKind::Call(l_fn, l_args), ExprKind::Call(r_fn, r_args)) => {
+            l_args.len() == 1
+                && r_args.len() == 1
+                && SpanlessEq::new(cx).eq_expr(l_fn, r_fn)
+                && is_same_key_or_element(cx, &l_args[0], &r_args[0], left_id, right_id)
+        },
+        _ => false,
+    }
+}
+
+fn is_same_key_or_element(
+    cx: &LateContext<'_, '_>,
+    left: &Expr<'_>,
+    right: &Expr<'_>,
+    left_id: HirId,
+    right_id: HirId,
+) -> bool {
+    (var_def_id(cx, left) == Some(left_id) && var_def_id(cx, right) == Some(right_id))
+        || is_same_key(cx, left, right, left_id, right_id)
+}
+
+/// Matches `{ acc = value; break; }` and returns `value`.
+fn assign_and_break<'tcx>(cx: &LateContext<'_, 'tcx>, block: &'tcx Expr<'tcx>, acc: HirId) -> Option<&'tcx Expr<'tcx>> {
+    if let ExprKind::Block(block, _) = block.kind {
+        let exprs: Vec<_> = stmts_exprs(block.stmts, block.expr).collect();
+        if_chain! {
+            if block.stmts.iter().all(|stmt| !matches!(stmt.kind, StmtKind::Local(..) | StmtKind::Item(..)));
+            if let [assign, brk] = exprs.as_slice();
+            if let ExprKind::Assign(ref target, ref value, _) = assign.kind;
+            if var_def_id(cx, target) == Some(acc);
+            if let ExprKind::Break(destination, None) = brk.kind;
+            if destination.label.is_none();
+            then {
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
+/// Does `expr` use any of the bindings of the loop pattern `pat`?
+fn uses_pat_binding<'tcx>(cx: &LateContext<'_, 'tcx>, pat: &Pat<'_>, expr: &'tcx Expr<'_>) -> bool {
+    let mut used = false;
+    pat.each_binding(|_, id, _, _| used |= local_use_count(cx, id, expr) > 0);
+    used
+}
+
+/// Is `expr` the binding of the loop pattern `pat`, possibly dereferenced?
+fn is_pat_binding(cx: &LateContext<'_, '_>, pat: &Pat<'_>, expr: &Expr<'_>) -> bool {
+    let mut pat = pat;
+    while let PatKind::Ref(inner, _) = pat.kind {
+        pat = inner;
+    }
+    let mut expr = expr;
+    while let ExprKind::Unary(UnOp::UnDeref, ref inner) = expr.kind {
+        expr = inner;
+    }
+    matches!(pat.kind, PatKind::Binding(_, id, _, None) if var_def_id(cx, expr) == Some(id))
+}
+
+/// `for x in iter { v.push(f(x)); }` => `v.extend(iter.map(|x| f(x)));`
+fn check_manual_extend<'a, 'tcx>(
+    cx: &LateContext<'a, 'tcx>,
+    span: Span,
+    pat: &'tcx Pat<'tcx>,
+    arg: &'tcx Expr<'tcx>,
+    body: &'tcx Expr<'tcx>,
+) {
+    if_chain! {
+        if let Some(expr) = single_expr(body);
+        if let ExprKind::MethodCall(ref method, _, ref args) = expr.kind;
+        if method.ident.name == sym!(push);
+        if is_type_diagnostic_item(cx, walk_ptrs_ty(cx.tables.expr_ty(&args[0])), sym!(vec_type));
+        let (target, value) = (&args[0], &args[1]);
+        if !contains_expr(cx, value, target) && !contains_expr(cx, arg, target);
+        if !has_control_flow(cx, value);
+        then {
+            let mut applicability = Applicability::MaybeIncorrect;
+            // `v.extend(iter)` moves the same items that `for x in iter { v.push(x) }` pushes
+            let iter = if matches!(pat.kind, PatKind::Binding(..)) && is_pat_binding(cx, pat, value) {
+                snippet_with_applicability(cx, arg.span, "..", &mut applicability).into_owned()
+            } else {
+                format!(
+                    "{}.map(|{}| {})",
+                    make_iterator_snippet(cx, arg, &mut applicability),
+                    snippet_with_applicability(cx, pat.span, "_", &mut applicability),
+                    snippet_with_applicability(cx, value.span, "..", &mut applicability),
+                )
+            };
+            span_lint_and_sugg(
+                cx,
+                MANUAL_ITERATOR_LOOP,
+                span,
+                "this loop can be replaced by a call to `Vec::extend`",
+                "replace it with",
+                format!(
+                    "{}.extend({});",
+                    sugg::Sugg::hir_with_applicability(cx, target, "..", &mut applicability).maybe_par(),
+                    iter
+                ),
+                applicability,
+            );
+        }
+    }
+}
+
+/// `let mut i = 0; while i < v.len() { if p(v[i]) { v.remove(i); } else { i += 1; } }`
+/// => `v.retain(|x| !p(*x));`
+fn check_manual_retain<'a, 'tcx>(
+    cx: &LateContext<'a, 'tcx>,
+    span: Span,
+    local: &'tcx Local<'tcx>,
+    cond: &'tcx Expr<'tcx>,
+    body: &'tcx Expr<'tcx>,
+    mut rest: impl Iterator<Item = &'tcx Expr<'tcx>>,
+) {
+    if_chain! {
+        if let PatKind::Binding(BindingAnnotation::Mutable, index_id, _, None) = local.pat.kind;
+        if let Some(init) = local.init;
+        if is_integer_const(cx, init, 0);
+        // `i < v.len()`
+        if let ExprKind::Binary(op, ref index, ref len) = cond.kind;
+        if op.node == BinOpKind::Lt && var_def_id(cx, index) == Some(index_id);
+        if let ExprKind::MethodCall(ref len_method, _, ref len_args) = len.kind;
+        if len_method.ident.name == sym!(len);
+        let vec = &len_args[0];
+        if is_type_diagnostic_item(cx, walk_ptrs_ty(cx.tables.expr_ty(vec)), sym!(vec_type));
+        // `if p(v[i]) { v.remove(i); } else { i += 1; }`
+        if let Some(expr) = single_expr(body);
+        if let Some((remove_cond, then, Some(els))) = higher::if_block(expr);
+        if let Some(remove) = single_expr(then);
+        if let ExprKind::MethodCall(ref remove_method, _, ref remove_args) = remove.kind;
+        if remove_method.ident.name == sym!(remove);
+        if SpanlessEq::new(cx).eq_expr(&remove_args[0], vec) && var_def_id(cx, &remove_args[1]) == Some(index_id);
+        if let Some(increment) = single_expr(els);
+        if let ExprKind::AssignOp(increment_op, ref counter, ref one) = increment.kind;
+        if increment_op.node == BinOpKind::Add && var_def_id(cx, counter) == Some(index_id);
+        if is_integer_const(cx, one, 1);
+        // the condition may only look at `v[i]`
+        if !has_control_flow(cx, remove_cond);
+        if let Some(index_exprs) = index_uses(cx, vec, index_id, remove_cond);
+        if local_use_count(cx, index_id, remove_cond) == index_exprs.len();
+        // the index isn't needed after the loop
+        if rest.all(|expr| local_use_count(cx, index_id, expr) == 0);
+        if is_name_free(cx, remove_cond, "item");
+        then {
+            let mut applicability = Applicability::MaybeIncorrect;
+            let (cond, negate) = match remove_cond.kind {
+                ExprKind::Unary(UnOp::UnNot, ref inner) => (&**inner, false),
+                _ => (remove_cond, true),
+            };
+            let edits = index_exprs
+                .iter()
+                .map(|&hir_id| element_replacement(cx, hir_id, "item", false))
+                .collect::<Vec<_>>();
+            let keep = match snippet_with_edits(cx, cond.span, &edits) {
+                Some(keep) => keep,
+                None => return,
+            };
+            let keep = if !negate {
+                keep
+            } else if let sugg::Sugg::NonParen(_) = sugg::Sugg::hir(cx, cond, "..") {
+                format!("!{}", keep)
+            } else {
+                format!("!({})", keep)
+            };
+            span_lint_and_sugg(
+                cx,
+                MANUAL_ITERATOR_LOOP,
+                span,
+                "this loop can be replaced by a call to `Vec::retain`",
+                "replace it with",
+                format!(
+                    "{}.retain(|item| {});",
+                    sugg::Sugg::hir_with_applicability(cx, vec, "..", &mut applicability).maybe_par(),
+                    keep
+                ),
+                applicability,
+            );
+        }
+    }
+}
+
+/// Returns the snippet of `span` with the `edits` applied, if they are all inside of it.
+fn snippet_with_edits(cx: &LateContext<'_, '_>, span: Span, edits: &[(Span, String)]) -> Option<String> {
+    let snip = snippet_opt(cx, span)?;
+    let mut edits: Vec<_> = edits.iter().collect();
+    edits.sort_by_key(|(edit_span, _)| edit_span.lo());
+    let mut result = String::new();
+    let mut pos = 0;
+    for (edit_span, replacement) in edits {
+        if edit_span.from_expansion() || !span.contains(*edit_span) {
+            return None;
+        }
+        let lo = (edit_span.lo() - span.lo()).to_usize();
+        let hi = (edit_span.hi() - span.lo()).to_usize();
+        result.push_str(snip.get(pos..lo)?);
+        result.push_str(replacement);
+        pos = hi;
+    }
+    result.push_str(snip.get(pos..)?);
+    Some(result)
+}
+
+/// Returns the `seq[index]` expressions in `expr`, or `None` if `seq` is used in any other way.
+fn index_uses<'a, 'tcx>(
+    cx: &'a LateContext<'a, 'tcx>,
+    seq: &'tcx Expr<'tcx>,
+    index: HirId,
+    expr: &'tcx Expr<'tcx>,
+) -> Option<Vec<HirId>> {
+    let mut visitor = IndexUseVisitor {
+        cx,
+        seq,
+        index,
+        index_exprs: Vec::new(),
+        seq_used: false,
+    };
+    visitor.visit_expr(expr);
+    if visitor.seq_used {
+        None
+    } else {
+        Some(visitor.index_exprs)
+    }
+}
+
+struct IndexUseVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'a, 'tcx>,
+    seq: &'tcx Expr<'tcx>,
+    index: HirId,
+    index_exprs: Vec<HirId>,
+    seq_used: bool,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for IndexUseVisitor<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Index(ref seq, ref index) = expr.kind {
+            if var_def_id(self.cx, index) == Some(self.index) && SpanlessEq::new(self.cx).eq_expr(seq, self.seq) {
+                self.index_exprs.push(expr.hir_id);
+                return;
+            }
+        }
+        if SpanlessEq::new(self.cx).eq_expr(expr, self.seq) {
+            self.seq_used = true;
+            return;
+        }
+        walk_expr(self, expr);
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.cx.tcx.hir())
+    }
+}
+
+/// Does `expr` contain an expression equal to `needle` (according to `SpanlessEq`)?
+fn contains_expr<'a, 'tcx>(cx: &'a LateContext<'a, 'tcx>, expr: &'tcx Expr<'_>, needle: &'tcx Expr<'tcx>) -> bool {
+    let mut visitor = ContainsExprVisitor {
+        cx,
+        needle,
+        found: false,
+    };
+    visitor.visit_expr(expr);
+    visitor.found
+}
+
+struct ContainsExprVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'a, 'tcx>,
+    needle: &'tcx Expr<'tcx>,
+    found: bool,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for ContainsExprVisitor<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        if self.found {
+            return;
+        }
+        if SpanlessEq::new(self.cx).eq_expr(expr, self.needle) {
+            self.found = true;
+        } else {
+            walk_expr(self, expr);
+        }
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.cx.tcx.hir())
+    }
+}
@@ -0,0 +1,627 @@
+use crate::utils::msrvs::{self, meets_msrv, RustcVersion};
+use crate::utils::sugg::Sugg;
+use crate::utils::usage::{has_control_flow, mutated_variables};
+use crate::utils::{
+    higher, in_macro, is_copy, is_ctor_or_promotable_const_function, is_type_diagnostic_item, match_def_path,
+    match_qpath, paths, remove_blocks, snippet_with_applicability, span_lint_and_sugg, walk_ptrs_ty, SpanlessEq,
+};
+use if_chain::if_chain;
+use rustc_errors::Applicability;
+use rustc_hir::def::Res;
+use rustc_hir::{Arm, BindingAnnotation, Expr, ExprKind, MatchSource, Pat, PatKind, QPath, UnOp};
+use rustc_lint::{LateContext, LateLintPass, Lint, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions on an `Option` that
+    /// reimplement `Option::map`.
+    ///
+    /// **Why is this bad?** `map` is shorter and says what the code does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x = Some(1);
+    /// let y = match x {
+    ///     Some(v) => Some(v + 1),
+    ///     None => None,
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x = Some(1);
+    /// let y = x.map(|v| v + 1);
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_MAP,
+    complexity,
+    "reimplementation of `Option::map`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions on an `Option` or
+    /// `Result` that reimplement `unwrap_or` or `unwrap_or_else`.
+    ///
+    /// **Why is this bad?** The method is shorter and says what the code does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x = Some(1);
+    /// let y = if let Some(v) = x { v } else { 2 };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x = Some(1);
+    /// let y = x.unwrap_or(2);
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_UNWRAP_OR,
+    complexity,
+    "reimplementation of `unwrap_or` or `unwrap_or_else`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions on an `Option` or
+    /// `Result` that fall back to the default value of the contained type.
+    ///
+    /// **Why is this bad?** `unwrap_or_default` is shorter and says what the code does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x: Option<String> = None;
+    /// let y = match x {
+    ///     Some(v) => v,
+    ///     None => String::new(),
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x: Option<String> = None;
+    /// let y = x.unwrap_or_default();
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_UNWRAP_OR_DEFAULT,
+    complexity,
+    "reimplementation of `unwrap_or_default`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions that turn an `Option`
+    /// into a `Result`.
+    ///
+    /// **Why is this bad?** `ok_or` and `ok_or_else` are shorter and say what the code does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x = Some(1);
+    /// let y: Result<i32, &str> = match x {
+    ///     Some(v) => Ok(v),
+    ///     None => Err("no value"),
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x = Some(1);
+    /// let y: Result<i32, &str> = x.ok_or("no value");
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_OK_OR,
+    complexity,
+    "reimplementation of `Option::ok_or` or `Option::ok_or_else`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions that turn a `Result`
+    /// into an `Option`, discarding the error.
+    ///
+    /// **Why is this bad?** `Result::ok` is shorter and says what the code does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x: Result<i32, ()> = Ok(1);
+    /// let y = match x {
+    ///     Ok(v) => Some(v),
+    ///     Err(_) => None,
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x: Result<i32, ()> = Ok(1);
+    /// let y = x.ok();
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_OK,
+    complexity,
+    "reimplementation of `Result::ok`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions on an `Option` that
+    /// only keep the value if a condition holds.
+    ///
+    /// **Why is this bad?** `Option::filter` is shorter and says what the code does.
+    ///
+    /// **Known problems:** The condition is moved into a closure taking the value by
+    /// reference. For types that are not `Copy` the suggestion might not compile as is.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x = Some(1);
+    /// let y = match x {
+    ///     Some(v) => if v > 0 { Some(v) } else { None },
+    ///     None => None,
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x = Some(1);
+    /// let y = x.filter(|&v| v > 0);
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_FILTER,
+    complexity,
+    "reimplementation of `Option::filter`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions that unwrap one level
+    /// of an `Option<Option<T>>`.
+    ///
+    /// **Why is this bad?** `Option::flatten` is shorter and says what the code does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x = Some(Some(1));
+    /// let y = match x {
+    ///     Some(v) => v,
+    ///     None => None,
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x = Some(Some(1));
+    /// let y = x.flatten();
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_FLATTEN,
+    complexity,
+    "reimplementation of `Option::flatten`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `if` expressions that check a prefix with `starts_with`
+    /// and slice it off by hand.
+    ///
+    /// **Why is this bad?** `str::strip_prefix` is shorter and can't get the index wrong.
+    ///
+    /// **Known problems:** `str::strip_prefix` requires Rust 1.45. Set the `msrv` configuration
+    /// for crates that support older versions.
+    ///
+    /// **Example:**
+    /// ```rust,ignore
+    /// let rest = if s.starts_with("--") { Some(&s["--".len()..]) } else { None };
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let rest = s.strip_prefix("--");
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MANUAL_STRIP,
+    complexity,
+    "reimplementation of `str::strip_prefix`"
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct ManualCombinators {
+    msrv: Option<RustcVersion>,
+}
+
+impl ManualCombinators {
+    #[must_use]
+    pub fn new(msrv: Option<RustcVersion>) -> Self {
+        Self { msrv }
+    }
+}
+
+impl_lint_pass!(ManualCombinators => [
+    MANUAL_MAP,
+    MANUAL_UNWRAP_OR,
+    MANUAL_UNWRAP_OR_DEFAULT,
+    MANUAL_OK_OR,
+    MANUAL_OK,
+    MANUAL_FILTER,
+    MANUAL_FLATTEN,
+    MANUAL_STRIP,
+]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ManualCombinators {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr<'tcx>) {
+        if in_external_macro(cx.sess(), expr.span) || in_macro(expr.span) {
+            return;
+        }
+
+        if let ExprKind::Match(ref scrutinee, arms, source) = expr.kind {
+            let is_match_or_if_let_else = matches!(
+                source,
+                MatchSource::Normal
+                    | MatchSource::IfLetDesugar {
+                        contains_else_clause: true
+                    }
+            );
+            if is_match_or_if_let_else && !in_macro(scrutinee.span) {
+                if let Some(two_arms) = TwoArms::new(cx, scrutinee, arms) {
+                    check_two_arms(cx, expr, &two_arms);
+                }
+            }
+        }
+
+        if let Some((cond, then, Some(els))) = higher::if_block(expr) {
+            if meets_msrv(self.msrv, msrvs::STR_STRIP_PREFIX) {
+                check_strip(cx, expr, cond, then, els);
+            }
+        }
+    }
+}
+
+/// A `match` or `if let` on an `Option` or `Result` with one arm for the value and one for
+/// the other case.
+struct TwoArms<'a, 'tcx> {
+    scrutinee: &'a Expr<'tcx>,
+    /// The type of the scrutinee, `Option` or `Result`
+    ty_name: &'static str,
+    /// The pattern binding the value in `Some(..)` or `Ok(..)`
+    binding: &'a Pat<'tcx>,
+    /// The body of the `Some(..)` or `Ok(..)` arm, without blocks
+    value_body: &'a Expr<'tcx>,
+    /// The body of the `None`, `Err(_)` or `_` arm, without blocks
+    other_body: &'a Expr<'tcx>,
+}
+
+impl<'a, 'tcx> TwoArms<'a, 'tcx> {
+    fn new(cx: &LateContext<'_, 'tcx>, scrutinee: &'a Expr<'tcx>, arms: &'a [Arm<'tcx>]) -> Option<Self> {
+        if arms.len() != 2 || arms.iter().any(|arm| arm.guard.is_some()) {
+            return None;
+        }
+
+        let ty = cx.tables.expr_ty(scrutinee);
+        let (ty_name, value_path, other_path): (_, &[&str], &[&str]) =
+            if is_type_diagnostic_item(cx, ty, sym!(option_type)) {
+                ("Option", &paths::OPTION_SOME, &paths::OPTION_NONE)
+            } else if is_type_diagnostic_item(cx, ty, sym!(result_type)) {
+                ("Result", &paths::RESULT_OK, &paths::RESULT_ERR)
+            } else {
+                return None;
+            };
+
+        let (value_arm, other_arm) = if value_binding(&arms[0].pat, value_path).is_some() {
+            (&arms[0], &arms[1])
+        } else {
+            (&arms[1], &arms[0])
+        };
+        let binding = value_binding(&value_arm.pat, value_path)?;
+        if !is_other_pat(&other_arm.pat, other_path) {
+            return None;
+        }
+        // `Some(x)` binds by reference if the scrutinee is a reference to an `Option`
+        if let Some(ty::BindingMode::BindByReference(_)) = cx.tables.pat_binding_modes().get(binding.hir_id) {
+            return None;
+        }
+
+        Some(Self {
+            scrutinee,
+            ty_name,
+            binding,
+            value_body: remove_blocks(&value_arm.body),
+            other_body: remove_blocks(&other_arm.body),
+        })
+    }
+
+    /// Is `expr` the value bound by the pattern?
+    fn is_binding(&self, expr: &Expr<'_>) -> bool {
+        match (&self.binding.kind, &expr.kind) {
+            (PatKind::Binding(..), ExprKind::Path(QPath::Resolved(None, ref path))) => {
+                path.res == Res::Local(self.binding.hir_id)
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Returns the pattern in `Some(x)` or `Ok(x)` if it binds the value by value or ignores it.
+fn value_binding<'a, 'tcx>(pat: &'a Pat<'tcx>, path: &[&str]) -> Option<&'a Pat<'tcx>> {
+    if_chain! {
+        if let PatKind::TupleStruct(ref qpath, [inner], None) = pat.kind;
+        if match_qpath(qpath, path);
+        if matches!(
+            inner.kind,
+            PatKind::Wild
+                | PatKind::Binding(BindingAnnotation::Unannotated, .., None)
+                | PatKind::Binding(BindingAnnotation::Mutable, .., None)
+        );
+        then {
+            Some(inner)
+        } else {
+            None
+        }
+    }
+}
+
+/// Is `pat` `_`, `None` or `Err(_)`?
+fn is_other_pat(pat: &Pat<'_>, path: &[&str]) -> bool {
+    match pat.kind {
+        PatKind::Wild => true,
+        PatKind::Path(ref qpath) => match_qpath(qpath, path),
+        PatKind::TupleStruct(ref qpath, [inner], None) => {
+            matches!(inner.kind, PatKind::Wild) && match_qpath(qpath, path)
+        },
+        _ => false,
+    }
+}
+
+fn check_two_arms<'tcx>(cx: &LateContext<'_, 'tcx>, expr: &Expr<'_>, arms: &TwoArms<'tcx, 'tcx>) {
+    let mut applicability = Applicability::MachineApplicable;
+    let recv = Sugg::hir_with_applicability(cx, arms.scrutinee, "..", &mut applicability).maybe_par();
+    let is_option = arms.ty_name == "Option";
+    let other_is_none = is_path(arms.other_body, &paths::OPTION_NONE);
+
+    // `Some(x) => x, None => None`
+    if is_option
+        && other_is_none
+        && arms.is_binding(arms.value_body)
+        && is_option_of_option(cx.tables.expr_ty(arms.scrutinee))
+    {
+        let sugg = format!("{}.flatten()", recv);
+        return emit(cx, MANUAL_FLATTEN, expr, arms.ty_name, "flatten", sugg, applicability);
+    }
+
+    // `Some(x) => x, None => default`
+    if arms.is_binding(arms.value_body) {
+        if has_control_flow(cx, arms.other_body) {
+            return;
+        }
+        if is_default_value(cx, arms.other_body) {
+            let sugg = format!("{}.unwrap_or_default()", recv);
+            return emit(
+                cx,
+                MANUAL_UNWRAP_OR_DEFAULT,
+                expr,
+                arms.ty_name,
+                "unwrap_or_default",
+                sugg,
+                applicability,
+            );
+        }
+        let default = snippet_with_applicability(cx, arms.other_body.span, "..", &mut applicability);
+        let (method, sugg) = if is_cheap(cx, arms.other_body) {
+            ("unwrap_or", format!("{}.unwrap_or({})", recv, default))
+        } else {
+            // `Result::unwrap_or_else` passes the error to the closure
+            let params = if is_option { "||" } else { "|_|" };
+            let sugg = format!("{}.unwrap_or_else({} {})", recv, params, default);
+            ("unwrap_or_else", sugg)
+        };
+        return emit(cx, MANUAL_UNWRAP_OR, expr, arms.ty_name, method, sugg, applicability);
+    }
+
+    if let Some(value) = ctor_arg(arms.value_body, &paths::OPTION_SOME) {
+        if !other_is_none {
+            return;
+        }
+        if is_option {
+            // `Some(x) => Some(f(x)), None => None`
+            if arms.is_binding(value) || has_control_flow(cx, value) {
+                return;
+            }
+            // `Some(_)` doesn't move the value out of the scrutinee, but `map` does
+            let as_ref = if matches!(arms.binding.kind, PatKind::Wild)
+                && is_place(arms.scrutinee)
+                && !is_copy(cx, cx.tables.expr_ty(arms.scrutinee))
+            {
+                ".as_ref()"
+            } else {
+                ""
+            };
+            let sugg = format!(
+                "{}{}.map(|{}| {})",
+                recv,
+                as_ref,
+                snippet_with_applicability(cx, arms.binding.span, "..", &mut applicability),
+                snippet_with_applicability(cx, value.span, "..", &mut applicability),
+            );
+            emit(cx, MANUAL_MAP, expr, arms.ty_name, "map", sugg, applicability);
+        } else if arms.is_binding(value) {
+            // `Ok(x) => Some(x), Err(_) => None`
+            let sugg = format!("{}.ok()", recv);
+            emit(cx, MANUAL_OK, expr, arms.ty_name, "ok", sugg, applicability);
+        }
+        return;
+    }
+
+    if !is_option {
+        return;
+    }
+
+    // `Some(x) => Ok(x), None => Err(e)`
+    if_chain! {
+        if let Some(value) = ctor_arg(arms.value_body, &paths::RESULT_OK);
+        if arms.is_binding(value);
+        if let Some(err) = ctor_arg(arms.other_body, &paths::RESULT_ERR);
+        if !has_control_flow(cx, err);
+        then {
+            let err_snippet = snippet_with_applicability(cx, err.span, "..", &mut applicability);
+            let (method, sugg) = if is_cheap(cx, err) {
+                ("ok_or", format!("{}.ok_or({})", recv, err_snippet))
+            } else {
+                ("ok_or_else", format!("{}.ok_or_else(|| {})", recv, err_snippet))
+            };
+            return emit(cx, MANUAL_OK_OR, expr, arms.ty_name, method, sugg, applicability);
+        }
+    }
+
+    // `Some(x) => if cond { Some(x) } else { None }, None => None`
+    if_chain! {
+        if other_is_none;
+        if let Some((cond, then, Some(els))) = higher::if_block(arms.value_body);
+        if let Some(value) = ctor_arg(remove_blocks(then), &paths::OPTION_SOME);
+        if arms.is_binding(value);
+        if is_path(remove_blocks(els), &paths::OPTION_NONE);
+        if !has_control_flow(cx, cond);
+        if let PatKind::Binding(_, binding_id, ident, None) = arms.binding.kind;
+        // the closure gets a shared reference, so the condition can't mutate the value
+        if mutated_variables(cond, cx).map_or(false, |mutated| !mutated.contains(&binding_id));
+        then {
+            // `filter` passes the value by reference, so `Copy` values can be matched by `&x` to
+            // keep the condition as is
+            let param = if is_copy(cx, cx.tables.pat_ty(arms.binding)) {
+                format!("&{}", ident)
+            } else {
+                applicability = Applicability::MaybeIncorrect;
+                ident.to_string()
+            };
+            let sugg = format!(
+                "{}.filter(|{}| {})",
+                recv,
+                param,
+                snippet_with_applicability(cx, cond.span, "..", &mut applicability),
+            );
+            emit(cx, MANUAL_FILTER, expr, arms.ty_name, "filter", sugg, applicability);
+        }
+    }
+}
+
+/// Is `expr` a place that a `match` can bind from without moving it, e.g. a local or a field?
+fn is_place(expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Path(QPath::Resolved(None, ref path)) => matches!(path.res, Res::Local(_) | Res::Def(..)),
+        ExprKind::Field(ref base, _) | ExprKind::Index(ref base, _) => is_place(base),
+        ExprKind::Unary(UnOp::UnDeref, _) => true,
+        _ => false,
+    }
+}
+
+fn emit(
+    cx: &LateContext<'_, '_>,
+    lint: &'static Lint,
+    expr: &Expr<'_>,
+    ty_name: &str,
+    method: &str,
+    sugg: String,
+    applicability: Applicability,
+) {
+    span_lint_and_sugg(
+        cx,
+        lint,
+        expr.span,
+        &format!("manual implementation of `{}::{}`", ty_name, method),
+        "try this",
+        sugg,
+        applicability,
+    );
+}
+
+/// Checks for `if s.starts_with(p) { Some(&s[p.len()..]) } else { None }`.
+fn check_strip(cx: &LateContext<'_, '_>, expr: &Expr<'_>, cond: &Expr<'_>, then: &Expr<'_>, els: &Expr<'_>) {
+    if_chain! {
+        if let ExprKind::MethodCall(ref method, _, [s, prefix]) = cond.kind;
+        if method.ident.name == sym!(starts_with);
+        if let ty::Str = walk_ptrs_ty(cx.tables.expr_ty(s)).kind;
+        if let Some(value) = ctor_arg(remove_blocks(then), &paths::OPTION_SOME);
+        if let ExprKind::AddrOf(_, _, ref index) = value.kind;
+        if let ExprKind::Index(ref sliced, ref range) = index.kind;
+        if let Some(higher::Range { start: Some(start), end: None, .. }) = higher::range(cx, range);
+        if let ExprKind::MethodCall(ref len, _, [len_recv]) = start.kind;
+        if len.ident.name == sym!(len);
+        if is_path(remove_blocks(els), &paths::OPTION_NONE);
+        let mut eq = SpanlessEq::new(cx);
+        if eq.eq_expr(s, sliced) && eq.eq_expr(prefix, len_recv);
+        then {
+            let mut applicability = Applicability::MachineApplicable;
+            span_lint_and_sugg(
+                cx,
+                MANUAL_STRIP,
+                expr.span,
+                "manual implementation of `str::strip_prefix`",
+                "try this",
+                format!(
+                    "{}.strip_prefix({})",
+                    Sugg::hir_with_applicability(cx, s, "..", &mut applicability).maybe_par(),
+                    snippet_with_applicability(cx, prefix.span, "..", &mut applicability),
+                ),
+                applicability,
+            );
+        }
+    }
+}
+
+/// Returns the argument of `Some(..)`, `Ok(..)` or `Err(..)`, depending on `path`.
+fn ctor_arg<'a, 'tcx>(expr: &'a Expr<'tcx>, path: &[&str]) -> Option<&'a Expr<'tcx>> {
+    match expr.kind {
+        ExprKind::Call(ref func, [arg]) if is_path(func, path) => Some(arg),
+        _ => None,
+    }
+}
+
+fn is_path(expr: &Expr<'_>, path: &[&str]) -> bool {
+    match expr.kind {
+        ExprKind::Path(ref qpath) => match_qpath(qpath, path),
+        _ => false,
+    }
+}
+
+fn is_option_of_option(ty: Ty<'_>) -> bool {
+    match ty.kind {
+        ty::Adt(_, substs) => match substs.type_at(0).kind {
+            ty::Adt(inner, _) => ty.ty_adt_def() == Some(inner),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Is `expr` a call to `Default::default()`, `T::default()`, `String::new()` or `Vec::new()`?
+fn is_default_value(cx: &LateContext<'_, '_>, expr: &Expr<'_>) -> bool {
+    if_chain! {
+        if let ExprKind::Call(ref func, []) = expr.kind;
+        if let ExprKind::Path(ref qpath) = func.kind;
+        if let Some(def_id) = cx.tables.qpath_res(qpath, func.hir_id).opt_def_id();
+        then {
+            let trait_id = cx
+                .tcx
+                .trait_of_item(def_id)
+                .or_else(|| cx.tcx.impl_of_method(def_id).and_then(|impl_id| cx.tcx.trait_id_of_impl(impl_id)));
+            trait_id.map_or(false, |trait_id| match_def_path(cx, trait_id, &paths::DEFAULT_TRAIT))
+                || match_def_path(cx, def_id, &paths::STRING_NEW)
+                || match_def_path(cx, def_id, &paths::VEC_NEW)
+        } else {
+            false
+        }
+    }
+}
+
+/// Is `expr` cheap enough to evaluate eagerly, i.e. made of literals, paths and constructors?
+fn is_cheap(cx: &LateContext<'_, '_>, expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Lit(_) | ExprKind::Path(_) => true,
+        ExprKind::AddrOf(_, _, ref inner) | ExprKind::Field(ref inner, _) | ExprKind::Unary(UnOp::UnNeg, ref inner) => {
+            is_cheap(cx, inner)
+        },
+        ExprKind::Tup(exprs) | ExprKind::Array(exprs) => exprs.iter().all(|e| is_cheap(cx, e)),
+        ExprKind::Call(_, args) => {
+            is_ctor_or_promotable_const_function(cx, expr) && args.iter().all(|arg| is_cheap(cx, arg))
+        },
+        _ => false,
+    }
+}
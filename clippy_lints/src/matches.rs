@@ -1,20 +1,21 @@
-use crate::consts::{constant, miri_to_const, Constant};
+use crate::consts::{constant, constant_simple, miri_to_const, Constant};
+use crate::utils::pat_subsumption::SimplePat;
 use crate::utils::paths;
-use crate::utils::sugg::Sugg;
+use crate::utils::sugg::{self, Sugg};
 use crate::utils::usage::is_unused;
 use crate::utils::{
     expr_block, get_arg_name, get_parent_expr, in_macro, indent_of, is_allowed, is_expn_of, is_refutable,
     is_type_diagnostic_item, is_wild, match_qpath, match_type, match_var, multispan_sugg, remove_blocks, snippet,
-    snippet_block, snippet_with_applicability, span_lint_and_help, span_lint_and_note, span_lint_and_sugg,
-    span_lint_and_then, walk_ptrs_ty,
+    snippet_block, snippet_with_applicability, span_lint_and_fix, span_lint_and_help, span_lint_and_note,
+    span_lint_and_sugg, span_lint_and_then, walk_ptrs_ty, SpanlessEq,
 };
 use if_chain::if_chain;
 use rustc_ast::ast::LitKind;
 use rustc_errors::Applicability;
-use rustc_hir::def::CtorKind;
+use rustc_hir::def::{CtorKind, Res};
 use rustc_hir::{
-    Arm, BindingAnnotation, Block, BorrowKind, Expr, ExprKind, Local, MatchSource, Mutability, Node, Pat, PatKind,
-    QPath, RangeEnd,
+    Arm, BindingAnnotation, Block, BorrowKind, Expr, ExprKind, Guard, HirId, Local, MatchSource, Mutability, Node, Pat,
+    PatKind, QPath, RangeEnd,
 };
 use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_middle::lint::in_external_macro;
@@ -41,6 +42,7 @@ declare_clippy_lint! {
     ///     _ => (),
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub SINGLE_MATCH,
     style,
     "a `match` statement with a single nontrivial arm (i.e., where the other arm is `_ => {}`) instead of `if let`"
@@ -80,6 +82,7 @@ declare_clippy_lint! {
     ///     bar(&other_ref);
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub SINGLE_MATCH_ELSE,
     pedantic,
     "a `match` statement with two arms where the second arm's pattern is a placeholder instead of a specific match pattern"
@@ -103,6 +106,7 @@ declare_clippy_lint! {
     ///     _ => frob(&x),
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MATCH_REF_PATS,
     style,
     "a `match` or `if let` with all arms prefixed with `&` instead of deref-ing the match expression"
@@ -137,6 +141,7 @@ declare_clippy_lint! {
     ///     bar();
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MATCH_BOOL,
     pedantic,
     "a `match` on a boolean expression instead of an `if..else` block"
@@ -159,6 +164,7 @@ declare_clippy_lint! {
     ///     _ => (),
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MATCH_OVERLAPPING_ARM,
     style,
     "a `match` with overlapping arms"
@@ -181,6 +187,7 @@ declare_clippy_lint! {
     ///     Err(_) => panic!("err"),
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MATCH_WILD_ERR_ARM,
     style,
     "a `match` with `Err(_)` arm and take drastic actions"
@@ -202,6 +209,7 @@ declare_clippy_lint! {
     ///     Some(ref v) => Some(v),
     /// };
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MATCH_AS_REF,
     complexity,
     "a `match` on an Option value instead of using `as_ref()` or `as_mut`"
@@ -224,6 +232,7 @@ declare_clippy_lint! {
     ///     _ => {},
     /// }
     /// ```
+    #[clippy::version = "1.34.0"]
     pub WILDCARD_ENUM_MATCH_ARM,
     restriction,
     "a wildcard enum match arm using `_`"
@@ -244,6 +253,7 @@ declare_clippy_lint! {
     ///     "bar" | _ => {},
     /// }
     /// ```
+    #[clippy::version = "1.42.0"]
     pub WILDCARD_IN_OR_PATTERNS,
     complexity,
     "a wildcard pattern used with others patterns in same match arm"
@@ -279,6 +289,7 @@ declare_clippy_lint! {
     /// let wrapper = Wrapper::Data(42);
     /// let Wrapper::Data(data) = wrapper;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub INFALLIBLE_DESTRUCTURING_MATCH,
     style,
     "a `match` statement with a single infallible arm instead of a `let`"
@@ -307,6 +318,7 @@ declare_clippy_lint! {
     /// // Good
     /// let (c, d) = (a, b);
     /// ```
+    #[clippy::version = "1.43.0"]
     pub MATCH_SINGLE_BINDING,
     complexity,
     "a match with a single binding instead of using `let` statement"
@@ -337,11 +349,133 @@ declare_clippy_lint! {
     ///     _ => {},
     /// }
     /// ```
+    #[clippy::version = "1.43.0"]
     pub REST_PAT_IN_FULLY_BOUND_STRUCTS,
     restriction,
     "a match on a struct that binds all fields but still uses the wildcard pattern"
 }
 
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` arms that can never be reached because an
+    /// earlier arm has a guard that is always true and a pattern that covers theirs.
+    ///
+    /// **Why is this bad?** The compiler only warns about unreachable patterns if the
+    /// earlier arm has no guard at all, so the dead arm goes unnoticed. It is most likely
+    /// a mistake.
+    ///
+    /// **Known problems:** Only guards that evaluate to `true` without looking at
+    /// constants are considered always true.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x = Some(1);
+    /// match x {
+    ///     Some(_) if true => {},
+    ///     Some(0) => {}, // never reached
+    ///     None => {},
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MATCH_UNREACHABLE_ARM,
+    style,
+    "a `match` arm that is covered by an earlier arm with an always true guard"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` guards that only test what the pattern of
+    /// the arm already ensures, like `Some(_) if x.is_some()` when matching on `x`.
+    ///
+    /// **Why is this bad?** The guard is always true, it only makes the reader wonder
+    /// what it is for.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x = Some(1);
+    /// match x {
+    ///     y @ Some(_) if y.is_some() => {},
+    ///     _ => {},
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x = Some(1);
+    /// match x {
+    ///     y @ Some(_) => {},
+    ///     _ => {},
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub REDUNDANT_GUARD,
+    complexity,
+    "a `match` guard that is always true because of the pattern of the arm"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for consecutive `match` arms with the same body whose
+    /// tuple patterns only differ in one element, where the two patterns of that element
+    /// together cover all values, like `true` and `false` or `Some(_)` and `None`.
+    ///
+    /// **Why is this bad?** The two arms are one arm that ignores that element.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let (a, b) = (true, Some(1));
+    /// match (a, b) {
+    ///     (true, Some(_)) => 1,
+    ///     (true, None) => 1,
+    ///     (false, _) => 2,
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let (a, b) = (true, Some(1));
+    /// match (a, b) {
+    ///     (true, _) => 1,
+    ///     (false, _) => 2,
+    /// };
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MATCH_COLLAPSIBLE_ARMS,
+    complexity,
+    "consecutive `match` arms that can be collapsed by ignoring one tuple element"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for consecutive `match` arms with the same body whose
+    /// patterns only differ in a nested literal, and for or-patterns whose alternatives
+    /// only differ in a nested literal, when the `or_patterns` feature is enabled.
+    ///
+    /// **Why is this bad?** Nesting the or-pattern says that only this part differs.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust,ignore
+    /// match x {
+    ///     Some(1) | Some(2) => {},
+    ///     (0, y) => f(y),
+    ///     (1, y) => f(y),
+    ///     _ => {},
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// match x {
+    ///     Some(1 | 2) => {},
+    ///     (0 | 1, y) => f(y),
+    ///     _ => {},
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub UNNESTED_OR_PATTERNS,
+    pedantic,
+    "patterns that only differ in a nested literal and can use a nested or-pattern"
+}
+
 #[derive(Default)]
 pub struct Matches {
     infallible_destructuring_match_linted: bool,
@@ -359,7 +493,11 @@ impl_lint_pass!(Matches => [
     WILDCARD_IN_OR_PATTERNS,
     MATCH_SINGLE_BINDING,
     INFALLIBLE_DESTRUCTURING_MATCH,
-    REST_PAT_IN_FULLY_BOUND_STRUCTS
+    REST_PAT_IN_FULLY_BOUND_STRUCTS,
+    MATCH_UNREACHABLE_ARM,
+    REDUNDANT_GUARD,
+    MATCH_COLLAPSIBLE_ARMS,
+    UNNESTED_OR_PATTERNS
 ]);
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Matches {
@@ -375,6 +513,10 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Matches {
             check_wild_enum_match(cx, ex, arms);
             check_match_as_ref(cx, ex, arms, expr);
             check_wild_in_or_pats(cx, arms);
+            check_unreachable_arms(cx, arms);
+            check_redundant_guards(cx, ex, arms);
+            check_collapsible_arms(cx, arms);
+            check_unnested_or_patterns(cx, arms);
 
             if self.infallible_destructuring_match_linted {
                 self.infallible_destructuring_match_linted = false;
@@ -713,9 +855,7 @@ fn check_wild_enum_match(cx: &LateContext<'_, '_>, ex: &Expr<'_>, arms: &[Arm<'_
 
         let mut missing_variants = vec![];
         if let ty::Adt(def, _) = ty.kind {
-            for variant in &def.variants {
-                missing_variants.push(variant);
-            }
+            missing_variants.extend(&def.variants);
         }
 
         for arm in arms {
@@ -896,6 +1036,328 @@ fn check_wild_in_or_pats(cx: &LateContext<'_, '_>, arms: &[Arm<'_>]) {
     }
 }
 
+fn check_unreachable_arms(cx: &LateContext<'_, '_>, arms: &[Arm<'_>]) {
+    for (i, arm) in arms.iter().enumerate() {
+        if_chain! {
+            if let Some(Guard::If(guard)) = arm.guard;
+            if !guard.span.from_expansion();
+            if let Some(Constant::Bool(true)) = constant_simple(cx, cx.tables, guard);
+            then {
+                let covering = SimplePat::from_hir(cx, arm.pat);
+                for later in &arms[i + 1..] {
+                    if !in_macro(later.pat.span) && covering.subsumes(cx.tcx, &SimplePat::from_hir(cx, later.pat)) {
+                        span_lint_and_note(
+                            cx,
+                            MATCH_UNREACHABLE_ARM,
+                            later.pat.span,
+                            "this arm is unreachable",
+                            Some(guard.span),
+                            "an earlier arm with this guard, which is always true, matches all of its values",
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_redundant_guards(cx: &LateContext<'_, '_>, ex: &Expr<'_>, arms: &[Arm<'_>]) {
+    for arm in arms {
+        if_chain! {
+            if !in_macro(arm.span);
+            if let Some(Guard::If(guard)) = arm.guard;
+            if let Some((tested, guard_pat)) = guard_pattern(cx, guard);
+            if let Some(arm_pat) = tested_pattern(cx, ex, arm.pat, tested);
+            if guard_pat.subsumes(cx.tcx, &SimplePat::from_hir(cx, arm_pat));
+            then {
+                // for `matches!`, remove the whole call instead of its expansion
+                let span = arm.pat.span.shrink_to_hi().to(guard.span.source_callsite());
+                span_lint_and_sugg(
+                    cx,
+                    REDUNDANT_GUARD,
+                    span,
+                    "this guard is always true because of the pattern of the arm",
+                    "remove the guard",
+                    String::new(),
+                    Applicability::MachineApplicable,
+                );
+            }
+        }
+    }
+}
+
+/// Returns the expression tested by a guard like `x.is_some()` or `matches!(x, Some(_))`, and
+/// the pattern it is tested against.
+fn guard_pattern<'a, 'tcx>(cx: &LateContext<'_, 'tcx>, guard: &'a Expr<'a>) -> Option<(&'a Expr<'a>, SimplePat<'tcx>)> {
+    if is_expn_of(guard.span, "matches").is_some() {
+        if_chain! {
+            if let ExprKind::Match(tested, [arm, wild], MatchSource::Normal) = &guard.kind;
+            if arm.guard.is_none() && wild.guard.is_none() && is_wild(wild.pat);
+            if let Some(Constant::Bool(true)) = constant_simple(cx, cx.tables, &arm.body);
+            if let Some(Constant::Bool(false)) = constant_simple(cx, cx.tables, &wild.body);
+            then {
+                return Some((*tested, SimplePat::from_hir(cx, arm.pat)));
+            }
+        }
+        return None;
+    }
+    if_chain! {
+        if !guard.span.from_expansion();
+        if let ExprKind::MethodCall(path, _, [tested]) = &guard.kind;
+        if let Some(variant_name) = match &*path.ident.as_str() {
+            "is_some" => Some("Some"),
+            "is_none" => Some("None"),
+            "is_ok" => Some("Ok"),
+            "is_err" => Some("Err"),
+            _ => None,
+        };
+        let ty = walk_ptrs_ty(cx.tables.expr_ty(tested));
+        if is_type_diagnostic_item(cx, ty, sym!(option_type)) || is_type_diagnostic_item(cx, ty, sym!(result_type));
+        if let ty::Adt(adt, _) = ty.kind;
+        if let Some(variant) = adt.variants.iter().find(|variant| variant.ident.as_str() == variant_name);
+        then {
+            Some((tested, SimplePat::any_of_variant(adt, variant)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the part of `pat` that the local `tested` is matched against: all of it if `tested`
+/// is the scrutinee, or the subpattern of the binding if it is bound by `tested @ subpattern`.
+fn tested_pattern<'a>(
+    cx: &LateContext<'_, '_>,
+    ex: &Expr<'_>,
+    pat: &'a Pat<'a>,
+    tested: &Expr<'_>,
+) -> Option<&'a Pat<'a>> {
+    let tested_id = local_id(cx, tested)?;
+    if local_id(cx, ex) == Some(tested_id) {
+        return Some(pat);
+    }
+    let mut found = None;
+    pat.walk(|pat| match pat.kind {
+        PatKind::Binding(_, id, _, Some(subpattern)) if id == tested_id => {
+            found = Some(subpattern);
+            false
+        },
+        _ => true,
+    });
+    found
+}
+
+fn local_id(cx: &LateContext<'_, '_>, expr: &Expr<'_>) -> Option<HirId> {
+    if let ExprKind::Path(ref qpath) = expr.kind {
+        if let Res::Local(id) = cx.tables.qpath_res(qpath, expr.hir_id) {
+            return Some(id);
+        }
+    }
+    None
+}
+
+fn check_collapsible_arms(cx: &LateContext<'_, '_>, arms: &[Arm<'_>]) {
+    let mut i = 0;
+    while i + 1 < arms.len() {
+        let (first, second) = (&arms[i], &arms[i + 1]);
+        if_chain! {
+            if let Some(position) = collapsible_position(cx, first, second);
+            if let PatKind::Tuple(pats, None) = first.pat.kind;
+            then {
+                let fix = sugg::Fix::new("ignore the element that differs", Applicability::MachineApplicable)
+                    .replace(pats[position].span, "_")
+                    .remove(first.span.shrink_to_hi().to(second.span));
+                span_lint_and_fix(
+                    cx,
+                    MATCH_COLLAPSIBLE_ARMS,
+                    first.span.to(second.span),
+                    "these match arms can be collapsed into one",
+                    fix,
+                );
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Returns the position of the only element in which the tuple patterns of two arms with the
+/// same body differ, if the two patterns of that element match all of its values together.
+fn collapsible_position(cx: &LateContext<'_, '_>, first: &Arm<'_>, second: &Arm<'_>) -> Option<usize> {
+    if first.guard.is_some() || second.guard.is_some() || in_macro(first.span) || in_macro(second.span) {
+        return None;
+    }
+    if !SpanlessEq::new(cx).eq_expr(&first.body, &second.body) {
+        return None;
+    }
+    if let (PatKind::Tuple(pats, None), PatKind::Tuple(other_pats, None)) = (&first.pat.kind, &second.pat.kind) {
+        if pats.len() != other_pats.len() {
+            return None;
+        }
+        let mut differing = pats
+            .iter()
+            .zip(other_pats.iter())
+            .enumerate()
+            .filter(|(_, (pat, other))| !SpanlessEq::new(cx).eq_pat(pat, other));
+        let (position, (pat, other)) = differing.next()?;
+        if differing.next().is_none()
+            && !pat.contains_bindings()
+            && !other.contains_bindings()
+            && SimplePat::from_hir(cx, pat).complements(&SimplePat::from_hir(cx, other))
+        {
+            return Some(position);
+        }
+    }
+    None
+}
+
+fn check_unnested_or_patterns(cx: &LateContext<'_, '_>, arms: &[Arm<'_>]) {
+    if !cx.tcx.features().or_patterns {
+        return;
+    }
+
+    // consecutive arms with the same body
+    let mut i = 0;
+    while i < arms.len() {
+        let first = &arms[i];
+        let mut literals = Vec::new();
+        let mut last = i;
+        if first.guard.is_none() && !in_macro(first.span) {
+            for arm in &arms[i + 1..] {
+                if arm.guard.is_some()
+                    || in_macro(arm.span)
+                    || !SpanlessEq::new(cx).eq_expr(&first.body, &arm.body)
+                    || collapsible_position(cx, first, arm).is_some()
+                {
+                    break;
+                }
+                match nested_literal_diff(cx, first.pat, arm.pat) {
+                    Some((left, right)) if literals.is_empty() || left.hir_id == literals[0].hir_id => {
+                        if literals.is_empty() {
+                            literals.push(left);
+                        }
+                        literals.push(right);
+                        last += 1;
+                    },
+                    _ => break,
+                }
+            }
+        }
+        if last > i {
+            let fix = sugg::Fix::new("merge them with a nested or-pattern", Applicability::MachineApplicable)
+                .replace(literals[0].span, join_pats(cx, &literals))
+                .remove(first.span.shrink_to_hi().to(arms[last].span));
+            span_lint_and_fix(
+                cx,
+                UNNESTED_OR_PATTERNS,
+                first.span.to(arms[last].span),
+                "these match arms only differ in a nested literal",
+                fix,
+            );
+        }
+        i = last + 1;
+    }
+
+    // or-patterns whose alternatives only differ in a nested literal
+    for arm in arms {
+        if_chain! {
+            if !in_macro(arm.span);
+            if let PatKind::Or(alternatives) = arm.pat.kind;
+            if let Some(literals) = alternatives[1..].iter().try_fold(vec![], |mut literals, alternative| {
+                let (left, right) = nested_literal_diff(cx, alternatives[0], alternative)?;
+                if literals.is_empty() {
+                    literals.push(left);
+                } else if left.hir_id != literals[0].hir_id {
+                    return None;
+                }
+                literals.push(right);
+                Some(literals)
+            });
+            if !literals.is_empty();
+            then {
+                let pat = alternatives[0];
+                let mut applicability = Applicability::MachineApplicable;
+                let mut nested = snippet_with_applicability(cx, pat.span, "..", &mut applicability).into_owned();
+                let start = (literals[0].span.lo() - pat.span.lo()).0 as usize;
+                let end = (literals[0].span.hi() - pat.span.lo()).0 as usize;
+                nested.replace_range(start..end, &join_pats(cx, &literals));
+                span_lint_and_sugg(
+                    cx,
+                    UNNESTED_OR_PATTERNS,
+                    arm.pat.span,
+                    "the alternatives of this or-pattern only differ in a nested literal",
+                    "nest the or-pattern",
+                    nested,
+                    applicability,
+                );
+            }
+        }
+    }
+}
+
+fn join_pats(cx: &LateContext<'_, '_>, pats: &[&Pat<'_>]) -> String {
+    pats.iter()
+        .map(|pat| snippet(cx, pat.span, ".."))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Returns the literals that two patterns differ in, if they are the same except for one
+/// literal or range nested in tuples or tuple structs.
+///
+/// Literals at the top level are left to `match_same_arms`. Literals below references or
+/// bindings are skipped as well, since `&0 | 1` would not mean `&(0 | 1)`.
+fn nested_literal_diff<'a>(
+    cx: &LateContext<'_, '_>,
+    left: &'a Pat<'a>,
+    right: &'a Pat<'a>,
+) -> Option<(&'a Pat<'a>, &'a Pat<'a>)> {
+    match pat_diff(cx, left, right) {
+        PatDiff::Literal(left_lit, right_lit) if left_lit.hir_id != left.hir_id => Some((left_lit, right_lit)),
+        _ => None,
+    }
+}
+
+enum PatDiff<'a> {
+    Same,
+    /// The patterns only differ in this pair of literals or ranges
+    Literal(&'a Pat<'a>, &'a Pat<'a>),
+    Other,
+}
+
+fn pat_diff<'a>(cx: &LateContext<'_, '_>, left: &'a Pat<'a>, right: &'a Pat<'a>) -> PatDiff<'a> {
+    if SpanlessEq::new(cx).eq_pat(left, right) {
+        return PatDiff::Same;
+    }
+    match (&left.kind, &right.kind) {
+        (PatKind::Lit(_) | PatKind::Range(..), PatKind::Lit(_) | PatKind::Range(..)) => PatDiff::Literal(left, right),
+        (PatKind::Tuple(pats, rest), PatKind::Tuple(other_pats, other_rest)) if rest == other_rest => {
+            fields_diff(cx, pats, other_pats)
+        },
+        (PatKind::TupleStruct(qpath, pats, rest), PatKind::TupleStruct(other_qpath, other_pats, other_rest))
+            if rest == other_rest
+                && cx.tables.qpath_res(qpath, left.hir_id) == cx.tables.qpath_res(other_qpath, right.hir_id) =>
+        {
+            fields_diff(cx, pats, other_pats)
+        },
+        _ => PatDiff::Other,
+    }
+}
+
+fn fields_diff<'a>(cx: &LateContext<'_, '_>, pats: &'a [&'a Pat<'a>], others: &'a [&'a Pat<'a>]) -> PatDiff<'a> {
+    if pats.len() != others.len() {
+        return PatDiff::Other;
+    }
+    let mut diff = PatDiff::Same;
+    for (pat, other) in pats.iter().zip(others) {
+        match (pat_diff(cx, pat, other), &diff) {
+            (PatDiff::Same, _) => {},
+            (PatDiff::Literal(left, right), PatDiff::Same) => diff = PatDiff::Literal(left, right),
+            _ => return PatDiff::Other,
+        }
+    }
+    diff
+}
+
 fn check_match_single_binding<'a>(cx: &LateContext<'_, 'a>, ex: &Expr<'a>, arms: &[Arm<'_>], expr: &Expr<'_>) {
     if in_macro(expr.span) || arms.len() != 1 || is_refutable(cx, arms[0].pat) {
         return;
@@ -1090,10 +1552,7 @@ fn is_unit_expr(expr: &Expr<'_>) -> bool {
 
 // Checks if arm has the form `None => None`
 fn is_none_arm(arm: &Arm<'_>) -> bool {
-    match arm.pat.kind {
-        PatKind::Path(ref path) if match_qpath(path, &paths::OPTION_NONE) => true,
-        _ => false,
-    }
+    matches!(arm.pat.kind, PatKind::Path(ref path) if match_qpath(path, &paths::OPTION_NONE))
 }
 
 // Checks if arm has the form `Some(ref v) => Some(v)` (checks for `ref` and `ref mut`)
@@ -1,6 +1,7 @@
+use crate::utils::sugg::Fix;
 use crate::utils::{
-    in_macro, match_def_path, match_qpath, paths, snippet, snippet_with_applicability, span_lint_and_help,
-    span_lint_and_sugg, span_lint_and_then,
+    add_fix, in_macro, match_def_path, match_qpath, paths, snippet, snippet_opt, snippet_with_applicability,
+    span_lint_and_help, span_lint_and_sugg, span_lint_and_then,
 };
 use if_chain::if_chain;
 use rustc_errors::Applicability;
@@ -33,6 +34,7 @@ declare_clippy_lint! {
     /// let mut an_option = Some(0);
     /// let taken = an_option.take();
     /// ```
+    #[clippy::version = "1.31.0"]
     pub MEM_REPLACE_OPTION_WITH_NONE,
     style,
     "replacing an `Option` with `None` instead of `take()`"
@@ -65,6 +67,7 @@ declare_clippy_lint! {
     /// The [take_mut](https://docs.rs/take_mut) crate offers a sound solution,
     /// at the cost of either lazily creating a replacement value or aborting
     /// on panic, to ensure that the uninitialized value cannot be observed.
+    #[clippy::version = "1.39.0"]
     pub MEM_REPLACE_WITH_UNINIT,
     correctness,
     "`mem::replace(&mut _, mem::uninitialized())` or `mem::replace(&mut _, mem::zeroed())`"
@@ -89,6 +92,7 @@ declare_clippy_lint! {
     /// let mut text = String::from("foo");
     /// let taken = std::mem::take(&mut text);
     /// ```
+    #[clippy::version = "1.42.0"]
     pub MEM_REPLACE_WITH_DEFAULT,
     style,
     "replacing a value of type `T` with `T::default()` instead of using `std::mem::take`"
@@ -167,10 +171,16 @@ fn check_replace_with_uninit(cx: &LateContext<'_, '_>, src: &Expr<'_>, expr_span
     }
 }
 
-fn check_replace_with_default(cx: &LateContext<'_, '_>, src: &Expr<'_>, dest: &Expr<'_>, expr_span: Span) {
+fn check_replace_with_default(
+    cx: &LateContext<'_, '_>,
+    func: &Expr<'_>,
+    src: &Expr<'_>,
+    dest: &Expr<'_>,
+    expr: &Expr<'_>,
+) {
     if let ExprKind::Call(ref repl_func, _) = src.kind {
         if_chain! {
-            if !in_external_macro(cx.tcx.sess, expr_span);
+            if !in_external_macro(cx.tcx.sess, expr.span);
             if let ExprKind::Path(ref repl_func_qpath) = repl_func.kind;
             if let Some(repl_def_id) = cx.tables.qpath_res(repl_func_qpath, repl_func.hir_id).opt_def_id();
             if match_def_path(cx, repl_def_id, &paths::DEFAULT_TRAIT_METHOD);
@@ -178,18 +188,11 @@ fn check_replace_with_default(cx: &LateContext<'_, '_>, src: &Expr<'_>, dest: &E
                 span_lint_and_then(
                     cx,
                     MEM_REPLACE_WITH_DEFAULT,
-                    expr_span,
+                    expr.span,
                     "replacing a value of type `T` with `T::default()` is better expressed using `std::mem::take`",
                     |diag| {
-                        if !in_macro(expr_span) {
-                            let suggestion = format!("std::mem::take({})", snippet(cx, dest.span, ""));
-
-                            diag.span_suggestion(
-                                expr_span,
-                                "consider using",
-                                suggestion,
-                                Applicability::MachineApplicable
-                            );
+                        if !in_macro(expr.span) {
+                            add_fix(cx, diag, take_fix(cx, func, dest, expr));
                         }
                     }
                 );
@@ -198,6 +201,23 @@ fn check_replace_with_default(cx: &LateContext<'_, '_>, src: &Expr<'_>, dest: &E
     }
 }
 
+/// Builds the `mem::take` call replacing `expr`, spelled like the `mem::replace` call `func`. A
+/// `replace` imported on its own is replaced with an imported `take`.
+fn take_fix(cx: &LateContext<'_, '_>, func: &Expr<'_>, dest: &Expr<'_>, expr: &Expr<'_>) -> Fix {
+    let dest = snippet(cx, dest.span, "");
+    let fix = Fix::new("consider using", Applicability::MachineApplicable);
+    match snippet_opt(cx, func.span) {
+        Some(func) if func == "replace" => fix
+            .replace(expr.span, format!("take({})", dest))
+            .add_import(cx, expr.hir_id, "std::mem::take"),
+        Some(func) if func.ends_with("::replace") => fix.replace(
+            expr.span,
+            format!("{}take({})", func.trim_end_matches("replace"), dest),
+        ),
+        _ => fix.replace(expr.span, format!("std::mem::take({})", dest)),
+    }
+}
+
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MemReplace {
     fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr<'_>) {
         if_chain! {
@@ -210,7 +230,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MemReplace {
             then {
                 check_replace_option_with_none(cx, src, dest, expr.span);
                 check_replace_with_uninit(cx, src, expr.span);
-                check_replace_with_default(cx, src, dest, expr.span);
+                check_replace_with_default(cx, func, src, dest, expr);
             }
         }
     }
@@ -0,0 +1,404 @@
+//! Declarative lints on method chains like `iter.filter(p).next()`.
+//!
+//! Each lint is an entry in `PATTERNS` that states the methods of the chain, what the receiver has
+//! to be, the shapes of the arguments and how the chain is rewritten. `check` matches all
+//! patterns against an expression and emits the lints with uniform suggestion handling.
+
+use crate::utils::closures::{Closure, ClosureShape};
+use crate::utils::sugg::Fix;
+use crate::utils::{
+    get_arg_name, is_diag_item_or_path, is_type_diag_item_or_path, match_qpath, method_calls, paths,
+    snippet_with_applicability, span_lint, span_lint_and_fix, span_lint_and_help, span_lint_and_note,
+};
+use if_chain::if_chain;
+use rustc_errors::Applicability;
+use rustc_hir as hir;
+use rustc_lint::{LateContext, Lint};
+use rustc_span::source_map::Span;
+use rustc_span::symbol::Symbol;
+use std::borrow::Cow;
+
+use super::{FILTER_NEXT, FLAT_MAP_IDENTITY, ITER_SKIP_NEXT, MAP_FLATTEN, SEARCH_IS_SOME};
+
+/// A lint on a method chain.
+pub(super) struct ChainPattern {
+    pub lint: &'static Lint,
+    /// The methods of the chain, in the order they are called
+    pub methods: &'static [Method],
+    /// What the chain is called on
+    pub receiver: Receiver,
+    pub msg: &'static str,
+    pub fix: ChainFix,
+    /// Emit the lint without the fix if one of the snippets used by the fix spans multiple lines
+    pub single_line: bool,
+    /// How the argument snippets are adjusted before they are used by the fix
+    pub rewrite: Rewrite,
+}
+
+/// A method of a chain.
+pub(super) struct Method {
+    pub name: &'static str,
+    /// The shapes of the arguments, not counting `self`. Missing entries match any argument.
+    pub args: &'static [ArgShape],
+}
+
+/// A method called with any arguments.
+const fn method(name: &'static str) -> Method {
+    Method { name, args: &[] }
+}
+
+/// What a method chain has to be called on.
+pub(super) enum Receiver {
+    /// The first method is a method of the trait with the given diagnostic item, falling back to
+    /// the path if the toolchain doesn't have the diagnostic item
+    Trait(&'static str, &'static [&'static str]),
+    /// The receiver of the first method has the type with the given diagnostic item or path
+    Type(&'static str, &'static [&'static str]),
+}
+
+/// The shape of a method argument.
+///
+/// There is no shape for the number of closure parameters: the `Fn*` bound of the method already
+/// fixes it, so it can't tell two arguments of the same method apart.
+pub(super) enum ArgShape {
+    /// A closure returning its only parameter, i.e. `|x| x`
+    IdentityClosure,
+    /// A path to the given function, e.g. `std::convert::identity`
+    Path(&'static [&'static str]),
+}
+
+/// How a chain lint helps fixing the code.
+///
+/// The templates can refer to the receiver of the chain as `{recv}` and to the arguments as
+/// `{m.n}`, the `n`th argument (not counting `self`) of the `m`th method of the chain.
+pub(super) enum ChainFix {
+    /// A suggestion replacing `span` with `template`
+    Sugg {
+        help: &'static str,
+        template: &'static str,
+        span: FixSpan,
+    },
+    /// A note showing `template`
+    Note(&'static str),
+    /// A help message
+    Help(&'static str),
+}
+
+/// The code replaced by a `ChainFix::Sugg`.
+pub(super) enum FixSpan {
+    /// The whole chain
+    Chain,
+    /// From the name of the given method to the end of the chain
+    FromMethod(usize),
+}
+
+/// Adjustments to argument snippets.
+pub(super) enum Rewrite {
+    None,
+    /// Rewrites the closure passed to `find` so it can be passed to `any`: `any` takes its items by
+    /// value, so `|&x| ..` becomes `|x| ..` and `|x| **x ..` becomes `|x| *x ..`
+    FindClosureToAny,
+}
+
+/// The lints on method chains.
+const PATTERNS: &[ChainPattern] = &[
+    ChainPattern {
+        lint: FILTER_NEXT,
+        methods: &[method("filter"), method("next")],
+        receiver: Receiver::Trait("Iterator", &paths::ITERATOR),
+        msg: "called `filter(p).next()` on an `Iterator`. This is more succinctly expressed by calling \
+              `.find(p)` instead.",
+        fix: ChainFix::Note("replace `filter({0.0}).next()` with `find({0.0})`"),
+        single_line: true,
+        rewrite: Rewrite::None,
+    },
+    ChainPattern {
+        lint: ITER_SKIP_NEXT,
+        methods: &[method("skip"), method("next")],
+        receiver: Receiver::Trait("Iterator", &paths::ITERATOR),
+        msg: "called `skip(x).next()` on an iterator",
+        fix: ChainFix::Help("this is more succinctly expressed by calling `nth(x)`"),
+        single_line: false,
+        rewrite: Rewrite::None,
+    },
+    ChainPattern {
+        lint: MAP_FLATTEN,
+        methods: &[method("map"), method("flatten")],
+        receiver: Receiver::Trait("Iterator", &paths::ITERATOR),
+        msg: "called `map(..).flatten()` on an `Iterator`. This is more succinctly expressed by calling \
+              `.flat_map(..)`",
+        fix: ChainFix::Sugg {
+            help: "try using `flat_map` instead",
+            template: "{recv}.flat_map({0.0})",
+            span: FixSpan::Chain,
+        },
+        single_line: false,
+        rewrite: Rewrite::None,
+    },
+    ChainPattern {
+        lint: MAP_FLATTEN,
+        methods: &[method("map"), method("flatten")],
+        receiver: Receiver::Type("option_type", &paths::OPTION),
+        msg: "called `map(..).flatten()` on an `Option`. This is more succinctly expressed by calling `.and_then(..)`",
+        fix: ChainFix::Sugg {
+            help: "try using `and_then` instead",
+            template: "{recv}.and_then({0.0})",
+            span: FixSpan::Chain,
+        },
+        single_line: false,
+        rewrite: Rewrite::None,
+    },
+    ChainPattern {
+        lint: SEARCH_IS_SOME,
+        methods: &[method("find"), method("is_some")],
+        receiver: Receiver::Trait("Iterator", &paths::ITERATOR),
+        msg: "called `is_some()` after searching an `Iterator` with find. This is more succinctly expressed by \
+              calling `any()`.",
+        fix: ChainFix::Sugg {
+            help: "try this",
+            template: "any({0.0})",
+            span: FixSpan::FromMethod(0),
+        },
+        single_line: true,
+        rewrite: Rewrite::FindClosureToAny,
+    },
+    ChainPattern {
+        lint: SEARCH_IS_SOME,
+        methods: &[method("position"), method("is_some")],
+        receiver: Receiver::Trait("Iterator", &paths::ITERATOR),
+        msg: "called `is_some()` after searching an `Iterator` with position. This is more succinctly expressed by \
+              calling `any()`.",
+        fix: ChainFix::Sugg {
+            help: "try this",
+            template: "any({0.0})",
+            span: FixSpan::FromMethod(0),
+        },
+        single_line: true,
+        rewrite: Rewrite::None,
+    },
+    ChainPattern {
+        lint: SEARCH_IS_SOME,
+        methods: &[method("rposition"), method("is_some")],
+        receiver: Receiver::Trait("Iterator", &paths::ITERATOR),
+        msg: "called `is_some()` after searching an `Iterator` with rposition. This is more succinctly expressed by \
+              calling `any()`.",
+        fix: ChainFix::Sugg {
+            help: "try this",
+            template: "any({0.0})",
+            span: FixSpan::FromMethod(0),
+        },
+        single_line: true,
+        rewrite: Rewrite::None,
+    },
+    ChainPattern {
+        lint: FLAT_MAP_IDENTITY,
+        methods: &[Method {
+            name: "flat_map",
+            args: &[ArgShape::IdentityClosure],
+        }],
+        receiver: Receiver::Trait("Iterator", &paths::ITERATOR),
+        msg: "called `flat_map(|x| x)` on an `Iterator`",
+        fix: ChainFix::Sugg {
+            help: "try",
+            template: "flatten()",
+            span: FixSpan::FromMethod(0),
+        },
+        single_line: false,
+        rewrite: Rewrite::None,
+    },
+    ChainPattern {
+        lint: FLAT_MAP_IDENTITY,
+        methods: &[Method {
+            name: "flat_map",
+            args: &[ArgShape::Path(&paths::STD_CONVERT_IDENTITY)],
+        }],
+        receiver: Receiver::Trait("Iterator", &paths::ITERATOR),
+        msg: "called `flat_map(std::convert::identity)` on an `Iterator`",
+        fix: ChainFix::Sugg {
+            help: "try",
+            template: "flatten()",
+            span: FixSpan::FromMethod(0),
+        },
+        single_line: false,
+        rewrite: Rewrite::None,
+    },
+];
+
+/// The longest chain in `PATTERNS`.
+const MAX_CHAIN_LEN: usize = 2;
+
+/// A method chain matched by a pattern.
+struct Chain<'tcx> {
+    /// The whole chain
+    expr: &'tcx hir::Expr<'tcx>,
+    /// The method calls, in the order they are called
+    calls: Vec<&'tcx hir::Expr<'tcx>>,
+    /// The arguments of the calls, including `self`
+    args: Vec<&'tcx [hir::Expr<'tcx>]>,
+    /// The spans of the method names
+    spans: Vec<Span>,
+}
+
+/// Checks `expr` against all chain patterns.
+pub(super) fn check<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+    let (names, arg_lists, spans) = method_calls(expr, MAX_CHAIN_LEN);
+    for pattern in PATTERNS {
+        let len = pattern.methods.len();
+        if names.len() < len
+            || !pattern
+                .methods
+                .iter()
+                .rev()
+                .zip(&names)
+                .all(|(m, name)| name.as_str() == m.name)
+        {
+            continue;
+        }
+
+        // `method_calls` returns the calls from the last to the first one
+        let mut calls = vec![expr];
+        calls.extend(arg_lists[..len - 1].iter().map(|args| &args[0]));
+        calls.reverse();
+        let chain = Chain {
+            expr,
+            calls,
+            args: arg_lists[..len].iter().rev().copied().collect(),
+            spans: spans[..len].iter().rev().copied().collect(),
+        };
+        if matches_receiver(cx, &pattern.receiver, &chain) && matches_args(cx, pattern.methods, &chain) {
+            emit(cx, pattern, &chain);
+        }
+    }
+}
+
+fn matches_receiver(cx: &LateContext<'_, '_>, receiver: &Receiver, chain: &Chain<'_>) -> bool {
+    match *receiver {
+        Receiver::Trait(diag_item, path) => cx
+            .tables
+            .type_dependent_def_id(chain.calls[0].hir_id)
+            .and_then(|did| cx.tcx.trait_of_item(did))
+            .map_or(false, |trait_id| {
+                is_diag_item_or_path(cx, trait_id, Symbol::intern(diag_item), path)
+            }),
+        Receiver::Type(diag_item, path) => {
+            let ty = cx.tables.expr_ty(&chain.args[0][0]);
+            is_type_diag_item_or_path(cx, ty, Symbol::intern(diag_item), path)
+        },
+    }
+}
+
+fn matches_args<'tcx>(cx: &LateContext<'_, 'tcx>, methods: &[Method], chain: &Chain<'tcx>) -> bool {
+    methods.iter().zip(&chain.args).all(|(method, args)| {
+        method
+            .args
+            .iter()
+            .enumerate()
+            .all(|(i, shape)| args.get(i + 1).map_or(false, |arg| matches_shape(cx, shape, arg)))
+    })
+}
+
+fn matches_shape<'tcx>(cx: &LateContext<'_, 'tcx>, shape: &ArgShape, arg: &'tcx hir::Expr<'tcx>) -> bool {
+    match *shape {
+        ArgShape::IdentityClosure => {
+            Closure::new(cx, arg).map_or(false, |closure| matches!(closure.shape(cx), ClosureShape::Identity))
+        },
+        ArgShape::Path(path) => match arg.kind {
+            hir::ExprKind::Path(ref qpath) => match_qpath(qpath, path),
+            _ => false,
+        },
+    }
+}
+
+fn emit(cx: &LateContext<'_, '_>, pattern: &ChainPattern, chain: &Chain<'_>) {
+    let mut applicability = Applicability::MachineApplicable;
+    let template = match pattern.fix {
+        ChainFix::Sugg { template, .. } | ChainFix::Note(template) => template,
+        ChainFix::Help(help) => {
+            span_lint_and_help(cx, pattern.lint, chain.expr.span, pattern.msg, None, help);
+            return;
+        },
+    };
+    let rendered = match render(cx, pattern, chain, template, &mut applicability) {
+        Some(rendered) => rendered,
+        None => {
+            span_lint(cx, pattern.lint, chain.expr.span, pattern.msg);
+            return;
+        },
+    };
+
+    match pattern.fix {
+        ChainFix::Sugg { help, span, .. } => {
+            let span = match span {
+                FixSpan::Chain => chain.expr.span,
+                FixSpan::FromMethod(i) => chain.spans[i].with_hi(chain.expr.span.hi()),
+            };
+            let fix = Fix::new(help, applicability).replace(span, rendered);
+            span_lint_and_fix(cx, pattern.lint, span, pattern.msg, fix);
+        },
+        ChainFix::Note(_) => span_lint_and_note(cx, pattern.lint, chain.expr.span, pattern.msg, None, &rendered),
+        ChainFix::Help(_) => unreachable!(),
+    }
+}
+
+/// Fills in the placeholders of `template`. Returns `None` if the pattern only allows single
+/// line snippets and one of them spans multiple lines.
+fn render(
+    cx: &LateContext<'_, '_>,
+    pattern: &ChainPattern,
+    chain: &Chain<'_>,
+    template: &str,
+    applicability: &mut Applicability,
+) -> Option<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}').expect("unclosed placeholder in chain template");
+        rendered.push_str(&rest[..start]);
+
+        let placeholder = &rest[start + 1..end];
+        let snippet = if placeholder == "recv" {
+            snippet_with_applicability(cx, chain.args[0][0].span, "..", applicability)
+        } else {
+            let mut indices = placeholder
+                .split('.')
+                .map(|i| i.parse::<usize>().expect("invalid placeholder"));
+            let (m, n) = (indices.next().unwrap(), indices.next().unwrap());
+            let arg = &chain.args[m][n + 1];
+            let snippet = snippet_with_applicability(cx, arg.span, "..", applicability);
+            match pattern.rewrite {
+                Rewrite::FindClosureToAny => {
+                    Cow::Owned(find_closure_to_any(cx, arg, &snippet).unwrap_or_else(|| snippet.into_owned()))
+                },
+                Rewrite::None => snippet,
+            }
+        };
+        if pattern.single_line && snippet.lines().count() > 1 {
+            return None;
+        }
+        rendered.push_str(&snippet);
+        rest = &rest[end + 1..];
+    }
+    rendered.push_str(rest);
+    Some(rendered)
+}
+
+/// Suggests `any(|x| ..)` instead of `any(|&x| ..)` for `find(|&x| ..).is_some()`, and
+/// `any(|..| *..)` instead of `any(|..| **..)` for `find(|..| **..).is_some()`.
+fn find_closure_to_any(cx: &LateContext<'_, '_>, arg: &hir::Expr<'_>, snippet: &str) -> Option<String> {
+    if_chain! {
+        if let hir::ExprKind::Closure(_, _, body_id, ..) = arg.kind;
+        let closure_body = cx.tcx.hir().body(body_id);
+        if let Some(closure_arg) = closure_body.params.get(0);
+        then {
+            if let hir::PatKind::Ref(..) = closure_arg.pat.kind {
+                Some(snippet.replacen('&', "", 1))
+            } else if let Some(name) = get_arg_name(&closure_arg.pat) {
+                Some(snippet.replace(&format!("*{}", name), &name.as_str()))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
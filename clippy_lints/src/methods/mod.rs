@@ -1,3 +1,4 @@
+mod chains;
 mod inefficient_to_string;
 mod manual_saturating_arithmetic;
 mod option_map_unwrap_or;
@@ -22,14 +23,15 @@ use rustc_span::source_map::Span;
 use rustc_span::symbol::{sym, SymbolStr};
 
 use crate::consts::{constant, Constant};
+use crate::utils::closures::{Closure, ClosureShape};
 use crate::utils::usage::mutated_variables;
 use crate::utils::{
-    get_arg_name, get_parent_expr, get_trait_def_id, has_iter_method, implements_trait, in_macro, is_copy,
+    get_parent_expr, get_trait_def_id, has_iter_method, implements_trait, in_macro, is_copy,
     is_ctor_or_promotable_const_function, is_expn_of, is_type_diagnostic_item, iter_input_pats, last_path_segment,
-    match_def_path, match_qpath, match_trait_method, match_type, match_var, method_calls, method_chain_args, paths,
-    remove_blocks, return_ty, same_tys, single_segment_path, snippet, snippet_with_applicability,
-    snippet_with_macro_callsite, span_lint, span_lint_and_help, span_lint_and_note, span_lint_and_sugg,
-    span_lint_and_then, sugg, walk_ptrs_ty, walk_ptrs_ty_depth, SpanlessEq,
+    match_def_path, match_qpath, match_trait_method, match_type, method_calls, method_chain_args, paths, remove_blocks,
+    return_ty, same_tys, single_segment_path, snippet, snippet_with_applicability, snippet_with_macro_callsite,
+    span_lint, span_lint_and_help, span_lint_and_note, span_lint_and_sugg, span_lint_and_then, sugg, walk_ptrs_ty,
+    walk_ptrs_ty_depth, SpanlessEq,
 };
 
 declare_clippy_lint! {
@@ -57,6 +59,7 @@ declare_clippy_lint! {
     /// let opt = Some(1);
     /// opt.expect("more helpful message");
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub OPTION_UNWRAP_USED,
     restriction,
     "using `Option.unwrap()`, which should at least get a better message using `expect()`"
@@ -89,6 +92,7 @@ declare_clippy_lint! {
     /// let res: Result<usize, ()> = Ok(1);
     /// res.expect("more helpful message");
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub RESULT_UNWRAP_USED,
     restriction,
     "using `Result.unwrap()`, which might be better handled"
@@ -118,6 +122,7 @@ declare_clippy_lint! {
     /// let opt = Some(1);
     /// opt?;
     /// ```
+    #[clippy::version = "1.40.0"]
     pub OPTION_EXPECT_USED,
     restriction,
     "using `Option.expect()`, which might be better handled"
@@ -147,6 +152,7 @@ declare_clippy_lint! {
     /// res?;
     /// # Ok::<(), ()>(())
     /// ```
+    #[clippy::version = "1.40.0"]
     pub RESULT_EXPECT_USED,
     restriction,
     "using `Result.expect()`, which might be better handled"
@@ -176,6 +182,7 @@ declare_clippy_lint! {
     ///     }
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub SHOULD_IMPLEMENT_TRAIT,
     style,
     "defining a method that should be implementing a std trait"
@@ -209,6 +216,7 @@ declare_clippy_lint! {
     ///     }
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub WRONG_SELF_CONVENTION,
     style,
     "defining a method named with an established prefix (like \"into_\") that takes `self` with the wrong convention"
@@ -233,6 +241,7 @@ declare_clippy_lint! {
     ///     }
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub WRONG_PUB_SELF_CONVENTION,
     restriction,
     "defining a public method named with an established prefix (like \"into_\") that takes `self` with the wrong convention"
@@ -251,6 +260,7 @@ declare_clippy_lint! {
     /// # let x = Ok::<_, ()>(());
     /// x.ok().expect("why did I do this again?")
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub OK_EXPECT,
     style,
     "using `ok().expect()`, which gives worse error messages than calling `expect` directly on the Result"
@@ -269,6 +279,7 @@ declare_clippy_lint! {
     /// # let x = Some(1);
     /// x.map(|a| a + 1).unwrap_or(0);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub OPTION_MAP_UNWRAP_OR,
     pedantic,
     "using `Option.map(f).unwrap_or(a)`, which is more succinctly expressed as `map_or(a, f)`"
@@ -288,6 +299,7 @@ declare_clippy_lint! {
     /// # fn some_function() -> usize { 1 }
     /// x.map(|a| a + 1).unwrap_or_else(some_function);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub OPTION_MAP_UNWRAP_OR_ELSE,
     pedantic,
     "using `Option.map(f).unwrap_or_else(g)`, which is more succinctly expressed as `map_or_else(g, f)`"
@@ -307,6 +319,7 @@ declare_clippy_lint! {
     /// # fn some_function(foo: ()) -> usize { 1 }
     /// x.map(|a| a + 1).unwrap_or_else(some_function);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub RESULT_MAP_UNWRAP_OR_ELSE,
     pedantic,
     "using `Result.map(f).unwrap_or_else(g)`, which is more succinctly expressed as `.map_or_else(g, f)`"
@@ -326,6 +339,7 @@ declare_clippy_lint! {
     /// opt.map_or(None, |a| Some(a + 1))
     /// # ;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub OPTION_MAP_OR_NONE,
     style,
     "using `Option.map_or(None, f)`, which is more succinctly expressed as `and_then(f)`"
@@ -352,6 +366,7 @@ declare_clippy_lint! {
     /// # let r: Result<u32, &str> = Ok(1);
     /// assert_eq!(Some(1), r.ok());
     /// ```
+    #[clippy::version = "1.44.0"]
     pub RESULT_MAP_OR_INTO_OPTION,
     style,
     "using `Result.map_or(None, Some)`, which is more succinctly expressed as `ok()`"
@@ -378,6 +393,7 @@ declare_clippy_lint! {
     /// let x = Some("foo");
     /// let _ = x.map(|s| s.len());
     /// ```
+    #[clippy::version = "1.39.0"]
     pub OPTION_AND_THEN_SOME,
     complexity,
     "using `Option.and_then(|x| Some(y))`, which is more succinctly expressed as `map(|x| y)`"
@@ -401,6 +417,7 @@ declare_clippy_lint! {
     /// # let vec = vec![1];
     /// vec.iter().find(|x| **x == 0);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub FILTER_NEXT,
     complexity,
     "using `filter(p).next()`, which is more succinctly expressed as `.find(p)`"
@@ -424,6 +441,7 @@ declare_clippy_lint! {
     /// # let vec = vec![1];
     /// vec.iter().find(|x| **x != 0);
     /// ```
+    #[clippy::version = "1.42.0"]
     pub SKIP_WHILE_NEXT,
     complexity,
     "using `skip_while(p).next()`, which is more succinctly expressed as `.find(!p)`"
@@ -442,6 +460,7 @@ declare_clippy_lint! {
     /// let vec = vec![vec![1]];
     /// vec.iter().map(|x| x.iter()).flatten();
     /// ```
+    #[clippy::version = "1.31.0"]
     pub MAP_FLATTEN,
     pedantic,
     "using combinations of `flatten` and `map` which can usually be written as a single method call"
@@ -462,6 +481,7 @@ declare_clippy_lint! {
     /// let vec = vec![1];
     /// vec.iter().filter(|x| **x == 0).map(|x| *x * 2);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub FILTER_MAP,
     pedantic,
     "using combinations of `filter`, `map`, `filter_map` and `flat_map` which can usually be written as a single method call"
@@ -484,6 +504,7 @@ declare_clippy_lint! {
     /// ```rust
     ///  (0..3).find_map(|x| if x == 2 { Some(x) } else { None });
     /// ```
+    #[clippy::version = "1.36.0"]
     pub FILTER_MAP_NEXT,
     pedantic,
     "using combination of `filter_map` and `next` which can usually be written as a single method call"
@@ -506,6 +527,7 @@ declare_clippy_lint! {
     /// # let iter = vec![vec![0]].into_iter();
     /// iter.flatten();
     /// ```
+    #[clippy::version = "1.39.0"]
     pub FLAT_MAP_IDENTITY,
     complexity,
     "call to `flat_map` where `flatten` is sufficient"
@@ -528,6 +550,7 @@ declare_clippy_lint! {
     /// ```rust
     ///  (0..3).find_map(|x| if x == 2 { Some(x * 2) } else { None });
     /// ```
+    #[clippy::version = "1.36.0"]
     pub FIND_MAP,
     pedantic,
     "using a combination of `find` and `map` can usually be written as a single method call"
@@ -552,6 +575,7 @@ declare_clippy_lint! {
     /// # let vec = vec![1];
     /// vec.iter().any(|x| *x == 0);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub SEARCH_IS_SOME,
     complexity,
     "using an iterator search followed by `is_some()`, which is more succinctly expressed as a call to `any()`"
@@ -576,6 +600,7 @@ declare_clippy_lint! {
     /// let name = "foo";
     /// if name.starts_with('_') {};
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CHARS_NEXT_CMP,
     style,
     "using `.chars().next()` to check if a string starts with a char"
@@ -607,6 +632,7 @@ declare_clippy_lint! {
     /// # let foo = Some(String::new());
     /// foo.unwrap_or_default();
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub OR_FUN_CALL,
     perf,
     "using any `*or` method with a function call, which suggests `*or_else`"
@@ -642,6 +668,7 @@ declare_clippy_lint! {
     /// # let err_msg = "I'm a teapot";
     /// foo.unwrap_or_else(|| panic!("Err {}: {}", err_code, err_msg));
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub EXPECT_FUN_CALL,
     perf,
     "using any `expect` method with a function call"
@@ -659,6 +686,7 @@ declare_clippy_lint! {
     /// ```rust
     /// 42u64.clone();
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CLONE_ON_COPY,
     complexity,
     "using `clone` on a `Copy` type"
@@ -679,6 +707,7 @@ declare_clippy_lint! {
     /// let x = Rc::new(1);
     /// x.clone();
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CLONE_ON_REF_PTR,
     restriction,
     "using 'clone' on a ref-counted pointer"
@@ -701,6 +730,7 @@ declare_clippy_lint! {
     ///     println!("{:p} {:p}", *y, z); // prints out the same pointer
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CLONE_DOUBLE_REF,
     correctness,
     "using `clone` on `&&T`"
@@ -724,6 +754,7 @@ declare_clippy_lint! {
     /// // OK, the specialized impl is used
     /// ["foo", "bar"].iter().map(|&s| s.to_string());
     /// ```
+    #[clippy::version = "1.40.0"]
     pub INEFFICIENT_TO_STRING,
     pedantic,
     "using `to_string` on `&&T` where `T: ToString`"
@@ -769,6 +800,7 @@ declare_clippy_lint! {
     ///     }
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub NEW_RET_NO_SELF,
     style,
     "not returning type containing `Self` in a `new` method"
@@ -785,6 +817,7 @@ declare_clippy_lint! {
     ///
     /// **Example:**
     /// `_.split("x")` could be `_.split('x')`
+    #[clippy::version = "pre 1.29.0"]
     pub SINGLE_CHAR_PATTERN,
     perf,
     "using a single-character str where a char could be used, e.g., `_.split(\"x\")`"
@@ -819,6 +852,7 @@ declare_clippy_lint! {
     ///     call_some_ffi_func(c_str.as_ptr());
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub TEMPORARY_CSTRING_AS_PTR,
     correctness,
     "getting the inner pointer of a temporary `CString`"
@@ -838,6 +872,7 @@ declare_clippy_lint! {
     ///     //..
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub ITERATOR_STEP_BY_ZERO,
     correctness,
     "using `Iterator::step_by(0)`, which will panic at runtime"
@@ -866,6 +901,7 @@ declare_clippy_lint! {
     /// # s.insert(1);
     /// let x = s.iter().next();
     /// ```
+    #[clippy::version = "1.42.0"]
     pub ITER_NTH_ZERO,
     style,
     "replace `iter.nth(0)` with `iter.next()`"
@@ -892,6 +928,7 @@ declare_clippy_lint! {
     /// let bad_vec = some_vec.get(3);
     /// let bad_slice = &some_vec[..].get(3);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub ITER_NTH,
     perf,
     "using `.iter().nth()` on a standard library type with O(1) element access"
@@ -916,6 +953,7 @@ declare_clippy_lint! {
     /// let bad_vec = some_vec.iter().nth(3);
     /// let bad_slice = &some_vec[..].iter().nth(3);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub ITER_SKIP_NEXT,
     style,
     "using `.skip(x).next()` on an iterator"
@@ -949,6 +987,7 @@ declare_clippy_lint! {
     /// let last = some_vec[3];
     /// some_vec[0] = 1;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub GET_UNWRAP,
     restriction,
     "using `.get().unwrap()` or `.get_mut().unwrap()` when using `[]` would work instead"
@@ -978,6 +1017,7 @@ declare_clippy_lint! {
     /// s.push_str(abc);
     /// s.push_str(&def);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub STRING_EXTEND_CHARS,
     style,
     "using `x.extend(s.chars())` where s is a `&str` or `String`"
@@ -1001,6 +1041,7 @@ declare_clippy_lint! {
     /// let s = [1, 2, 3, 4, 5];
     /// let s2: Vec<isize> = s.to_vec();
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub ITER_CLONED_COLLECT,
     style,
     "using `.cloned().collect()` on slice to create a `Vec`"
@@ -1021,6 +1062,7 @@ declare_clippy_lint! {
     /// name.chars().last() == Some('_') || name.chars().next_back() == Some('-')
     /// # ;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CHARS_LAST_CMP,
     style,
     "using `.chars().last()` or `.chars().next_back()` to check if a string ends with a char"
@@ -1046,6 +1088,7 @@ declare_clippy_lint! {
     /// let x: &[i32] = &[1, 2, 3, 4, 5];
     /// do_stuff(x);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub USELESS_ASREF,
     complexity,
     "using `as_ref` where the types before and after the call are the same"
@@ -1069,6 +1112,7 @@ declare_clippy_lint! {
     /// ```rust
     /// let _ = (0..3).any(|x| x > 2);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub UNNECESSARY_FOLD,
     style,
     "using `fold` when a more succinct alternative exists"
@@ -1100,6 +1144,7 @@ declare_clippy_lint! {
     /// ```rust
     /// let _ = (0..4).map(|x| x + 1);
     /// ```
+    #[clippy::version = "1.31.0"]
     pub UNNECESSARY_FILTER_MAP,
     complexity,
     "using `filter_map` when a more succinct alternative exists"
@@ -1120,6 +1165,7 @@ declare_clippy_lint! {
     /// ```rust
     /// let _ = (&vec![3, 4, 5]).into_iter();
     /// ```
+    #[clippy::version = "1.32.0"]
     pub INTO_ITER_ON_REF,
     style,
     "using `.into_iter()` on a reference"
@@ -1139,6 +1185,7 @@ declare_clippy_lint! {
     /// ```rust
     /// let _ = (0..3).map(|x| x + 2).count();
     /// ```
+    #[clippy::version = "1.39.0"]
     pub SUSPICIOUS_MAP,
     complexity,
     "suspicious usage of map"
@@ -1171,6 +1218,7 @@ declare_clippy_lint! {
     ///     MaybeUninit::uninit().assume_init()
     /// };
     /// ```
+    #[clippy::version = "1.39.0"]
     pub UNINIT_ASSUMED_INIT,
     correctness,
     "`MaybeUninit::uninit().assume_init()`"
@@ -1198,6 +1246,7 @@ declare_clippy_lint! {
     /// let add = x.saturating_add(y);
     /// let sub = x.saturating_sub(y);
     /// ```
+    #[clippy::version = "1.39.0"]
     pub MANUAL_SATURATING_ARITHMETIC,
     style,
     "`.chcked_add/sub(x).unwrap_or(MAX/MIN)`"
@@ -1215,6 +1264,7 @@ declare_clippy_lint! {
     /// ```rust
     /// unsafe { (&() as *const ()).offset(1) };
     /// ```
+    #[clippy::version = "1.41.0"]
     pub ZST_OFFSET,
     correctness,
     "Check for offset calculations on raw pointers to zero-sized types"
@@ -1255,6 +1305,7 @@ declare_clippy_lint! {
     /// # Ok::<_, std::io::Error>(())
     /// # };
     /// ```
+    #[clippy::version = "1.42.0"]
     pub FILETYPE_IS_FILE,
     restriction,
     "`FileType::is_file` is not recommended to test for readable file type"
@@ -1280,6 +1331,7 @@ declare_clippy_lint! {
     /// opt.as_deref()
     /// # ;
     /// ```
+    #[clippy::version = "1.42.0"]
     pub OPTION_AS_REF_DEREF,
     complexity,
     "using `as_ref().map(Deref::deref)`, which is more succinctly expressed as `as_deref()`"
@@ -1345,6 +1397,8 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Methods {
             return;
         }
 
+        chains::check(cx, expr);
+
         let (method_names, arg_lists, method_spans) = method_calls(expr, 2);
         let method_names: Vec<SymbolStr> = method_names.iter().map(|s| s.as_str()).collect();
         let method_names: Vec<&str> = method_names.iter().map(|s| &**s).collect();
@@ -1359,23 +1413,13 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Methods {
             ["unwrap_or_else", "map"] => lint_map_unwrap_or_else(cx, expr, arg_lists[1], arg_lists[0]),
             ["map_or", ..] => lint_map_or_none(cx, expr, arg_lists[0]),
             ["and_then", ..] => lint_option_and_then_some(cx, expr, arg_lists[0]),
-            ["next", "filter"] => lint_filter_next(cx, expr, arg_lists[1]),
             ["next", "skip_while"] => lint_skip_while_next(cx, expr, arg_lists[1]),
-            ["map", "filter"] => lint_filter_map(cx, expr, arg_lists[1], arg_lists[0]),
+            ["map", "filter"] => lint_filter_map(cx, expr, arg_lists[1], arg_lists[0], method_spans[1]),
             ["map", "filter_map"] => lint_filter_map_map(cx, expr, arg_lists[1], arg_lists[0]),
             ["next", "filter_map"] => lint_filter_map_next(cx, expr, arg_lists[1]),
             ["map", "find"] => lint_find_map(cx, expr, arg_lists[1], arg_lists[0]),
             ["flat_map", "filter"] => lint_filter_flat_map(cx, expr, arg_lists[1], arg_lists[0]),
             ["flat_map", "filter_map"] => lint_filter_map_flat_map(cx, expr, arg_lists[1], arg_lists[0]),
-            ["flat_map", ..] => lint_flat_map_identity(cx, expr, arg_lists[0], method_spans[0]),
-            ["flatten", "map"] => lint_map_flatten(cx, expr, arg_lists[1]),
-            ["is_some", "find"] => lint_search_is_some(cx, expr, "find", arg_lists[1], arg_lists[0], method_spans[1]),
-            ["is_some", "position"] => {
-                lint_search_is_some(cx, expr, "position", arg_lists[1], arg_lists[0], method_spans[1])
-            },
-            ["is_some", "rposition"] => {
-                lint_search_is_some(cx, expr, "rposition", arg_lists[1], arg_lists[0], method_spans[1])
-            },
             ["extend", ..] => lint_extend(cx, expr, arg_lists[0]),
             ["as_ptr", "unwrap"] | ["as_ptr", "expect"] => {
                 lint_cstring_as_ptr(cx, expr, &arg_lists[1][0], &arg_lists[0][0])
@@ -1384,8 +1428,12 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Methods {
             ["nth", "iter_mut"] => lint_iter_nth(cx, expr, &arg_lists, true),
             ["nth", ..] => lint_iter_nth_zero(cx, expr, arg_lists[0]),
             ["step_by", ..] => lint_step_by(cx, expr, arg_lists[0]),
-            ["next", "skip"] => lint_iter_skip_next(cx, expr),
-            ["collect", "cloned"] => lint_iter_cloned_collect(cx, expr, arg_lists[1]),
+            ["collect", "cloned"] => lint_iter_cloned_collect(cx, expr, arg_lists[1], "cloned()"),
+            ["collect", "map"] => {
+                if is_clone_closure(cx, &arg_lists[1][1]) {
+                    lint_iter_cloned_collect(cx, expr, arg_lists[1], "map(|x| x.clone())")
+                }
+            },
             ["as_ref"] => lint_asref(cx, expr, "as_ref", arg_lists[0]),
             ["as_mut"] => lint_asref(cx, expr, "as_mut", arg_lists[0]),
             ["fold", ..] => lint_unnecessary_fold(cx, expr, arg_lists[0], method_spans[0]),
@@ -1444,7 +1492,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Methods {
                     eq: op.node == hir::BinOpKind::Eq,
                 };
                 lint_binary_expr_with_method_call(cx, &mut info);
-            }
+            },
             _ => (),
         }
     }
@@ -1810,10 +1858,10 @@ fn lint_expect_fun_call(
                     ty::Ref(ty::ReStatic, ..)
                 )
             }),
-            hir::ExprKind::Path(ref p) => match cx.tables.qpath_res(p, arg.hir_id) {
-                hir::def::Res::Def(hir::def::DefKind::Const | hir::def::DefKind::Static, _) => true,
-                _ => false,
-            },
+            hir::ExprKind::Path(ref p) => matches!(
+                cx.tables.qpath_res(p, arg.hir_id),
+                hir::def::Res::Def(hir::def::DefKind::Const | hir::def::DefKind::Static, _)
+            ),
             _ => false,
         }
     }
@@ -1994,13 +2042,7 @@ fn lint_clone_on_copy(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, arg: &hir:
                     .tables
                     .expr_adjustments(arg)
                     .iter()
-                    .filter(|adj| {
-                        if let ty::adjustment::Adjust::Deref(_) = adj.kind {
-                            true
-                        } else {
-                            false
-                        }
-                    })
+                    .filter(|adj| matches!(adj.kind, ty::adjustment::Adjust::Deref(_)))
                     .count();
                 let derefs: String = iter::repeat('*').take(deref_count).collect();
                 snip = Some(("try dereferencing it", format!("{}{}", derefs, snippet)));
@@ -2109,6 +2151,7 @@ fn lint_iter_cloned_collect<'a, 'tcx>(
     cx: &LateContext<'a, 'tcx>,
     expr: &hir::Expr<'_>,
     iter_args: &'tcx [hir::Expr<'_>],
+    cloning_method: &str,
 ) {
     if_chain! {
         if is_type_diagnostic_item(cx, cx.tables.expr_ty(expr), sym!(vec_type));
@@ -2120,8 +2163,11 @@ fn lint_iter_cloned_collect<'a, 'tcx>(
                 cx,
                 ITER_CLONED_COLLECT,
                 to_replace,
-                "called `iter().cloned().collect()` on a slice to create a `Vec`. Calling `to_vec()` is both faster and \
-                 more readable",
+                &format!(
+                    "called `iter().{}.collect()` on a slice to create a `Vec`. Calling `to_vec()` is both faster and \
+                     more readable",
+                    cloning_method
+                ),
                 "try",
                 ".to_vec()".to_string(),
                 Applicability::MachineApplicable,
@@ -2130,11 +2176,36 @@ fn lint_iter_cloned_collect<'a, 'tcx>(
     }
 }
 
-fn lint_unnecessary_fold(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, fold_args: &[hir::Expr<'_>], fold_span: Span) {
-    fn check_fold_with_op(
-        cx: &LateContext<'_, '_>,
+/// Is `expr` a closure like `|x| x.clone()` that clones the referenced items of an iterator?
+fn is_clone_closure<'tcx>(cx: &LateContext<'_, 'tcx>, expr: &'tcx hir::Expr<'tcx>) -> bool {
+    if_chain! {
+        if let Some(closure) = Closure::new(cx, expr);
+        if let ClosureShape::MethodCall(segment, []) = closure.shape(cx);
+        if segment.ident.name == sym!(clone);
+        let value = closure.value();
+        if let Some(method_id) = cx.tables.type_dependent_def_id(value.hir_id);
+        if match_def_path(cx, method_id, &paths::CLONE_TRAIT_METHOD);
+        // `x.clone()` clones the item behind the reference rather than the reference itself
+        if let hir::ExprKind::MethodCall(_, _, ref args) = value.kind;
+        if let ty::Ref(_, item_ty, _) = cx.tables.expr_ty(&args[0]).kind;
+        then {
+            cx.tables.expr_ty(value) == item_ty
+        } else {
+            false
+        }
+    }
+}
+
+fn lint_unnecessary_fold<'tcx>(
+    cx: &LateContext<'_, 'tcx>,
+    expr: &hir::Expr<'_>,
+    fold_args: &'tcx [hir::Expr<'tcx>],
+    fold_span: Span,
+) {
+    fn check_fold_with_op<'tcx>(
+        cx: &LateContext<'_, 'tcx>,
         expr: &hir::Expr<'_>,
-        fold_args: &[hir::Expr<'_>],
+        fold_args: &'tcx [hir::Expr<'tcx>],
         fold_span: Span,
         op: hir::BinOpKind,
         replacement_method_name: &str,
@@ -2142,20 +2213,17 @@ fn lint_unnecessary_fold(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, fold_ar
     ) {
         if_chain! {
             // Extract the body of the closure passed to fold
-            if let hir::ExprKind::Closure(_, _, body_id, _, _) = fold_args[2].kind;
-            let closure_body = cx.tcx.hir().body(body_id);
-            let closure_expr = remove_blocks(&closure_body.value);
+            if let Some(closure) = Closure::new(cx, &fold_args[2]);
 
             // Check if the closure body is of the form `acc <op> some_expr(x)`
-            if let hir::ExprKind::Binary(ref bin_op, ref left_expr, ref right_expr) = closure_expr.kind;
+            if let hir::ExprKind::Binary(ref bin_op, ref left_expr, ref right_expr) = closure.value().kind;
             if bin_op.node == op;
 
-            // Extract the names of the two arguments to the closure
-            if let Some(first_arg_ident) = get_arg_name(&closure_body.params[0].pat);
-            if let Some(second_arg_ident) = get_arg_name(&closure_body.params[1].pat);
-
-            if match_var(&*left_expr, first_arg_ident);
-            if replacement_has_args || match_var(&*right_expr, second_arg_ident);
+            // Check that the accumulator is on the left and, without a closure to pass on, that the
+            // item (possibly dereferenced) is on the right
+            if closure.is_param(left_expr, 0);
+            if let Some(second_arg_ident) = closure.param_name(1);
+            if replacement_has_args || closure.is_param_or_deref(right_expr, 1);
 
             then {
                 let mut applicability = Applicability::MachineApplicable;
@@ -2187,6 +2255,15 @@ fn lint_unnecessary_fold(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, fold_ar
         }
     }
 
+    #[allow(clippy::float_cmp)]
+    fn is_float(constant: &Constant, value: f64) -> bool {
+        match *constant {
+            Constant::F32(f) => f64::from(f) == value,
+            Constant::F64(f) => f == value,
+            _ => false,
+        }
+    }
+
     // Check that this is a call to Iterator::fold rather than just some function called fold
     if !match_trait_method(cx, expr, &paths::ITERATOR) {
         return;
@@ -2197,23 +2274,25 @@ fn lint_unnecessary_fold(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, fold_ar
         "Expected fold_args to have three entries - the receiver, the initial value and the closure"
     );
 
-    // Check if the first argument to .fold is a suitable literal
-    if let hir::ExprKind::Lit(ref lit) = fold_args[1].kind {
-        match lit.node {
-            ast::LitKind::Bool(false) => {
-                check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::Or, "any", true)
-            },
-            ast::LitKind::Bool(true) => {
-                check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::And, "all", true)
-            },
-            ast::LitKind::Int(0, _) => {
-                check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::Add, "sum", false)
-            },
-            ast::LitKind::Int(1, _) => {
-                check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::Mul, "product", false)
-            },
-            _ => (),
-        }
+    // Check if the first argument to .fold is a suitable constant
+    match constant(cx, cx.tables, &fold_args[1]).map(|(c, _)| c) {
+        Some(Constant::Bool(false)) => {
+            check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::Or, "any", true)
+        },
+        Some(Constant::Bool(true)) => {
+            check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::And, "all", true)
+        },
+        Some(Constant::Int(0)) => check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::Add, "sum", false),
+        Some(Constant::Int(1)) => {
+            check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::Mul, "product", false)
+        },
+        Some(ref c) if is_float(c, 0.0) => {
+            check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::Add, "sum", false)
+        },
+        Some(ref c) if is_float(c, 1.0) => {
+            check_fold_with_op(cx, expr, fold_args, fold_span, hir::BinOpKind::Mul, "product", false)
+        },
+        _ => (),
     }
 }
 
@@ -2357,20 +2436,6 @@ fn lint_get_unwrap<'a, 'tcx>(
     );
 }
 
-fn lint_iter_skip_next(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) {
-    // lint if caller of skip is an Iterator
-    if match_trait_method(cx, expr, &paths::ITERATOR) {
-        span_lint_and_help(
-            cx,
-            ITER_SKIP_NEXT,
-            expr.span,
-            "called `skip(x).next()` on an iterator",
-            None,
-            "this is more succinctly expressed by calling `nth(x)`",
-        );
-    }
-}
-
 fn derefs_to_slice<'a, 'tcx>(
     cx: &LateContext<'a, 'tcx>,
     expr: &'tcx hir::Expr<'tcx>,
@@ -2489,45 +2554,6 @@ fn lint_ok_expect(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, ok_args: &[hir
     }
 }
 
-/// lint use of `map().flatten()` for `Iterators` and 'Options'
-fn lint_map_flatten<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'_>, map_args: &'tcx [hir::Expr<'_>]) {
-    // lint if caller of `.map().flatten()` is an Iterator
-    if match_trait_method(cx, expr, &paths::ITERATOR) {
-        let msg = "called `map(..).flatten()` on an `Iterator`. \
-                   This is more succinctly expressed by calling `.flat_map(..)`";
-        let self_snippet = snippet(cx, map_args[0].span, "..");
-        let func_snippet = snippet(cx, map_args[1].span, "..");
-        let hint = format!("{0}.flat_map({1})", self_snippet, func_snippet);
-        span_lint_and_sugg(
-            cx,
-            MAP_FLATTEN,
-            expr.span,
-            msg,
-            "try using `flat_map` instead",
-            hint,
-            Applicability::MachineApplicable,
-        );
-    }
-
-    // lint if caller of `.map().flatten()` is an Option
-    if is_type_diagnostic_item(cx, cx.tables.expr_ty(&map_args[0]), sym!(option_type)) {
-        let msg = "called `map(..).flatten()` on an `Option`. \
-                    This is more succinctly expressed by calling `.and_then(..)`";
-        let self_snippet = snippet(cx, map_args[0].span, "..");
-        let func_snippet = snippet(cx, map_args[1].span, "..");
-        let hint = format!("{0}.and_then({1})", self_snippet, func_snippet);
-        span_lint_and_sugg(
-            cx,
-            MAP_FLATTEN,
-            expr.span,
-            msg,
-            "try using `and_then` instead",
-            hint,
-            Applicability::MachineApplicable,
-        );
-    }
-}
-
 /// lint use of `map().unwrap_or_else()` for `Option`s and `Result`s
 fn lint_map_unwrap_or_else<'a, 'tcx>(
     cx: &LateContext<'a, 'tcx>,
@@ -2739,33 +2765,6 @@ fn lint_option_and_then_some(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, arg
     }
 }
 
-/// lint use of `filter().next()` for `Iterators`
-fn lint_filter_next<'a, 'tcx>(
-    cx: &LateContext<'a, 'tcx>,
-    expr: &'tcx hir::Expr<'_>,
-    filter_args: &'tcx [hir::Expr<'_>],
-) {
-    // lint if caller of `.filter().next()` is an Iterator
-    if match_trait_method(cx, expr, &paths::ITERATOR) {
-        let msg = "called `filter(p).next()` on an `Iterator`. This is more succinctly expressed by calling \
-                   `.find(p)` instead.";
-        let filter_snippet = snippet(cx, filter_args[1].span, "..");
-        if filter_snippet.lines().count() <= 1 {
-            // add note if not multi-line
-            span_lint_and_note(
-                cx,
-                FILTER_NEXT,
-                expr.span,
-                msg,
-                None,
-                &format!("replace `filter({0}).next()` with `find({0})`", filter_snippet),
-            );
-        } else {
-            span_lint(cx, FILTER_NEXT, expr.span, msg);
-        }
-    }
-}
-
 /// lint use of `skip_while().next()` for `Iterators`
 fn lint_skip_while_next<'a, 'tcx>(
     cx: &LateContext<'a, 'tcx>,
@@ -2789,17 +2788,86 @@ fn lint_skip_while_next<'a, 'tcx>(
 fn lint_filter_map<'a, 'tcx>(
     cx: &LateContext<'a, 'tcx>,
     expr: &'tcx hir::Expr<'_>,
-    _filter_args: &'tcx [hir::Expr<'_>],
-    _map_args: &'tcx [hir::Expr<'_>],
+    filter_args: &'tcx [hir::Expr<'tcx>],
+    map_args: &'tcx [hir::Expr<'tcx>],
+    filter_span: Span,
 ) {
     // lint if caller of `.filter().map()` is an Iterator
     if match_trait_method(cx, expr, &paths::ITERATOR) {
         let msg = "called `filter(p).map(q)` on an `Iterator`";
         let hint = "this is more succinctly expressed by calling `.filter_map(..)` instead";
-        span_lint_and_help(cx, FILTER_MAP, expr.span, msg, None, hint);
+        let mut applicability = Applicability::MachineApplicable;
+        if let Some(sugg) = filter_map_sugg(cx, &filter_args[1], &map_args[1], &mut applicability) {
+            span_lint_and_sugg(
+                cx,
+                FILTER_MAP,
+                filter_span.with_hi(expr.span.hi()),
+                msg,
+                hint,
+                sugg,
+                applicability,
+            );
+        } else {
+            span_lint_and_help(cx, FILTER_MAP, expr.span, msg, None, hint);
+        }
     }
 }
 
+/// Merges `filter(|&x| p).map(|x| q)` into `filter_map(|x| if p { Some(q) } else { None })`.
+///
+/// `p` is then evaluated on the item instead of a reference to it, so `filter(|x| p)` is only
+/// merged if `p` reads a field of `x` or calls a method on it, which auto-dereference.
+fn filter_map_sugg<'tcx>(
+    cx: &LateContext<'_, 'tcx>,
+    filter_arg: &'tcx hir::Expr<'tcx>,
+    map_arg: &'tcx hir::Expr<'tcx>,
+    applicability: &mut Applicability,
+) -> Option<String> {
+    // merging a `move` closure would move the captures of the other one as well
+    if [filter_arg, map_arg]
+        .iter()
+        .any(|arg| matches!(arg.kind, hir::ExprKind::Closure(hir::CaptureBy::Value, ..)))
+    {
+        return None;
+    }
+    let filter = Closure::new(cx, filter_arg)?;
+    let map = Closure::new(cx, map_arg)?;
+    let (filter_param, map_param) = match (filter.body.params, map.body.params) {
+        ([filter_param], [map_param]) => (filter_param, map_param),
+        _ => return None,
+    };
+    let (filter_pat, by_ref) = match filter_param.pat.kind {
+        hir::PatKind::Ref(ref pat, _) => (&**pat, true),
+        _ => (filter_param.pat, false),
+    };
+    match (&filter_pat.kind, &map_param.pat.kind) {
+        (
+            hir::PatKind::Binding(hir::BindingAnnotation::Unannotated, _, filter_ident, None),
+            hir::PatKind::Binding(_, _, map_ident, None),
+        ) if filter_ident.name == map_ident.name => {},
+        _ => return None,
+    }
+    match filter.shape(cx) {
+        ClosureShape::Bool | ClosureShape::Field(_) | ClosureShape::MethodCall(..) if by_ref => {},
+        ClosureShape::Field(_) => {},
+        // the method may be implemented for the reference only
+        ClosureShape::MethodCall(_, []) => *applicability = Applicability::MaybeIncorrect,
+        _ => return None,
+    }
+
+    let cond = snippet_with_applicability(cx, filter.value().span, "..", applicability);
+    if cond.contains('{') {
+        // struct literals need parentheses in the condition of an `if`
+        *applicability = Applicability::MaybeIncorrect;
+    }
+    Some(format!(
+        "filter_map(|{}| if {} {{ Some({}) }} else {{ None }})",
+        snippet_with_applicability(cx, map_param.pat.span, "..", applicability),
+        cond,
+        snippet_with_applicability(cx, map.value().span, "..", applicability),
+    ))
+}
+
 /// lint use of `filter_map().next()` for `Iterators`
 fn lint_filter_map_next<'a, 'tcx>(
     cx: &LateContext<'a, 'tcx>,
@@ -2887,111 +2955,6 @@ fn lint_filter_map_flat_map<'a, 'tcx>(
     }
 }
 
-/// lint use of `flat_map` for `Iterators` where `flatten` would be sufficient
-fn lint_flat_map_identity<'a, 'tcx>(
-    cx: &LateContext<'a, 'tcx>,
-    expr: &'tcx hir::Expr<'_>,
-    flat_map_args: &'tcx [hir::Expr<'_>],
-    flat_map_span: Span,
-) {
-    if match_trait_method(cx, expr, &paths::ITERATOR) {
-        let arg_node = &flat_map_args[1].kind;
-
-        let apply_lint = |message: &str| {
-            span_lint_and_sugg(
-                cx,
-                FLAT_MAP_IDENTITY,
-                flat_map_span.with_hi(expr.span.hi()),
-                message,
-                "try",
-                "flatten()".to_string(),
-                Applicability::MachineApplicable,
-            );
-        };
-
-        if_chain! {
-            if let hir::ExprKind::Closure(_, _, body_id, _, _) = arg_node;
-            let body = cx.tcx.hir().body(*body_id);
-
-            if let hir::PatKind::Binding(_, _, binding_ident, _) = body.params[0].pat.kind;
-            if let hir::ExprKind::Path(hir::QPath::Resolved(_, ref path)) = body.value.kind;
-
-            if path.segments.len() == 1;
-            if path.segments[0].ident.as_str() == binding_ident.as_str();
-
-            then {
-                apply_lint("called `flat_map(|x| x)` on an `Iterator`");
-            }
-        }
-
-        if_chain! {
-            if let hir::ExprKind::Path(ref qpath) = arg_node;
-
-            if match_qpath(qpath, &paths::STD_CONVERT_IDENTITY);
-
-            then {
-                apply_lint("called `flat_map(std::convert::identity)` on an `Iterator`");
-            }
-        }
-    }
-}
-
-/// lint searching an Iterator followed by `is_some()`
-fn lint_search_is_some<'a, 'tcx>(
-    cx: &LateContext<'a, 'tcx>,
-    expr: &'tcx hir::Expr<'_>,
-    search_method: &str,
-    search_args: &'tcx [hir::Expr<'_>],
-    is_some_args: &'tcx [hir::Expr<'_>],
-    method_span: Span,
-) {
-    // lint if caller of search is an Iterator
-    if match_trait_method(cx, &is_some_args[0], &paths::ITERATOR) {
-        let msg = format!(
-            "called `is_some()` after searching an `Iterator` with {}. This is more succinctly \
-             expressed by calling `any()`.",
-            search_method
-        );
-        let search_snippet = snippet(cx, search_args[1].span, "..");
-        if search_snippet.lines().count() <= 1 {
-            // suggest `any(|x| ..)` instead of `any(|&x| ..)` for `find(|&x| ..).is_some()`
-            // suggest `any(|..| *..)` instead of `any(|..| **..)` for `find(|..| **..).is_some()`
-            let any_search_snippet = if_chain! {
-                if search_method == "find";
-                if let hir::ExprKind::Closure(_, _, body_id, ..) = search_args[1].kind;
-                let closure_body = cx.tcx.hir().body(body_id);
-                if let Some(closure_arg) = closure_body.params.get(0);
-                then {
-                    if let hir::PatKind::Ref(..) = closure_arg.pat.kind {
-                        Some(search_snippet.replacen('&', "", 1))
-                    } else if let Some(name) = get_arg_name(&closure_arg.pat) {
-                        Some(search_snippet.replace(&format!("*{}", name), &name.as_str()))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            };
-            // add note if not multi-line
-            span_lint_and_sugg(
-                cx,
-                SEARCH_IS_SOME,
-                method_span.with_hi(expr.span.hi()),
-                &msg,
-                "try this",
-                format!(
-                    "any({})",
-                    any_search_snippet.as_ref().map_or(&*search_snippet, String::as_str)
-                ),
-                Applicability::MachineApplicable,
-            );
-        } else {
-            span_lint(cx, SEARCH_IS_SOME, expr.span, &msg);
-        }
-    }
-}
-
 /// Used for `lint_binary_expr_with_method_call`.
 #[derive(Copy, Clone)]
 struct BinaryExprInfo<'a> {
@@ -1,8 +1,9 @@
+use crate::utils::interior_mut::InteriorMut;
 use crate::utils::{match_def_path, paths, span_lint, trait_ref_of_method, walk_ptrs_ty};
 use rustc_hir as hir;
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::{Adt, Array, RawPtr, Ref, Slice, Tuple, Ty, TypeAndMut};
-use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::source_map::Span;
 
 declare_clippy_lint! {
@@ -12,8 +13,12 @@ declare_clippy_lint! {
     /// `BtreeSet` rely on either the hash or the order of keys be unchanging,
     /// so having types with interior mutability is a bad idea.
     ///
-    /// **Known problems:** We don't currently account for `Rc` or `Arc`, so
-    /// this may yield false positives.
+    /// Key types are also checked through their generic arguments, so e.g.
+    /// `Rc<RefCell<T>>` and `Vec<Cell<T>>` keys are linted as well.
+    ///
+    /// **Known problems:** Types whose `Hash` and `Ord` implementations ignore
+    /// the mutable part are linted anyway. Such types can be listed in the
+    /// `ignore-interior-mutability` configuration (default: `["bytes::Bytes"]`).
     ///
     /// **Example:**
     /// ```rust
@@ -44,81 +49,102 @@ declare_clippy_lint! {
     ///     let _: HashSet<Bad> = HashSet::new();
     /// }
     /// ```
+    #[clippy::version = "1.42.0"]
     pub MUTABLE_KEY_TYPE,
     correctness,
     "Check for mutable `Map`/`Set` key type"
 }
 
-declare_lint_pass!(MutableKeyType => [ MUTABLE_KEY_TYPE ]);
+pub struct MutableKeyType {
+    interior_mut: InteriorMut,
+}
+
+impl MutableKeyType {
+    pub fn new(ignore_interior_mutability: &[String]) -> Self {
+        Self {
+            interior_mut: InteriorMut::new(ignore_interior_mutability),
+        }
+    }
+}
+
+impl_lint_pass!(MutableKeyType => [ MUTABLE_KEY_TYPE ]);
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MutableKeyType {
     fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::Item<'tcx>) {
         if let hir::ItemKind::Fn(ref sig, ..) = item.kind {
-            check_sig(cx, item.hir_id, &sig.decl);
+            self.check_sig(cx, item.hir_id, &sig.decl);
         }
     }
 
     fn check_impl_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::ImplItem<'tcx>) {
         if let hir::ImplItemKind::Fn(ref sig, ..) = item.kind {
             if trait_ref_of_method(cx, item.hir_id).is_none() {
-                check_sig(cx, item.hir_id, &sig.decl);
+                self.check_sig(cx, item.hir_id, &sig.decl);
             }
         }
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::TraitItem<'tcx>) {
         if let hir::TraitItemKind::Fn(ref sig, ..) = item.kind {
-            check_sig(cx, item.hir_id, &sig.decl);
+            self.check_sig(cx, item.hir_id, &sig.decl);
         }
     }
 
-    fn check_local(&mut self, cx: &LateContext<'_, '_>, local: &hir::Local<'_>) {
+    fn check_local(&mut self, cx: &LateContext<'a, 'tcx>, local: &hir::Local<'_>) {
         if let hir::PatKind::Wild = local.pat.kind {
             return;
         }
-        check_ty(cx, local.span, cx.tables.pat_ty(&*local.pat));
+        self.check_ty(cx, local.span, cx.tables.pat_ty(&*local.pat));
     }
 }
 
-fn check_sig<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, item_hir_id: hir::HirId, decl: &hir::FnDecl<'_>) {
-    let fn_def_id = cx.tcx.hir().local_def_id(item_hir_id);
-    let fn_sig = cx.tcx.fn_sig(fn_def_id);
-    for (hir_ty, ty) in decl.inputs.iter().zip(fn_sig.inputs().skip_binder().iter()) {
-        check_ty(cx, hir_ty.span, ty);
+impl MutableKeyType {
+    fn check_sig<'a, 'tcx>(&mut self, cx: &LateContext<'a, 'tcx>, item_hir_id: hir::HirId, decl: &hir::FnDecl<'_>) {
+        let fn_def_id = cx.tcx.hir().local_def_id(item_hir_id);
+        let fn_sig = cx.tcx.fn_sig(fn_def_id);
+        for (hir_ty, ty) in decl.inputs.iter().zip(fn_sig.inputs().skip_binder().iter()) {
+            self.check_ty(cx, hir_ty.span, ty);
+        }
+        self.check_ty(
+            cx,
+            decl.output.span(),
+            cx.tcx.erase_late_bound_regions(&fn_sig.output()),
+        );
     }
-    check_ty(
-        cx,
-        decl.output.span(),
-        cx.tcx.erase_late_bound_regions(&fn_sig.output()),
-    );
-}
 
-// We want to lint 1. sets or maps with 2. not immutable key types and 3. no unerased
-// generics (because the compiler cannot ensure immutability for unknown types).
-fn check_ty<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, span: Span, ty: Ty<'tcx>) {
-    let ty = walk_ptrs_ty(ty);
-    if let Adt(def, substs) = ty.kind {
-        if [&paths::HASHMAP, &paths::BTREEMAP, &paths::HASHSET, &paths::BTREESET]
-            .iter()
-            .any(|path| match_def_path(cx, def.did, &**path))
-            && is_mutable_type(cx, substs.type_at(0), span)
-        {
-            span_lint(cx, MUTABLE_KEY_TYPE, span, "mutable key type");
+    // We want to lint 1. sets or maps with 2. not immutable key types and 3. no unerased
+    // generics (because the compiler cannot ensure immutability for unknown types).
+    fn check_ty<'a, 'tcx>(&mut self, cx: &LateContext<'a, 'tcx>, span: Span, ty: Ty<'tcx>) {
+        let ty = walk_ptrs_ty(ty);
+        if let Adt(def, substs) = ty.kind {
+            if [&paths::HASHMAP, &paths::BTREEMAP, &paths::HASHSET, &paths::BTREESET]
+                .iter()
+                .any(|path| match_def_path(cx, def.did, &**path))
+                && self.is_mutable_type(cx, substs.type_at(0))
+            {
+                span_lint(cx, MUTABLE_KEY_TYPE, span, "mutable key type");
+            }
         }
     }
-}
 
-fn is_mutable_type<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, ty: Ty<'tcx>, span: Span) -> bool {
-    match ty.kind {
-        RawPtr(TypeAndMut { ty: inner_ty, mutbl }) | Ref(_, inner_ty, mutbl) => {
-            mutbl == hir::Mutability::Mut || is_mutable_type(cx, inner_ty, span)
-        },
-        Slice(inner_ty) => is_mutable_type(cx, inner_ty, span),
-        Array(inner_ty, size) => {
-            size.try_eval_usize(cx.tcx, cx.param_env).map_or(true, |u| u != 0) && is_mutable_type(cx, inner_ty, span)
-        },
-        Tuple(..) => ty.tuple_fields().any(|ty| is_mutable_type(cx, ty, span)),
-        Adt(..) => cx.tcx.layout_of(cx.param_env.and(ty)).is_ok() && !ty.is_freeze(cx.tcx, cx.param_env, span),
-        _ => false,
+    fn is_mutable_type<'a, 'tcx>(&mut self, cx: &LateContext<'a, 'tcx>, ty: Ty<'tcx>) -> bool {
+        match ty.kind {
+            RawPtr(TypeAndMut { ty: inner_ty, mutbl }) | Ref(_, inner_ty, mutbl) => {
+                mutbl == hir::Mutability::Mut || self.is_mutable_type(cx, inner_ty)
+            },
+            Slice(inner_ty) => self.is_mutable_type(cx, inner_ty),
+            Array(inner_ty, size) => {
+                size.try_eval_usize(cx.tcx, cx.param_env).map_or(true, |u| u != 0) && self.is_mutable_type(cx, inner_ty)
+            },
+            Tuple(..) => ty.tuple_fields().any(|ty| self.is_mutable_type(cx, ty)),
+            // Look through the generic arguments as well, those are often stored behind a
+            // pointer, e.g. in a `Vec` or an `Rc`.
+            Adt(def, substs) => {
+                !self.interior_mut.is_ignored(cx, def.did)
+                    && (self.interior_mut.is_interior_mut_ty(cx, ty) == Some(true)
+                        || substs.types().any(|ty| self.is_mutable_type(cx, ty)))
+            },
+            _ => false,
+        }
     }
 }
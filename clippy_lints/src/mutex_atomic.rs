@@ -26,6 +26,7 @@ declare_clippy_lint! {
     /// # let y = 1;
     /// let x = Mutex::new(&y);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MUTEX_ATOMIC,
     perf,
     "using a mutex where an atomic value could be used instead"
@@ -47,6 +48,7 @@ declare_clippy_lint! {
     /// # use std::sync::Mutex;
     /// let x = Mutex::new(0usize);
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub MUTEX_INTEGER,
     nursery,
     "using a mutex for an integer type"
@@ -45,6 +45,7 @@ declare_clippy_lint! {
     /// reference and
     /// de-referenced.
     /// As such, it could just be |a| a.is_empty()
+    #[clippy::version = "pre 1.29.0"]
     pub NEEDLESS_BORROWED_REFERENCE,
     complexity,
     "taking a needless borrowed reference"
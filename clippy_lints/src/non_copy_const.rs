@@ -4,16 +4,21 @@
 
 use std::ptr;
 
+use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::def::{DefKind, Res};
-use rustc_hir::{Expr, ExprKind, ImplItem, ImplItemKind, Item, ItemKind, Node, TraitItem, TraitItemKind, UnOp};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{
+    Expr, ExprKind, ImplItem, ImplItemKind, Item, ItemKind, Mutability, Node, TraitItem, TraitItemKind, UnOp,
+};
 use rustc_lint::{LateContext, LateLintPass, Lint};
 use rustc_middle::ty::adjustment::Adjust;
-use rustc_middle::ty::{Ty, TypeFlags};
-use rustc_session::{declare_lint_pass, declare_tool_lint};
-use rustc_span::{InnerSpan, Span, DUMMY_SP};
+use rustc_middle::ty::{self, Ty, TypeFlags};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{InnerSpan, Span};
 use rustc_typeck::hir_ty_to_ty;
 
-use crate::utils::{in_constant, is_copy, qpath_res, span_lint_and_then};
+use crate::utils::interior_mut::InteriorMut;
+use crate::utils::{in_constant, is_copy, qpath_res, span_lint_and_note, span_lint_and_then};
 
 declare_clippy_lint! {
     /// **What it does:** Checks for declaration of `const` items which is interior
@@ -46,6 +51,7 @@ declare_clippy_lint! {
     /// STATIC_ATOM.store(9, SeqCst);
     /// assert_eq!(STATIC_ATOM.load(SeqCst), 9); // use a `static` item to refer to the same instance
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub DECLARE_INTERIOR_MUTABLE_CONST,
     correctness,
     "declaring `const` with interior mutability"
@@ -78,11 +84,46 @@ declare_clippy_lint! {
     /// STATIC_ATOM.store(9, SeqCst);
     /// assert_eq!(STATIC_ATOM.load(SeqCst), 9); // use a `static` item to refer to the same instance
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub BORROW_INTERIOR_MUTABLE_CONST,
     correctness,
     "referencing `const` with interior mutability"
 }
 
+declare_clippy_lint! {
+    /// **What it does:** Checks for `static` items of interior mutable types
+    /// that are only `Sync` because of an `unsafe impl Sync` in the current
+    /// crate.
+    ///
+    /// **Why is this bad?** A `static` is shared by all threads. The compiler
+    /// can't check that the `unsafe impl` actually synchronizes the accesses
+    /// to the `Cell`s inside, so a wrong impl silently introduces data races.
+    /// Types like `Mutex` or the atomics are `Sync` without any extra `unsafe`.
+    ///
+    /// **Known problems:** The lint doesn't check how the type accesses its
+    /// interior mutable parts, so correctly synchronized types, e.g. ones built
+    /// around a spin lock, are linted as well.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// use std::cell::Cell;
+    ///
+    /// struct Counter(Cell<u32>);
+    /// unsafe impl Sync for Counter {}
+    ///
+    /// // Bad.
+    /// static COUNTER: Counter = Counter(Cell::new(0));
+    ///
+    /// // Good.
+    /// use std::sync::atomic::AtomicU32;
+    /// static ATOMIC_COUNTER: AtomicU32 = AtomicU32::new(0);
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub UNSAFE_SYNC_STATIC,
+    pedantic,
+    "`static` item of an interior mutable type that is only `Sync` through an `unsafe impl`"
+}
+
 #[allow(dead_code)]
 #[derive(Copy, Clone)]
 enum Source {
@@ -109,50 +150,124 @@ impl Source {
     }
 }
 
-fn verify_ty_bound<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, ty: Ty<'tcx>, source: Source) {
-    if ty.is_freeze(cx.tcx, cx.param_env, DUMMY_SP) || is_copy(cx, ty) {
-        // An `UnsafeCell` is `!Copy`, and an `UnsafeCell` is also the only type which
-        // is `!Freeze`, thus if our type is `Copy` we can be sure it must be `Freeze`
-        // as well.
-        return;
+pub struct NonCopyConst {
+    interior_mut: InteriorMut,
+}
+
+impl NonCopyConst {
+    pub fn new(ignore_interior_mutability: &[String]) -> Self {
+        Self {
+            interior_mut: InteriorMut::new(ignore_interior_mutability),
+        }
     }
 
-    let (lint, msg, span) = source.lint();
-    span_lint_and_then(cx, lint, span, msg, |diag| {
-        if span.from_expansion() {
-            return; // Don't give suggestions into macros.
+    fn verify_ty_bound<'a, 'tcx>(&mut self, cx: &LateContext<'a, 'tcx>, ty: Ty<'tcx>, source: Source) {
+        if is_copy(cx, ty) || self.interior_mut.is_interior_mut_ty(cx, ty) == Some(false) {
+            // An `UnsafeCell` is `!Copy`, and an `UnsafeCell` is also the only type which
+            // is `!Freeze`, thus if our type is `Copy` we can be sure it must be `Freeze`
+            // as well. That also covers generic types with a `Copy` bound.
+            return;
         }
-        match source {
-            Source::Item { .. } => {
-                let const_kw_span = span.from_inner(InnerSpan::new(0, 5));
-                diag.span_label(const_kw_span, "make this a static item (maybe with lazy_static)");
-            },
-            Source::Assoc { ty: ty_span, .. } => {
-                if ty.flags.intersects(TypeFlags::HAS_FREE_LOCAL_NAMES) {
-                    diag.span_label(ty_span, &format!("consider requiring `{}` to be `Copy`", ty));
+
+        let (lint, msg, span) = source.lint();
+        span_lint_and_then(cx, lint, span, msg, |diag| {
+            if span.from_expansion() {
+                return; // Don't give suggestions into macros.
+            }
+            match source {
+                Source::Item { .. } => {
+                    let const_kw_span = span.from_inner(InnerSpan::new(0, 5));
+                    diag.span_label(const_kw_span, "make this a static item (maybe with lazy_static)");
+                },
+                Source::Assoc { ty: ty_span, .. } => {
+                    if ty.flags.intersects(TypeFlags::HAS_FREE_LOCAL_NAMES) {
+                        diag.span_label(ty_span, &format!("consider requiring `{}` to be `Copy`", ty));
+                    }
+                },
+                Source::Expr { .. } => {
+                    diag.help("assign this const to a local or static variable, and use the variable here");
+                },
+            }
+        });
+    }
+
+    /// Finds the interior mutable type with an `unsafe impl Sync` in the current crate that
+    /// makes `ty` `Sync`, together with that impl.
+    fn find_unsafe_sync<'tcx>(
+        &mut self,
+        cx: &LateContext<'_, 'tcx>,
+        ty: Ty<'tcx>,
+        visited: &mut FxHashSet<DefId>,
+    ) -> Option<(Ty<'tcx>, DefId)> {
+        match ty.kind {
+            ty::Adt(def, substs) => {
+                if !visited.insert(def.did) {
+                    return None;
+                }
+                match sync_impl(cx, ty) {
+                    Some(impl_did) => {
+                        if impl_did.is_local() && self.interior_mut.is_interior_mut_ty(cx, ty) == Some(true) {
+                            Some((ty, impl_did))
+                        } else {
+                            None
+                        }
+                    },
+                    // The auto trait impl depends on the fields
+                    None if def.did.is_local() => def
+                        .all_fields()
+                        .find_map(|field| self.find_unsafe_sync(cx, field.ty(cx.tcx, substs), visited)),
+                    None => substs.types().find_map(|ty| self.find_unsafe_sync(cx, ty, visited)),
                 }
             },
-            Source::Expr { .. } => {
-                diag.help("assign this const to a local or static variable, and use the variable here");
-            },
+            ty::Array(inner, _) | ty::Slice(inner) => self.find_unsafe_sync(cx, inner, visited),
+            ty::Tuple(..) => ty.tuple_fields().find_map(|ty| self.find_unsafe_sync(cx, ty, visited)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the explicit `Sync` impl of the type, if any.
+fn sync_impl<'tcx>(cx: &LateContext<'_, 'tcx>, ty: Ty<'tcx>) -> Option<DefId> {
+    let sync_trait = cx.tcx.lang_items().sync_trait()?;
+    let mut found = None;
+    cx.tcx.for_each_relevant_impl(sync_trait, ty, |impl_did| {
+        if found.is_none() && cx.tcx.impl_polarity(impl_did) == ty::ImplPolarity::Positive {
+            found = Some(impl_did);
         }
     });
+    found
 }
 
-declare_lint_pass!(NonCopyConst => [DECLARE_INTERIOR_MUTABLE_CONST, BORROW_INTERIOR_MUTABLE_CONST]);
+impl_lint_pass!(NonCopyConst => [DECLARE_INTERIOR_MUTABLE_CONST, BORROW_INTERIOR_MUTABLE_CONST, UNSAFE_SYNC_STATIC]);
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NonCopyConst {
     fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, it: &'tcx Item<'_>) {
-        if let ItemKind::Const(hir_ty, ..) = &it.kind {
-            let ty = hir_ty_to_ty(cx.tcx, hir_ty);
-            verify_ty_bound(cx, ty, Source::Item { item: it.span });
+        match &it.kind {
+            ItemKind::Const(hir_ty, ..) => {
+                let ty = hir_ty_to_ty(cx.tcx, hir_ty);
+                self.verify_ty_bound(cx, ty, Source::Item { item: it.span });
+            },
+            ItemKind::Static(hir_ty, Mutability::Not, _) => {
+                let ty = hir_ty_to_ty(cx.tcx, hir_ty);
+                if let Some((unsafe_ty, impl_did)) = self.find_unsafe_sync(cx, ty, &mut FxHashSet::default()) {
+                    span_lint_and_note(
+                        cx,
+                        UNSAFE_SYNC_STATIC,
+                        it.span,
+                        "this `static` is only `Sync` through an `unsafe impl Sync`",
+                        Some(cx.tcx.def_span(impl_did)),
+                        &format!("the interior mutable type `{}` implements `Sync` here", unsafe_ty),
+                    );
+                }
+            },
+            _ => {},
         }
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'a, 'tcx>, trait_item: &'tcx TraitItem<'_>) {
         if let TraitItemKind::Const(hir_ty, ..) = &trait_item.kind {
             let ty = hir_ty_to_ty(cx.tcx, hir_ty);
-            verify_ty_bound(
+            self.verify_ty_bound(
                 cx,
                 ty,
                 Source::Assoc {
@@ -170,7 +285,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NonCopyConst {
             // Ensure the impl is an inherent impl.
             if let ItemKind::Impl { of_trait: None, .. } = item.kind {
                 let ty = hir_ty_to_ty(cx.tcx, hir_ty);
-                verify_ty_bound(
+                self.verify_ty_bound(
                     cx,
                     ty,
                     Source::Assoc {
@@ -238,10 +353,10 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NonCopyConst {
 
             let ty = if needs_check_adjustment {
                 let adjustments = cx.tables.expr_adjustments(dereferenced_expr);
-                if let Some(i) = adjustments.iter().position(|adj| match adj.kind {
-                    Adjust::Borrow(_) | Adjust::Deref(_) => true,
-                    _ => false,
-                }) {
+                if let Some(i) = adjustments
+                    .iter()
+                    .position(|adj| matches!(adj.kind, Adjust::Borrow(_) | Adjust::Deref(_)))
+                {
                     if i == 0 {
                         cx.tables.expr_ty(dereferenced_expr)
                     } else {
@@ -255,7 +370,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NonCopyConst {
                 cx.tables.expr_ty(dereferenced_expr)
             };
 
-            verify_ty_bound(cx, ty, Source::Expr { expr: expr.span });
+            self.verify_ty_bound(cx, ty, Source::Expr { expr: expr.span });
         }
     }
 }
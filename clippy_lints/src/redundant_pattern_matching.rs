@@ -1,19 +1,27 @@
-use crate::utils::{match_qpath, match_trait_method, paths, snippet, span_lint_and_then};
+use crate::utils::{
+    in_macro, is_type_diagnostic_item, is_wild, match_trait_method, paths, remove_blocks, snippet,
+    snippet_with_applicability, span_lint_and_sugg, span_lint_and_then,
+};
 use if_chain::if_chain;
 use rustc_ast::ast::LitKind;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::Applicability;
-use rustc_hir::{Arm, Expr, ExprKind, MatchSource, PatKind, QPath};
+use rustc_hir::def::{CtorOf, DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Arm, Expr, ExprKind, Guard, HirId, MatchSource, Mutability, Pat, PatKind};
 use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, Ty};
 use rustc_session::{declare_lint_pass, declare_tool_lint};
 
 declare_clippy_lint! {
-    /// **What it does:** Lint for redundant pattern matching over `Result` or
-    /// `Option`
+    /// **What it does:** Lint for redundant pattern matching over `Result`, `Option`
+    /// or any other enum with a method like `is_some` for the matched variant
     ///
     /// **Why is this bad?** It's more concise and clear to just use the proper
     /// utility function
     ///
-    /// **Known problems:** None.
+    /// **Known problems:** For enums other than `Option` and `Result`, an inherent
+    /// method `is_<variant>(&self) -> bool` is assumed to test for the variant.
     ///
     /// **Example:**
     ///
@@ -37,53 +45,76 @@ declare_clippy_lint! {
     /// if Some(42).is_some() {}
     /// Ok::<i32, i32>(42).is_ok();
     /// ```
+    #[clippy::version = "1.31.0"]
     pub REDUNDANT_PATTERN_MATCHING,
     style,
     "use the proper utility function avoiding an `if let`"
 }
 
-declare_lint_pass!(RedundantPatternMatching => [REDUNDANT_PATTERN_MATCHING]);
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` or `if let` expressions that produce a bool
+    /// from whether a pattern matches, like `match x { Some(1) => true, _ => false }`.
+    /// Exhaustive matches without a `_` arm are not linted, as they have to be revisited
+    /// when a variant is added.
+    ///
+    /// **Why is this bad?** The `matches!` macro does the same and is shorter.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let x = Some(5);
+    /// let a = match x {
+    ///     Some(0) => true,
+    ///     _ => false,
+    /// };
+    /// let b = if let Some(1..=3) = x { true } else { false };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let x = Some(5);
+    /// let a = matches!(x, Some(0));
+    /// let b = matches!(x, Some(1..=3));
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MATCH_LIKE_MATCHES_MACRO,
+    pedantic,
+    "a `match` or `if let` with `true` and `false` arms that could be `matches!`"
+}
+
+declare_lint_pass!(RedundantPatternMatching => [REDUNDANT_PATTERN_MATCHING, MATCH_LIKE_MATCHES_MACRO]);
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for RedundantPatternMatching {
     fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr<'_>) {
         if let ExprKind::Match(op, arms, ref match_source) = &expr.kind {
             match match_source {
                 MatchSource::Normal => find_sugg_for_match(cx, expr, op, arms),
-                MatchSource::IfLetDesugar { .. } => find_sugg_for_if_let(cx, expr, op, arms, "if"),
-                MatchSource::WhileLetDesugar => find_sugg_for_if_let(cx, expr, op, arms, "while"),
-                _ => return,
+                MatchSource::IfLetDesugar { contains_else_clause } => {
+                    if !find_sugg_for_if_let(cx, expr, op, arms, "if") && *contains_else_clause {
+                        find_matches_sugg(cx, expr, op, arms);
+                    }
+                },
+                MatchSource::WhileLetDesugar => {
+                    find_sugg_for_if_let(cx, expr, op, arms, "while");
+                },
+                _ => (),
             }
         }
     }
 }
 
+/// Lints `if let` or `while let` on a pattern that only tests for a variant, returns whether it
+/// linted.
 fn find_sugg_for_if_let<'a, 'tcx>(
     cx: &LateContext<'a, 'tcx>,
     expr: &'tcx Expr<'_>,
     op: &Expr<'_>,
     arms: &[Arm<'_>],
     keyword: &'static str,
-) {
-    let good_method = match arms[0].pat.kind {
-        PatKind::TupleStruct(ref path, ref patterns, _) if patterns.len() == 1 => {
-            if let PatKind::Wild = patterns[0].kind {
-                if match_qpath(path, &paths::RESULT_OK) {
-                    "is_ok()"
-                } else if match_qpath(path, &paths::RESULT_ERR) {
-                    "is_err()"
-                } else if match_qpath(path, &paths::OPTION_SOME) {
-                    "is_some()"
-                } else {
-                    return;
-                }
-            } else {
-                return;
-            }
-        },
-
-        PatKind::Path(ref path) if match_qpath(path, &paths::OPTION_NONE) => "is_none()",
-
-        _ => return,
+) -> bool {
+    let (good_method, applicability) = match variant_method(cx, expr.hir_id, arms[0].pat) {
+        Some((method, applicability)) => (method, applicability.unwrap_or(Applicability::MachineApplicable)),
+        None => return false,
     };
 
     // check that `while_let_on_iterator` lint does not trigger
@@ -93,7 +124,7 @@ fn find_sugg_for_if_let<'a, 'tcx>(
         if method_path.ident.name == sym!(next);
         if match_trait_method(cx, op, &paths::ITERATOR);
         then {
-            return;
+            return false;
         }
     }
 
@@ -118,99 +149,211 @@ fn find_sugg_for_if_let<'a, 'tcx>(
                 span,
                 "try this",
                 format!("{} {}.{}", keyword, snippet(cx, op_span, "_"), good_method),
-                Applicability::MachineApplicable, // snippet
+                applicability,
             );
         },
     );
+    true
 }
 
 fn find_sugg_for_match<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr<'_>, op: &Expr<'_>, arms: &[Arm<'_>]) {
-    if arms.len() == 2 {
-        let node_pair = (&arms[0].pat.kind, &arms[1].pat.kind);
-
-        let found_good_method = match node_pair {
-            (
-                PatKind::TupleStruct(ref path_left, ref patterns_left, _),
-                PatKind::TupleStruct(ref path_right, ref patterns_right, _),
-            ) if patterns_left.len() == 1 && patterns_right.len() == 1 => {
-                if let (PatKind::Wild, PatKind::Wild) = (&patterns_left[0].kind, &patterns_right[0].kind) {
-                    find_good_method_for_match(
-                        arms,
-                        path_left,
-                        path_right,
-                        &paths::RESULT_OK,
-                        &paths::RESULT_ERR,
-                        "is_ok()",
-                        "is_err()",
-                    )
-                } else {
-                    None
-                }
-            },
-            (PatKind::TupleStruct(ref path_left, ref patterns, _), PatKind::Path(ref path_right))
-            | (PatKind::Path(ref path_left), PatKind::TupleStruct(ref path_right, ref patterns, _))
-                if patterns.len() == 1 =>
-            {
-                if let PatKind::Wild = patterns[0].kind {
-                    find_good_method_for_match(
-                        arms,
-                        path_left,
-                        path_right,
-                        &paths::OPTION_SOME,
-                        &paths::OPTION_NONE,
-                        "is_some()",
-                        "is_none()",
-                    )
-                } else {
-                    None
-                }
-            },
-            _ => None,
-        };
-
-        if let Some(good_method) = found_good_method {
+    // `matches!` expands to such a `match`
+    if in_macro(expr.span) {
+        return;
+    }
+    if_chain! {
+        if let Some((arm, negated)) = find_bool_arm(arms);
+        if arm.guard.is_none();
+        if let Some((method, applicability)) = variant_method(cx, expr.hir_id, arm.pat);
+        then {
+            // `Ok(_) => false, Err(_) => true` is `is_err()` rather than `!is_ok()`
+            let (good_method, negated) = match variant_method(cx, expr.hir_id, arms[1].pat) {
+                Some((other_method, _)) if negated => (other_method, false),
+                _ => (method, negated),
+            };
+            let negation = if negated { "!" } else { "" };
             span_lint_and_then(
                 cx,
                 REDUNDANT_PATTERN_MATCHING,
                 expr.span,
-                &format!("redundant pattern matching, consider using `{}`", good_method),
+                &format!("redundant pattern matching, consider using `{}{}`", negation, good_method),
                 |diag| {
                     let span = expr.span.to(op.span);
                     diag.span_suggestion(
                         span,
                         "try this",
-                        format!("{}.{}", snippet(cx, op.span, "_"), good_method),
-                        Applicability::MaybeIncorrect, // snippet
+                        format!("{}{}.{}", negation, snippet(cx, op.span, "_"), good_method),
+                        applicability.unwrap_or(Applicability::MaybeIncorrect), // snippet
                     );
                 },
             );
+            return;
         }
     }
+    find_matches_sugg(cx, expr, op, arms);
 }
 
-fn find_good_method_for_match<'a>(
-    arms: &[Arm<'_>],
-    path_left: &QPath<'_>,
-    path_right: &QPath<'_>,
-    expected_left: &[&str],
-    expected_right: &[&str],
-    should_be_left: &'a str,
-    should_be_right: &'a str,
-) -> Option<&'a str> {
-    let body_node_pair = if match_qpath(path_left, expected_left) && match_qpath(path_right, expected_right) {
-        (&(*arms[0].body).kind, &(*arms[1].body).kind)
-    } else if match_qpath(path_right, expected_left) && match_qpath(path_left, expected_right) {
-        (&(*arms[1].body).kind, &(*arms[0].body).kind)
-    } else {
+/// Lints a `match` or `if let` with `true` and `false` arms that could be `matches!`.
+fn find_matches_sugg(cx: &LateContext<'_, '_>, expr: &Expr<'_>, op: &Expr<'_>, arms: &[Arm<'_>]) {
+    if_chain! {
+        if !in_macro(expr.span) && !in_macro(op.span);
+        // `bool`s are handled by `match_bool`
+        if !cx.tables.expr_ty(op).is_bool();
+        if let Some((arm, negated)) = find_bool_arm(arms);
+        if is_wild(arms[1].pat);
+        if !matches!(arm.pat.kind, PatKind::Wild | PatKind::Binding(.., None));
+        then {
+            let mut applicability = Applicability::MachineApplicable;
+            let guard = match arm.guard {
+                Some(Guard::If(guard)) => format!(
+                    " if {}",
+                    snippet_with_applicability(cx, guard.span, "..", &mut applicability)
+                ),
+                None => String::new(),
+            };
+            span_lint_and_sugg(
+                cx,
+                MATCH_LIKE_MATCHES_MACRO,
+                expr.span,
+                "this can be written with the `matches!` macro",
+                "try this",
+                format!(
+                    "{}matches!({}, {}{})",
+                    if negated { "!" } else { "" },
+                    snippet_with_applicability(cx, op.span, "..", &mut applicability),
+                    snippet_with_applicability(cx, arm.pat.span, "..", &mut applicability),
+                    guard,
+                ),
+                applicability,
+            );
+        }
+    }
+}
+
+/// Returns the first arm of a two-armed `match` whose arms are `true` and `false`, and whether
+/// the result is negated, as in `match x { Some(_) => false, _ => true }`. The second arm matches
+/// all other values, so only the first pattern matters.
+fn find_bool_arm<'a>(arms: &'a [Arm<'a>]) -> Option<(&'a Arm<'a>, bool)> {
+    if arms.len() != 2 || arms[1].guard.is_some() {
         return None;
-    };
+    }
+    let first = bool_lit(arms[0].body)?;
+    if bool_lit(arms[1].body)? == first {
+        None
+    } else {
+        Some((&arms[0], !first))
+    }
+}
 
-    match body_node_pair {
-        (ExprKind::Lit(ref lit_left), ExprKind::Lit(ref lit_right)) => match (&lit_left.node, &lit_right.node) {
-            (LitKind::Bool(true), LitKind::Bool(false)) => Some(should_be_left),
-            (LitKind::Bool(false), LitKind::Bool(true)) => Some(should_be_right),
+fn bool_lit(expr: &Expr<'_>) -> Option<bool> {
+    let expr = remove_blocks(expr);
+    match expr.kind {
+        ExprKind::Lit(ref lit) if !expr.span.from_expansion() => match lit.node {
+            LitKind::Bool(value) => Some(value),
             _ => None,
         },
         _ => None,
     }
 }
+
+/// Returns a method like `is_some()` that tests for the variant matched by `pat`, if it matches
+/// all values of one variant and the enum has an inherent `is_<variant>(&self) -> bool` method
+/// that applies to the matched type. The applicability is `None` for `Option` and `Result`, for
+/// other enums we can only rely on the name of the method.
+fn variant_method(cx: &LateContext<'_, '_>, hir_id: HirId, pat: &Pat<'_>) -> Option<(String, Option<Applicability>)> {
+    let qpath = match pat.kind {
+        PatKind::TupleStruct(ref qpath, pats, _) if pats.iter().all(is_wild) => qpath,
+        PatKind::Struct(ref qpath, fields, _) if fields.iter().all(|field| is_wild(&field.pat)) => qpath,
+        PatKind::Path(ref qpath) => qpath,
+        _ => return None,
+    };
+    let ty = cx.tables.pat_ty(pat);
+    let adt = match ty.kind {
+        ty::Adt(adt, _) if adt.is_enum() => adt,
+        _ => return None,
+    };
+    let res = cx.tables.qpath_res(qpath, pat.hir_id);
+    if !matches!(
+        res,
+        Res::Def(DefKind::Ctor(CtorOf::Variant, _), _) | Res::Def(DefKind::Variant, _)
+    ) {
+        return None;
+    }
+    let name = format!("is_{}", to_snake_case(&adt.variant_of_res(res).ident.as_str()));
+
+    let map = cx.tcx.hir();
+    let module = map.local_def_id(map.get_module_parent_node(hir_id)).to_def_id();
+    // the method itself may be implemented with such a pattern
+    let enclosing = map.local_def_id(map.get_parent_item(hir_id)).to_def_id();
+    let has_method = cx
+        .tcx
+        .inherent_impls(adt.did)
+        .iter()
+        .filter(|&&imp| impl_applies_to(cx, imp, ty))
+        .flat_map(|&imp| cx.tcx.associated_items(imp).in_definition_order())
+        .any(|item| {
+            if item.kind != ty::AssocKind::Fn
+                || !item.fn_has_self_parameter
+                || item.ident.name.as_str() != name
+                || item.def_id == enclosing
+                || !cx.tcx.generics_of(item.def_id).params.is_empty()
+            {
+                return false;
+            }
+            let sig = cx.tcx.fn_sig(item.def_id);
+            let sig = sig.skip_binder();
+            sig.inputs().len() == 1
+                && matches!(sig.inputs()[0].kind, ty::Ref(_, _, Mutability::Not))
+                && sig.output().is_bool()
+                && cx.tcx.visibility(item.def_id).is_accessible_from(module, cx.tcx)
+        });
+    if !has_method {
+        return None;
+    }
+    let applicability =
+        if is_type_diagnostic_item(cx, ty, sym!(option_type)) || is_type_diagnostic_item(cx, ty, sym!(result_type)) {
+            None
+        } else {
+            Some(Applicability::MaybeIncorrect)
+        };
+    Some((format!("{}()", name), applicability))
+}
+
+/// Checks that the inherent impl `imp` applies to `ty`, either because it is implemented for
+/// exactly that type or because it is generic over all type parameters of the enum, each with its
+/// own parameter and without any bounds other than `Sized`.
+fn impl_applies_to<'tcx>(cx: &LateContext<'_, 'tcx>, imp: DefId, ty: Ty<'tcx>) -> bool {
+    let self_ty = cx.tcx.type_of(imp);
+    if cx.tcx.erase_regions(&self_ty) == cx.tcx.erase_regions(&ty) {
+        return true;
+    }
+    let substs = match self_ty.kind {
+        ty::Adt(_, substs) => substs,
+        _ => return false,
+    };
+    let sized_trait = cx.tcx.lang_items().sized_trait();
+    let mut params = FxHashSet::default();
+    substs
+        .types()
+        .all(|ty| matches!(ty.kind, ty::Param(param) if params.insert(param.index)))
+        && cx
+            .tcx
+            .predicates_of(imp)
+            .predicates
+            .iter()
+            .all(|(predicate, _)| match predicate {
+                ty::Predicate::Trait(poly_trait_predicate, _) => Some(poly_trait_predicate.def_id()) == sized_trait,
+                _ => false,
+            })
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.char_indices() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
@@ -0,0 +1,14 @@
+//! The table of renamed lints.
+//!
+//! Adding a line here is all that's needed to rename a lint: it's registered with rustc in
+//! `register_renamed`, used by `cargo clippy --migrate-lint-names` and listed on the lint list
+//! website. This file is also compiled into `cargo-clippy`, so it must not use anything else from
+//! `clippy_lints`.
+
+/// The renamed lints as `(old name, new name)`, in the order they were renamed.
+pub static RENAMED_LINTS: &[(&str, &str)] = &[
+    ("clippy::stutter", "clippy::module_name_repetitions"),
+    ("clippy::new_without_default_derive", "clippy::new_without_default"),
+    ("clippy::cyclomatic_complexity", "clippy::cognitive_complexity"),
+    ("clippy::const_static_lifetime", "clippy::redundant_static_lifetimes"),
+];
@@ -28,6 +28,7 @@ declare_clippy_lint! {
     /// let mut vec2 = Vec::with_capacity(len);
     /// vec2.extend(repeat(0).take(len))
     /// ```
+    #[clippy::version = "1.32.0"]
     pub SLOW_VECTOR_INITIALIZATION,
     perf,
     "slow vector initialization"
@@ -1,18 +1,21 @@
 #![allow(rustc::default_hash_types)]
 
 use std::borrow::Cow;
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
 use std::collections::BTreeMap;
 
 use if_chain::if_chain;
+use itertools::Itertools;
 use rustc_ast::ast::{FloatTy, IntTy, LitFloatType, LitIntType, LitKind, UintTy};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
-use rustc_hir::intravisit::{walk_body, walk_expr, walk_ty, FnKind, NestedVisitorMap, Visitor};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::intravisit::{walk_body, walk_expr, walk_generic_args, walk_ty, FnKind, NestedVisitorMap, Visitor};
 use rustc_hir::{
-    BinOpKind, Body, Expr, ExprKind, FnDecl, FnRetTy, FnSig, GenericArg, GenericParamKind, HirId, ImplItem,
-    ImplItemKind, Item, ItemKind, Lifetime, Local, MatchSource, MutTy, Mutability, QPath, Stmt, StmtKind, TraitFn,
-    TraitItem, TraitItemKind, TyKind, UnOp,
+    BinOpKind, Body, Expr, ExprKind, FnDecl, FnRetTy, FnSig, GenericArg, GenericArgs, GenericParamKind, HirId,
+    ImplItem, ImplItemKind, Item, ItemKind, Lifetime, LifetimeName, Local, MatchSource, Mod, MutTy, Mutability,
+    ParamName, QPath, Stmt, StmtKind, TraitFn, TraitItem, TraitItemKind, TyKind, UnOp,
 };
 use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_middle::hir::map::Map;
@@ -29,10 +32,11 @@ use rustc_typeck::hir_ty_to_ty;
 use crate::consts::{constant, Constant};
 use crate::utils::paths;
 use crate::utils::{
-    clip, comparisons, differing_macro_contexts, higher, in_constant, int_bits, is_type_diagnostic_item,
-    last_path_segment, match_def_path, match_path, method_chain_args, multispan_sugg, numeric_literal::NumericLiteral,
-    qpath_res, same_tys, sext, snippet, snippet_opt, snippet_with_applicability, snippet_with_macro_callsite,
-    span_lint, span_lint_and_help, span_lint_and_sugg, span_lint_and_then, unsext,
+    clip, comparisons, differing_macro_contexts, higher, in_constant, indent_of, int_bits, is_allowed,
+    is_type_diagnostic_item, last_path_segment, match_def_path, match_path, method_chain_args, multispan_sugg,
+    numeric_literal::NumericLiteral, qpath_res, same_tys, sext, snippet, snippet_opt, snippet_with_applicability,
+    snippet_with_macro_callsite, span_lint, span_lint_and_help, span_lint_and_sugg, span_lint_and_then,
+    span_lint_hir_and_then, unsext,
 };
 
 declare_clippy_lint! {
@@ -58,6 +62,7 @@ declare_clippy_lint! {
     ///     values: Vec<Foo>,
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub BOX_VEC,
     perf,
     "usage of `Box<Vec<T>>`, vector elements are already on the heap"
@@ -86,6 +91,7 @@ declare_clippy_lint! {
     ///     values: Vec<i32>,
     /// }
     /// ```
+    #[clippy::version = "1.33.0"]
     pub VEC_BOX,
     complexity,
     "usage of `Vec<Box<T>>` where T: Sized, vector elements are already on the heap"
@@ -124,6 +130,7 @@ declare_clippy_lint! {
     ///     Contents::None
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub OPTION_OPTION,
     pedantic,
     "usage of `Option<Option<T>>`"
@@ -160,6 +167,7 @@ declare_clippy_lint! {
     /// # use std::collections::LinkedList;
     /// let x: LinkedList<usize> = LinkedList::new();
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub LINKEDLIST,
     pedantic,
     "usage of LinkedList, usually a vector is faster, or a more specialized data structure like a `VecDeque`"
@@ -183,6 +191,7 @@ declare_clippy_lint! {
     /// ```rust,ignore
     /// fn foo(bar: &T) { ... }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub BORROWED_BOX,
     complexity,
     "a borrow of a boxed type"
@@ -207,6 +216,7 @@ declare_clippy_lint! {
     /// ```rust
     /// fn foo(bar: &usize) {}
     /// ```
+    #[clippy::version = "1.44.0"]
     pub REDUNDANT_ALLOCATION,
     perf,
     "redundant allocation"
@@ -326,14 +336,15 @@ impl Types {
                 if let Some(def_id) = res.opt_def_id() {
                     if Some(def_id) == cx.tcx.lang_items().owned_box() {
                         if let Some(span) = match_borrows_parameter(cx, qpath) {
+                            let mut applicability = Applicability::MachineApplicable;
                             span_lint_and_sugg(
                                 cx,
                                 REDUNDANT_ALLOCATION,
                                 hir_ty.span,
                                 "usage of `Box<&T>`",
                                 "try",
-                                snippet(cx, span, "..").to_string(),
-                                Applicability::MachineApplicable,
+                                snippet_with_applicability(cx, span, "..", &mut applicability).to_string(),
+                                applicability,
                             );
                             return; // don't recurse into the type
                         }
@@ -350,38 +361,41 @@ impl Types {
                         }
                     } else if cx.tcx.is_diagnostic_item(sym::Rc, def_id) {
                         if let Some(span) = match_type_parameter(cx, qpath, &paths::RC) {
+                            let mut applicability = Applicability::MachineApplicable;
                             span_lint_and_sugg(
                                 cx,
                                 REDUNDANT_ALLOCATION,
                                 hir_ty.span,
                                 "usage of `Rc<Rc<T>>`",
                                 "try",
-                                snippet(cx, span, "..").to_string(),
-                                Applicability::MachineApplicable,
+                                snippet_with_applicability(cx, span, "..", &mut applicability).to_string(),
+                                applicability,
                             );
                             return; // don't recurse into the type
                         }
                         if let Some(span) = match_type_parameter(cx, qpath, &paths::BOX) {
+                            let mut applicability = Applicability::MachineApplicable;
                             span_lint_and_sugg(
                                 cx,
                                 REDUNDANT_ALLOCATION,
                                 hir_ty.span,
                                 "usage of `Rc<Box<T>>`",
                                 "try",
-                                snippet(cx, span, "..").to_string(),
-                                Applicability::MachineApplicable,
+                                snippet_with_applicability(cx, span, "..", &mut applicability).to_string(),
+                                applicability,
                             );
                             return; // don't recurse into the type
                         }
                         if let Some(span) = match_borrows_parameter(cx, qpath) {
+                            let mut applicability = Applicability::MachineApplicable;
                             span_lint_and_sugg(
                                 cx,
                                 REDUNDANT_ALLOCATION,
                                 hir_ty.span,
                                 "usage of `Rc<&T>`",
                                 "try",
-                                snippet(cx, span, "..").to_string(),
-                                Applicability::MachineApplicable,
+                                snippet_with_applicability(cx, span, "..", &mut applicability).to_string(),
+                                applicability,
                             );
                             return; // don't recurse into the type
                         }
@@ -593,6 +607,7 @@ declare_clippy_lint! {
     ///     1;
     /// };
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub LET_UNIT_VALUE,
     pedantic,
     "creating a `let` binding to a value of unit type, which usually can't be used afterwards"
@@ -674,6 +689,7 @@ declare_clippy_lint! {
     /// assert_eq!({ foo(); }, { bar(); });
     /// ```
     /// will always succeed
+    #[clippy::version = "pre 1.29.0"]
     pub UNIT_CMP,
     correctness,
     "comparing unit values"
@@ -747,6 +763,7 @@ declare_clippy_lint! {
     ///     baz(a);
     /// })
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub UNIT_ARG,
     complexity,
     "passing unit to a function"
@@ -814,17 +831,11 @@ fn is_questionmark_desugar_marked_call(expr: &Expr<'_>) -> bool {
 }
 
 fn is_unit(ty: Ty<'_>) -> bool {
-    match ty.kind {
-        ty::Tuple(slice) if slice.is_empty() => true,
-        _ => false,
-    }
+    matches!(ty.kind, ty::Tuple(slice) if slice.is_empty())
 }
 
 fn is_unit_literal(expr: &Expr<'_>) -> bool {
-    match expr.kind {
-        ExprKind::Tup(ref slice) if slice.is_empty() => true,
-        _ => false,
-    }
+    matches!(expr.kind, ExprKind::Tup(ref slice) if slice.is_empty())
 }
 
 declare_clippy_lint! {
@@ -847,6 +858,7 @@ declare_clippy_lint! {
     /// let x = u64::MAX;
     /// x as f64;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CAST_PRECISION_LOSS,
     pedantic,
     "casts that cause loss of precision, e.g., `x as f32` where `x: u64`"
@@ -868,6 +880,7 @@ declare_clippy_lint! {
     /// let y: i8 = -1;
     /// y as u128; // will return 18446744073709551615
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CAST_SIGN_LOSS,
     pedantic,
     "casts from signed types to unsigned types, e.g., `x as u32` where `x: i32`"
@@ -890,6 +903,7 @@ declare_clippy_lint! {
     ///     x as u8
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CAST_POSSIBLE_TRUNCATION,
     pedantic,
     "casts that may cause truncation of the value, e.g., `x as u8` where `x: u32`, or `x as i32` where `x: f32`"
@@ -913,6 +927,7 @@ declare_clippy_lint! {
     /// ```rust
     /// u32::MAX as i32; // will yield a value of `-1`
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CAST_POSSIBLE_WRAP,
     pedantic,
     "casts that may cause wrapping around the value, e.g., `x as i32` where `x: u32` and `x > i32::MAX`"
@@ -945,6 +960,7 @@ declare_clippy_lint! {
     ///     u64::from(x)
     /// }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CAST_LOSSLESS,
     pedantic,
     "casts using `as` that are known to be lossless, e.g., `x as u64` where `x: u8`"
@@ -961,6 +977,7 @@ declare_clippy_lint! {
     /// ```rust
     /// let _ = 2i32 as i32;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub UNNECESSARY_CAST,
     complexity,
     "cast to the same type, e.g., `x as i32` where `x: i32`"
@@ -981,6 +998,7 @@ declare_clippy_lint! {
     /// let _ = (&1u8 as *const u8) as *const u16;
     /// let _ = (&mut 1u8 as *mut u8) as *mut u16;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CAST_PTR_ALIGNMENT,
     correctness,
     "cast from a pointer to a more-strictly-aligned pointer"
@@ -1008,6 +1026,7 @@ declare_clippy_lint! {
     /// fn fun2() -> i32 { 1 }
     /// let a = fun2 as usize;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub FN_TO_NUMERIC_CAST,
     style,
     "casting a function pointer to a numeric type other than usize"
@@ -1038,6 +1057,7 @@ declare_clippy_lint! {
     /// let fn_ptr = fn2 as usize;
     /// let fn_ptr_truncated = fn_ptr as i32;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub FN_TO_NUMERIC_CAST_WITH_TRUNCATION,
     style,
     "casting a function pointer to a numeric type not wide enough to store the address"
@@ -1068,10 +1088,7 @@ fn int_ty_to_nbits(typ: Ty<'_>, tcx: TyCtxt<'_>) -> u64 {
 }
 
 fn is_isize_or_usize(typ: Ty<'_>) -> bool {
-    match typ.kind {
-        ty::Int(IntTy::Isize) | ty::Uint(UintTy::Usize) => true,
-        _ => false,
-    }
+    matches!(typ.kind, ty::Int(IntTy::Isize) | ty::Uint(UintTy::Usize))
 }
 
 fn span_precision_loss_lint(cx: &LateContext<'_, '_>, expr: &Expr<'_>, cast_from: Ty<'_>, cast_to_f64: bool) {
@@ -1516,7 +1533,13 @@ declare_clippy_lint! {
     /// **Why is this bad?** Too complex types make the code less readable. Consider
     /// using a `type` definition to simplify them.
     ///
-    /// **Known problems:** None.
+    /// The lint suggests a `type` alias for a part of the type: the largest part that
+    /// occurs more than once in it, or else the most deeply nested part that makes the
+    /// type simple enough. Types in the same module that have the same part are
+    /// suggested to use the same alias.
+    ///
+    /// **Known problems:** The name of the suggested alias is made from the names of
+    /// the types in it, which isn't always a good name.
     ///
     /// **Example:**
     /// ```rust
@@ -1525,6 +1548,16 @@ declare_clippy_lint! {
     ///     inner: Rc<Vec<Vec<Box<(u32, u32, u32, u32)>>>>,
     /// }
     /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::rc::Rc;
+    /// type Items = Vec<Box<(u32, u32, u32, u32)>>;
+    ///
+    /// struct Foo {
+    ///     inner: Rc<Vec<Items>>,
+    /// }
+    /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub TYPE_COMPLEXITY,
     complexity,
     "usage of very complex types that might be better factored into `type` definitions"
@@ -1532,18 +1565,41 @@ declare_clippy_lint! {
 
 pub struct TypeComplexity {
     threshold: u64,
+    /// The complex types of the modules that are being checked, innermost last
+    modules: Vec<ModuleTypes>,
 }
 
 impl TypeComplexity {
     #[must_use]
     pub fn new(threshold: u64) -> Self {
-        Self { threshold }
+        Self {
+            threshold,
+            modules: Vec::new(),
+        }
     }
 }
 
 impl_lint_pass!(TypeComplexity => [TYPE_COMPLEXITY]);
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TypeComplexity {
+    fn check_mod(&mut self, cx: &LateContext<'a, 'tcx>, module: &'tcx Mod<'_>, _: Span, _: HirId) {
+        let item_names = module
+            .item_ids
+            .iter()
+            .map(|id| cx.tcx.hir().item(id.id).ident.to_string())
+            .collect();
+        self.modules.push(ModuleTypes {
+            item_names,
+            ..ModuleTypes::default()
+        });
+    }
+
+    fn check_mod_post(&mut self, cx: &LateContext<'a, 'tcx>, _: &'tcx Mod<'_>, _: Span, _: HirId) {
+        if let Some(module) = self.modules.pop() {
+            report_complex_types(cx, module);
+        }
+    }
+
     fn check_fn(
         &mut self,
         cx: &LateContext<'a, 'tcx>,
@@ -1551,28 +1607,47 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TypeComplexity {
         decl: &'tcx FnDecl<'_>,
         _: &'tcx Body<'_>,
         _: Span,
-        _: HirId,
+        hir_id: HirId,
     ) {
-        self.check_fndecl(cx, decl);
+        self.check_fndecl(cx, decl, hir_id);
     }
 
     fn check_struct_field(&mut self, cx: &LateContext<'a, 'tcx>, field: &'tcx hir::StructField<'_>) {
         // enum variants are also struct fields now
-        self.check_type(cx, &field.ty);
+        self.check_type(cx, &field.ty, field.hir_id);
     }
 
     fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx Item<'_>) {
+        // modules get their own `ModuleTypes`
+        if !matches!(item.kind, ItemKind::Mod(_)) {
+            if let Some(module) = self.modules.last_mut() {
+                if module.item_depth == 0 {
+                    module.alias_pos = Some(alias_pos(item));
+                }
+                module.item_depth += 1;
+            }
+        }
         match item.kind {
-            ItemKind::Static(ref ty, _, _) | ItemKind::Const(ref ty, _) => self.check_type(cx, ty),
+            ItemKind::Static(ref ty, _, _) | ItemKind::Const(ref ty, _) => self.check_type(cx, ty, item.hir_id),
             // functions, enums, structs, impls and traits are covered
             _ => (),
         }
     }
 
+    fn check_item_post(&mut self, _: &LateContext<'a, 'tcx>, item: &'tcx Item<'_>) {
+        if !matches!(item.kind, ItemKind::Mod(_)) {
+            if let Some(module) = self.modules.last_mut() {
+                module.item_depth -= 1;
+            }
+        }
+    }
+
     fn check_trait_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx TraitItem<'_>) {
         match item.kind {
-            TraitItemKind::Const(ref ty, _) | TraitItemKind::Type(_, Some(ref ty)) => self.check_type(cx, ty),
-            TraitItemKind::Fn(FnSig { ref decl, .. }, TraitFn::Required(_)) => self.check_fndecl(cx, decl),
+            TraitItemKind::Const(ref ty, _) | TraitItemKind::Type(_, Some(ref ty)) => {
+                self.check_type(cx, ty, item.hir_id)
+            },
+            TraitItemKind::Fn(FnSig { ref decl, .. }, TraitFn::Required(_)) => self.check_fndecl(cx, decl, item.hir_id),
             // methods with default impl are covered by check_fn
             _ => (),
         }
@@ -1580,7 +1655,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TypeComplexity {
 
     fn check_impl_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx ImplItem<'_>) {
         match item.kind {
-            ImplItemKind::Const(ref ty, _) | ImplItemKind::TyAlias(ref ty) => self.check_type(cx, ty),
+            ImplItemKind::Const(ref ty, _) | ImplItemKind::TyAlias(ref ty) => self.check_type(cx, ty, item.hir_id),
             // methods are covered by check_fn
             _ => (),
         }
@@ -1588,48 +1663,273 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TypeComplexity {
 
     fn check_local(&mut self, cx: &LateContext<'a, 'tcx>, local: &'tcx Local<'_>) {
         if let Some(ref ty) = local.ty {
-            self.check_type(cx, ty);
+            self.check_type(cx, ty, local.hir_id);
         }
     }
 }
 
 impl<'a, 'tcx> TypeComplexity {
-    fn check_fndecl(&self, cx: &LateContext<'a, 'tcx>, decl: &'tcx FnDecl<'_>) {
+    fn check_fndecl(&mut self, cx: &LateContext<'a, 'tcx>, decl: &'tcx FnDecl<'_>, hir_id: HirId) {
         for arg in decl.inputs {
-            self.check_type(cx, arg);
+            self.check_type(cx, arg, hir_id);
         }
         if let FnRetTy::Return(ref ty) = decl.output {
-            self.check_type(cx, ty);
+            self.check_type(cx, ty, hir_id);
         }
     }
 
-    fn check_type(&self, cx: &LateContext<'_, '_>, ty: &hir::Ty<'_>) {
+    fn check_type(&mut self, cx: &LateContext<'_, '_>, ty: &hir::Ty<'_>, hir_id: HirId) {
         if ty.span.from_expansion() {
             return;
         }
-        let score = {
-            let mut visitor = TypeComplexityVisitor { score: 0, nest: 1 };
-            visitor.visit_ty(ty);
-            visitor.score
+        let mut visitor = TypeComplexityVisitor {
+            score: 0,
+            nest: 1,
+            parts: Vec::new(),
+            current: Vec::new(),
+            fn_args: Vec::new(),
         };
+        visitor.visit_ty(ty);
+
+        if visitor.score > self.threshold {
+            if let Some(module) = self.modules.last_mut() {
+                module.types.push(ComplexType {
+                    hir_id,
+                    span: ty.span,
+                    alias_pos: module.alias_pos,
+                    part: aliased_part(cx, &visitor.parts, self.threshold),
+                });
+            }
+        }
+    }
+}
 
-        if score > self.threshold {
-            span_lint(
-                cx,
-                TYPE_COMPLEXITY,
-                ty.span,
-                "very complex type used. Consider factoring parts into `type` definitions",
-            );
+/// The complex types of a module. They are reported after the whole module is checked, so that
+/// all uses of the same type get the same type alias.
+#[derive(Default)]
+struct ModuleTypes {
+    /// The names of the items of the module, a new type alias can't have any of them
+    item_names: FxHashSet<String>,
+    /// How deep the current item is nested in the items of the module
+    item_depth: usize,
+    /// Where a type alias can be added in front of the item of the module that is checked
+    alias_pos: Option<Span>,
+    types: Vec<ComplexType>,
+}
+
+struct ComplexType {
+    hir_id: HirId,
+    span: Span,
+    alias_pos: Option<Span>,
+    part: AliasedPart,
+}
+
+/// The part of a complex type that is best moved to a type alias.
+struct AliasedPart {
+    /// The code of the part, the same code in another type can use the same alias
+    code: String,
+    /// Where the part occurs in the type
+    spans: Vec<Span>,
+    /// The name of the alias if it is the first one with that name
+    name: String,
+    /// The lifetimes and type parameters the alias needs, `None` if the part can't be written in a
+    /// type alias because it has `Self`, `_` or elided lifetimes
+    generics: Option<Vec<String>>,
+}
+
+/// Returns where a type alias can be added in front of `item`.
+fn alias_pos(item: &Item<'_>) -> Span {
+    item.attrs
+        .iter()
+        .map(|attr| attr.span)
+        .filter(|span| !span.from_expansion())
+        .fold(item.span, |pos, span| if span.lo() < pos.lo() { span } else { pos })
+        .shrink_to_lo()
+}
+
+/// Picks the part of a complex type to move to a type alias: the largest part that occurs more
+/// than once, otherwise the most deeply nested part that makes the type simple enough, otherwise
+/// the part that removes most of the complexity. The type itself is picked if it has no parts
+/// that are made of other types.
+fn aliased_part(cx: &LateContext<'_, '_>, parts: &[TypePart], threshold: u64) -> AliasedPart {
+    let code_of = |part: &TypePart| snippet(cx, part.span, "..").split_whitespace().join(" ");
+    let candidates = parts
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, part)| part.compound && !part.span.from_expansion())
+        .map(|(i, part)| (i, code_of(part)))
+        .collect::<Vec<_>>();
+    let total = parts[0].score;
+    // replacing a part with an alias leaves a simple path at the same nesting level
+    let simplified = |i: usize| total - parts[i].score + 10 * parts[i].nest;
+
+    let repeated = candidates
+        .iter()
+        .filter(|(_, code)| candidates.iter().filter(|(_, other)| other == code).count() > 1)
+        .max_by_key(|(i, _)| (parts[*i].score, Reverse(*i)));
+    let deepest = || {
+        candidates
+            .iter()
+            .filter(|(i, _)| simplified(*i) <= threshold)
+            .max_by_key(|(i, _)| (parts[*i].depth, Reverse(*i)))
+    };
+    let largest = || {
+        candidates
+            .iter()
+            .max_by_key(|(i, _)| (parts[*i].score.saturating_sub(10 * parts[*i].nest), Reverse(*i)))
+    };
+    let (index, code) = match repeated.or_else(deepest).or_else(largest) {
+        Some((i, code)) => (*i, code.clone()),
+        None => (0, code_of(&parts[0])),
+    };
+
+    let spans = if index == 0 {
+        vec![parts[0].span]
+    } else {
+        candidates
+            .iter()
+            .filter(|(_, other)| *other == code)
+            .map(|(i, _)| parts[*i].span)
+            .collect()
+    };
+    let in_part = &parts[index..parts[index].end];
+    let mut name = in_part
+        .iter()
+        .filter_map(|part| part.name.as_deref())
+        .dedup()
+        .take(3)
+        .collect::<String>();
+    if name.is_empty() {
+        name = "ComplexType".to_string();
+    }
+    let generics = if in_part.iter().any(|part| part.unnameable) {
+        None
+    } else {
+        let generics = in_part.iter().flat_map(|part| &part.generics).unique();
+        let (lifetimes, params): (Vec<_>, Vec<_>) = generics.cloned().partition(|generic| generic.starts_with('\''));
+        Some(lifetimes.into_iter().chain(params).collect())
+    };
+    AliasedPart {
+        code,
+        spans,
+        name,
+        generics,
+    }
+}
+
+fn report_complex_types(cx: &LateContext<'_, '_>, module: ModuleTypes) {
+    let mut names = module.item_names;
+    // the code of the aliased parts and the names of their aliases
+    let mut aliases = FxHashMap::default();
+    for ty in module.types {
+        if is_allowed(cx, TYPE_COMPLEXITY, ty.hir_id) {
+            continue;
         }
+        let part = &ty.part;
+        let alias = match (&part.generics, ty.alias_pos) {
+            (Some(generics), Some(pos)) => {
+                let params = if generics.is_empty() {
+                    String::new()
+                } else {
+                    format!("<{}>", generics.join(", "))
+                };
+                let existing = aliases.get(&part.code).cloned();
+                let name = existing.clone().unwrap_or_else(|| {
+                    let name = unused_name(&mut names, &part.name);
+                    aliases.insert(part.code.clone(), name.clone());
+                    name
+                });
+                Some((format!("{}{}", name, params), existing.is_none(), pos))
+            },
+            _ => None,
+        };
+        span_lint_hir_and_then(
+            cx,
+            TYPE_COMPLEXITY,
+            ty.hir_id,
+            ty.span,
+            "very complex type used. Consider factoring parts into `type` definitions",
+            |diag| {
+                let (alias, is_first, pos) = match alias {
+                    Some(alias) => alias,
+                    None => {
+                        diag.span_help(part.spans[0], "consider factoring out this part into a type alias");
+                        return;
+                    },
+                };
+                let mut sugg = part.spans.iter().map(|span| (*span, alias.clone())).collect::<Vec<_>>();
+                if is_first {
+                    let indent = indent_of(cx, pos).unwrap_or(0);
+                    let def = format!("type {} = {};\n{}", alias, part.code, " ".repeat(indent));
+                    sugg.insert(0, (pos, def));
+                    diag.multipart_suggestion(
+                        &format!("consider factoring out `{}` into a type alias", part.code),
+                        sugg,
+                        Applicability::MaybeIncorrect,
+                    );
+                } else {
+                    diag.multipart_suggestion(
+                        "use the same type alias as the first occurrence of this type",
+                        sugg,
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+            },
+        );
     }
 }
 
+/// Returns `name`, or `name` with a number if it is used, and marks it as used.
+fn unused_name(names: &mut FxHashSet<String>, name: &str) -> String {
+    let name = (1..)
+        .map(|i| {
+            if i == 1 {
+                name.to_string()
+            } else {
+                format!("{}{}", name, i)
+            }
+        })
+        .find(|name| !names.contains(name))
+        .unwrap();
+    names.insert(name.clone());
+    name
+}
+
+/// A type in a checked type, that is the checked type itself or one of its parts, in the order
+/// they are visited.
+struct TypePart {
+    span: Span,
+    /// The complexity score of the type, including its parts
+    score: u64,
+    /// The nesting level the type is at
+    nest: u64,
+    /// How many types it is part of
+    depth: usize,
+    /// The index after its last part
+    end: usize,
+    /// Whether the type is made of other types, only those are worth a type alias
+    compound: bool,
+    /// What a type alias containing the type is named after, e.g. `Vec` for `Vec<u8>`
+    name: Option<String>,
+    /// The lifetimes and type parameters the type uses directly
+    generics: Vec<String>,
+    /// Whether the type can't be written in a type alias, like `Self` and `_`
+    unnameable: bool,
+}
+
 /// Walks a type and assigns a complexity score to it.
 struct TypeComplexityVisitor {
     /// total complexity score of the type
     score: u64,
     /// current nesting level
     nest: u64,
+    /// the type and its parts
+    parts: Vec<TypePart>,
+    /// the indices of the parts the visitor is in
+    current: Vec<usize>,
+    /// the argument tuples of `Fn(..)` bounds, which can't be replaced with an alias
+    fn_args: Vec<HirId>,
 }
 
 impl<'tcx> Visitor<'tcx> for TypeComplexityVisitor {
@@ -1648,10 +1948,10 @@ impl<'tcx> Visitor<'tcx> for TypeComplexityVisitor {
 
             TyKind::TraitObject(ref param_bounds, _) => {
                 let has_lifetime_parameters = param_bounds.iter().any(|bound| {
-                    bound.bound_generic_params.iter().any(|gen| match gen.kind {
-                        GenericParamKind::Lifetime { .. } => true,
-                        _ => false,
-                    })
+                    bound
+                        .bound_generic_params
+                        .iter()
+                        .any(|gen| matches!(gen.kind, GenericParamKind::Lifetime { .. }))
                 });
                 if has_lifetime_parameters {
                     // complex trait bounds like A<'a, 'b>
@@ -1664,16 +1964,107 @@ impl<'tcx> Visitor<'tcx> for TypeComplexityVisitor {
 
             _ => (0, 0),
         };
+        let index = self.parts.len();
+        let fn_args = self.fn_args.contains(&ty.hir_id);
+        self.parts.push(TypePart {
+            span: ty.span,
+            score: 0,
+            nest: self.nest,
+            depth: self.current.len(),
+            end: 0,
+            compound: !fn_args && is_compound(ty),
+            name: if fn_args { None } else { alias_name_part(ty) },
+            generics: Vec::new(),
+            unnameable: matches!(ty.kind, TyKind::Infer | TyKind::Def(..)),
+        });
+        if let TyKind::Path(QPath::Resolved(None, ref path)) = ty.kind {
+            match path.res {
+                Res::Def(DefKind::TyParam, _) if !path.segments[0].ident.as_str().contains(' ') => {
+                    self.parts[index].generics.push(path.segments[0].ident.to_string());
+                },
+                // `impl Trait` arguments are type parameters as well
+                Res::Def(DefKind::TyParam, _) | Res::SelfTy(..) => self.parts[index].unnameable = true,
+                _ => {},
+            }
+        }
+
+        let score = self.score;
         self.score += add_score;
         self.nest += sub_nest;
+        self.current.push(index);
         walk_ty(self, ty);
+        self.current.pop();
         self.nest -= sub_nest;
+        self.parts[index].score = self.score - score;
+        self.parts[index].end = self.parts.len();
+    }
+
+    fn visit_generic_args(&mut self, span: Span, args: &'tcx GenericArgs<'tcx>) {
+        if args.parenthesized {
+            self.fn_args.extend(args.args.iter().filter_map(|arg| match arg {
+                GenericArg::Type(ty) => Some(ty.hir_id),
+                _ => None,
+            }));
+        }
+        walk_generic_args(self, span, args);
     }
+
+    fn visit_lifetime(&mut self, lifetime: &'tcx Lifetime) {
+        if let Some(&index) = self.current.last() {
+            let part = &mut self.parts[index];
+            match lifetime.name {
+                LifetimeName::Param(ParamName::Plain(ident)) => part.generics.push(ident.to_string()),
+                LifetimeName::Static | LifetimeName::ImplicitObjectLifetimeDefault => {},
+                _ => part.unnameable = true,
+            }
+        }
+    }
+
     fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
         NestedVisitorMap::None
     }
 }
 
+/// Whether the type is made of other types.
+fn is_compound(ty: &hir::Ty<'_>) -> bool {
+    match ty.kind {
+        TyKind::Path(QPath::Resolved(_, ref path)) => path.segments.iter().any(|segment| {
+            segment
+                .args
+                .map_or(false, |args| !args.args.is_empty() || !args.bindings.is_empty())
+        }),
+        TyKind::Tup(tys) => !tys.is_empty(),
+        TyKind::Slice(..) | TyKind::Array(..) | TyKind::BareFn(..) | TyKind::TraitObject(..) => true,
+        _ => false,
+    }
+}
+
+/// Returns what a type alias containing the type is named after, e.g. `Vec` for `Vec<u8>` and
+/// `Pair` for a tuple of two types.
+fn alias_name_part(ty: &hir::Ty<'_>) -> Option<String> {
+    let name = match ty.kind {
+        TyKind::Path(QPath::Resolved(None, ref path))
+            if matches!(path.res, Res::Def(DefKind::TyParam, _) | Res::SelfTy(..)) =>
+        {
+            return None;
+        },
+        TyKind::Path(ref qpath) => last_path_segment(qpath).ident.to_string(),
+        TyKind::TraitObject(ref bounds, _) => bounds.first()?.trait_ref.path.segments.last()?.ident.to_string(),
+        TyKind::Tup(tys) => match tys.len() {
+            2 => "Pair".to_string(),
+            3 => "Triple".to_string(),
+            _ => "Tuple".to_string(),
+        },
+        TyKind::Slice(..) => "Slice".to_string(),
+        TyKind::Array(..) => "Array".to_string(),
+        TyKind::BareFn(..) => "Fn".to_string(),
+        _ => return None,
+    };
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
 declare_clippy_lint! {
     /// **What it does:** Checks for expressions where a character literal is cast
     /// to `u8` and suggests using a byte literal instead.
@@ -1696,6 +2087,7 @@ declare_clippy_lint! {
     /// ```rust,ignore
     /// b'x'
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub CHAR_LIT_AS_U8,
     complexity,
     "casting a character literal to `u8` truncates"
@@ -1761,6 +2153,7 @@ declare_clippy_lint! {
     /// if vec.len() <= 0 {}
     /// if 100 > i32::MAX {}
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub ABSURD_EXTREME_COMPARISONS,
     correctness,
     "a comparison with a maximum or minimum value that is always true or false"
@@ -1926,6 +2319,7 @@ declare_clippy_lint! {
     /// let x: u8 = 1;
     /// (x as u32) > 300;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub INVALID_UPCAST_COMPARISONS,
     pedantic,
     "a comparison involving an upcast which is always true or false"
@@ -2176,6 +2570,7 @@ declare_clippy_lint! {
     ///
     /// pub fn foo<S: BuildHasher>(map: &mut HashMap<i32, i32, S>) { }
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub IMPLICIT_HASHER,
     pedantic,
     "missing generalization over different hashers"
@@ -2533,6 +2928,7 @@ declare_clippy_lint! {
     ///     }
     /// }
     /// ```
+    #[clippy::version = "1.33.0"]
     pub CAST_REF_TO_MUT,
     correctness,
     "a cast of reference to a mutable pointer"
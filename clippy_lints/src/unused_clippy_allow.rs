@@ -0,0 +1,177 @@
+use crate::utils::sugg::DiagnosticBuilderExt;
+use crate::utils::{in_macro, is_allow_used, snippet_opt, span_lint_hir_and_then};
+use rustc_ast::ast::Attribute;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Applicability;
+use rustc_hir::{Crate, HirId};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::source_map::Span;
+use rustc_span::BytePos;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for Clippy lints in `allow` attributes that don't
+    /// suppress any warning.
+    ///
+    /// **Why is this bad?** The `allow` is usually left over from code that has
+    /// changed since. It clutters the code, and it hides the lint if the code it
+    /// covers gets a real warning later on.
+    ///
+    /// **Known problems:** Only the warnings of the current build count. An `allow`
+    /// that is needed for another target, set of features or `clippy.toml` is
+    /// reported as well. Some lints check whether they are allowed before doing any
+    /// work, the `allow`s of those always count as used. Lints loaded with
+    /// `--lint-lib` are not checked.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// #[allow(clippy::needless_return)]
+    /// fn one() -> u32 {
+    ///     1
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// fn one() -> u32 {
+    ///     1
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub UNUSED_CLIPPY_ALLOW,
+    restriction,
+    "`allow` attributes for Clippy lints that don't suppress any warning"
+}
+
+/// An `allow` attribute with at least one Clippy lint.
+struct AllowAttr {
+    /// The node the attribute belongs to.
+    hir_id: HirId,
+    span: Span,
+    /// The lints in the attribute, with the name of the ones that are checked. Lints of rustc and
+    /// `reason = ".."` are left out.
+    lints: Vec<(Span, Option<String>)>,
+}
+
+pub struct UnusedClippyAllow {
+    /// The lowercase names of the lints declared by Clippy itself. The lints of `--lint-lib`s are
+    /// emitted by passes that run after this one, so their `allow`s are not checked.
+    clippy_lints: FxHashSet<String>,
+    allows: Vec<AllowAttr>,
+}
+
+impl UnusedClippyAllow {
+    pub fn new(clippy_lints: FxHashSet<String>) -> Self {
+        Self {
+            clippy_lints,
+            allows: Vec::new(),
+        }
+    }
+}
+
+impl_lint_pass!(UnusedClippyAllow => [UNUSED_CLIPPY_ALLOW]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedClippyAllow {
+    fn check_attribute(&mut self, cx: &LateContext<'a, 'tcx>, attr: &'tcx Attribute) {
+        if in_macro(attr.span) || !attr.check_name(sym!(allow)) {
+            return;
+        }
+        let items = match attr.meta_item_list() {
+            Some(items) => items,
+            None => return,
+        };
+        let lints = items
+            .iter()
+            .filter_map(|item| item.meta_item().filter(|meta_item| meta_item.is_word()))
+            .map(|meta_item| {
+                let segments = &meta_item.path.segments;
+                let name = if segments.len() == 2
+                    && segments[0].ident.name == sym!(clippy)
+                    && segments[1].ident.name != sym!(unused_clippy_allow)
+                {
+                    Some(format!("clippy::{}", segments[1].ident))
+                } else {
+                    None
+                };
+                (meta_item.span, name.filter(|name| self.clippy_lints.contains(name)))
+            })
+            .collect::<Vec<_>>();
+        if lints.iter().any(|(_, name)| name.is_some()) {
+            self.allows.push(AllowAttr {
+                hir_id: cx.last_node_with_lint_attrs,
+                span: attr.span,
+                lints,
+            });
+        }
+    }
+
+    // The pass is registered after all other Clippy passes, so every Clippy lint has been emitted
+    // when this runs.
+    fn check_crate_post(&mut self, cx: &LateContext<'a, 'tcx>, _: &'tcx Crate<'_>) {
+        for allow in &self.allows {
+            let unused = allow
+                .lints
+                .iter()
+                .map(|(span, name)| name.is_some() && !is_allow_used(*span))
+                .collect::<Vec<_>>();
+            let unused_names = allow
+                .lints
+                .iter()
+                .zip(&unused)
+                .filter(|(_, unused)| **unused)
+                .filter_map(|((_, name), _)| name.as_ref())
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>();
+            if unused_names.is_empty() {
+                continue;
+            }
+
+            let msg = format!(
+                "this `allow` doesn't suppress any warning of {}",
+                unused_names.join(", ")
+            );
+            span_lint_hir_and_then(cx, UNUSED_CLIPPY_ALLOW, allow.hir_id, allow.span, &msg, |diag| {
+                if unused.iter().all(|unused| *unused) {
+                    diag.suggest_remove_item(cx, allow.span, "remove the attribute", Applicability::MachineApplicable);
+                } else if let Some(sugg) = without_unused_lints(cx, allow, &unused) {
+                    diag.span_suggestion(
+                        allow.span,
+                        "remove the unused lints",
+                        sugg,
+                        Applicability::MachineApplicable,
+                    );
+                }
+            });
+        }
+    }
+}
+
+/// Returns the code of the attribute without the unused lints and their commas. At least one
+/// lint is kept.
+fn without_unused_lints(cx: &LateContext<'_, '_>, allow: &AllowAttr, unused: &[bool]) -> Option<String> {
+    let mut attr = snippet_opt(cx, allow.span)?;
+    let lints = &allow.lints;
+    let offset = |pos: BytePos| (pos - allow.span.lo()).to_usize();
+    let mut removals = Vec::new();
+    let mut i = 0;
+    while i < lints.len() {
+        if !unused[i] {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < lints.len() && unused[i] {
+            i += 1;
+        }
+        removals.push(if i < lints.len() {
+            // `a, b, kept` -> `kept`
+            offset(lints[start].0.lo())..offset(lints[i].0.lo())
+        } else {
+            // `kept, a, b` -> `kept`
+            offset(lints[start - 1].0.hi())..offset(lints[i - 1].0.hi())
+        });
+    }
+    for range in removals.into_iter().rev() {
+        attr.replace_range(range, "");
+    }
+    Some(attr)
+}
@@ -2,13 +2,14 @@
 //! to generate a clippy lint detecting said code automatically.
 
 use crate::utils::{get_attr, higher};
-use rustc_ast::ast::{Attribute, LitFloatType, LitKind};
+use rustc_ast::ast::{self, Attribute, LitFloatType, LitKind};
+use rustc_ast::visit::Visitor as AstVisitor;
 use rustc_ast::walk_list;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir as hir;
 use rustc_hir::intravisit::{NestedVisitorMap, Visitor};
 use rustc_hir::{BindingAnnotation, Block, Expr, ExprKind, Pat, PatKind, QPath, Stmt, StmtKind, TyKind};
-use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintContext};
 use rustc_middle::hir::map::Map;
 use rustc_session::Session;
 use rustc_session::{declare_lint_pass, declare_tool_lint};
@@ -16,6 +17,14 @@ use rustc_session::{declare_lint_pass, declare_tool_lint};
 declare_clippy_lint! {
     /// **What it does:** Generates clippy code that detects the offending pattern
     ///
+    /// The value of the attribute selects what is generated:
+    ///
+    /// * `#[clippy::author]`: an `if_chain!` matching the HIR, for late lint passes
+    /// * `#[clippy::author = "early"]`: an `if_chain!` matching the AST, for early lint passes
+    /// * `#[clippy::author = "skeleton"]`, `#[clippy::author = "early_skeleton"]`: a complete lint
+    ///   file with `declare_clippy_lint!`, the lint pass and the `check_*` method, pre-filled with the
+    ///   HIR or AST matcher respectively
+    ///
     /// **Example:**
     /// ```rust,ignore
     /// // ./tests/ui/my_lint.rs
@@ -46,138 +55,352 @@ declare_clippy_lint! {
     ///     }
     /// }
     /// ```
+    #[clippy::version = "1.29.0"]
     pub LINT_AUTHOR,
     internal_warn,
     "helper for writing lints"
 }
 
 declare_lint_pass!(Author => [LINT_AUTHOR]);
+declare_lint_pass!(EarlyAuthor => [LINT_AUTHOR]);
+
+/// Like `print!`, but appends to the output buffer of a print visitor.
+macro_rules! out {
+    ($v:expr, $($arg:tt)*) => {
+        $v.out.push_str(&format!($($arg)*))
+    };
+}
+
+/// Like `println!`, but appends to the output buffer of a print visitor.
+macro_rules! outln {
+    ($v:expr, $($arg:tt)*) => {{
+        out!($v, $($arg)*);
+        $v.out.push('\n');
+    }};
+}
 
-fn prelude() {
-    println!("if_chain! {{");
+/// What `#[clippy::author]` generates, selected by the value of the attribute.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// `#[clippy::author]` or `#[clippy::author = "late"]`
+    Late,
+    /// `#[clippy::author = "early"]`
+    Early,
+    /// `#[clippy::author = "skeleton"]`
+    LateSkeleton,
+    /// `#[clippy::author = "early_skeleton"]`
+    EarlySkeleton,
 }
 
-fn done() {
-    println!("    then {{");
-    println!("        // report your lint here");
+impl Mode {
+    fn from_value(value: Option<&str>) -> Option<Self> {
+        match value {
+            None | Some("late") => Some(Self::Late),
+            Some("early") => Some(Self::Early),
+            Some("skeleton") => Some(Self::LateSkeleton),
+            Some("early_skeleton") => Some(Self::EarlySkeleton),
+            _ => None,
+        }
+    }
+
+    fn is_early(self) -> bool {
+        self == Self::Early || self == Self::EarlySkeleton
+    }
+
+    fn is_skeleton(self) -> bool {
+        self == Self::LateSkeleton || self == Self::EarlySkeleton
+    }
+}
+
+/// The lint pass method a generated matcher belongs in.
+struct Hook {
+    /// The name of the method, e.g. `check_expr`
+    method: &'static str,
+    /// The name of the node parameter, this is what the matcher starts destructuring
+    param: &'static str,
+    /// The type of the node, without the reference
+    ty: &'static str,
+}
+
+const fn hook(method: &'static str, param: &'static str, ty: &'static str) -> Hook {
+    Hook { method, param, ty }
+}
+
+/// Prints the code generated for a node in the given mode.
+fn emit(mode: Mode, hook: &Hook, matcher: &str) {
+    if !mode.is_skeleton() {
+        print!("{}", if_chain(matcher, "// report your lint here"));
+        return;
+    }
+
+    let (uses, impl_header, signature) = if mode.is_early() {
+        (
+            "use crate::utils::{match_path_ast, span_lint};\n\
+             use if_chain::if_chain;\n\
+             use rustc_ast::ast::*;\n\
+             use rustc_lint::{EarlyContext, EarlyLintPass};",
+            "impl EarlyLintPass for NewLint {",
+            format!(
+                "fn {}(&mut self, cx: &EarlyContext<'_>, {}: &{}) {{",
+                hook.method, hook.param, hook.ty
+            ),
+        )
+    } else {
+        (
+            "use crate::utils::{higher, match_qpath, span_lint};\n\
+             use if_chain::if_chain;\n\
+             use rustc_ast::ast::{LitFloatType, LitKind};\n\
+             use rustc_hir::*;\n\
+             use rustc_lint::{LateContext, LateLintPass};",
+            "impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NewLint {",
+            format!(
+                "fn {}(&mut self, cx: &LateContext<'a, 'tcx>, {}: &'tcx {}) {{",
+                hook.method, hook.param, hook.ty
+            ),
+        )
+    };
+    let report = format!(
+        "span_lint(cx, NEW_LINT, {}.span, \"default lint description\");",
+        hook.param
+    );
+
+    println!("{}", uses);
+    println!("use rustc_session::{{declare_lint_pass, declare_tool_lint}};");
+    println!();
+    println!("declare_clippy_lint! {{");
+    println!("    /// **What it does:**");
+    println!("    ///");
+    println!("    /// **Why is this bad?**");
+    println!("    ///");
+    println!("    /// **Known problems:** None.");
+    println!("    ///");
+    println!("    /// **Example:**");
+    println!("    ///");
+    println!("    /// ```rust");
+    println!("    /// // example code where clippy issues a warning");
+    println!("    /// ```");
+    println!("    /// Use instead:");
+    println!("    /// ```rust");
+    println!("    /// // example code which does not raise clippy warning");
+    println!("    /// ```");
+    println!("    #[clippy::version = \"{}\"]", stabilization_version());
+    println!("    pub NEW_LINT,");
+    println!("    nursery,");
+    println!("    \"default lint description\"");
+    println!("}}");
+    println!();
+    println!("declare_lint_pass!(NewLint => [NEW_LINT]);");
+    println!();
+    println!("{}", impl_header);
+    println!("    {}", signature);
+    for line in if_chain(matcher, &report).lines() {
+        println!("        {}", line);
+    }
     println!("    }}");
     println!("}}");
 }
 
+/// Returns the version of the running rustc, which ships the new lint first, e.g. `1.45.0` for
+/// `1.45.0-nightly`.
+fn stabilization_version() -> &'static str {
+    rustc_interface::util::version_str()
+        .and_then(|version| version.split(|c: char| c == '-' || c == ' ').next())
+        .unwrap_or("<version>")
+}
+
+/// Wraps the conditions of a matcher into an `if_chain!`.
+fn if_chain(matcher: &str, then: &str) -> String {
+    format!("if_chain! {{\n{}    then {{\n        {}\n    }}\n}}\n", matcher, then)
+}
+
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Author {
     fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::Item<'_>) {
-        if !has_attr(cx.sess(), &item.attrs) {
-            return;
+        if let Some(mode) = late_mode(cx.sess(), &item.attrs) {
+            let mut v = PrintVisitor::new("item");
+            v.visit_item(item);
+            emit(mode, &hook("check_item", "item", "Item<'_>"), &v.out);
         }
-        prelude();
-        PrintVisitor::new("item").visit_item(item);
-        done();
     }
 
     fn check_impl_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::ImplItem<'_>) {
-        if !has_attr(cx.sess(), &item.attrs) {
-            return;
+        if let Some(mode) = late_mode(cx.sess(), &item.attrs) {
+            let mut v = PrintVisitor::new("item");
+            v.visit_impl_item(item);
+            emit(mode, &hook("check_impl_item", "item", "ImplItem<'_>"), &v.out);
         }
-        prelude();
-        PrintVisitor::new("item").visit_impl_item(item);
-        done();
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::TraitItem<'_>) {
-        if !has_attr(cx.sess(), &item.attrs) {
-            return;
+        if let Some(mode) = late_mode(cx.sess(), &item.attrs) {
+            let mut v = PrintVisitor::new("item");
+            v.visit_trait_item(item);
+            emit(mode, &hook("check_trait_item", "item", "TraitItem<'_>"), &v.out);
         }
-        prelude();
-        PrintVisitor::new("item").visit_trait_item(item);
-        done();
     }
 
     fn check_variant(&mut self, cx: &LateContext<'a, 'tcx>, var: &'tcx hir::Variant<'_>) {
-        if !has_attr(cx.sess(), &var.attrs) {
-            return;
+        if let Some(mode) = late_mode(cx.sess(), &var.attrs) {
+            let mut v = PrintVisitor::new("var");
+            let parent_hir_id = cx.tcx.hir().get_parent_node(var.id);
+            v.visit_variant(var, &hir::Generics::empty(), parent_hir_id);
+            emit(mode, &hook("check_variant", "var", "Variant<'_>"), &v.out);
         }
-        prelude();
-        let parent_hir_id = cx.tcx.hir().get_parent_node(var.id);
-        PrintVisitor::new("var").visit_variant(var, &hir::Generics::empty(), parent_hir_id);
-        done();
     }
 
     fn check_struct_field(&mut self, cx: &LateContext<'a, 'tcx>, field: &'tcx hir::StructField<'_>) {
-        if !has_attr(cx.sess(), &field.attrs) {
-            return;
+        if let Some(mode) = late_mode(cx.sess(), &field.attrs) {
+            let mut v = PrintVisitor::new("field");
+            v.visit_struct_field(field);
+            emit(mode, &hook("check_struct_field", "field", "StructField<'_>"), &v.out);
         }
-        prelude();
-        PrintVisitor::new("field").visit_struct_field(field);
-        done();
     }
 
     fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'_>) {
-        if !has_attr(cx.sess(), &expr.attrs) {
-            return;
+        if let Some(mode) = late_mode(cx.sess(), &expr.attrs) {
+            let mut v = PrintVisitor::new("expr");
+            v.visit_expr(expr);
+            emit(mode, &hook("check_expr", "expr", "Expr<'_>"), &v.out);
         }
-        prelude();
-        PrintVisitor::new("expr").visit_expr(expr);
-        done();
     }
 
     fn check_arm(&mut self, cx: &LateContext<'a, 'tcx>, arm: &'tcx hir::Arm<'_>) {
-        if !has_attr(cx.sess(), &arm.attrs) {
-            return;
+        if let Some(mode) = late_mode(cx.sess(), &arm.attrs) {
+            let mut v = PrintVisitor::new("arm");
+            v.visit_arm(arm);
+            emit(mode, &hook("check_arm", "arm", "Arm<'_>"), &v.out);
         }
-        prelude();
-        PrintVisitor::new("arm").visit_arm(arm);
-        done();
     }
 
     fn check_stmt(&mut self, cx: &LateContext<'a, 'tcx>, stmt: &'tcx hir::Stmt<'_>) {
-        if !has_attr(cx.sess(), stmt.kind.attrs()) {
-            return;
+        if let Some(mode) = late_mode(cx.sess(), stmt.kind.attrs()) {
+            let mut v = PrintVisitor::new("stmt");
+            v.visit_stmt(stmt);
+            emit(mode, &hook("check_stmt", "stmt", "Stmt<'_>"), &v.out);
         }
-        prelude();
-        PrintVisitor::new("stmt").visit_stmt(stmt);
-        done();
     }
 
     fn check_foreign_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::ForeignItem<'_>) {
-        if !has_attr(cx.sess(), &item.attrs) {
+        if let Some(mode) = late_mode(cx.sess(), &item.attrs) {
+            let mut v = PrintVisitor::new("item");
+            v.visit_foreign_item(item);
+            emit(mode, &hook("check_foreign_item", "item", "ForeignItem<'_>"), &v.out);
+        }
+    }
+}
+
+impl EarlyLintPass for EarlyAuthor {
+    fn check_attribute(&mut self, cx: &EarlyContext<'_>, attr: &Attribute) {
+        if get_attr(cx.sess(), std::slice::from_ref(attr), "author")
+            .next()
+            .is_none()
+        {
             return;
         }
-        prelude();
-        PrintVisitor::new("item").visit_foreign_item(item);
-        done();
+        let value = attr.value_str().map(|value| value.as_str());
+        if value.as_deref() != Some("ignore") && Mode::from_value(value.as_deref()).is_none() {
+            cx.sess().span_err(
+                attr.span,
+                "unknown `clippy::author` mode, expected one of `late`, `early`, `skeleton` or `early_skeleton`",
+            );
+        }
+    }
+
+    fn check_expr(&mut self, cx: &EarlyContext<'_>, expr: &ast::Expr) {
+        if let Some(mode) = early_mode(cx.sess(), &expr.attrs) {
+            let mut v = EarlyPrintVisitor::new("expr");
+            v.visit_expr(expr);
+            emit(mode, &hook("check_expr", "expr", "Expr"), &v.out);
+        }
+    }
+
+    fn check_stmt(&mut self, cx: &EarlyContext<'_>, stmt: &ast::Stmt) {
+        // attributes on expression statements are handled in `check_expr`
+        if let ast::StmtKind::Local(ref local) = stmt.kind {
+            if let Some(mode) = early_mode(cx.sess(), &local.attrs) {
+                let mut v = EarlyPrintVisitor::new("stmt");
+                v.visit_stmt(stmt);
+                emit(mode, &hook("check_stmt", "stmt", "Stmt"), &v.out);
+            }
+        }
+    }
+}
+
+/// Returns the next name for a binding called `s`, numbering repeated names starting from `1`.
+fn next_name(ids: &mut FxHashMap<&'static str, usize>, s: &'static str) -> String {
+    use std::collections::hash_map::Entry::{Occupied, Vacant};
+    match ids.entry(s) {
+        // already there: start numbering from `1`
+        Occupied(mut occ) => {
+            let val = occ.get_mut();
+            *val += 1;
+            format!("{}{}", s, *val)
+        },
+        // not there: insert and return name as given
+        Vacant(vac) => {
+            vac.insert(0);
+            s.to_owned()
+        },
     }
 }
 
+/// Prints the checks for the literal `lit_pat`, whose `LitKind` is in the field `field`.
+fn print_lit(out: &mut String, ids: &mut FxHashMap<&'static str, usize>, kind: &LitKind, lit_pat: &str, field: &str) {
+    let check = match *kind {
+        LitKind::Bool(val) => format!("    if let LitKind::Bool({:?}) = {}.{};", val, lit_pat, field),
+        LitKind::Char(c) => format!("    if let LitKind::Char({:?}) = {}.{};", c, lit_pat, field),
+        LitKind::Err(val) => format!("    if let LitKind::Err({}) = {}.{};", val, lit_pat, field),
+        LitKind::Byte(b) => format!("    if let LitKind::Byte({}) = {}.{};", b, lit_pat, field),
+        // FIXME: also check int type
+        LitKind::Int(i, _) => format!("    if let LitKind::Int({}, _) = {}.{};", i, lit_pat, field),
+        LitKind::Float(_, LitFloatType::Suffixed(_)) => format!(
+            "    if let LitKind::Float(_, LitFloatType::Suffixed(_)) = {}.{};",
+            lit_pat, field
+        ),
+        LitKind::Float(_, LitFloatType::Unsuffixed) => format!(
+            "    if let LitKind::Float(_, LitFloatType::Unsuffixed) = {}.{};",
+            lit_pat, field
+        ),
+        LitKind::ByteStr(ref vec) => {
+            let vec_pat = next_name(ids, "vec");
+            format!(
+                "    if let LitKind::ByteStr(ref {}) = {}.{};\n    if let [{:?}] = **{};",
+                vec_pat, lit_pat, field, vec, vec_pat
+            )
+        },
+        LitKind::Str(ref text, _) => {
+            let str_pat = next_name(ids, "s");
+            format!(
+                "    if let LitKind::Str(ref {}, _) = {}.{};\n    if {}.as_str() == {:?}",
+                str_pat,
+                lit_pat,
+                field,
+                str_pat,
+                &*text.as_str()
+            )
+        },
+    };
+    out.push_str(&check);
+    out.push('\n');
+}
+
 impl PrintVisitor {
     #[must_use]
     fn new(s: &'static str) -> Self {
         Self {
             ids: FxHashMap::default(),
             current: s.to_owned(),
+            out: String::new(),
         }
     }
 
     fn next(&mut self, s: &'static str) -> String {
-        use std::collections::hash_map::Entry::{Occupied, Vacant};
-        match self.ids.entry(s) {
-            // already there: start numbering from `1`
-            Occupied(mut occ) => {
-                let val = occ.get_mut();
-                *val += 1;
-                format!("{}{}", s, *val)
-            },
-            // not there: insert and return name as given
-            Vacant(vac) => {
-                vac.insert(0);
-                s.to_owned()
-            },
-        }
+        next_name(&mut self.ids, s)
     }
 
     fn print_qpath(&mut self, path: &QPath<'_>) {
-        print!("    if match_qpath({}, &[", self.current);
-        print_path(path, &mut true);
-        println!("]);");
+        out!(self, "    if match_qpath({}, &[", self.current);
+        print_path(&mut self.out, path, &mut true);
+        outln!(self, "]);");
     }
 }
 
@@ -187,6 +410,8 @@ struct PrintVisitor {
     ids: FxHashMap<&'static str, usize>,
     /// the name that needs to be destructured
     current: String,
+    /// the generated conditions
+    out: String,
 }
 
 impl<'tcx> Visitor<'tcx> for PrintVisitor {
@@ -201,16 +426,23 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             let then_pat = self.next("then");
             if let Some(else_) = opt_else {
                 let else_pat = self.next("else_");
-                println!(
+                outln!(
+                    self,
                     "    if let Some((ref {}, ref {}, Some({}))) = higher::if_block(&{});",
-                    cond_pat, then_pat, else_pat, self.current
+                    cond_pat,
+                    then_pat,
+                    else_pat,
+                    self.current
                 );
                 self.current = else_pat;
                 self.visit_expr(else_);
             } else {
-                println!(
+                outln!(
+                    self,
                     "    if let Some((ref {}, ref {}, None)) = higher::if_block(&{});",
-                    cond_pat, then_pat, self.current
+                    cond_pat,
+                    then_pat,
+                    self.current
                 );
             }
             self.current = cond_pat;
@@ -220,19 +452,19 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             return;
         }
 
-        print!("    if let ExprKind::");
+        out!(self, "    if let ExprKind::");
         let current = format!("{}.kind", self.current);
         match expr.kind {
             ExprKind::Box(ref inner) => {
                 let inner_pat = self.next("inner");
-                println!("Box(ref {}) = {};", inner_pat, current);
+                outln!(self, "Box(ref {}) = {};", inner_pat, current);
                 self.current = inner_pat;
                 self.visit_expr(inner);
             },
             ExprKind::Array(ref elements) => {
                 let elements_pat = self.next("elements");
-                println!("Array(ref {}) = {};", elements_pat, current);
-                println!("    if {}.len() == {};", elements_pat, elements.len());
+                outln!(self, "Array(ref {}) = {};", elements_pat, current);
+                outln!(self, "    if {}.len() == {};", elements_pat, elements.len());
                 for (i, element) in elements.iter().enumerate() {
                     self.current = format!("{}[{}]", elements_pat, i);
                     self.visit_expr(element);
@@ -241,23 +473,30 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             ExprKind::Call(ref func, ref args) => {
                 let func_pat = self.next("func");
                 let args_pat = self.next("args");
-                println!("Call(ref {}, ref {}) = {};", func_pat, args_pat, current);
+                outln!(self, "Call(ref {}, ref {}) = {};", func_pat, args_pat, current);
                 self.current = func_pat;
                 self.visit_expr(func);
-                println!("    if {}.len() == {};", args_pat, args.len());
+                outln!(self, "    if {}.len() == {};", args_pat, args.len());
                 for (i, arg) in args.iter().enumerate() {
                     self.current = format!("{}[{}]", args_pat, i);
                     self.visit_expr(arg);
                 }
             },
             ExprKind::MethodCall(ref _method_name, ref _generics, ref _args) => {
-                println!("MethodCall(ref method_name, ref generics, ref args) = {};", current);
-                println!("    // unimplemented: `ExprKind::MethodCall` is not further destructured at the moment");
+                outln!(
+                    self,
+                    "MethodCall(ref method_name, ref generics, ref args) = {};",
+                    current
+                );
+                outln!(
+                    self,
+                    "    // unimplemented: `ExprKind::MethodCall` is not further destructured at the moment"
+                );
             },
             ExprKind::Tup(ref elements) => {
                 let elements_pat = self.next("elements");
-                println!("Tup(ref {}) = {};", elements_pat, current);
-                println!("    if {}.len() == {};", elements_pat, elements.len());
+                outln!(self, "Tup(ref {}) = {};", elements_pat, current);
+                outln!(self, "    if {}.len() == {};", elements_pat, elements.len());
                 for (i, element) in elements.iter().enumerate() {
                     self.current = format!("{}[{}]", elements_pat, i);
                     self.visit_expr(element);
@@ -267,11 +506,15 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 let op_pat = self.next("op");
                 let left_pat = self.next("left");
                 let right_pat = self.next("right");
-                println!(
+                outln!(
+                    self,
                     "Binary(ref {}, ref {}, ref {}) = {};",
-                    op_pat, left_pat, right_pat, current
+                    op_pat,
+                    left_pat,
+                    right_pat,
+                    current
                 );
-                println!("    if BinOpKind::{:?} == {}.node;", op.node, op_pat);
+                outln!(self, "    if BinOpKind::{:?} == {}.node;", op.node, op_pat);
                 self.current = left_pat;
                 self.visit_expr(left);
                 self.current = right_pat;
@@ -279,48 +522,23 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             },
             ExprKind::Unary(ref op, ref inner) => {
                 let inner_pat = self.next("inner");
-                println!("Unary(UnOp::{:?}, ref {}) = {};", op, inner_pat, current);
+                outln!(self, "Unary(UnOp::{:?}, ref {}) = {};", op, inner_pat, current);
                 self.current = inner_pat;
                 self.visit_expr(inner);
             },
             ExprKind::Lit(ref lit) => {
                 let lit_pat = self.next("lit");
-                println!("Lit(ref {}) = {};", lit_pat, current);
-                match lit.node {
-                    LitKind::Bool(val) => println!("    if let LitKind::Bool({:?}) = {}.node;", val, lit_pat),
-                    LitKind::Char(c) => println!("    if let LitKind::Char({:?}) = {}.node;", c, lit_pat),
-                    LitKind::Err(val) => println!("    if let LitKind::Err({}) = {}.node;", val, lit_pat),
-                    LitKind::Byte(b) => println!("    if let LitKind::Byte({}) = {}.node;", b, lit_pat),
-                    // FIXME: also check int type
-                    LitKind::Int(i, _) => println!("    if let LitKind::Int({}, _) = {}.node;", i, lit_pat),
-                    LitKind::Float(_, LitFloatType::Suffixed(_)) => println!(
-                        "    if let LitKind::Float(_, LitFloatType::Suffixed(_)) = {}.node;",
-                        lit_pat
-                    ),
-                    LitKind::Float(_, LitFloatType::Unsuffixed) => println!(
-                        "    if let LitKind::Float(_, LitFloatType::Unsuffixed) = {}.node;",
-                        lit_pat
-                    ),
-                    LitKind::ByteStr(ref vec) => {
-                        let vec_pat = self.next("vec");
-                        println!("    if let LitKind::ByteStr(ref {}) = {}.node;", vec_pat, lit_pat);
-                        println!("    if let [{:?}] = **{};", vec, vec_pat);
-                    },
-                    LitKind::Str(ref text, _) => {
-                        let str_pat = self.next("s");
-                        println!("    if let LitKind::Str(ref {}, _) = {}.node;", str_pat, lit_pat);
-                        println!("    if {}.as_str() == {:?}", str_pat, &*text.as_str())
-                    },
-                }
+                outln!(self, "Lit(ref {}) = {};", lit_pat, current);
+                print_lit(&mut self.out, &mut self.ids, &lit.node, &lit_pat, "node");
             },
             ExprKind::Cast(ref expr, ref ty) => {
                 let cast_pat = self.next("expr");
                 let cast_ty = self.next("cast_ty");
                 let qp_label = self.next("qp");
 
-                println!("Cast(ref {}, ref {}) = {};", cast_pat, cast_ty, current);
+                outln!(self, "Cast(ref {}, ref {}) = {};", cast_pat, cast_ty, current);
                 if let TyKind::Path(ref qp) = ty.kind {
-                    println!("    if let TyKind::Path(ref {}) = {}.kind;", qp_label, cast_ty);
+                    outln!(self, "    if let TyKind::Path(ref {}) = {}.kind;", qp_label, cast_ty);
                     self.current = qp_label;
                     self.print_qpath(qp);
                 }
@@ -329,7 +547,7 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             },
             ExprKind::Type(ref expr, ref _ty) => {
                 let cast_pat = self.next("expr");
-                println!("Type(ref {}, _) = {};", cast_pat, current);
+                outln!(self, "Type(ref {}, _) = {};", cast_pat, current);
                 self.current = cast_pat;
                 self.visit_expr(expr);
             },
@@ -337,7 +555,14 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 let body_pat = self.next("body");
                 let des = loop_desugaring_name(desugaring);
                 let label_pat = self.next("label");
-                println!("Loop(ref {}, ref {}, {}) = {};", body_pat, label_pat, des, current);
+                outln!(
+                    self,
+                    "Loop(ref {}, ref {}, {}) = {};",
+                    body_pat,
+                    label_pat,
+                    des,
+                    current
+                );
                 self.current = body_pat;
                 self.visit_block(body);
             },
@@ -345,20 +570,27 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 let des = desugaring_name(desugaring);
                 let expr_pat = self.next("expr");
                 let arms_pat = self.next("arms");
-                println!("Match(ref {}, ref {}, {}) = {};", expr_pat, arms_pat, des, current);
+                outln!(
+                    self,
+                    "Match(ref {}, ref {}, {}) = {};",
+                    expr_pat,
+                    arms_pat,
+                    des,
+                    current
+                );
                 self.current = expr_pat;
                 self.visit_expr(expr);
-                println!("    if {}.len() == {};", arms_pat, arms.len());
+                outln!(self, "    if {}.len() == {};", arms_pat, arms.len());
                 for (i, arm) in arms.iter().enumerate() {
                     self.current = format!("{}[{}].body", arms_pat, i);
                     self.visit_expr(&arm.body);
                     if let Some(ref guard) = arm.guard {
                         let guard_pat = self.next("guard");
-                        println!("    if let Some(ref {}) = {}[{}].guard;", guard_pat, arms_pat, i);
+                        outln!(self, "    if let Some(ref {}) = {}[{}].guard;", guard_pat, arms_pat, i);
                         match guard {
                             hir::Guard::If(ref if_expr) => {
                                 let if_expr_pat = self.next("expr");
-                                println!("    if let Guard::If(ref {}) = {};", if_expr_pat, guard_pat);
+                                outln!(self, "    if let Guard::If(ref {}) = {};", if_expr_pat, guard_pat);
                                 self.current = if_expr_pat;
                                 self.visit_expr(if_expr);
                             },
@@ -369,27 +601,33 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 }
             },
             ExprKind::Closure(ref _capture_clause, ref _func, _, _, _) => {
-                println!("Closure(ref capture_clause, ref func, _, _, _) = {};", current);
-                println!("    // unimplemented: `ExprKind::Closure` is not further destructured at the moment");
+                outln!(self, "Closure(ref capture_clause, ref func, _, _, _) = {};", current);
+                outln!(
+                    self,
+                    "    // unimplemented: `ExprKind::Closure` is not further destructured at the moment"
+                );
             },
             ExprKind::Yield(ref sub, _) => {
                 let sub_pat = self.next("sub");
-                println!("Yield(ref sub) = {};", current);
+                outln!(self, "Yield(ref sub) = {};", current);
                 self.current = sub_pat;
                 self.visit_expr(sub);
             },
             ExprKind::Block(ref block, _) => {
                 let block_pat = self.next("block");
-                println!("Block(ref {}) = {};", block_pat, current);
+                outln!(self, "Block(ref {}) = {};", block_pat, current);
                 self.current = block_pat;
                 self.visit_block(block);
             },
             ExprKind::Assign(ref target, ref value, _) => {
                 let target_pat = self.next("target");
                 let value_pat = self.next("value");
-                println!(
+                outln!(
+                    self,
                     "Assign(ref {}, ref {}, ref _span) = {};",
-                    target_pat, value_pat, current
+                    target_pat,
+                    value_pat,
+                    current
                 );
                 self.current = target_pat;
                 self.visit_expr(target);
@@ -400,11 +638,15 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 let op_pat = self.next("op");
                 let target_pat = self.next("target");
                 let value_pat = self.next("value");
-                println!(
+                outln!(
+                    self,
                     "AssignOp(ref {}, ref {}, ref {}) = {};",
-                    op_pat, target_pat, value_pat, current
+                    op_pat,
+                    target_pat,
+                    value_pat,
+                    current
                 );
-                println!("    if BinOpKind::{:?} == {}.node;", op.node, op_pat);
+                outln!(self, "    if BinOpKind::{:?} == {}.node;", op.node, op_pat);
                 self.current = target_pat;
                 self.visit_expr(target);
                 self.current = value_pat;
@@ -413,15 +655,15 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             ExprKind::Field(ref object, ref field_ident) => {
                 let obj_pat = self.next("object");
                 let field_name_pat = self.next("field_name");
-                println!("Field(ref {}, ref {}) = {};", obj_pat, field_name_pat, current);
-                println!("    if {}.as_str() == {:?}", field_name_pat, field_ident.as_str());
+                outln!(self, "Field(ref {}, ref {}) = {};", obj_pat, field_name_pat, current);
+                outln!(self, "    if {}.as_str() == {:?}", field_name_pat, field_ident.as_str());
                 self.current = obj_pat;
                 self.visit_expr(object);
             },
             ExprKind::Index(ref object, ref index) => {
                 let object_pat = self.next("object");
                 let index_pat = self.next("index");
-                println!("Index(ref {}, ref {}) = {};", object_pat, index_pat, current);
+                outln!(self, "Index(ref {}, ref {}) = {};", object_pat, index_pat, current);
                 self.current = object_pat;
                 self.visit_expr(object);
                 self.current = index_pat;
@@ -429,15 +671,19 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             },
             ExprKind::Path(ref path) => {
                 let path_pat = self.next("path");
-                println!("Path(ref {}) = {};", path_pat, current);
+                outln!(self, "Path(ref {}) = {};", path_pat, current);
                 self.current = path_pat;
                 self.print_qpath(path);
             },
             ExprKind::AddrOf(kind, mutability, ref inner) => {
                 let inner_pat = self.next("inner");
-                println!(
+                outln!(
+                    self,
                     "AddrOf(BorrowKind::{:?}, Mutability::{:?}, ref {}) = {};",
-                    kind, mutability, inner_pat, current
+                    kind,
+                    mutability,
+                    inner_pat,
+                    current
                 );
                 self.current = inner_pat;
                 self.visit_expr(inner);
@@ -446,66 +692,85 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 let destination_pat = self.next("destination");
                 if let Some(ref value) = *opt_value {
                     let value_pat = self.next("value");
-                    println!("Break(ref {}, Some(ref {})) = {};", destination_pat, value_pat, current);
+                    outln!(
+                        self,
+                        "Break(ref {}, Some(ref {})) = {};",
+                        destination_pat,
+                        value_pat,
+                        current
+                    );
                     self.current = value_pat;
                     self.visit_expr(value);
                 } else {
-                    println!("Break(ref {}, None) = {};", destination_pat, current);
+                    outln!(self, "Break(ref {}, None) = {};", destination_pat, current);
                 }
                 // FIXME: implement label printing
             },
             ExprKind::Continue(ref _destination) => {
                 let destination_pat = self.next("destination");
-                println!("Again(ref {}) = {};", destination_pat, current);
+                outln!(self, "Again(ref {}) = {};", destination_pat, current);
                 // FIXME: implement label printing
             },
             ExprKind::Ret(ref opt_value) => {
                 if let Some(ref value) = *opt_value {
                     let value_pat = self.next("value");
-                    println!("Ret(Some(ref {})) = {};", value_pat, current);
+                    outln!(self, "Ret(Some(ref {})) = {};", value_pat, current);
                     self.current = value_pat;
                     self.visit_expr(value);
                 } else {
-                    println!("Ret(None) = {};", current);
+                    outln!(self, "Ret(None) = {};", current);
                 }
             },
             ExprKind::LlvmInlineAsm(_) => {
-                println!("LlvmInlineAsm(_) = {};", current);
-                println!("    // unimplemented: `ExprKind::LlvmInlineAsm` is not further destructured at the moment");
+                outln!(self, "LlvmInlineAsm(_) = {};", current);
+                outln!(
+                    self,
+                    "    // unimplemented: `ExprKind::LlvmInlineAsm` is not further destructured at the moment"
+                );
             },
             ExprKind::Struct(ref path, ref fields, ref opt_base) => {
                 let path_pat = self.next("path");
                 let fields_pat = self.next("fields");
                 if let Some(ref base) = *opt_base {
                     let base_pat = self.next("base");
-                    println!(
+                    outln!(
+                        self,
                         "Struct(ref {}, ref {}, Some(ref {})) = {};",
-                        path_pat, fields_pat, base_pat, current
+                        path_pat,
+                        fields_pat,
+                        base_pat,
+                        current
                     );
                     self.current = base_pat;
                     self.visit_expr(base);
                 } else {
-                    println!("Struct(ref {}, ref {}, None) = {};", path_pat, fields_pat, current);
+                    outln!(
+                        self,
+                        "Struct(ref {}, ref {}, None) = {};",
+                        path_pat,
+                        fields_pat,
+                        current
+                    );
                 }
                 self.current = path_pat;
                 self.print_qpath(path);
-                println!("    if {}.len() == {};", fields_pat, fields.len());
-                println!("    // unimplemented: field checks");
+                outln!(self, "    if {}.len() == {};", fields_pat, fields.len());
+                outln!(self, "    // unimplemented: field checks");
             },
             // FIXME: compute length (needs type info)
             ExprKind::Repeat(ref value, _) => {
                 let value_pat = self.next("value");
-                println!("Repeat(ref {}, _) = {};", value_pat, current);
-                println!("// unimplemented: repeat count check");
+                outln!(self, "Repeat(ref {}, _) = {};", value_pat, current);
+                outln!(self, "// unimplemented: repeat count check");
                 self.current = value_pat;
                 self.visit_expr(value);
             },
             ExprKind::Err => {
-                println!("Err = {}", current);
+                outln!(self, "Err = {}", current);
             },
             ExprKind::DropTemps(ref expr) => {
                 let expr_pat = self.next("expr");
-                println!("DropTemps(ref {}) = {};", expr_pat, current);
+                outln!(self, "DropTemps(ref {}) = {};", expr_pat, current);
                 self.current = expr_pat;
                 self.visit_expr(expr);
             },
@@ -514,8 +779,8 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
 
     fn visit_block(&mut self, block: &Block<'_>) {
         let trailing_pat = self.next("trailing_expr");
-        println!("    if let Some({}) = &{}.expr;", trailing_pat, self.current);
-        println!("    if {}.stmts.len() == {};", self.current, block.stmts.len());
+        outln!(self, "    if let Some({}) = &{}.expr;", trailing_pat, self.current);
+        outln!(self, "    if {}.stmts.len() == {};", self.current, block.stmts.len());
         let current = self.current.clone();
         for (i, stmt) in block.stmts.iter().enumerate() {
             self.current = format!("{}.stmts[{}]", current, i);
@@ -525,10 +790,10 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
 
     #[allow(clippy::too_many_lines)]
     fn visit_pat(&mut self, pat: &Pat<'_>) {
-        print!("    if let PatKind::");
+        out!(self, "    if let PatKind::");
         let current = format!("{}.kind", self.current);
         match pat.kind {
-            PatKind::Wild => println!("Wild = {};", current),
+            PatKind::Wild => outln!(self, "Wild = {};", current),
             PatKind::Binding(anno, .., ident, ref sub) => {
                 let anno_pat = match anno {
                     BindingAnnotation::Unannotated => "BindingAnnotation::Unannotated",
@@ -539,83 +804,99 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 let name_pat = self.next("name");
                 if let Some(ref sub) = *sub {
                     let sub_pat = self.next("sub");
-                    println!(
+                    outln!(
+                        self,
                         "Binding({}, _, {}, Some(ref {})) = {};",
-                        anno_pat, name_pat, sub_pat, current
+                        anno_pat,
+                        name_pat,
+                        sub_pat,
+                        current
                     );
                     self.current = sub_pat;
                     self.visit_pat(sub);
                 } else {
-                    println!("Binding({}, _, {}, None) = {};", anno_pat, name_pat, current);
+                    outln!(self, "Binding({}, _, {}, None) = {};", anno_pat, name_pat, current);
                 }
-                println!("    if {}.as_str() == \"{}\";", name_pat, ident.as_str());
+                outln!(self, "    if {}.as_str() == \"{}\";", name_pat, ident.as_str());
             },
             PatKind::Struct(ref path, ref fields, ignore) => {
                 let path_pat = self.next("path");
                 let fields_pat = self.next("fields");
-                println!(
+                outln!(
+                    self,
                     "Struct(ref {}, ref {}, {}) = {};",
-                    path_pat, fields_pat, ignore, current
+                    path_pat,
+                    fields_pat,
+                    ignore,
+                    current
                 );
                 self.current = path_pat;
                 self.print_qpath(path);
-                println!("    if {}.len() == {};", fields_pat, fields.len());
-                println!("    // unimplemented: field checks");
+                outln!(self, "    if {}.len() == {};", fields_pat, fields.len());
+                outln!(self, "    // unimplemented: field checks");
             },
             PatKind::Or(ref fields) => {
                 let fields_pat = self.next("fields");
-                println!("Or(ref {}) = {};", fields_pat, current);
-                println!("    if {}.len() == {};", fields_pat, fields.len());
-                println!("    // unimplemented: field checks");
+                outln!(self, "Or(ref {}) = {};", fields_pat, current);
+                outln!(self, "    if {}.len() == {};", fields_pat, fields.len());
+                outln!(self, "    // unimplemented: field checks");
             },
             PatKind::TupleStruct(ref path, ref fields, skip_pos) => {
                 let path_pat = self.next("path");
                 let fields_pat = self.next("fields");
-                println!(
+                outln!(
+                    self,
                     "TupleStruct(ref {}, ref {}, {:?}) = {};",
-                    path_pat, fields_pat, skip_pos, current
+                    path_pat,
+                    fields_pat,
+                    skip_pos,
+                    current
                 );
                 self.current = path_pat;
                 self.print_qpath(path);
-                println!("    if {}.len() == {};", fields_pat, fields.len());
-                println!("    // unimplemented: field checks");
+                outln!(self, "    if {}.len() == {};", fields_pat, fields.len());
+                outln!(self, "    // unimplemented: field checks");
             },
             PatKind::Path(ref path) => {
                 let path_pat = self.next("path");
-                println!("Path(ref {}) = {};", path_pat, current);
+                outln!(self, "Path(ref {}) = {};", path_pat, current);
                 self.current = path_pat;
                 self.print_qpath(path);
             },
             PatKind::Tuple(ref fields, skip_pos) => {
                 let fields_pat = self.next("fields");
-                println!("Tuple(ref {}, {:?}) = {};", fields_pat, skip_pos, current);
-                println!("    if {}.len() == {};", fields_pat, fields.len());
-                println!("    // unimplemented: field checks");
+                outln!(self, "Tuple(ref {}, {:?}) = {};", fields_pat, skip_pos, current);
+                outln!(self, "    if {}.len() == {};", fields_pat, fields.len());
+                outln!(self, "    // unimplemented: field checks");
             },
             PatKind::Box(ref pat) => {
                 let pat_pat = self.next("pat");
-                println!("Box(ref {}) = {};", pat_pat, current);
+                outln!(self, "Box(ref {}) = {};", pat_pat, current);
                 self.current = pat_pat;
                 self.visit_pat(pat);
             },
             PatKind::Ref(ref pat, muta) => {
                 let pat_pat = self.next("pat");
-                println!("Ref(ref {}, Mutability::{:?}) = {};", pat_pat, muta, current);
+                outln!(self, "Ref(ref {}, Mutability::{:?}) = {};", pat_pat, muta, current);
                 self.current = pat_pat;
                 self.visit_pat(pat);
             },
             PatKind::Lit(ref lit_expr) => {
                 let lit_expr_pat = self.next("lit_expr");
-                println!("Lit(ref {}) = {}", lit_expr_pat, current);
+                outln!(self, "Lit(ref {}) = {}", lit_expr_pat, current);
                 self.current = lit_expr_pat;
                 self.visit_expr(lit_expr);
             },
             PatKind::Range(ref start, ref end, end_kind) => {
                 let start_pat = self.next("start");
                 let end_pat = self.next("end");
-                println!(
+                outln!(
+                    self,
                     "Range(ref {}, ref {}, RangeEnd::{:?}) = {};",
-                    start_pat, end_pat, end_kind, current
+                    start_pat,
+                    end_pat,
+                    end_kind,
+                    current
                 );
                 self.current = start_pat;
                 walk_list!(self, visit_expr, start);
@@ -627,21 +908,25 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 let end_pat = self.next("end");
                 if let Some(ref middle) = middle {
                     let middle_pat = self.next("middle");
-                    println!(
+                    outln!(
+                        self,
                         "Slice(ref {}, Some(ref {}), ref {}) = {};",
-                        start_pat, middle_pat, end_pat, current
+                        start_pat,
+                        middle_pat,
+                        end_pat,
+                        current
                     );
                     self.current = middle_pat;
                     self.visit_pat(middle);
                 } else {
-                    println!("Slice(ref {}, None, ref {}) = {};", start_pat, end_pat, current);
+                    outln!(self, "Slice(ref {}, None, ref {}) = {};", start_pat, end_pat, current);
                 }
-                println!("    if {}.len() == {};", start_pat, start.len());
+                outln!(self, "    if {}.len() == {};", start_pat, start.len());
                 for (i, pat) in start.iter().enumerate() {
                     self.current = format!("{}[{}]", start_pat, i);
                     self.visit_pat(pat);
                 }
-                println!("    if {}.len() == {};", end_pat, end.len());
+                outln!(self, "    if {}.len() == {};", end_pat, end.len());
                 for (i, pat) in end.iter().enumerate() {
                     self.current = format!("{}[{}]", end_pat, i);
                     self.visit_pat(pat);
@@ -651,16 +936,16 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
     }
 
     fn visit_stmt(&mut self, s: &Stmt<'_>) {
-        print!("    if let StmtKind::");
+        out!(self, "    if let StmtKind::");
         let current = format!("{}.kind", self.current);
         match s.kind {
             // A local (let) binding:
             StmtKind::Local(ref local) => {
                 let local_pat = self.next("local");
-                println!("Local(ref {}) = {};", local_pat, current);
+                outln!(self, "Local(ref {}) = {};", local_pat, current);
                 if let Some(ref init) = local.init {
                     let init_pat = self.next("init");
-                    println!("    if let Some(ref {}) = {}.init;", init_pat, local_pat);
+                    outln!(self, "    if let Some(ref {}) = {}.init;", init_pat, local_pat);
                     self.current = init_pat;
                     self.visit_expr(init);
                 }
@@ -669,13 +954,13 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             },
             // An item binding:
             StmtKind::Item(_) => {
-                println!("Item(item_id) = {};", current);
+                outln!(self, "Item(item_id) = {};", current);
             },
 
             // Expr without trailing semi-colon (must have unit type):
             StmtKind::Expr(ref e) => {
                 let e_pat = self.next("e");
-                println!("Expr(ref {}, _) = {}", e_pat, current);
+                outln!(self, "Expr(ref {}, _) = {}", e_pat, current);
                 self.current = e_pat;
                 self.visit_expr(e);
             },
@@ -683,7 +968,7 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
             // Expr with trailing semi-colon (may have any type):
             StmtKind::Semi(ref e) => {
                 let e_pat = self.next("e");
-                println!("Semi(ref {}, _) = {}", e_pat, current);
+                outln!(self, "Semi(ref {}, _) = {}", e_pat, current);
                 self.current = e_pat;
                 self.visit_expr(e);
             },
@@ -695,8 +980,511 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
     }
 }
 
-fn has_attr(sess: &Session, attrs: &[Attribute]) -> bool {
-    get_attr(sess, attrs, "author").count() > 0
+struct EarlyPrintVisitor {
+    /// Fields are the current index that needs to be appended to pattern
+    /// binding names
+    ids: FxHashMap<&'static str, usize>,
+    /// the name that needs to be destructured
+    current: String,
+    /// the generated conditions
+    out: String,
+}
+
+impl EarlyPrintVisitor {
+    #[must_use]
+    fn new(s: &'static str) -> Self {
+        Self {
+            ids: FxHashMap::default(),
+            current: s.to_owned(),
+            out: String::new(),
+        }
+    }
+
+    fn next(&mut self, s: &'static str) -> String {
+        next_name(&mut self.ids, s)
+    }
+
+    fn print_path(&mut self, path: &ast::Path) {
+        let segments: Vec<_> = path
+            .segments
+            .iter()
+            .map(|segment| format!("{:?}", segment.ident.as_str()))
+            .collect();
+        outln!(
+            self,
+            "    if match_path_ast({}, &[{}]);",
+            self.current,
+            segments.join(", ")
+        );
+    }
+
+    /// Prints a catch-all pattern for a variant that is not destructured.
+    fn unimplemented(&mut self, kind: &impl std::fmt::Debug, current: &str) {
+        let debug = format!("{:?}", kind);
+        let variant: String = debug.chars().take_while(char::is_ascii_alphanumeric).collect();
+        outln!(self, "{}(..) = {};", variant, current);
+        outln!(
+            self,
+            "    // unimplemented: `{}` is not further destructured at the moment",
+            variant
+        );
+    }
+}
+
+impl<'ast> AstVisitor<'ast> for EarlyPrintVisitor {
+    #[allow(clippy::too_many_lines)]
+    fn visit_expr(&mut self, expr: &'ast ast::Expr) {
+        out!(self, "    if let ExprKind::");
+        let current = format!("{}.kind", self.current);
+        match expr.kind {
+            ast::ExprKind::Box(ref inner) => {
+                let inner_pat = self.next("inner");
+                outln!(self, "Box(ref {}) = {};", inner_pat, current);
+                self.current = inner_pat;
+                self.visit_expr(inner);
+            },
+            ast::ExprKind::Array(ref elements) | ast::ExprKind::Tup(ref elements) => {
+                let variant = if let ast::ExprKind::Array(..) = expr.kind {
+                    "Array"
+                } else {
+                    "Tup"
+                };
+                let elements_pat = self.next("elements");
+                outln!(self, "{}(ref {}) = {};", variant, elements_pat, current);
+                outln!(self, "    if {}.len() == {};", elements_pat, elements.len());
+                for (i, element) in elements.iter().enumerate() {
+                    self.current = format!("{}[{}]", elements_pat, i);
+                    self.visit_expr(element);
+                }
+            },
+            ast::ExprKind::Call(ref func, ref args) => {
+                let func_pat = self.next("func");
+                let args_pat = self.next("args");
+                outln!(self, "Call(ref {}, ref {}) = {};", func_pat, args_pat, current);
+                self.current = func_pat;
+                self.visit_expr(func);
+                outln!(self, "    if {}.len() == {};", args_pat, args.len());
+                for (i, arg) in args.iter().enumerate() {
+                    self.current = format!("{}[{}]", args_pat, i);
+                    self.visit_expr(arg);
+                }
+            },
+            ast::ExprKind::MethodCall(ref method, ref args, ..) => {
+                let method_pat = self.next("method");
+                let args_pat = self.next("args");
+                outln!(
+                    self,
+                    "MethodCall(ref {}, ref {}, ..) = {};",
+                    method_pat,
+                    args_pat,
+                    current
+                );
+                outln!(
+                    self,
+                    "    if {}.ident.as_str() == {:?};",
+                    method_pat,
+                    method.ident.as_str()
+                );
+                // the receiver is the first argument
+                outln!(self, "    if {}.len() == {};", args_pat, args.len());
+                for (i, arg) in args.iter().enumerate() {
+                    self.current = format!("{}[{}]", args_pat, i);
+                    self.visit_expr(arg);
+                }
+            },
+            ast::ExprKind::Binary(ref op, ref left, ref right) => {
+                let op_pat = self.next("op");
+                let left_pat = self.next("left");
+                let right_pat = self.next("right");
+                outln!(
+                    self,
+                    "Binary(ref {}, ref {}, ref {}) = {};",
+                    op_pat,
+                    left_pat,
+                    right_pat,
+                    current
+                );
+                outln!(self, "    if BinOpKind::{:?} == {}.node;", op.node, op_pat);
+                self.current = left_pat;
+                self.visit_expr(left);
+                self.current = right_pat;
+                self.visit_expr(right);
+            },
+            ast::ExprKind::Unary(op, ref inner) => {
+                let inner_pat = self.next("inner");
+                outln!(self, "Unary(UnOp::{:?}, ref {}) = {};", op, inner_pat, current);
+                self.current = inner_pat;
+                self.visit_expr(inner);
+            },
+            ast::ExprKind::Lit(ref lit) => {
+                let lit_pat = self.next("lit");
+                outln!(self, "Lit(ref {}) = {};", lit_pat, current);
+                print_lit(&mut self.out, &mut self.ids, &lit.kind, &lit_pat, "kind");
+            },
+            ast::ExprKind::Cast(ref inner, _) | ast::ExprKind::Type(ref inner, _) => {
+                let variant = if let ast::ExprKind::Cast(..) = expr.kind {
+                    "Cast"
+                } else {
+                    "Type"
+                };
+                let expr_pat = self.next("expr");
+                outln!(self, "{}(ref {}, _) = {};", variant, expr_pat, current);
+                self.current = expr_pat;
+                self.visit_expr(inner);
+            },
+            ast::ExprKind::Let(ref pat, ref expr) => {
+                let pat_pat = self.next("pat");
+                let expr_pat = self.next("expr");
+                outln!(self, "Let(ref {}, ref {}) = {};", pat_pat, expr_pat, current);
+                self.current = expr_pat;
+                self.visit_expr(expr);
+                self.current = pat_pat;
+                self.visit_pat(pat);
+            },
+            ast::ExprKind::If(ref cond, ref then, ref opt_else) => {
+                let cond_pat = self.next("cond");
+                let then_pat = self.next("then");
+                if let Some(ref else_) = *opt_else {
+                    let else_pat = self.next("else_");
+                    outln!(
+                        self,
+                        "If(ref {}, ref {}, Some(ref {})) = {};",
+                        cond_pat,
+                        then_pat,
+                        else_pat,
+                        current
+                    );
+                    self.current = else_pat;
+                    self.visit_expr(else_);
+                } else {
+                    outln!(self, "If(ref {}, ref {}, None) = {};", cond_pat, then_pat, current);
+                }
+                self.current = cond_pat;
+                self.visit_expr(cond);
+                self.current = then_pat;
+                self.visit_block(then);
+            },
+            ast::ExprKind::While(ref cond, ref body, _) => {
+                let cond_pat = self.next("cond");
+                let body_pat = self.next("body");
+                outln!(self, "While(ref {}, ref {}, _) = {};", cond_pat, body_pat, current);
+                self.current = cond_pat;
+                self.visit_expr(cond);
+                self.current = body_pat;
+                self.visit_block(body);
+            },
+            ast::ExprKind::ForLoop(ref pat, ref iter, ref body, _) => {
+                let pat_pat = self.next("pat");
+                let iter_pat = self.next("iter");
+                let body_pat = self.next("body");
+                outln!(
+                    self,
+                    "ForLoop(ref {}, ref {}, ref {}, _) = {};",
+                    pat_pat,
+                    iter_pat,
+                    body_pat,
+                    current
+                );
+                self.current = pat_pat;
+                self.visit_pat(pat);
+                self.current = iter_pat;
+                self.visit_expr(iter);
+                self.current = body_pat;
+                self.visit_block(body);
+            },
+            ast::ExprKind::Loop(ref body, _) => {
+                let body_pat = self.next("body");
+                outln!(self, "Loop(ref {}, _) = {};", body_pat, current);
+                self.current = body_pat;
+                self.visit_block(body);
+            },
+            ast::ExprKind::Match(ref expr, ref arms) => {
+                let expr_pat = self.next("expr");
+                let arms_pat = self.next("arms");
+                outln!(self, "Match(ref {}, ref {}) = {};", expr_pat, arms_pat, current);
+                self.current = expr_pat;
+                self.visit_expr(expr);
+                outln!(self, "    if {}.len() == {};", arms_pat, arms.len());
+                for (i, arm) in arms.iter().enumerate() {
+                    self.current = format!("{}[{}].body", arms_pat, i);
+                    self.visit_expr(&arm.body);
+                    if let Some(ref guard) = arm.guard {
+                        let guard_pat = self.next("guard");
+                        outln!(self, "    if let Some(ref {}) = {}[{}].guard;", guard_pat, arms_pat, i);
+                        self.current = guard_pat;
+                        self.visit_expr(guard);
+                    }
+                    self.current = format!("{}[{}].pat", arms_pat, i);
+                    self.visit_pat(&arm.pat);
+                }
+            },
+            ast::ExprKind::Block(ref block, _) => {
+                let block_pat = self.next("block");
+                outln!(self, "Block(ref {}, _) = {};", block_pat, current);
+                self.current = block_pat;
+                self.visit_block(block);
+            },
+            ast::ExprKind::Assign(ref target, ref value, _) => {
+                let target_pat = self.next("target");
+                let value_pat = self.next("value");
+                outln!(self, "Assign(ref {}, ref {}, _) = {};", target_pat, value_pat, current);
+                self.current = target_pat;
+                self.visit_expr(target);
+                self.current = value_pat;
+                self.visit_expr(value);
+            },
+            ast::ExprKind::AssignOp(ref op, ref target, ref value) => {
+                let op_pat = self.next("op");
+                let target_pat = self.next("target");
+                let value_pat = self.next("value");
+                outln!(
+                    self,
+                    "AssignOp(ref {}, ref {}, ref {}) = {};",
+                    op_pat,
+                    target_pat,
+                    value_pat,
+                    current
+                );
+                outln!(self, "    if BinOpKind::{:?} == {}.node;", op.node, op_pat);
+                self.current = target_pat;
+                self.visit_expr(target);
+                self.current = value_pat;
+                self.visit_expr(value);
+            },
+            ast::ExprKind::Field(ref object, ref field_ident) => {
+                let obj_pat = self.next("object");
+                let field_name_pat = self.next("field_name");
+                outln!(self, "Field(ref {}, ref {}) = {};", obj_pat, field_name_pat, current);
+                outln!(
+                    self,
+                    "    if {}.as_str() == {:?};",
+                    field_name_pat,
+                    field_ident.as_str()
+                );
+                self.current = obj_pat;
+                self.visit_expr(object);
+            },
+            ast::ExprKind::Index(ref object, ref index) => {
+                let object_pat = self.next("object");
+                let index_pat = self.next("index");
+                outln!(self, "Index(ref {}, ref {}) = {};", object_pat, index_pat, current);
+                self.current = object_pat;
+                self.visit_expr(object);
+                self.current = index_pat;
+                self.visit_expr(index);
+            },
+            ast::ExprKind::Path(_, ref path) => {
+                let path_pat = self.next("path");
+                outln!(self, "Path(_, ref {}) = {};", path_pat, current);
+                self.current = path_pat;
+                self.print_path(path);
+            },
+            ast::ExprKind::AddrOf(kind, mutability, ref inner) => {
+                let inner_pat = self.next("inner");
+                outln!(
+                    self,
+                    "AddrOf(BorrowKind::{:?}, Mutability::{:?}, ref {}) = {};",
+                    kind,
+                    mutability,
+                    inner_pat,
+                    current
+                );
+                self.current = inner_pat;
+                self.visit_expr(inner);
+            },
+            ast::ExprKind::Ret(ref opt_value) | ast::ExprKind::Break(_, ref opt_value) => {
+                let variant = if let ast::ExprKind::Ret(..) = expr.kind {
+                    "Ret("
+                } else {
+                    "Break(_, "
+                };
+                // FIXME: implement label printing
+                if let Some(ref value) = *opt_value {
+                    let value_pat = self.next("value");
+                    outln!(self, "{}Some(ref {})) = {};", variant, value_pat, current);
+                    self.current = value_pat;
+                    self.visit_expr(value);
+                } else {
+                    outln!(self, "{}None) = {};", variant, current);
+                }
+            },
+            ast::ExprKind::Paren(ref inner) | ast::ExprKind::Try(ref inner) => {
+                let variant = if let ast::ExprKind::Paren(..) = expr.kind {
+                    "Paren"
+                } else {
+                    "Try"
+                };
+                let inner_pat = self.next("inner");
+                outln!(self, "{}(ref {}) = {};", variant, inner_pat, current);
+                self.current = inner_pat;
+                self.visit_expr(inner);
+            },
+            ast::ExprKind::Err => outln!(self, "Err = {};", current),
+            ref kind => self.unimplemented(kind, &current),
+        }
+    }
+
+    fn visit_block(&mut self, block: &'ast ast::Block) {
+        outln!(self, "    if {}.stmts.len() == {};", self.current, block.stmts.len());
+        let current = self.current.clone();
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            self.current = format!("{}.stmts[{}]", current, i);
+            self.visit_stmt(stmt);
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast ast::Stmt) {
+        out!(self, "    if let StmtKind::");
+        let current = format!("{}.kind", self.current);
+        match stmt.kind {
+            ast::StmtKind::Local(ref local) => {
+                let local_pat = self.next("local");
+                outln!(self, "Local(ref {}) = {};", local_pat, current);
+                if let Some(ref init) = local.init {
+                    let init_pat = self.next("init");
+                    outln!(self, "    if let Some(ref {}) = {}.init;", init_pat, local_pat);
+                    self.current = init_pat;
+                    self.visit_expr(init);
+                }
+                self.current = format!("{}.pat", local_pat);
+                self.visit_pat(&local.pat);
+            },
+            ast::StmtKind::Item(_) => outln!(self, "Item(_) = {};", current),
+            ast::StmtKind::Expr(ref e) | ast::StmtKind::Semi(ref e) => {
+                let variant = if let ast::StmtKind::Expr(..) = stmt.kind {
+                    "Expr"
+                } else {
+                    "Semi"
+                };
+                let e_pat = self.next("e");
+                outln!(self, "{}(ref {}) = {};", variant, e_pat, current);
+                self.current = e_pat;
+                self.visit_expr(e);
+            },
+            ast::StmtKind::Empty => outln!(self, "Empty = {};", current),
+            ref kind => self.unimplemented(kind, &current),
+        }
+    }
+
+    fn visit_pat(&mut self, pat: &'ast ast::Pat) {
+        out!(self, "    if let PatKind::");
+        let current = format!("{}.kind", self.current);
+        match pat.kind {
+            ast::PatKind::Wild => outln!(self, "Wild = {};", current),
+            ast::PatKind::Rest => outln!(self, "Rest = {};", current),
+            ast::PatKind::Ident(mode, ident, ref sub) => {
+                let mode_pat = match mode {
+                    ast::BindingMode::ByValue(m) => format!("BindingMode::ByValue(Mutability::{:?})", m),
+                    ast::BindingMode::ByRef(m) => format!("BindingMode::ByRef(Mutability::{:?})", m),
+                };
+                let name_pat = self.next("name");
+                if let Some(ref sub) = *sub {
+                    let sub_pat = self.next("sub");
+                    outln!(
+                        self,
+                        "Ident({}, {}, Some(ref {})) = {};",
+                        mode_pat,
+                        name_pat,
+                        sub_pat,
+                        current
+                    );
+                    self.current = sub_pat;
+                    self.visit_pat(sub);
+                } else {
+                    outln!(self, "Ident({}, {}, None) = {};", mode_pat, name_pat, current);
+                }
+                outln!(self, "    if {}.as_str() == {:?};", name_pat, ident.as_str());
+            },
+            ast::PatKind::Tuple(ref fields) | ast::PatKind::Slice(ref fields) | ast::PatKind::Or(ref fields) => {
+                let variant = match pat.kind {
+                    ast::PatKind::Tuple(..) => "Tuple",
+                    ast::PatKind::Slice(..) => "Slice",
+                    _ => "Or",
+                };
+                let fields_pat = self.next("fields");
+                outln!(self, "{}(ref {}) = {};", variant, fields_pat, current);
+                outln!(self, "    if {}.len() == {};", fields_pat, fields.len());
+                for (i, field) in fields.iter().enumerate() {
+                    self.current = format!("{}[{}]", fields_pat, i);
+                    self.visit_pat(field);
+                }
+            },
+            ast::PatKind::TupleStruct(ref path, ref fields) => {
+                let path_pat = self.next("path");
+                let fields_pat = self.next("fields");
+                outln!(self, "TupleStruct(ref {}, ref {}) = {};", path_pat, fields_pat, current);
+                self.current = path_pat;
+                self.print_path(path);
+                outln!(self, "    if {}.len() == {};", fields_pat, fields.len());
+                for (i, field) in fields.iter().enumerate() {
+                    self.current = format!("{}[{}]", fields_pat, i);
+                    self.visit_pat(field);
+                }
+            },
+            ast::PatKind::Struct(ref path, ref fields, rest) => {
+                let path_pat = self.next("path");
+                let fields_pat = self.next("fields");
+                outln!(
+                    self,
+                    "Struct(ref {}, ref {}, {}) = {};",
+                    path_pat,
+                    fields_pat,
+                    rest,
+                    current
+                );
+                self.current = path_pat;
+                self.print_path(path);
+                outln!(self, "    if {}.len() == {};", fields_pat, fields.len());
+                outln!(self, "    // unimplemented: field checks");
+            },
+            ast::PatKind::Path(_, ref path) => {
+                let path_pat = self.next("path");
+                outln!(self, "Path(_, ref {}) = {};", path_pat, current);
+                self.current = path_pat;
+                self.print_path(path);
+            },
+            ast::PatKind::Box(ref inner) | ast::PatKind::Paren(ref inner) => {
+                let variant = if let ast::PatKind::Box(..) = pat.kind {
+                    "Box"
+                } else {
+                    "Paren"
+                };
+                let pat_pat = self.next("pat");
+                outln!(self, "{}(ref {}) = {};", variant, pat_pat, current);
+                self.current = pat_pat;
+                self.visit_pat(inner);
+            },
+            ast::PatKind::Ref(ref inner, muta) => {
+                let pat_pat = self.next("pat");
+                outln!(self, "Ref(ref {}, Mutability::{:?}) = {};", pat_pat, muta, current);
+                self.current = pat_pat;
+                self.visit_pat(inner);
+            },
+            ast::PatKind::Lit(ref lit_expr) => {
+                let lit_expr_pat = self.next("lit_expr");
+                outln!(self, "Lit(ref {}) = {};", lit_expr_pat, current);
+                self.current = lit_expr_pat;
+                self.visit_expr(lit_expr);
+            },
+            ref kind => self.unimplemented(kind, &current),
+        }
+    }
+}
+
+/// Returns the mode selected by a `#[clippy::author]` attribute in `attrs`, if there is one.
+fn author_mode(sess: &Session, attrs: &[Attribute]) -> Option<Mode> {
+    let attr = get_attr(sess, attrs, "author").next()?;
+    Mode::from_value(attr.value_str().map(|value| value.as_str()).as_deref())
+}
+
+/// Returns the mode of a `#[clippy::author]` attribute that is handled by the late pass.
+fn late_mode(sess: &Session, attrs: &[Attribute]) -> Option<Mode> {
+    author_mode(sess, attrs).filter(|mode| !mode.is_early())
+}
+
+/// Returns the mode of a `#[clippy::author]` attribute that is handled by the early pass.
+fn early_mode(sess: &Session, attrs: &[Attribute]) -> Option<Mode> {
+    author_mode(sess, attrs).filter(|mode| mode.is_early())
 }
 
 #[must_use]
@@ -729,29 +1517,29 @@ fn loop_desugaring_name(des: hir::LoopSource) -> &'static str {
     }
 }
 
-fn print_path(path: &QPath<'_>, first: &mut bool) {
+fn print_path(out: &mut String, path: &QPath<'_>, first: &mut bool) {
     match *path {
         QPath::Resolved(_, ref path) => {
             for segment in path.segments {
                 if *first {
                     *first = false;
                 } else {
-                    print!(", ");
+                    out.push_str(", ");
                 }
-                print!("{:?}", segment.ident.as_str());
+                out.push_str(&format!("{:?}", segment.ident.as_str()));
             }
         },
         QPath::TypeRelative(ref ty, ref segment) => match ty.kind {
             hir::TyKind::Path(ref inner_path) => {
-                print_path(inner_path, first);
+                print_path(out, inner_path, first);
                 if *first {
                     *first = false;
                 } else {
-                    print!(", ");
+                    out.push_str(", ");
                 }
-                print!("{:?}", segment.ident.as_str());
+                out.push_str(&format!("{:?}", segment.ident.as_str()));
             },
-            ref other => print!("/* unimplemented: {:?}*/", other),
+            ref other => out.push_str(&format!("/* unimplemented: {:?}*/", other)),
         },
     }
 }
@@ -0,0 +1,115 @@
+//! Utilities to analyze the bodies of closures passed to iterator adapters and similar methods.
+
+use crate::utils::{get_arg_name, match_qpath, paths, remove_blocks};
+use rustc_ast::ast::Name;
+use rustc_hir::def::Res;
+use rustc_hir::{Body, Expr, ExprKind, PatKind, PathSegment, QPath, UnOp};
+use rustc_lint::LateContext;
+use rustc_span::symbol::Ident;
+
+/// The body of a closure expression.
+pub struct Closure<'tcx> {
+    pub body: &'tcx Body<'tcx>,
+}
+
+/// What a closure returns, as recognized by `Closure::shape`.
+#[derive(Debug)]
+pub enum ClosureShape<'tcx> {
+    /// `|x| x`
+    Identity,
+    /// `|x| x.field`. Unlike `|x| x.method()`, there is no path that could replace the closure.
+    Field(Ident),
+    /// `|x| Some(..)`, with the wrapped expression
+    Some(&'tcx Expr<'tcx>),
+    /// `|x| None`
+    None,
+    /// `|x| x.method(..)`, with the method and its arguments (not counting `self`)
+    MethodCall(&'tcx PathSegment<'tcx>, &'tcx [Expr<'tcx>]),
+    /// Any other closure returning a `bool`
+    Bool,
+    /// Anything else
+    Other,
+}
+
+impl<'tcx> Closure<'tcx> {
+    /// Returns the closure if `expr` is a closure expression.
+    pub fn new(cx: &LateContext<'_, 'tcx>, expr: &'tcx Expr<'tcx>) -> Option<Self> {
+        if let ExprKind::Closure(_, _, body_id, _, _) = expr.kind {
+            Some(Self {
+                body: cx.tcx.hir().body(body_id),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The value of the closure, without blocks that only contain an expression.
+    pub fn value(&self) -> &'tcx Expr<'tcx> {
+        remove_blocks(&self.body.value)
+    }
+
+    /// The name bound by the `i`th parameter if it is a simple binding, possibly behind a
+    /// reference pattern like `|&x|`.
+    pub fn param_name(&self, i: usize) -> Option<Name> {
+        self.body.params.get(i).and_then(|param| get_arg_name(&param.pat))
+    }
+
+    /// Is `expr` the binding of the `i`th parameter?
+    pub fn is_param(&self, expr: &Expr<'_>, i: usize) -> bool {
+        let param = match self.body.params.get(i) {
+            Some(param) => param,
+            None => return false,
+        };
+        let mut pat = param.pat;
+        while let PatKind::Ref(inner, _) = pat.kind {
+            pat = inner;
+        }
+        match (&pat.kind, &expr.kind) {
+            (PatKind::Binding(.., None), ExprKind::Path(QPath::Resolved(None, ref path))) => {
+                path.res == Res::Local(pat.hir_id)
+            },
+            _ => false,
+        }
+    }
+
+    /// Is `expr` the binding of the `i`th parameter, possibly dereferenced like `*x`?
+    pub fn is_param_or_deref(&self, expr: &Expr<'_>, i: usize) -> bool {
+        let mut expr = expr;
+        while let ExprKind::Unary(UnOp::UnDeref, ref inner) = expr.kind {
+            expr = inner;
+        }
+        self.is_param(expr, i)
+    }
+
+    /// Recognizes the common shapes of single parameter closures.
+    pub fn shape(&self, cx: &LateContext<'_, 'tcx>) -> ClosureShape<'tcx> {
+        if self.body.params.len() != 1 {
+            return ClosureShape::Other;
+        }
+
+        let value = self.value();
+        match value.kind {
+            _ if self.is_param(value, 0) && matches!(self.body.params[0].pat.kind, PatKind::Binding(..)) => {
+                ClosureShape::Identity
+            },
+            ExprKind::Field(ref base, ident) if self.is_param(base, 0) => ClosureShape::Field(ident),
+            ExprKind::MethodCall(ref segment, _, ref args) if self.is_param(&args[0], 0) => {
+                ClosureShape::MethodCall(segment, &args[1..])
+            },
+            ExprKind::Call(ref func, ref args) if args.len() == 1 => match func.kind {
+                ExprKind::Path(ref qpath) if match_qpath(qpath, &paths::OPTION_SOME) => ClosureShape::Some(&args[0]),
+                _ => Self::bool_or_other(cx, value),
+            },
+            ExprKind::Path(ref qpath) if match_qpath(qpath, &paths::OPTION_NONE) => ClosureShape::None,
+            _ => Self::bool_or_other(cx, value),
+        }
+    }
+
+    fn bool_or_other(cx: &LateContext<'_, 'tcx>, value: &Expr<'_>) -> ClosureShape<'tcx> {
+        if cx.tables.expr_ty(value).is_bool() {
+            ClosureShape::Bool
+        } else {
+            ClosureShape::Other
+        }
+    }
+}
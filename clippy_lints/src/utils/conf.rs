@@ -5,6 +5,8 @@
 use lazy_static::lazy_static;
 use rustc_ast::ast::{LitKind, MetaItemKind, NestedMetaItem};
 use rustc_span::source_map;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use source_map::Span;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
@@ -72,9 +74,9 @@ macro_rules! define_Conf {
                     #[serde(with = $config_str)]
                     pub $config: $Ty,
                 )+
-                #[allow(dead_code)]
+                /// The `[third-party]` table, read by lint libraries loaded with `--lint-lib`
                 #[serde(default)]
-                third_party: Option<::toml::Value>,
+                pub(super) third_party: Option<::toml::Value>,
             }
 
             $(
@@ -108,8 +110,12 @@ pub use self::helpers::Conf;
 define_Conf! {
     /// Lint: BLACKLISTED_NAME. The list of blacklisted names to lint about
     (blacklisted_names, "blacklisted_names": Vec<String>, ["foo", "bar", "baz", "quux"].iter().map(ToString::to_string).collect()),
-    /// Lint: COGNITIVE_COMPLEXITY. The maximum cognitive complexity a function can have
+    /// Lint: COGNITIVE_COMPLEXITY. The maximum cognitive complexity a function can have. Nesting adds points since the scoring was reworked, so thresholds chosen for the old scoring may need to be raised
     (cognitive_complexity_threshold, "cognitive_complexity_threshold": u64, 25),
+    /// Lint: COGNITIVE_COMPLEXITY. The points of each construct, e.g. `{ if = 2, nesting = 0 }`. The keys are `if`, `else`, `match`, `loop`, `bool-ops`, `recursion`, `labeled-jumps` and `nesting`, which is added for every level an `if`, `match` or loop is nested
+    (cognitive_complexity_weights, "cognitive_complexity_weights": crate::utils::conf::CognitiveComplexityWeights, crate::utils::conf::CognitiveComplexityWeights::default()),
+    /// Lint: COGNITIVE_COMPLEXITY. Whether the arms of a `match` on an enum don't count as nesting
+    (cognitive_complexity_flat_enum_matches, "cognitive_complexity_flat_enum_matches": bool, false),
     /// DEPRECATED LINT: CYCLOMATIC_COMPLEXITY. Use the Cognitive Complexity lint instead.
     (cyclomatic_complexity_threshold, "cyclomatic_complexity_threshold": Option<u64>, None),
     /// Lint: DOC_MARKDOWN. The list of words this lint should not consider as identifiers needing ticks
@@ -158,6 +164,59 @@ define_Conf! {
     (max_struct_bools, "max_struct_bools": u64, 3),
     /// Lint: FN_PARAMS_EXCESSIVE_BOOLS. The maximum number of bools function parameters can have
     (max_fn_params_bools, "max_fn_params_bools": u64, 3),
+    /// Lint: DUPLICATE_CODE. The minimum number of expressions a function body or block must have to be checked for copies
+    (duplicate_code_threshold, "duplicate_code_threshold": u64, 30),
+    /// Lint: DROPPED_MUST_USE. The full paths of types whose values must be used, like builders that return `Self` by value, e.g. `["my_crate::config::Builder"]`
+    (must_use_types, "must_use_types": Vec<String>, Vec::new()),
+    /// Lint: MUTABLE_KEY_TYPE, DECLARE_INTERIOR_MUTABLE_CONST, BORROW_INTERIOR_MUTABLE_CONST, UNSAFE_SYNC_STATIC. The full paths of types that are not considered interior mutable, e.g. because their `Hash` ignores the mutable part
+    (ignore_interior_mutability, "ignore_interior_mutability": Vec<String>, ["bytes::Bytes"].iter().map(ToString::to_string).collect()),
+    /// Lint: BLOCKING_IN_ASYNC. The full paths of additional functions that block the current thread, e.g. `["my_crate::db::query"]`
+    (blocking_functions, "blocking_functions": Vec<String>, Vec::new()),
+    /// Lint: LARGE_FUTURES. The maximum size of a future in bytes
+    (future_size_threshold, "future_size_threshold": u64, 16384),
+    /// Lint: MANUAL_STRIP. The minimum Rust version that the crate supports, e.g. `"1.40"`. Lints don't suggest code that needs a newer version
+    (msrv, "msrv": Option<String>, None),
+}
+
+/// The points the constructs add to the cognitive complexity of a function.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct CognitiveComplexityWeights {
+    /// `if` and `else if`
+    #[serde(rename = "if")]
+    pub if_: u64,
+    /// `else`
+    #[serde(rename = "else")]
+    pub else_: u64,
+    /// `match`
+    #[serde(rename = "match")]
+    pub match_: u64,
+    /// `loop`, `while` and `for`
+    #[serde(rename = "loop")]
+    pub loop_: u64,
+    /// A sequence of `&&` or `||`
+    pub bool_ops: u64,
+    /// A call of the function itself
+    pub recursion: u64,
+    /// `break` and `continue` to a label
+    pub labeled_jumps: u64,
+    /// Added to `if`, `match` and loops for every level they are nested
+    pub nesting: u64,
+}
+
+impl Default for CognitiveComplexityWeights {
+    fn default() -> Self {
+        Self {
+            if_: 1,
+            else_: 1,
+            match_: 1,
+            loop_: 1,
+            bool_ops: 1,
+            recursion: 1,
+            labeled_jumps: 1,
+            nesting: 1,
+        }
+    }
 }
 
 impl Default for Conf {
@@ -167,6 +226,23 @@ impl Default for Conf {
     }
 }
 
+impl Conf {
+    /// Reads the `[third-party.<name>]` table of the configuration file into a `T`.
+    ///
+    /// This is how lint libraries loaded with `--lint-lib` get their configuration. Returns
+    /// `Ok(None)` if there is no such table.
+    pub fn third_party_conf<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, Error> {
+        match self.third_party.as_ref().and_then(|third_party| third_party.get(name)) {
+            Some(table) => table
+                .clone()
+                .try_into()
+                .map(Some)
+                .map_err(|e| Error::Toml(e.to_string())),
+            None => Ok(None),
+        }
+    }
+}
+
 /// Search for the configuration file.
 pub fn lookup_conf_file() -> io::Result<Option<PathBuf>> {
     /// Possible filename to search for.
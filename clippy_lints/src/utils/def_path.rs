@@ -0,0 +1,127 @@
+//! Resolution of the item paths in `paths.rs`.
+//!
+//! Hardcoded paths break whenever an item moves inside of `std`, so diagnostic items and lang
+//! items should be preferred. The functions in this module try those first and only fall back to
+//! the path if the current toolchain doesn't know the item. Paths are resolved at most once per
+//! crate.
+
+use crate::utils::{match_def_path, path_to_res};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::Crate;
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::symbol::Symbol;
+use std::cell::RefCell;
+
+thread_local! {
+    /// The paths resolved in the crate that is currently linted. A `DefId` is meaningless in any
+    /// other compilation session, so `ClearResolvedPaths` empties this around each crate.
+    static RESOLVED_PATHS: RefCell<FxHashMap<&'static [&'static str], Option<DefId>>> = RefCell::default();
+}
+
+declare_lint_pass!(
+    /// Clears the resolved paths before and after linting a crate. It has to be registered before
+    /// all other late lint passes.
+    ClearResolvedPaths => []
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ClearResolvedPaths {
+    fn check_crate(&mut self, _: &LateContext<'a, 'tcx>, _: &'tcx Crate<'_>) {
+        RESOLVED_PATHS.with(|resolved| resolved.borrow_mut().clear());
+    }
+
+    fn check_crate_post(&mut self, _: &LateContext<'a, 'tcx>, _: &'tcx Crate<'_>) {
+        RESOLVED_PATHS.with(|resolved| resolved.borrow_mut().clear());
+    }
+}
+
+/// Resolves `path` to the `DefId` of the item it refers to. The result is cached until the end of
+/// the crate.
+pub fn resolve_def_path(cx: &LateContext<'_, '_>, path: &'static [&'static str]) -> Option<DefId> {
+    if let Some(&did) = RESOLVED_PATHS.with(|resolved| resolved.borrow().get(path).copied()) {
+        return did;
+    }
+    let did = path_to_res(cx, path).and_then(|res| res.opt_def_id());
+    RESOLVED_PATHS.with(|resolved| resolved.borrow_mut().insert(path, did));
+    did
+}
+
+/// Checks if `did` refers to the item at `path`.
+///
+/// Paths that cannot be resolved, e.g. because they go through a private module, are compared
+/// against the def path of `did` instead.
+pub fn is_def_path(cx: &LateContext<'_, '_>, did: DefId, path: &'static [&'static str]) -> bool {
+    match resolve_def_path(cx, path) {
+        Some(resolved) => resolved == did,
+        None => match_def_path(cx, did, path),
+    }
+}
+
+/// Checks if `did` is the diagnostic item `diag_item`. If the toolchain doesn't define such a
+/// diagnostic item, `did` is checked against `path` instead.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// if is_diag_item_or_path(cx, adt.did, sym!(vec_type), &paths::VEC) {
+///     // ...
+/// }
+/// ```
+pub fn is_diag_item_or_path(
+    cx: &LateContext<'_, '_>,
+    did: DefId,
+    diag_item: Symbol,
+    path: &'static [&'static str],
+) -> bool {
+    match cx.tcx.get_diagnostic_item(diag_item) {
+        Some(item) => item == did,
+        None => is_def_path(cx, did, path),
+    }
+}
+
+/// Checks if `did` is the lang item `lang_item`, falling back to `path` if there is no such lang
+/// item.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// if is_lang_item_or_path(cx, trait_id, cx.tcx.lang_items().deref_trait(), &paths::DEREF_TRAIT) {
+///     // ...
+/// }
+/// ```
+pub fn is_lang_item_or_path(
+    cx: &LateContext<'_, '_>,
+    did: DefId,
+    lang_item: Option<DefId>,
+    path: &'static [&'static str],
+) -> bool {
+    match lang_item {
+        Some(item) => item == did,
+        None => is_def_path(cx, did, path),
+    }
+}
+
+/// Checks if `ty` is a struct, enum or union type that is the diagnostic item `diag_item`, see
+/// `is_diag_item_or_path`.
+pub fn is_type_diag_item_or_path(
+    cx: &LateContext<'_, '_>,
+    ty: Ty<'_>,
+    diag_item: Symbol,
+    path: &'static [&'static str],
+) -> bool {
+    match ty.kind {
+        ty::Adt(adt, _) => is_diag_item_or_path(cx, adt.did, diag_item, path),
+        _ => false,
+    }
+}
+
+/// Checks if `ty` is a struct, enum or union type at `path`. Unlike `match_type`, this resolves
+/// the path once and compares `DefId`s afterwards.
+pub fn is_type_def_path(cx: &LateContext<'_, '_>, ty: Ty<'_>, path: &'static [&'static str]) -> bool {
+    match ty.kind {
+        ty::Adt(adt, _) => is_def_path(cx, adt.did, path),
+        _ => false,
+    }
+}
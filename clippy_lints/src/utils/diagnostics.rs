@@ -1,11 +1,52 @@
 //! Clippy wrappers around rustc's diagnostic functions.
 
+use crate::utils::sugg::Fix;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{Applicability, CodeSuggestion, DiagnosticBuilder, Substitution, SubstitutionPart, SuggestionStyle};
 use rustc_hir::HirId;
-use rustc_lint::{LateContext, Lint, LintContext};
+use rustc_lint::{EarlyContext, LateContext, Level, Lint, LintContext};
+use rustc_middle::lint::LintSource;
 use rustc_span::source_map::{MultiSpan, Span};
+use rustc_span::BytePos;
+use std::cell::RefCell;
 use std::env;
 
+thread_local! {
+    /// The spans of the lints in `allow` attributes that suppressed a Clippy lint, used by
+    /// `unused_clippy_allow`.
+    static USED_ALLOWS: RefCell<FxHashSet<Span>> = RefCell::default();
+}
+
+/// A lint context that knows where the level of a lint at the current node comes from.
+pub trait LintLevelSource: LintContext {
+    /// Returns the level of `lint` at the current node and where it is set.
+    fn lint_level_source(&self, lint: &'static Lint) -> (Level, LintSource);
+}
+
+impl LintLevelSource for LateContext<'_, '_> {
+    fn lint_level_source(&self, lint: &'static Lint) -> (Level, LintSource) {
+        self.tcx.lint_level_at_node(lint, self.last_node_with_lint_attrs)
+    }
+}
+
+impl LintLevelSource for EarlyContext<'_> {
+    fn lint_level_source(&self, lint: &'static Lint) -> (Level, LintSource) {
+        self.builder.lint_level(lint)
+    }
+}
+
+/// Records the `allow` attribute the level comes from, if the lint is allowed by one.
+pub(crate) fn record_allow(level: (Level, LintSource)) {
+    if let (Level::Allow, LintSource::Node(_, span, _)) = level {
+        USED_ALLOWS.with(|used| used.borrow_mut().insert(span));
+    }
+}
+
+/// Checks if the lint at `span` in an `allow` attribute suppressed a Clippy lint.
+pub(crate) fn is_allow_used(span: Span) -> bool {
+    USED_ALLOWS.with(|used| used.borrow().contains(&span))
+}
+
 fn docs_link(diag: &mut DiagnosticBuilder<'_>, lint: &'static Lint) {
     if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err() {
         diag.help(&format!(
@@ -36,7 +77,8 @@ fn docs_link(diag: &mut DiagnosticBuilder<'_>, lint: &'static Lint) {
 /// 17 |     std::mem::forget(seven);
 ///    |     ^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
-pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: &str) {
+pub fn span_lint<T: LintLevelSource>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: &str) {
+    record_allow(cx.lint_level_source(lint));
     cx.struct_span_lint(lint, sp, |diag| {
         let mut diag = diag.build(msg);
         docs_link(&mut diag, lint);
@@ -62,7 +104,7 @@ pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<Mult
 ///    |
 ///    = help: Consider using `f64::NAN` if you would like a constant representing NaN
 /// ```
-pub fn span_lint_and_help<'a, T: LintContext>(
+pub fn span_lint_and_help<'a, T: LintLevelSource>(
     cx: &'a T,
     lint: &'static Lint,
     span: Span,
@@ -70,6 +112,7 @@ pub fn span_lint_and_help<'a, T: LintContext>(
     help_span: Option<Span>,
     help: &str,
 ) {
+    record_allow(cx.lint_level_source(lint));
     cx.struct_span_lint(lint, span, |diag| {
         let mut diag = diag.build(msg);
         if let Some(help_span) = help_span {
@@ -103,7 +146,7 @@ pub fn span_lint_and_help<'a, T: LintContext>(
 /// 10 |     forget(&SomeStruct);
 ///    |            ^^^^^^^^^^^
 /// ```
-pub fn span_lint_and_note<'a, T: LintContext>(
+pub fn span_lint_and_note<'a, T: LintLevelSource>(
     cx: &'a T,
     lint: &'static Lint,
     span: Span,
@@ -111,6 +154,7 @@ pub fn span_lint_and_note<'a, T: LintContext>(
     note_span: Option<Span>,
     note: &str,
 ) {
+    record_allow(cx.lint_level_source(lint));
     cx.struct_span_lint(lint, span, |diag| {
         let mut diag = diag.build(msg);
         if let Some(note_span) = note_span {
@@ -126,10 +170,11 @@ pub fn span_lint_and_note<'a, T: LintContext>(
 /// Like `span_lint` but allows to add notes, help and suggestions using a closure.
 ///
 /// If you need to customize your lint output a lot, use this function.
-pub fn span_lint_and_then<'a, T: LintContext, F>(cx: &'a T, lint: &'static Lint, sp: Span, msg: &str, f: F)
+pub fn span_lint_and_then<'a, T: LintLevelSource, F>(cx: &'a T, lint: &'static Lint, sp: Span, msg: &str, f: F)
 where
     F: for<'b> FnOnce(&mut DiagnosticBuilder<'b>),
 {
+    record_allow(cx.lint_level_source(lint));
     cx.struct_span_lint(lint, sp, |diag| {
         let mut diag = diag.build(msg);
         f(&mut diag);
@@ -139,6 +184,7 @@ where
 }
 
 pub fn span_lint_hir(cx: &LateContext<'_, '_>, lint: &'static Lint, hir_id: HirId, sp: Span, msg: &str) {
+    record_allow(cx.tcx.lint_level_at_node(lint, hir_id));
     cx.tcx.struct_span_lint_hir(lint, hir_id, sp, |diag| {
         let mut diag = diag.build(msg);
         docs_link(&mut diag, lint);
@@ -154,6 +200,7 @@ pub fn span_lint_hir_and_then(
     msg: &str,
     f: impl FnOnce(&mut DiagnosticBuilder<'_>),
 ) {
+    record_allow(cx.tcx.lint_level_at_node(lint, hir_id));
     cx.tcx.struct_span_lint_hir(lint, hir_id, sp, |diag| {
         let mut diag = diag.build(msg);
         f(&mut diag);
@@ -178,7 +225,7 @@ pub fn span_lint_hir_and_then(
 ///     = note: `-D fold-any` implied by `-D warnings`
 /// ```
 #[allow(clippy::collapsible_span_lint_calls)]
-pub fn span_lint_and_sugg<'a, T: LintContext>(
+pub fn span_lint_and_sugg<'a, T: LintLevelSource>(
     cx: &'a T,
     lint: &'static Lint,
     sp: Span,
@@ -192,6 +239,54 @@ pub fn span_lint_and_sugg<'a, T: LintContext>(
     });
 }
 
+/// Add a span lint with a structured `Fix`.
+///
+/// Use this instead of `span_lint_and_sugg` when the fix needs more than one edit, e.g. when an
+/// import has to be added or a function and all of its callers have to be changed.
+///
+/// ```ignore
+/// error: this map only has unit values
+///   --> $DIR/map_unit.rs:5:5
+///    |
+/// 5  |     let m: HashMap<u32, ()> = HashMap::new();
+///    |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+///    |
+/// help: use `HashSet` instead
+///    |
+/// 1  | use std::collections::HashSet;
+///    |
+/// 5  |     let m: HashSet<u32> = HashSet::new();
+///    |            ^^^^^^^^^^^^   ^^^^^^^^^^^^^^
+/// ```
+pub fn span_lint_and_fix<T: LintLevelSource>(cx: &T, lint: &'static Lint, sp: Span, msg: &str, fix: Fix) {
+    span_lint_and_then(cx, lint, sp, msg, |diag| add_fix(cx, diag, fix));
+}
+
+/// Adds the edits of `fix` to `diag` as suggestions.
+///
+/// A single suggestion can only change one file, so edits in different files are added as one
+/// suggestion per file, all with the same message.
+pub fn add_fix<T: LintContext>(cx: &T, diag: &mut DiagnosticBuilder<'_>, fix: Fix) {
+    let source_map = cx.sess().source_map();
+    let mut files: Vec<(BytePos, Vec<(Span, String)>)> = Vec::new();
+    for (span, replacement) in fix.edits() {
+        let file = source_map.lookup_source_file(span.lo()).start_pos;
+        match files.iter_mut().find(|(start, _)| *start == file) {
+            Some((_, edits)) => edits.push((*span, replacement.clone())),
+            None => files.push((file, vec![(*span, replacement.clone())])),
+        }
+    }
+
+    for (_, mut edits) in files {
+        if edits.len() == 1 {
+            let (span, replacement) = edits.remove(0);
+            diag.span_suggestion(span, fix.msg(), replacement, fix.applicability());
+        } else {
+            diag.multipart_suggestion(fix.msg(), edits, fix.applicability());
+        }
+    }
+}
+
 /// Create a suggestion made from several `span → replacement`.
 ///
 /// Note: in the JSON format (used by `compiletest_rs`), the help message will
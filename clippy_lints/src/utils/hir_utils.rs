@@ -1,11 +1,13 @@
 use crate::consts::{constant_context, constant_simple};
 use crate::utils::differing_macro_contexts;
 use rustc_ast::ast::Name;
+use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_hir::def::Res;
 use rustc_hir::{
     BinOpKind, Block, BlockCheckMode, BodyId, BorrowKind, CaptureBy, Expr, ExprKind, Field, FnRetTy, GenericArg,
-    GenericArgs, Guard, Lifetime, LifetimeName, ParamName, Pat, PatKind, Path, PathSegment, QPath, Stmt, StmtKind, Ty,
-    TyKind, TypeBinding,
+    GenericArgs, Guard, HirId, Lifetime, LifetimeName, ParamName, Pat, PatKind, Path, PathSegment, QPath, Stmt,
+    StmtKind, Ty, TyKind, TypeBinding,
 };
 use rustc_lint::LateContext;
 use rustc_middle::ich::StableHashingContextProvider;
@@ -25,6 +27,12 @@ pub struct SpanlessEq<'a, 'tcx> {
     /// If is true, never consider as equal expressions containing function
     /// calls.
     ignore_fn: bool,
+    /// If this is `Some`, local bindings are compared by where they are bound instead of by their
+    /// name. Maps the bindings of the left side to the ones of the right side.
+    locals: Option<FxHashMap<HirId, HirId>>,
+    /// The tables of the left and right side if they belong to different bodies. The types of the
+    /// compared expressions and patterns have to be the same then.
+    body_tables: Option<(&'a TypeckTables<'tcx>, &'a TypeckTables<'tcx>)>,
 }
 
 impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
@@ -33,6 +41,8 @@ impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
             cx,
             tables: cx.tables,
             ignore_fn: false,
+            locals: None,
+            body_tables: None,
         }
     }
 
@@ -41,6 +51,25 @@ impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
             cx: self.cx,
             tables: self.cx.tables,
             ignore_fn: true,
+            locals: self.locals,
+            body_tables: self.body_tables,
+        }
+    }
+
+    /// Considers code equal up to the names of local bindings, e.g. `let a = 1; a + 1` and
+    /// `let b = 1; b + 1`. Closures are compared by their parameters and bodies as well.
+    pub fn ignore_local_names(self) -> Self {
+        Self {
+            locals: Some(FxHashMap::default()),
+            ..self
+        }
+    }
+
+    /// Compares code from two different bodies, using `left` and `right` as their tables.
+    pub fn with_body_tables(self, left: &'a TypeckTables<'tcx>, right: &'a TypeckTables<'tcx>) -> Self {
+        Self {
+            body_tables: Some((left, right)),
+            ..self
         }
     }
 
@@ -71,9 +100,14 @@ impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
             return false;
         }
 
+        let (l_tables, r_tables) = self.body_tables.unwrap_or((self.tables, self.tables));
+        if self.body_tables.is_some() && l_tables.expr_ty_opt(left) != r_tables.expr_ty_opt(right) {
+            return false;
+        }
+
         if let (Some(l), Some(r)) = (
-            constant_simple(self.cx, self.tables, left),
-            constant_simple(self.cx, self.tables, right),
+            constant_simple(self.cx, l_tables, left),
+            constant_simple(self.cx, r_tables, right),
         ) {
             if l == r {
                 return true;
@@ -108,6 +142,13 @@ impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
             (&ExprKind::Call(l_fun, l_args), &ExprKind::Call(r_fun, r_args)) => {
                 !self.ignore_fn && self.eq_expr(l_fun, r_fun) && self.eq_exprs(l_args, r_args)
             },
+            (&ExprKind::Closure(lc, _, lb, _, lm), &ExprKind::Closure(rc, _, rb, _, rm)) if self.locals.is_some() => {
+                let (lb, rb) = (self.cx.tcx.hir().body(lb), self.cx.tcx.hir().body(rb));
+                lc == rc
+                    && lm == rm
+                    && over(lb.params, rb.params, |l, r| self.eq_pat(&l.pat, &r.pat))
+                    && self.eq_expr(&lb.value, &rb.value)
+            },
             (&ExprKind::Cast(ref lx, ref lt), &ExprKind::Cast(ref rx, ref rt))
             | (&ExprKind::Type(ref lx, ref lt), &ExprKind::Type(ref rx, ref rt)) => {
                 self.eq_expr(lx, rx) && self.eq_ty(lt, rt)
@@ -185,13 +226,25 @@ impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
 
     /// Checks whether two patterns are the same.
     pub fn eq_pat(&mut self, left: &Pat<'_>, right: &Pat<'_>) -> bool {
+        if let Some((l_tables, r_tables)) = self.body_tables {
+            if l_tables.pat_ty_opt(left) != r_tables.pat_ty_opt(right) {
+                return false;
+            }
+        }
+
         match (&left.kind, &right.kind) {
             (&PatKind::Box(ref l), &PatKind::Box(ref r)) => self.eq_pat(l, r),
             (&PatKind::TupleStruct(ref lp, ref la, ls), &PatKind::TupleStruct(ref rp, ref ra, rs)) => {
                 self.eq_qpath(lp, rp) && over(la, ra, |l, r| self.eq_pat(l, r)) && ls == rs
             },
-            (&PatKind::Binding(ref lb, .., ref li, ref lp), &PatKind::Binding(ref rb, .., ref ri, ref rp)) => {
-                lb == rb && li.name.as_str() == ri.name.as_str() && both(lp, rp, |l, r| self.eq_pat(l, r))
+            (&PatKind::Binding(ref lb, l_id, ref li, ref lp), &PatKind::Binding(ref rb, r_id, ref ri, ref rp)) => {
+                let same_name = if let Some(ref mut locals) = self.locals {
+                    locals.insert(l_id, r_id);
+                    true
+                } else {
+                    li.name.as_str() == ri.name.as_str()
+                };
+                lb == rb && same_name && both(lp, rp, |l, r| self.eq_pat(l, r))
             },
             (&PatKind::Path(ref l), &PatKind::Path(ref r)) => self.eq_qpath(l, r),
             (&PatKind::Lit(ref l), &PatKind::Lit(ref r)) => self.eq_expr(l, r),
@@ -214,6 +267,15 @@ impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
 
     #[allow(clippy::similar_names)]
     fn eq_qpath(&mut self, left: &QPath<'_>, right: &QPath<'_>) -> bool {
+        if let Some(ref locals) = self.locals {
+            if let (&QPath::Resolved(None, ref lpath), &QPath::Resolved(None, ref rpath)) = (left, right) {
+                if let (Res::Local(l_id), Res::Local(r_id)) = (lpath.res, rpath.res) {
+                    // bindings from outside of the compared code have to be the same
+                    return locals.get(&l_id).map_or(l_id == r_id, |&id| id == r_id);
+                }
+            }
+        }
+
         match (left, right) {
             (&QPath::Resolved(ref lty, ref lpath), &QPath::Resolved(ref rty, ref rpath)) => {
                 both(lty, rty, |l, r| self.eq_ty(l, r)) && self.eq_path(lpath, rpath)
@@ -357,6 +419,9 @@ pub struct SpanlessHash<'a, 'tcx> {
     cx: &'a LateContext<'a, 'tcx>,
     tables: &'a TypeckTables<'tcx>,
     s: StableHasher,
+    /// If this is `Some`, local bindings are hashed by the order in which they are first seen
+    /// instead of by their name.
+    locals: Option<FxHashMap<HirId, usize>>,
 }
 
 impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
@@ -365,6 +430,16 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
             cx,
             tables,
             s: StableHasher::new(),
+            locals: None,
+        }
+    }
+
+    /// Makes the hash independent of the names of local bindings, e.g. `let a = 1; a + 1` and
+    /// `let b = 1; b + 1` hash to the same value.
+    pub fn ignore_local_names(self) -> Self {
+        Self {
+            locals: Some(FxHashMap::default()),
+            ..self
         }
     }
 
@@ -464,7 +539,11 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
                 }
                 .hash(&mut self.s);
                 // closures inherit TypeckTables
-                self.hash_expr(&self.cx.tcx.hir().body(eid).value);
+                let body = self.cx.tcx.hir().body(eid);
+                for param in body.params {
+                    self.hash_pat(&param.pat);
+                }
+                self.hash_expr(&body.value);
             },
             ExprKind::Field(ref e, ref f) => {
                 self.hash_expr(e);
@@ -488,7 +567,7 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
                 self.hash_expr(e);
 
                 for arm in arms {
-                    // TODO: arm.pat?
+                    self.hash_pat(&arm.pat);
                     if let Some(ref e) = arm.guard {
                         self.hash_guard(e);
                     }
@@ -549,6 +628,14 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
     }
 
     pub fn hash_qpath(&mut self, p: &QPath<'_>) {
+        if let QPath::Resolved(None, ref path) = *p {
+            if let Res::Local(id) = path.res {
+                if self.hash_local(id) {
+                    return;
+                }
+            }
+        }
+
         match *p {
             QPath::Resolved(_, ref path) => {
                 self.hash_path(path);
@@ -567,11 +654,103 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
         }
     }
 
+    /// Hashes the position of a local binding if local names are ignored. Returns `false` if the
+    /// name should be hashed instead.
+    fn hash_local(&mut self, id: HirId) -> bool {
+        if let Some(ref mut locals) = self.locals {
+            let next = locals.len();
+            locals.entry(id).or_insert(next).hash(&mut self.s);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn hash_pat(&mut self, pat: &Pat<'_>) {
+        std::mem::discriminant(&pat.kind).hash(&mut self.s);
+
+        match pat.kind {
+            PatKind::Binding(ann, id, ident, sub) => {
+                std::mem::discriminant(&ann).hash(&mut self.s);
+                if !self.hash_local(id) {
+                    self.hash_name(ident.name);
+                }
+                if let Some(sub) = sub {
+                    self.hash_pat(sub);
+                }
+            },
+            PatKind::Box(ref pat) => {
+                self.hash_pat(pat);
+            },
+            PatKind::Ref(ref pat, mutbl) => {
+                mutbl.hash(&mut self.s);
+                self.hash_pat(pat);
+            },
+            PatKind::Lit(ref e) => {
+                self.hash_expr(e);
+            },
+            PatKind::Range(ref start, ref end, ref range_end) => {
+                if let Some(ref start) = *start {
+                    self.hash_expr(start);
+                }
+                if let Some(ref end) = *end {
+                    self.hash_expr(end);
+                }
+                std::mem::discriminant(range_end).hash(&mut self.s);
+            },
+            PatKind::Path(ref qpath) => {
+                self.hash_qpath(qpath);
+            },
+            PatKind::TupleStruct(ref qpath, pats, dotdot) => {
+                self.hash_qpath(qpath);
+                for pat in pats {
+                    self.hash_pat(pat);
+                }
+                dotdot.hash(&mut self.s);
+            },
+            PatKind::Tuple(pats, dotdot) => {
+                for pat in pats {
+                    self.hash_pat(pat);
+                }
+                dotdot.hash(&mut self.s);
+            },
+            PatKind::Or(pats) => {
+                for pat in pats {
+                    self.hash_pat(pat);
+                }
+            },
+            PatKind::Struct(ref qpath, fields, etc) => {
+                self.hash_qpath(qpath);
+                for field in fields {
+                    self.hash_name(field.ident.name);
+                    self.hash_pat(&field.pat);
+                }
+                etc.hash(&mut self.s);
+            },
+            PatKind::Slice(before, ref mid, after) => {
+                for pat in before {
+                    self.hash_pat(pat);
+                }
+                if let Some(ref mid) = *mid {
+                    self.hash_pat(mid);
+                }
+                for pat in after {
+                    self.hash_pat(pat);
+                }
+            },
+            PatKind::Wild => {},
+        }
+    }
+
     pub fn hash_stmt(&mut self, b: &Stmt<'_>) {
         std::mem::discriminant(&b.kind).hash(&mut self.s);
 
         match &b.kind {
             StmtKind::Local(local) => {
+                self.hash_pat(&local.pat);
+                if let Some(ref ty) = local.ty {
+                    self.hash_ty(ty);
+                }
                 if let Some(ref init) = local.init {
                     self.hash_expr(init);
                 }
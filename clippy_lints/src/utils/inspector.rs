@@ -6,6 +6,7 @@ use rustc_hir as hir;
 use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_session::Session;
 use rustc_session::{declare_lint_pass, declare_tool_lint};
+use std::io;
 
 declare_clippy_lint! {
     /// **What it does:** Dumps every ast/hir node which has the `#[clippy::dump]`
@@ -24,6 +25,17 @@ declare_clippy_lint! {
     /// visibility inherited from outer item
     /// extern crate dylib source: "/path/to/foo.so"
     /// ```
+    ///
+    /// Functions, methods and constants annotated with `#[clippy::dump = "mir"]`
+    /// have their optimized MIR dumped instead:
+    ///
+    /// ```rust,ignore
+    /// #[clippy::dump = "mir"]
+    /// fn foo(x: u32) -> u32 {
+    ///     x + 1
+    /// }
+    /// ```
+    #[clippy::version = "1.29.0"]
     pub DEEP_CODE_INSPECTION,
     internal_warn,
     "helper to dump info about code"
@@ -36,6 +48,10 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for DeepCodeInspector {
         if !has_attr(cx.sess(), &item.attrs) {
             return;
         }
+        if wants_mir(cx.sess(), &item.attrs) {
+            print_mir(cx, item.hir_id);
+            return;
+        }
         print_item(cx, item);
     }
 
@@ -43,6 +59,10 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for DeepCodeInspector {
         if !has_attr(cx.sess(), &item.attrs) {
             return;
         }
+        if wants_mir(cx.sess(), &item.attrs) {
+            print_mir(cx, item.hir_id);
+            return;
+        }
         println!("impl item `{}`", item.ident.name);
         match item.vis.node {
             hir::VisibilityKind::Public => println!("public"),
@@ -140,6 +160,22 @@ fn has_attr(sess: &Session, attrs: &[Attribute]) -> bool {
     get_attr(sess, attrs, "dump").count() > 0
 }
 
+/// Checks for `#[clippy::dump = "mir"]`.
+fn wants_mir(sess: &Session, attrs: &[Attribute]) -> bool {
+    get_attr(sess, attrs, "dump").any(|attr| attr.value_str().map_or(false, |value| value.as_str() == "mir"))
+}
+
+fn print_mir(cx: &LateContext<'_, '_>, hir_id: hir::HirId) {
+    if cx.tcx.hir().maybe_body_owned_by(hir_id).is_none() {
+        println!("no MIR: the item has no body");
+        return;
+    }
+    let def_id = cx.tcx.hir().local_def_id(hir_id).to_def_id();
+    if let Err(e) = rustc_mir::util::write_mir_pretty(cx.tcx, Some(def_id), &mut io::stdout()) {
+        println!("failed to dump MIR: {}", e);
+    }
+}
+
 #[allow(clippy::similar_names)]
 #[allow(clippy::too_many_lines)]
 fn print_expr(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, indent: usize) {
@@ -0,0 +1,145 @@
+//! A shared query for whether a type has interior mutability, i.e. contains an `UnsafeCell`
+//! without any indirection.
+//!
+//! Types can't outlive a lint pass, so instead of caching the result per `Ty`, every type
+//! definition is summarized once in terms of its generic parameters. Checking a concrete type
+//! then only has to look at the generic arguments the summary depends on.
+
+use crate::utils::match_def_path;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, AdtDef, Ty, TypeFoldable};
+use rustc_span::DUMMY_SP;
+
+/// How a type (definition) is interior mutable.
+#[derive(Clone)]
+enum Summary {
+    Never,
+    Always,
+    /// Only if one of the type parameters with the given indices is.
+    Params(Vec<u32>),
+    /// The type contains projections, trait objects or similar and has to be checked with
+    /// `is_freeze` once it is known.
+    Unknown,
+}
+
+impl Summary {
+    fn join(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Always, _) | (_, Self::Always) => Self::Always,
+            (Self::Unknown, _) | (_, Self::Unknown) => Self::Unknown,
+            (Self::Never, other) | (other, Self::Never) => other,
+            (Self::Params(mut left), Self::Params(right)) => {
+                left.extend(right);
+                left.sort_unstable();
+                left.dedup();
+                Self::Params(left)
+            },
+        }
+    }
+}
+
+/// Answers whether types are interior mutable, ignoring the types from the
+/// `ignore-interior-mutability` configuration.
+pub struct InteriorMut {
+    /// The paths of the ignored types, split into their segments.
+    ignored: Vec<Vec<String>>,
+    adts: FxHashMap<DefId, Summary>,
+}
+
+impl InteriorMut {
+    pub fn new(ignored: &[String]) -> Self {
+        Self {
+            ignored: ignored
+                .iter()
+                .map(|path| path.split("::").map(ToString::to_string).collect())
+                .collect(),
+            adts: FxHashMap::default(),
+        }
+    }
+
+    /// Is the type definition listed in `ignore-interior-mutability`?
+    pub fn is_ignored(&self, cx: &LateContext<'_, '_>, did: DefId) -> bool {
+        self.ignored.iter().any(|path| {
+            let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+            match_def_path(cx, did, &path)
+        })
+    }
+
+    /// Returns `Some(true)` if the type is interior mutable, `Some(false)` if it isn't and `None`
+    /// if that depends on generic parameters that are not known yet.
+    pub fn is_interior_mut_ty<'tcx>(&mut self, cx: &LateContext<'_, 'tcx>, ty: Ty<'tcx>) -> Option<bool> {
+        match self.summarize(cx, ty) {
+            Summary::Never => Some(false),
+            Summary::Always => Some(true),
+            Summary::Params(_) => None,
+            Summary::Unknown if ty.needs_subst() => None,
+            Summary::Unknown => Some(!ty.is_freeze(cx.tcx, cx.param_env, DUMMY_SP)),
+        }
+    }
+
+    fn summarize<'tcx>(&mut self, cx: &LateContext<'_, 'tcx>, ty: Ty<'tcx>) -> Summary {
+        match ty.kind {
+            ty::Adt(def, substs) => {
+                if self.is_ignored(cx, def.did) {
+                    return Summary::Never;
+                }
+                if Some(def.did) == cx.tcx.lang_items().unsafe_cell_type() {
+                    return Summary::Always;
+                }
+                match self.summarize_adt(cx, def) {
+                    Summary::Params(params) => params.iter().fold(Summary::Never, |acc, &index| {
+                        acc.join(self.summarize(cx, substs.type_at(index as usize)))
+                    }),
+                    Summary::Unknown if !ty.needs_subst() => {
+                        if ty.is_freeze(cx.tcx, cx.param_env, DUMMY_SP) {
+                            Summary::Never
+                        } else {
+                            Summary::Always
+                        }
+                    },
+                    summary => summary,
+                }
+            },
+            ty::Param(param) => Summary::Params(vec![param.index]),
+            ty::Array(inner, size) => {
+                if size.try_eval_usize(cx.tcx, cx.param_env) == Some(0) {
+                    Summary::Never
+                } else {
+                    self.summarize(cx, inner)
+                }
+            },
+            ty::Slice(inner) => self.summarize(cx, inner),
+            ty::Tuple(..) => ty
+                .tuple_fields()
+                .fold(Summary::Never, |acc, ty| acc.join(self.summarize(cx, ty))),
+            ty::Bool
+            | ty::Char
+            | ty::Int(_)
+            | ty::Uint(_)
+            | ty::Float(_)
+            | ty::Str
+            | ty::RawPtr(_)
+            | ty::Ref(..)
+            | ty::FnDef(..)
+            | ty::FnPtr(_)
+            | ty::Never => Summary::Never,
+            _ => Summary::Unknown,
+        }
+    }
+
+    fn summarize_adt<'tcx>(&mut self, cx: &LateContext<'_, 'tcx>, def: &'tcx AdtDef) -> Summary {
+        if let Some(summary) = self.adts.get(&def.did) {
+            return summary.clone();
+        }
+        // Sized types can only contain themselves behind a pointer, which is never interior
+        // mutable, so assuming `Never` while the fields are summarized doesn't change the result.
+        self.adts.insert(def.did, Summary::Never);
+        let summary = def.all_fields().fold(Summary::Never, |acc, field| {
+            acc.join(self.summarize(cx, cx.tcx.type_of(field.did)))
+        });
+        self.adts.insert(def.did, summary.clone());
+        summary
+    }
+}
@@ -1,7 +1,7 @@
 use crate::utils::SpanlessEq;
 use crate::utils::{
-    is_expn_of, match_def_path, match_qpath, match_type, method_calls, paths, run_lints, snippet, span_lint,
-    span_lint_and_help, span_lint_and_sugg, walk_ptrs_ty,
+    get_attr, is_expn_of, match_def_path, match_qpath, match_type, method_calls, path_to_res, paths, run_lints,
+    snippet, span_lint, span_lint_and_help, span_lint_and_sugg, walk_ptrs_ty,
 };
 use if_chain::if_chain;
 use rustc_ast::ast::{Crate as AstCrate, ItemKind, LitKind, Name, NodeId};
@@ -11,8 +11,8 @@ use rustc_errors::Applicability;
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::hir_id::CRATE_HIR_ID;
-use rustc_hir::intravisit::{NestedVisitorMap, Visitor};
-use rustc_hir::{Crate, Expr, ExprKind, HirId, Item, MutTy, Mutability, Path, StmtKind, Ty, TyKind};
+use rustc_hir::intravisit::{walk_expr, NestedVisitorMap, Visitor};
+use rustc_hir::{Crate, Expr, ExprKind, HirId, Item, MutTy, Mutability, Node, Path, StmtKind, Ty, TyKind};
 use rustc_lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass};
 use rustc_middle::hir::map::Map;
 use rustc_session::{declare_lint_pass, declare_tool_lint, impl_lint_pass};
@@ -29,6 +29,7 @@ declare_clippy_lint! {
     /// **Known problems:** None.
     ///
     /// **Example:** Wrong ordering of the util::paths constants.
+    #[clippy::version = "1.29.0"]
     pub CLIPPY_LINTS_INTERNAL,
     internal,
     "various things that will negatively affect your clippy experience"
@@ -53,6 +54,7 @@ declare_clippy_lint! {
     /// declare_lint_pass!(Pass => [LINT_1, LINT_2]);
     /// // missing FORGOTTEN_LINT
     /// ```
+    #[clippy::version = "1.29.0"]
     pub LINT_WITHOUT_LINT_PASS,
     internal,
     "declaring a lint without associating it in a LintPass"
@@ -77,6 +79,7 @@ declare_clippy_lint! {
     /// ```rust,ignore
     /// utils::span_lint(cx, LINT_NAME, "message");
     /// ```
+    #[clippy::version = "1.29.0"]
     pub COMPILER_LINT_FUNCTIONS,
     internal,
     "usage of the lint functions of the compiler instead of the utils::* variant"
@@ -100,6 +103,7 @@ declare_clippy_lint! {
     /// ```rust,ignore
     /// expr.span.ctxt().outer_expn_data()
     /// ```
+    #[clippy::version = "1.29.0"]
     pub OUTER_EXPN_EXPN_DATA,
     internal,
     "using `cx.outer_expn().expn_data()` instead of `cx.outer_expn_data()`"
@@ -118,6 +122,7 @@ declare_clippy_lint! {
     /// ```rust,ignore
     /// 🍦🍦🍦🍦🍦
     /// ```
+    #[clippy::version = "1.29.0"]
     pub PRODUCE_ICE,
     internal,
     "this message should not appear anywhere as we ICE before and don't emit the lint"
@@ -141,11 +146,71 @@ declare_clippy_lint! {
     /// ```rust,ignore
     /// declare_lint! { pub COOL_LINT, nursery, "a great new lint" }
     /// ```
+    #[clippy::version = "1.29.0"]
     pub DEFAULT_LINT,
     internal,
     "found 'default lint description' in a lint declaration"
 }
 
+declare_clippy_lint! {
+    /// **What it does:** Checks for `declare_clippy_lint!` and `declare_deprecated_lint!`
+    /// invocations without a `#[clippy::version]` attribute.
+    ///
+    /// **Why is this bad?** The version is shown on the lint list website and used by
+    /// `cargo dev changelog`. Every lint should state the Clippy version it was added in,
+    /// and every deprecated lint the version it was deprecated in.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// Bad:
+    /// ```rust,ignore
+    /// declare_clippy_lint! {
+    ///     pub COOL_LINT,
+    ///     nursery,
+    ///     "a great new lint"
+    /// }
+    /// ```
+    ///
+    /// Good:
+    /// ```rust,ignore
+    /// declare_clippy_lint! {
+    ///     #[clippy::version = "1.45.0"]
+    ///     pub COOL_LINT,
+    ///     nursery,
+    ///     "a great new lint"
+    /// }
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MISSING_CLIPPY_VERSION_ATTRIBUTE,
+    internal,
+    "found a lint declaration without a `#[clippy::version]` attribute"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `#[clippy::version]` attributes whose value is neither a
+    /// `major.minor.patch` version nor `pre 1.29.0`.
+    ///
+    /// **Why is this bad?** The tooling that reads the versions can't parse them.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// Bad:
+    /// ```rust,ignore
+    /// #[clippy::version = "1.45"]
+    /// ```
+    ///
+    /// Good:
+    /// ```rust,ignore
+    /// #[clippy::version = "1.45.0"]
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub INVALID_CLIPPY_VERSION_ATTRIBUTE,
+    internal,
+    "found an invalid `#[clippy::version]` attribute"
+}
+
 declare_clippy_lint! {
     /// **What it does:** Lints `span_lint_and_then` function calls, where the
     /// closure argument has only one statement and that statement is a method
@@ -202,11 +267,83 @@ declare_clippy_lint! {
     /// span_lint_and_note(cx, TEST_LINT, expr.span, lint_msg, Some(expr.span), note_msg);
     /// span_lint_and_note(cx, TEST_LINT, expr.span, lint_msg, None, note_msg);
     /// ```
+    #[clippy::version = "1.29.0"]
     pub COLLAPSIBLE_SPAN_LINT_CALLS,
     internal,
     "found collapsible `span_lint_and_then` calls"
 }
 
+declare_clippy_lint! {
+    /// **What it does:** Checks for calls to `utils::match_type()` on a type diagnostic item
+    /// and suggests to use `utils::is_type_diagnostic_item()` instead.
+    ///
+    /// **Why is this bad?** Diagnostic items don't break when the item is moved to a different
+    /// module of `std`. `utils::match_type()` compares the def path as a string, which does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// Bad:
+    /// ```rust,ignore
+    /// utils::match_type(cx, ty, &paths::VEC)
+    /// ```
+    ///
+    /// Good:
+    /// ```rust,ignore
+    /// utils::is_type_diagnostic_item(cx, ty, sym!(vec_type))
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MATCH_TYPE_ON_DIAGNOSTIC_ITEM,
+    internal,
+    "using `utils::match_type()` instead of `utils::is_type_diagnostic_item()`"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `span_lint_and_sugg` calls with
+    /// `Applicability::MachineApplicable` whose suggestion is built with `snippet`,
+    /// `snippet_block` or `Sugg::hir`.
+    ///
+    /// **Why is this bad?** These functions fall back to a placeholder like `".."` when the code
+    /// of the span isn't available, e.g. in macros. `cargo fix` applies machine-applicable
+    /// suggestions without asking, and the placeholder doesn't compile. The
+    /// `*_with_applicability` versions lower the applicability when they use the placeholder.
+    ///
+    /// **Known problems:** Only finds the calls in the suggestion argument itself, not in local
+    /// bindings that are used there.
+    ///
+    /// **Example:**
+    /// Bad:
+    /// ```rust,ignore
+    /// span_lint_and_sugg(
+    ///     cx,
+    ///     TEST_LINT,
+    ///     expr.span,
+    ///     lint_msg,
+    ///     help_msg,
+    ///     format!("&*{}", snippet(cx, arg.span, "..")),
+    ///     Applicability::MachineApplicable,
+    /// );
+    /// ```
+    ///
+    /// Good:
+    /// ```rust,ignore
+    /// let mut applicability = Applicability::MachineApplicable;
+    /// span_lint_and_sugg(
+    ///     cx,
+    ///     TEST_LINT,
+    ///     expr.span,
+    ///     lint_msg,
+    ///     help_msg,
+    ///     format!("&*{}", snippet_with_applicability(cx, arg.span, "..", &mut applicability)),
+    ///     applicability,
+    /// );
+    /// ```
+    #[clippy::version = "1.45.0"]
+    pub MACHINE_APPLICABLE_SNIPPET,
+    internal,
+    "using a snippet with a placeholder in a machine-applicable suggestion"
+}
+
 declare_lint_pass!(ClippyLintsInternal => [CLIPPY_LINTS_INTERNAL]);
 
 impl EarlyLintPass for ClippyLintsInternal {
@@ -249,16 +386,30 @@ pub struct LintWithoutLintPass {
     registered_lints: FxHashSet<Name>,
 }
 
-impl_lint_pass!(LintWithoutLintPass => [DEFAULT_LINT, LINT_WITHOUT_LINT_PASS]);
+impl_lint_pass!(LintWithoutLintPass => [
+    DEFAULT_LINT,
+    LINT_WITHOUT_LINT_PASS,
+    MISSING_CLIPPY_VERSION_ATTRIBUTE,
+    INVALID_CLIPPY_VERSION_ATTRIBUTE,
+]);
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for LintWithoutLintPass {
     fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx Item<'_>) {
-        if !run_lints(cx, &[DEFAULT_LINT], item.hir_id) {
+        if !run_lints(
+            cx,
+            &[
+                DEFAULT_LINT,
+                MISSING_CLIPPY_VERSION_ATTRIBUTE,
+                INVALID_CLIPPY_VERSION_ATTRIBUTE,
+            ],
+            item.hir_id,
+        ) {
             return;
         }
 
         if let hir::ItemKind::Static(ref ty, Mutability::Not, body_id) = item.kind {
             if is_lint_ref_type(cx, ty) {
+                check_clippy_version_attribute(cx, item);
                 let expr = &cx.tcx.hir().body(body_id).value;
                 if_chain! {
                     if let ExprKind::AddrOf(_, _, ref inner_exp) = expr.kind;
@@ -337,6 +488,61 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for LintWithoutLintPass {
     }
 }
 
+/// Checks the `#[clippy::version]` attribute of a lint declared with `declare_clippy_lint!` or
+/// `declare_deprecated_lint!`.
+fn check_clippy_version_attribute(cx: &LateContext<'_, '_>, item: &Item<'_>) {
+    let (call_site, version_of) = if let Some(span) = is_expn_of(item.span, "declare_clippy_lint") {
+        (span, "added")
+    } else if let Some(span) = is_expn_of(item.span, "declare_deprecated_lint") {
+        (span, "deprecated")
+    } else {
+        return;
+    };
+    match get_attr(cx.sess(), item.attrs, "version").next() {
+        Some(attr) => {
+            if !attr
+                .value_str()
+                .map_or(false, |value| is_valid_clippy_version(&value.as_str()))
+            {
+                span_lint_and_help(
+                    cx,
+                    INVALID_CLIPPY_VERSION_ATTRIBUTE,
+                    call_site,
+                    &format!(
+                        "the lint `{}` has an invalid `#[clippy::version]` attribute",
+                        item.ident.name
+                    ),
+                    None,
+                    "use a version like `#[clippy::version = \"1.45.0\"]`, or `\"pre 1.29.0\"` for older lints",
+                );
+            }
+        },
+        None => span_lint_and_help(
+            cx,
+            MISSING_CLIPPY_VERSION_ATTRIBUTE,
+            call_site,
+            &format!(
+                "the lint `{}` is missing the `#[clippy::version]` attribute",
+                item.ident.name
+            ),
+            None,
+            &format!(
+                "add `#[clippy::version = \"<version>\"]` with the version the lint is {} in",
+                version_of
+            ),
+        ),
+    }
+}
+
+/// Returns `true` for `pre 1.29.0` and for versions like `1.45.0`.
+fn is_valid_clippy_version(version: &str) -> bool {
+    version == "pre 1.29.0"
+        || (version.split('.').count() == 3
+            && version
+                .split('.')
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())))
+}
+
 fn is_lint_ref_type<'tcx>(cx: &LateContext<'_, 'tcx>, ty: &Ty<'_>) -> bool {
     if let TyKind::Rptr(
         _,
@@ -656,3 +862,170 @@ fn suggest_note(
         Applicability::MachineApplicable,
     );
 }
+
+declare_lint_pass!(MatchTypeOnDiagItem => [MATCH_TYPE_ON_DIAGNOSTIC_ITEM]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MatchTypeOnDiagItem {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'_>) {
+        if !run_lints(cx, &[MATCH_TYPE_ON_DIAGNOSTIC_ITEM], expr.hir_id) {
+            return;
+        }
+
+        if_chain! {
+            // Check if this is a call to utils::match_type()
+            if let ExprKind::Call(fn_path, [context, ty, ty_path]) = expr.kind;
+            if let ExprKind::Path(fn_qpath) = &fn_path.kind;
+            if match_qpath(&fn_qpath, &["utils", "match_type"]);
+            // Extract the path to the matched type
+            if let Some(segments) = path_to_matched_type(cx, ty_path);
+            let segments: Vec<&str> = segments.iter().map(|sym| &**sym).collect();
+            if let Some(ty_did) = path_to_res(cx, &segments[..]).and_then(|res| res.opt_def_id());
+            // Check if the matched type is a diagnostic item
+            let diag_items = cx.tcx.diagnostic_items(ty_did.krate);
+            if let Some(item_name) = diag_items.iter().find_map(|(k, v)| if *v == ty_did { Some(k) } else { None });
+            then {
+                let cx_snippet = snippet(cx, context.span, "_");
+                let ty_snippet = snippet(cx, ty.span, "_");
+
+                span_lint_and_sugg(
+                    cx,
+                    MATCH_TYPE_ON_DIAGNOSTIC_ITEM,
+                    expr.span,
+                    "usage of `utils::match_type()` on a type diagnostic item",
+                    "try",
+                    format!("utils::is_type_diagnostic_item({}, {}, sym!({}))", cx_snippet, ty_snippet, item_name),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+        }
+    }
+}
+
+/// Extracts the path segments from the path argument of `match_type`. The path can be given
+/// literally, through a local binding or through a `const` or `static` item.
+fn path_to_matched_type(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> Option<Vec<SymbolStr>> {
+    match &expr.kind {
+        ExprKind::AddrOf(.., expr) => return path_to_matched_type(cx, expr),
+        ExprKind::Path(qpath) => match cx.tables.qpath_res(qpath, expr.hir_id) {
+            Res::Local(hir_id) => {
+                let parent_id = cx.tcx.hir().get_parent_node(hir_id);
+                if let Some(Node::Local(local)) = cx.tcx.hir().find(parent_id) {
+                    if let Some(init) = local.init {
+                        return path_to_matched_type(cx, init);
+                    }
+                }
+            },
+            Res::Def(DefKind::Const, def_id) | Res::Def(DefKind::Static, def_id) => {
+                if let Some(Node::Item(item)) = cx.tcx.hir().get_if_local(def_id) {
+                    if let hir::ItemKind::Const(.., body_id) | hir::ItemKind::Static(.., body_id) = item.kind {
+                        let body = cx.tcx.hir().body(body_id);
+                        return path_to_matched_type(cx, &body.value);
+                    }
+                }
+            },
+            _ => {},
+        },
+        ExprKind::Array(exprs) => {
+            let segments: Vec<SymbolStr> = exprs
+                .iter()
+                .filter_map(|expr| {
+                    if let ExprKind::Lit(lit) = &expr.kind {
+                        if let LitKind::Str(sym, _) = lit.node {
+                            return Some(sym.as_str());
+                        }
+                    }
+
+                    None
+                })
+                .collect();
+
+            if segments.len() == exprs.len() {
+                return Some(segments);
+            }
+        },
+        _ => {},
+    }
+
+    None
+}
+
+/// The functions that return a placeholder for spans without code, but have a
+/// `*_with_applicability` version.
+const PLACEHOLDER_SNIPPET_FUNCTIONS: [&[&str]; 3] = [&["snippet"], &["snippet_block"], &["Sugg", "hir"]];
+
+declare_lint_pass!(MachineApplicableSnippet => [MACHINE_APPLICABLE_SNIPPET]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MachineApplicableSnippet {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'_>) {
+        if !run_lints(cx, &[MACHINE_APPLICABLE_SNIPPET], expr.hir_id) {
+            return;
+        }
+
+        if_chain! {
+            if let ExprKind::Call(func, [_, _, _, _, _, sugg, applicability]) = expr.kind;
+            if let ExprKind::Path(ref path) = func.kind;
+            if match_qpath(path, &["span_lint_and_sugg"]);
+            if is_machine_applicable(cx, applicability);
+            if let Some(snippet_call) = find_placeholder_snippet(sugg);
+            then {
+                span_lint_and_help(
+                    cx,
+                    MACHINE_APPLICABLE_SNIPPET,
+                    snippet_call.span,
+                    "this snippet can be a placeholder, but the suggestion is machine-applicable",
+                    Some(applicability.span),
+                    "use the `*_with_applicability` version of the function and pass its applicability here",
+                );
+            }
+        }
+    }
+}
+
+/// Checks if `expr` is `Applicability::MachineApplicable`, or a local binding initialized with it.
+fn is_machine_applicable(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> bool {
+    if let ExprKind::Path(qpath) = &expr.kind {
+        if let Res::Local(hir_id) = cx.tables.qpath_res(qpath, expr.hir_id) {
+            let parent_id = cx.tcx.hir().get_parent_node(hir_id);
+            if let Some(Node::Local(local)) = cx.tcx.hir().find(parent_id) {
+                return local.init.map_or(false, |init| is_machine_applicable(cx, init));
+            }
+            return false;
+        }
+        return match_qpath(qpath, &["Applicability", "MachineApplicable"]);
+    }
+    false
+}
+
+fn find_placeholder_snippet<'tcx>(expr: &'tcx hir::Expr<'tcx>) -> Option<&'tcx hir::Expr<'tcx>> {
+    let mut finder = PlaceholderSnippetFinder { found: None };
+    finder.visit_expr(expr);
+    finder.found
+}
+
+struct PlaceholderSnippetFinder<'tcx> {
+    found: Option<&'tcx hir::Expr<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for PlaceholderSnippetFinder<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if self.found.is_some() {
+            return;
+        }
+        if_chain! {
+            if let ExprKind::Call(func, _) = expr.kind;
+            if let ExprKind::Path(ref path) = func.kind;
+            if PLACEHOLDER_SNIPPET_FUNCTIONS.iter().any(|function| match_qpath(path, function));
+            then {
+                self.found = Some(expr);
+                return;
+            }
+        }
+        walk_expr(self, expr);
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+}
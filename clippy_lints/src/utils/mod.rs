@@ -4,20 +4,26 @@ pub mod sym;
 pub mod attrs;
 pub mod author;
 pub mod camel_case;
+pub mod closures;
 pub mod comparisons;
 pub mod conf;
 pub mod constants;
+pub mod def_path;
 mod diagnostics;
 pub mod higher;
 mod hir_utils;
 pub mod inspector;
+pub mod interior_mut;
 pub mod internal_lints;
+pub mod msrvs;
 pub mod numeric_literal;
+pub mod pat_subsumption;
 pub mod paths;
 pub mod ptr;
 pub mod sugg;
 pub mod usage;
 pub use self::attrs::*;
+pub use self::def_path::*;
 pub use self::diagnostics::*;
 pub use self::hir_utils::{SpanlessEq, SpanlessHash};
 
@@ -34,8 +40,9 @@ use rustc_hir::def_id::{DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_hir::intravisit::{NestedVisitorMap, Visitor};
 use rustc_hir::Node;
 use rustc_hir::{
-    def, Arm, Block, Body, Constness, Crate, Expr, ExprKind, FnDecl, HirId, ImplItem, ImplItemKind, Item, ItemKind,
-    MatchSource, Param, Pat, PatKind, Path, PathSegment, QPath, TraitItem, TraitItemKind, TraitRef, TyKind, Unsafety,
+    def, Arm, AsyncGeneratorKind, Block, Body, Constness, Crate, Expr, ExprKind, FnDecl, GeneratorKind, HirId,
+    ImplItem, ImplItemKind, Item, ItemKind, MatchSource, Param, Pat, PatKind, Path, PathSegment, QPath, TraitItem,
+    TraitItemKind, TraitRef, TyKind, Unsafety,
 };
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_lint::{LateContext, Level, Lint, LintContext};
@@ -107,11 +114,7 @@ pub fn in_constant(cx: &LateContext<'_, '_>, id: HirId) -> bool {
 #[must_use]
 pub fn in_macro(span: Span) -> bool {
     if span.from_expansion() {
-        if let ExpnKind::Desugaring(..) = span.ctxt().outer_expn_data().kind {
-            false
-        } else {
-            true
-        }
+        !matches!(span.ctxt().outer_expn_data().kind, ExpnKind::Desugaring(..))
     } else {
         false
     }
@@ -132,13 +135,13 @@ pub fn is_present_in_source<T: LintContext>(cx: &T, span: Span) -> bool {
 
 /// Checks if given pattern is a wildcard (`_`)
 pub fn is_wild<'tcx>(pat: &impl std::ops::Deref<Target = Pat<'tcx>>) -> bool {
-    match pat.kind {
-        PatKind::Wild => true,
-        _ => false,
-    }
+    matches!(pat.kind, PatKind::Wild)
 }
 
 /// Checks if type is struct, enum or union type with the given def path.
+///
+/// If the type is a diagnostic item, use `is_type_diagnostic_item` instead. Otherwise consider
+/// `is_type_def_path`, which resolves the path only once per crate.
 pub fn match_type(cx: &LateContext<'_, '_>, ty: Ty<'_>, path: &[&str]) -> bool {
     match ty.kind {
         ty::Adt(adt, _) => match_def_path(cx, adt.did, path),
@@ -889,6 +892,39 @@ pub fn return_ty<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, fn_item: hir::HirId) -> T
     cx.tcx.erase_late_bound_regions(&ret_ty)
 }
 
+/// Returns the kind of async body if `body` is the generator body of an `async fn`, an `async`
+/// block or an `async` closure.
+pub fn async_body_kind(body: &Body<'_>) -> Option<AsyncGeneratorKind> {
+    if let Some(GeneratorKind::Async(kind)) = body.generator_kind {
+        Some(kind)
+    } else {
+        None
+    }
+}
+
+/// Checks if `expr` is an `async` block.
+pub fn is_async_block(cx: &LateContext<'_, '_>, expr: &Expr<'_>) -> bool {
+    if let ExprKind::Closure(_, _, body_id, _, Some(_)) = expr.kind {
+        async_body_kind(cx.tcx.hir().body(body_id)) == Some(AsyncGeneratorKind::Block)
+    } else {
+        false
+    }
+}
+
+/// Checks if `ty` is an opaque type with a `Future` bound, e.g. the return type of an `async fn`.
+pub fn is_future_opaque<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, ty: Ty<'tcx>) -> bool {
+    if let ty::Opaque(id, subst) = ty.kind {
+        let preds = cx.tcx.predicates_of(id).instantiate(cx.tcx, subst);
+        preds.predicates.iter().any(|p| {
+            p.to_opt_poly_trait_ref().map_or(false, |trait_ref| {
+                Some(trait_ref.def_id()) == cx.tcx.lang_items().future_trait()
+            })
+        })
+    } else {
+        false
+    }
+}
+
 /// Checks if two types are the same.
 ///
 /// This discards any lifetime annotations, too.
@@ -1062,9 +1098,13 @@ pub fn is_try<'tcx>(expr: &'tcx Expr<'tcx>) -> Option<&'tcx Expr<'tcx>> {
 
 /// Returns `true` if the lint is allowed in the current context
 ///
-/// Useful for skipping long running code when it's unnecessary
+/// Useful for skipping long running code when it's unnecessary. Since the lint then can't know
+/// whether it would have been emitted, the `allow` attribute counts as used for
+/// `unused_clippy_allow`.
 pub fn is_allowed(cx: &LateContext<'_, '_>, lint: &'static Lint, id: HirId) -> bool {
-    cx.tcx.lint_level_at_node(lint, id).0 == Level::Allow
+    let level = cx.tcx.lint_level_at_node(lint, id);
+    diagnostics::record_allow(level);
+    level.0 == Level::Allow
 }
 
 pub fn get_arg_name(pat: &Pat<'_>) -> Option<ast::Name> {
@@ -1367,7 +1407,7 @@ pub fn is_no_std_crate(krate: &Crate<'_>) -> bool {
 /// ```
 pub fn is_trait_impl_item(cx: &LateContext<'_, '_>, hir_id: HirId) -> bool {
     if let Some(Node::Item(item)) = cx.tcx.hir().find(cx.tcx.hir().get_parent_node(hir_id)) {
-        matches!(item.kind, ItemKind::Impl{ of_trait: Some(_), .. })
+        matches!(item.kind, ItemKind::Impl { of_trait: Some(_), .. })
     } else {
         false
     }
@@ -1399,13 +1439,23 @@ pub fn fn_has_unsatisfiable_preds(cx: &LateContext<'_, '_>, did: DefId) -> bool
     )
 }
 
+/// Returns `true` if any of `lints` is enabled at `id`.
+///
+/// Like `is_allowed`, the `allow` attributes of the lints count as used for `unused_clippy_allow`
+/// if none of them is enabled.
 pub fn run_lints(cx: &LateContext<'_, '_>, lints: &[&'static Lint], id: HirId) -> bool {
-    lints.iter().any(|lint| {
-        matches!(
-            cx.tcx.lint_level_at_node(lint, id),
-            (Level::Forbid | Level::Deny | Level::Warn, _)
-        )
-    })
+    let levels = lints
+        .iter()
+        .map(|lint| cx.tcx.lint_level_at_node(lint, id))
+        .collect::<Vec<_>>();
+    if levels
+        .iter()
+        .any(|level| matches!(level, (Level::Forbid | Level::Deny | Level::Warn, _)))
+    {
+        return true;
+    }
+    levels.into_iter().for_each(diagnostics::record_allow);
+    false
 }
 
 #[cfg(test)]
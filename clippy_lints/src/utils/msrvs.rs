@@ -0,0 +1,54 @@
+//! Support for the `msrv` configuration, the minimum Rust version a crate supports.
+//!
+//! Lints that suggest APIs or syntax added in a recent Rust version check the configured version
+//! with `meets_msrv` first, so they don't suggest code that doesn't compile on the oldest
+//! supported compiler.
+
+use std::fmt;
+
+/// A Rust version like `1.45.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RustcVersion {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl RustcVersion {
+    #[must_use]
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parses a version like `1.45.0` or `1.45`.
+    #[must_use]
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.trim().split('.').map(|part| part.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let patch = match parts.next() {
+            Some(patch) => patch?,
+            None => 0,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for RustcVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// `str::strip_prefix` and `str::strip_suffix`
+pub const STR_STRIP_PREFIX: RustcVersion = RustcVersion::new(1, 45, 0);
+
+/// Returns `true` if code that requires Rust `required` can be suggested to a crate whose minimum
+/// supported Rust version is `msrv`. Without a configured `msrv`, every version is fine.
+#[must_use]
+pub fn meets_msrv(msrv: Option<RustcVersion>, required: RustcVersion) -> bool {
+    msrv.map_or(true, |msrv| msrv >= required)
+}
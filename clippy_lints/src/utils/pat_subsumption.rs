@@ -0,0 +1,232 @@
+//! A small model of patterns to reason about which values they match.
+//!
+//! `hir::Pat`s are lowered to `SimplePat`s, which only keep what is tested when matching: the
+//! variants, literals and ranges, but not the bindings. Whatever the model doesn't understand,
+//! like slices or constants, becomes `SimplePat::Unknown`, which only irrefutable patterns are
+//! considered to cover.
+
+use crate::consts::{constant, miri_to_const, Constant};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{FieldPat, Pat, PatKind, QPath, RangeEnd};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, AdtDef, Ty, TyCtxt, VariantDef};
+use std::cmp::Ordering;
+use std::iter;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimplePat<'tcx> {
+    /// Matches every value, like `_` or a binding without subpattern
+    Wild,
+    /// A literal like `1`, `'a'` or `true`, with its type
+    Lit(Constant, Ty<'tcx>),
+    /// A range like `1..=5`, with open ends replaced by the limits of the type
+    Range(Constant, Constant, RangeEnd, Ty<'tcx>),
+    /// A variant of an enum or a struct, with a pattern for every field
+    Variant {
+        def_id: DefId,
+        /// The number of variants of the type; structs have one
+        variants: usize,
+        fields: Vec<SimplePat<'tcx>>,
+    },
+    /// A tuple, with a pattern for every element
+    Tuple(Vec<SimplePat<'tcx>>),
+    Or(Vec<SimplePat<'tcx>>),
+    Unknown,
+}
+
+impl<'tcx> SimplePat<'tcx> {
+    /// Lowers `pat` to a `SimplePat`.
+    pub fn from_hir(cx: &LateContext<'_, 'tcx>, pat: &Pat<'_>) -> Self {
+        match pat.kind {
+            PatKind::Wild | PatKind::Binding(.., None) => Self::Wild,
+            PatKind::Binding(.., Some(inner)) | PatKind::Box(inner) | PatKind::Ref(inner, _) => {
+                Self::from_hir(cx, inner)
+            },
+            PatKind::Lit(expr) => match constant(cx, cx.tables, expr) {
+                Some((value, _)) => Self::Lit(value, cx.tables.pat_ty(pat)),
+                None => Self::Unknown,
+            },
+            PatKind::Range(lo, hi, end) => {
+                let ty = cx.tables.pat_ty(pat);
+                let lo = match lo {
+                    Some(lo) => constant(cx, cx.tables, lo).map(|(value, _)| value),
+                    None => ty.numeric_min_val(cx.tcx).and_then(miri_to_const),
+                };
+                let hi = match hi {
+                    Some(hi) => constant(cx, cx.tables, hi).map(|(value, _)| value),
+                    None => ty.numeric_max_val(cx.tcx).and_then(miri_to_const),
+                };
+                match (lo, hi) {
+                    (Some(lo), Some(hi)) => Self::Range(lo, hi, end, ty),
+                    _ => Self::Unknown,
+                }
+            },
+            PatKind::Tuple(pats, rest) => match cx.tables.pat_ty(pat).kind {
+                ty::Tuple(tys) => Self::Tuple(with_rest(cx, pats, rest, tys.len())),
+                _ => Self::Unknown,
+            },
+            PatKind::TupleStruct(ref qpath, pats, rest) => Self::variant(cx, pat, qpath, |variant| {
+                with_rest(cx, pats, rest, variant.fields.len())
+            }),
+            PatKind::Struct(ref qpath, fields, _) => {
+                Self::variant(cx, pat, qpath, |variant| struct_fields(cx, variant, fields))
+            },
+            PatKind::Path(ref qpath) => Self::variant(cx, pat, qpath, |variant| vec![Self::Wild; variant.fields.len()]),
+            PatKind::Or(pats) => Self::Or(pats.iter().map(|pat| Self::from_hir(cx, pat)).collect()),
+            PatKind::Slice(..) => Self::Unknown,
+        }
+    }
+
+    /// A pattern matching `variant` of `adt` with any fields, like `Some(_)`.
+    pub fn any_of_variant(adt: &AdtDef, variant: &VariantDef) -> Self {
+        Self::Variant {
+            def_id: variant.def_id,
+            variants: adt.variants.len(),
+            fields: vec![Self::Wild; variant.fields.len()],
+        }
+    }
+
+    fn variant(
+        cx: &LateContext<'_, 'tcx>,
+        pat: &Pat<'_>,
+        qpath: &QPath<'_>,
+        fields: impl FnOnce(&VariantDef) -> Vec<Self>,
+    ) -> Self {
+        let res = cx.tables.qpath_res(qpath, pat.hir_id);
+        match (res, &cx.tables.pat_ty(pat).kind) {
+            (Res::Err, _) | (Res::Def(DefKind::Const, _), _) | (Res::Def(DefKind::AssocConst, _), _) => Self::Unknown,
+            (_, ty::Adt(adt, _)) if !adt.is_union() => {
+                let variant = adt.variant_of_res(res);
+                Self::Variant {
+                    def_id: variant.def_id,
+                    variants: adt.variants.len(),
+                    fields: fields(variant),
+                }
+            },
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Does the pattern match every value of its type?
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Self::Wild => true,
+            Self::Tuple(fields) => fields.iter().all(Self::is_irrefutable),
+            Self::Variant { variants, fields, .. } => *variants == 1 && fields.iter().all(Self::is_irrefutable),
+            Self::Or(pats) => pats.iter().any(Self::is_irrefutable),
+            Self::Lit(..) | Self::Range(..) | Self::Unknown => false,
+        }
+    }
+
+    /// Does `self` match every value that `other` matches?
+    ///
+    /// This is conservative: `false` means that `self` might not match some of the values.
+    pub fn subsumes(&self, tcx: TyCtxt<'tcx>, other: &Self) -> bool {
+        if self.is_irrefutable() {
+            return true;
+        }
+        match (self, other) {
+            (_, Self::Or(others)) => others.iter().all(|other| self.subsumes(tcx, other)),
+            (Self::Or(pats), _) => pats.iter().any(|pat| pat.subsumes(tcx, other)),
+            (Self::Lit(left, _), Self::Lit(right, _)) => left == right,
+            (Self::Range(lo, hi, end, ty), Self::Lit(value, _)) => {
+                le(tcx, ty, lo, value) && below_end(tcx, ty, value, RangeEnd::Included, hi, *end)
+            },
+            (Self::Range(lo, hi, end, ty), Self::Range(other_lo, other_hi, other_end, _)) => {
+                le(tcx, ty, lo, other_lo) && below_end(tcx, ty, other_hi, *other_end, hi, *end)
+            },
+            (Self::Tuple(fields), Self::Tuple(other_fields)) => all_subsume(tcx, fields, other_fields),
+            (
+                Self::Variant { def_id, fields, .. },
+                Self::Variant {
+                    def_id: other_def_id,
+                    fields: other_fields,
+                    ..
+                },
+            ) => def_id == other_def_id && all_subsume(tcx, fields, other_fields),
+            _ => false,
+        }
+    }
+
+    /// Do `self` and `other` together match every value of their type, like `Some(_)` and
+    /// `None` or `true` and `false`?
+    pub fn complements(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Lit(Constant::Bool(left), _), Self::Lit(Constant::Bool(right), _)) => left != right,
+            (
+                Self::Variant {
+                    def_id,
+                    variants: 2,
+                    fields,
+                },
+                Self::Variant {
+                    def_id: other_def_id,
+                    variants: 2,
+                    fields: other_fields,
+                },
+            ) => {
+                def_id != other_def_id
+                    && fields.iter().all(Self::is_irrefutable)
+                    && other_fields.iter().all(Self::is_irrefutable)
+            },
+            _ => false,
+        }
+    }
+}
+
+fn with_rest<'tcx>(
+    cx: &LateContext<'_, 'tcx>,
+    pats: &[&Pat<'_>],
+    rest: Option<usize>,
+    len: usize,
+) -> Vec<SimplePat<'tcx>> {
+    let mut fields: Vec<_> = pats.iter().map(|pat| SimplePat::from_hir(cx, pat)).collect();
+    if let Some(pos) = rest {
+        let wild = iter::repeat(SimplePat::Wild).take(len.saturating_sub(pats.len()));
+        fields.splice(pos..pos, wild);
+    }
+    fields
+}
+
+fn struct_fields<'tcx>(
+    cx: &LateContext<'_, 'tcx>,
+    variant: &VariantDef,
+    pats: &[FieldPat<'_>],
+) -> Vec<SimplePat<'tcx>> {
+    let mut fields = vec![SimplePat::Wild; variant.fields.len()];
+    for pat in pats {
+        if let Some(i) = variant
+            .fields
+            .iter()
+            .position(|field| field.ident.name == pat.ident.name)
+        {
+            fields[i] = SimplePat::from_hir(cx, pat.pat);
+        }
+    }
+    fields
+}
+
+fn all_subsume<'tcx>(tcx: TyCtxt<'tcx>, pats: &[SimplePat<'tcx>], others: &[SimplePat<'tcx>]) -> bool {
+    pats.len() == others.len() && pats.iter().zip(others).all(|(pat, other)| pat.subsumes(tcx, other))
+}
+
+fn le<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>, left: &Constant, right: &Constant) -> bool {
+    Constant::partial_cmp(tcx, ty, left, right).map_or(false, |ord| ord != Ordering::Greater)
+}
+
+/// Does a range ending at `end` with `end_kind` stay within one ending at `limit`?
+fn below_end<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    end: &Constant,
+    end_kind: RangeEnd,
+    limit: &Constant,
+    limit_kind: RangeEnd,
+) -> bool {
+    match Constant::partial_cmp(tcx, ty, end, limit) {
+        Some(Ordering::Less) => true,
+        Some(Ordering::Equal) => limit_kind == RangeEnd::Included || end_kind == RangeEnd::Excluded,
+        _ => false,
+    }
+}
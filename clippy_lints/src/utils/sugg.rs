@@ -1,7 +1,7 @@
 //! Contains utility functions to generate suggestions.
 #![deny(clippy::missing_docs_in_private_items)]
 
-use crate::utils::{higher, snippet, snippet_opt, snippet_with_macro_callsite};
+use crate::utils::{higher, path_to_res, snippet, snippet_opt, snippet_with_macro_callsite};
 use rustc_ast::util::parser::AssocOp;
 use rustc_ast::{ast, token};
 use rustc_ast_pretty::pprust::token_kind_to_string;
@@ -609,9 +609,283 @@ impl<'a, 'b, 'c, T: LintContext> DiagnosticBuilderExt<'c, T> for rustc_errors::D
     }
 }
 
+/// A fix for a lint, made of several edits that have to be applied together.
+///
+/// Unlike a plain suggestion, a `Fix` can contain edits anywhere in the crate, e.g. changing the
+/// signature of a function together with all of its call sites, or adding a `use` item next to
+/// the existing ones. The applicability is downgraded to `MaybeIncorrect` as soon as one of the
+/// edits is inside of a macro expansion, since the edit would then apply to the macro and not to
+/// the code it was called from.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let fix = Fix::new("use `HashSet` instead", Applicability::MachineApplicable)
+///     .replace(ty.span, "HashSet<T>")
+///     .replace(init.span, "HashSet::new()")
+///     .add_import(cx, expr.hir_id, "std::collections::HashSet");
+/// span_lint_and_fix(cx, SOME_LINT, expr.span, "this map only has unit values", fix);
+/// ```
+pub struct Fix {
+    /// The help message shown with the suggestion
+    msg: String,
+    /// The replacements, in the order they were added
+    edits: Vec<(Span, String)>,
+    /// How confident we are that applying the edits results in correct code
+    applicability: Applicability,
+}
+
+impl Fix {
+    /// Creates an empty fix. `applicability` is how confident the lint is in the edits; it can
+    /// only ever be lowered by the edits added later on.
+    pub fn new(msg: impl Into<String>, applicability: Applicability) -> Self {
+        Self {
+            msg: msg.into(),
+            edits: Vec::new(),
+            applicability,
+        }
+    }
+
+    /// The help message of the fix.
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    /// The applicability of the fix, after taking all edits into account.
+    pub fn applicability(&self) -> Applicability {
+        self.applicability
+    }
+
+    /// The edits of the fix, in the order they were added.
+    pub fn edits(&self) -> &[(Span, String)] {
+        &self.edits
+    }
+
+    /// Replaces the code at `span` with `replacement`.
+    pub fn replace(mut self, span: Span, replacement: impl Into<String>) -> Self {
+        self.push(span, replacement.into());
+        self
+    }
+
+    /// Inserts `text` before `span`.
+    pub fn insert_before(self, span: Span, text: impl Into<String>) -> Self {
+        self.replace(span.shrink_to_lo(), text)
+    }
+
+    /// Inserts `text` after `span`.
+    pub fn insert_after(self, span: Span, text: impl Into<String>) -> Self {
+        self.replace(span.shrink_to_hi(), text)
+    }
+
+    /// Removes the code at `span`.
+    pub fn remove(self, span: Span) -> Self {
+        self.replace(span, String::new())
+    }
+
+    /// Lowers the applicability of the fix to `applicability` if that is less confident than the
+    /// current one.
+    pub fn downgrade(mut self, applicability: Applicability) -> Self {
+        self.applicability = least_confident(self.applicability, applicability);
+        self
+    }
+
+    /// Adds `use path;` to the module containing `scope`, unless the module already imports the
+    /// item, either by name, as part of a `use a::{b, c};` list or through a glob import of its
+    /// parent module.
+    ///
+    /// The existing `use` items are split into three classes: imports from `std`/`core`/`alloc`,
+    /// from other crates and from the current crate. The new item goes after the last item of its
+    /// class that sorts before it, or before the first item of its class. If the module has no
+    /// import of that class yet, the item starts a new block, separated by a blank line, before
+    /// the next class or after the last `use` item. Blank lines that already separate the `use`
+    /// items are not taken into account.
+    ///
+    /// If `path` cannot be resolved, or its name is already taken by another item in the module,
+    /// the applicability is lowered to `MaybeIncorrect`.
+    pub fn add_import(self, cx: &LateContext<'_, '_>, scope: hir::HirId, path: &str) -> Self {
+        let map = cx.tcx.hir();
+        let module_id = map.get_module_parent_node(scope);
+        let (module, _, _) = map.get_module(map.local_def_id(module_id));
+
+        let segments: Vec<&str> = path.split("::").collect();
+        let target = path_to_res(cx, &segments).and_then(|res| res.opt_def_id());
+        let name = segments.last().copied().unwrap_or_default();
+
+        let mut uses: Vec<(Span, String)> = Vec::new();
+        let mut first_item = None;
+        let mut name_taken = false;
+        for item_id in module.item_ids {
+            let item = map.item(item_id.id);
+            if item.span.from_expansion() {
+                continue;
+            }
+            first_item = first_item.or(Some(item.span));
+            match item.kind {
+                hir::ItemKind::Use(use_path, kind) => {
+                    let imported = use_path.res.opt_def_id();
+                    match kind {
+                        hir::UseKind::Single if item.ident.as_str() == name => {
+                            if target.is_some() && imported == target {
+                                return self;
+                            }
+                            name_taken = true;
+                        },
+                        hir::UseKind::Glob => {
+                            if let (Some(target), Some(imported)) = (target, imported) {
+                                if cx.tcx.parent(target) == Some(imported) {
+                                    return self;
+                                }
+                            }
+                        },
+                        _ => {},
+                    }
+                    if uses.iter().all(|(span, _)| *span != item.span) {
+                        if let Some(use_path) = snippet_opt(cx, item.span).as_deref().and_then(use_item_path) {
+                            uses.push((item.span, use_path.to_string()));
+                        }
+                    }
+                },
+                _ => name_taken |= item.ident.as_str() == name,
+            }
+        }
+        // the names of a `use a::{b, c};` list may have their own spans inside of the list's
+        uses = uses
+            .iter()
+            .filter(|(span, _)| !uses.iter().any(|(outer, _)| outer != span && outer.contains(*span)))
+            .cloned()
+            .collect();
+        uses.sort_by_key(|(span, _)| span.lo());
+
+        if name_taken {
+            return self.downgrade(Applicability::MaybeIncorrect);
+        }
+        let fix = if target.is_some() {
+            self
+        } else {
+            self.downgrade(Applicability::MaybeIncorrect)
+        };
+
+        let paths: Vec<&str> = uses.iter().map(|(_, path)| path.as_str()).collect();
+        match import_position(&paths, path) {
+            None => fix,
+            Some(ImportPosition::Before { index, new_group }) => {
+                let span = uses[index].0;
+                let indent = indentation(cx, span).unwrap_or_default();
+                let blank = if new_group { "\n" } else { "" };
+                fix.insert_before(span, format!("use {};\n{}{}", path, blank, indent))
+            },
+            Some(ImportPosition::After { index, new_group }) => {
+                let span = uses[index].0;
+                let indent = indentation(cx, span).unwrap_or_default();
+                let blank = if new_group { "\n" } else { "" };
+                fix.insert_after(span, format!("\n{}{}use {};", blank, indent, path))
+            },
+            // Without any `use` items we can't know whether the first item is preceded by doc
+            // comments or attributes, which would then apply to the import.
+            Some(ImportPosition::ModuleStart) => match first_item {
+                Some(span) => {
+                    let indent = indentation(cx, span).unwrap_or_default();
+                    fix.insert_before(span, format!("use {};\n\n{}", path, indent))
+                        .downgrade(Applicability::MaybeIncorrect)
+                },
+                None => fix.downgrade(Applicability::HasPlaceholders),
+            },
+        }
+    }
+
+    /// Adds an edit, lowering the applicability if it is inside of a macro expansion.
+    fn push(&mut self, span: Span, replacement: String) {
+        if span.from_expansion() {
+            self.applicability = least_confident(self.applicability, Applicability::MaybeIncorrect);
+        }
+        self.edits.push((span, replacement));
+    }
+}
+
+/// Returns the less confident of two applicabilities.
+fn least_confident(a: Applicability, b: Applicability) -> Applicability {
+    /// Orders applicabilities from most to least confident.
+    fn rank(applicability: Applicability) -> u8 {
+        match applicability {
+            Applicability::MachineApplicable => 0,
+            Applicability::MaybeIncorrect => 1,
+            Applicability::HasPlaceholders => 2,
+            Applicability::Unspecified => 3,
+        }
+    }
+    if rank(a) >= rank(b) {
+        a
+    } else {
+        b
+    }
+}
+
+/// Extracts the imported path from the source of a `use` item, e.g. `a::b` from
+/// `pub(crate) use a::b;`.
+fn use_item_path(snippet: &str) -> Option<&str> {
+    let start = snippet.find("use ")? + "use ".len();
+    Some(snippet[start..].trim_end().trim_end_matches(';').trim())
+}
+
+/// Where a new `use` item is inserted, relative to the existing `use` items of a module.
+#[derive(Debug, PartialEq)]
+enum ImportPosition {
+    /// Before the `use` item at `index`, starting a new group if `new_group` is set
+    Before { index: usize, new_group: bool },
+    /// After the `use` item at `index`, starting a new group if `new_group` is set
+    After { index: usize, new_group: bool },
+    /// The module has no `use` items
+    ModuleStart,
+}
+
+/// The group of a `use` path: the standard library, other crates or the current crate.
+fn import_group(path: &str) -> u8 {
+    match path.trim_start_matches("::").split("::").next() {
+        Some("std" | "core" | "alloc") => 0,
+        Some("crate" | "self" | "super") => 2,
+        _ => 1,
+    }
+}
+
+/// Finds the position of a new `use path;` among the existing imported `paths`, which are in
+/// source order. Returns `None` if `path` is already imported.
+fn import_position(paths: &[&str], path: &str) -> Option<ImportPosition> {
+    if paths.contains(&path) {
+        return None;
+    }
+    if paths.is_empty() {
+        return Some(ImportPosition::ModuleStart);
+    }
+
+    let group = import_group(path);
+    let same_group: Vec<usize> = (0..paths.len()).filter(|&i| import_group(paths[i]) == group).collect();
+    if let Some(&first) = same_group.first() {
+        let position = match same_group.iter().rev().find(|&&i| paths[i] < path) {
+            Some(&index) => ImportPosition::After {
+                index,
+                new_group: false,
+            },
+            None => ImportPosition::Before {
+                index: first,
+                new_group: false,
+            },
+        };
+        return Some(position);
+    }
+
+    // start a new group before the first group that comes after this one, or at the end
+    Some(match (0..paths.len()).find(|&i| import_group(paths[i]) > group) {
+        Some(index) => ImportPosition::Before { index, new_group: true },
+        None => ImportPosition::After {
+            index: paths.len() - 1,
+            new_group: true,
+        },
+    })
+}
+
 #[cfg(test)]
 mod test {
-    use super::Sugg;
+    use super::{import_position, use_item_path, ImportPosition, Sugg};
     use std::borrow::Cow;
 
     const SUGGESTION: Sugg<'static> = Sugg::NonParen(Cow::Borrowed("function_call()"));
@@ -625,4 +899,57 @@ mod test {
     fn blockify_transforms_sugg_into_a_block() {
         assert_eq!("{ function_call() }", SUGGESTION.blockify().to_string());
     }
+
+    #[test]
+    fn use_item_path_strips_visibility_and_semicolon() {
+        assert_eq!(Some("std::fmt"), use_item_path("use std::fmt;"));
+        assert_eq!(Some("a::{b, c}"), use_item_path("pub(crate) use a::{b, c};"));
+    }
+
+    #[test]
+    fn import_position_keeps_groups_sorted() {
+        let paths = ["std::fmt", "std::mem", "rustc_hir as hir", "crate::utils::snippet"];
+
+        assert_eq!(None, import_position(&paths, "std::mem"));
+        assert_eq!(
+            Some(ImportPosition::After {
+                index: 0,
+                new_group: false
+            }),
+            import_position(&paths, "std::io")
+        );
+        assert_eq!(
+            Some(ImportPosition::Before {
+                index: 0,
+                new_group: false
+            }),
+            import_position(&paths, "std::borrow::Cow")
+        );
+        assert_eq!(
+            Some(ImportPosition::After {
+                index: 2,
+                new_group: false
+            }),
+            import_position(&paths, "rustc_span::Span")
+        );
+        assert_eq!(Some(ImportPosition::ModuleStart), import_position(&[], "std::fmt"));
+    }
+
+    #[test]
+    fn import_position_starts_new_groups() {
+        assert_eq!(
+            Some(ImportPosition::Before {
+                index: 0,
+                new_group: true
+            }),
+            import_position(&["crate::utils"], "std::fmt")
+        );
+        assert_eq!(
+            Some(ImportPosition::After {
+                index: 0,
+                new_group: true
+            }),
+            import_position(&["std::fmt"], "crate::utils")
+        );
+    }
 }
@@ -3,7 +3,7 @@ use rustc_ast::ast;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::def::Res;
 use rustc_hir::intravisit::{walk_expr, NestedVisitorMap, Visitor};
-use rustc_hir::{Expr, HirId, Path};
+use rustc_hir::{Expr, ExprKind, HirId, MatchSource, Path, QPath};
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_lint::LateContext;
 use rustc_middle::hir::map::Map;
@@ -106,3 +106,72 @@ pub fn is_unused<'tcx>(ident: &'tcx Ident, body: &'tcx Expr<'_>) -> bool {
     walk_expr(&mut visitor, body);
     !visitor.used
 }
+
+/// Counts the uses of the local variable `id` in `expr`, including the uses inside of closures.
+pub fn local_use_count<'a, 'tcx>(cx: &'a LateContext<'a, 'tcx>, id: HirId, expr: &'tcx Expr<'_>) -> usize {
+    let mut visitor = LocalUseCounter { cx, id, count: 0 };
+    visitor.visit_expr(expr);
+    visitor.count
+}
+
+struct LocalUseCounter<'a, 'tcx> {
+    cx: &'a LateContext<'a, 'tcx>,
+    id: HirId,
+    count: usize,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for LocalUseCounter<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Path(QPath::Resolved(None, ref path)) = expr.kind {
+            if path.res == Res::Local(self.id) {
+                self.count += 1;
+            }
+        }
+        walk_expr(self, expr);
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.cx.tcx.hir())
+    }
+}
+
+/// Does `expr` contain `return`, `break`, `continue`, `?`, `.await` or diverging calls like
+/// `panic!()` outside of closures? Such expressions can't be moved into a closure or evaluated
+/// eagerly.
+pub fn has_control_flow<'tcx>(cx: &LateContext<'_, 'tcx>, expr: &'tcx Expr<'_>) -> bool {
+    let mut visitor = ControlFlowVisitor { cx, found: false };
+    visitor.visit_expr(expr);
+    visitor.found
+}
+
+struct ControlFlowVisitor<'a, 'b, 'tcx> {
+    cx: &'a LateContext<'b, 'tcx>,
+    found: bool,
+}
+
+impl<'a, 'b, 'tcx> Visitor<'tcx> for ControlFlowVisitor<'a, 'b, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        if self.found {
+            return;
+        }
+        match expr.kind {
+            ExprKind::Ret(..)
+            | ExprKind::Break(..)
+            | ExprKind::Continue(..)
+            | ExprKind::Yield(..)
+            | ExprKind::Match(_, _, MatchSource::TryDesugar)
+            | ExprKind::Match(_, _, MatchSource::AwaitDesugar) => self.found = true,
+            ExprKind::Closure(..) => {},
+            _ if self.cx.tables.expr_ty(expr).is_never() => self.found = true,
+            _ => walk_expr(self, expr),
+        }
+    }
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+}
@@ -16,6 +16,7 @@ declare_clippy_lint! {
     /// ```rust
     /// 0.0f32 / 0.0;
     /// ```
+    #[clippy::version = "pre 1.29.0"]
     pub ZERO_DIVIDED_BY_ZERO,
     complexity,
     "usage of `0.0 / 0.0` to obtain NaN instead of `f32::NAN` or `f64::NAN`"
@@ -0,0 +1,132 @@
+//! Utilities for lint libraries that are loaded into Clippy with `clippy-driver --lint-lib`.
+//!
+//! This crate re-exports the parts of `clippy_lints`' internal utilities that are useful outside
+//! of Clippy. Everything that is re-exported here is kept working across Clippy updates where
+//! possible, while `clippy_lints` itself gives no such guarantee. A lint library has to be built
+//! with the same toolchain and the same version of this crate as the driver that loads it.
+//!
+//! A lint library is a `dylib` that exports a `clippy_register_lints` function, see
+//! [`RegisterLintsFn`], and the version it was built against, see [`export_lint_lib_version!`]:
+//!
+//! ```rust,ignore
+//! #![feature(rustc_private)]
+//!
+//! extern crate rustc_hir;
+//! extern crate rustc_lint;
+//! extern crate rustc_session;
+//!
+//! use clippy_utils::{declare_clippy_lint, span_lint, Conf};
+//! use rustc_hir::Expr;
+//! use rustc_lint::{LateContext, LateLintPass, LintStore};
+//! use rustc_session::{declare_lint_pass, declare_tool_lint, Session};
+//!
+//! declare_clippy_lint! {
+//!     /// **What it does:** ...
+//!     #[clippy::version = "1.45.0"]
+//!     pub MY_LINT,
+//!     style,
+//!     "..."
+//! }
+//!
+//! declare_lint_pass!(MyLint => [MY_LINT]);
+//!
+//! impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MyLint {
+//!     fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr<'_>) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[no_mangle]
+//! pub fn clippy_register_lints(store: &mut LintStore, _: &Session, _: &Conf) {
+//!     store.register_lints(&[&MY_LINT]);
+//!     store.register_late_pass(|| Box::new(MyLint));
+//! }
+//!
+//! clippy_utils::export_lint_lib_version!();
+//! ```
+//!
+//! The lints are registered in the `clippy` tool namespace, but they are not part of Clippy's
+//! lint groups like `clippy::all`.
+
+#![feature(rustc_private)]
+#![warn(rust_2018_idioms, missing_docs)]
+#![cfg_attr(feature = "deny-warnings", deny(warnings))]
+
+pub use clippy_lints::{
+    declare_clippy_lint, sym, Conf, RegisterLintsFn, LINT_LIB_VERSION, LINT_LIB_VERSION_SYMBOL, REGISTER_LINTS_SYMBOL,
+};
+pub use if_chain::if_chain;
+
+// Emitting lints and suggestions
+pub use clippy_lints::utils::{
+    add_fix, multispan_sugg, span_lint, span_lint_and_fix, span_lint_and_help, span_lint_and_note, span_lint_and_sugg,
+    span_lint_and_then, span_lint_hir, span_lint_hir_and_then, LintLevelSource,
+};
+// Source snippets
+pub use clippy_lints::utils::{
+    expr_block, first_line_of_span, indent_of, snippet, snippet_block, snippet_opt, snippet_with_applicability,
+    snippet_with_macro_callsite,
+};
+// Macros and expansions
+pub use clippy_lints::utils::{differing_macro_contexts, in_macro, is_expn_of};
+// Paths and items
+pub use clippy_lints::utils::{
+    get_item_name, get_trait_def_id, is_def_path, is_diag_item_or_path, is_lang_item_or_path, last_path_segment,
+    match_def_path, match_path, match_path_ast, match_qpath, path_to_res, qpath_res, resolve_def_path,
+};
+// Types
+pub use clippy_lints::utils::{
+    implements_trait, is_copy, is_type_def_path, is_type_diag_item_or_path, is_type_diagnostic_item, match_type,
+    same_tys, walk_ptrs_ty,
+};
+// Expressions and the HIR tree
+pub use clippy_lints::utils::{
+    get_enclosing_block, get_parent_expr, is_allowed, is_automatically_derived, method_calls, method_chain_args,
+    remove_blocks,
+};
+// Structural comparison and hashing of expressions
+pub use clippy_lints::utils::{SpanlessEq, SpanlessHash};
+
+/// Exports the `clippy_lint_lib_version` function, which returns the version of `clippy_utils` and
+/// of the compiler the lint library was built with. The driver refuses to load lint libraries that
+/// don't export it or were built against a different version.
+#[macro_export]
+macro_rules! export_lint_lib_version {
+    () => {
+        #[no_mangle]
+        pub extern "C" fn clippy_lint_lib_version() -> *const ::std::os::raw::c_char {
+            $crate::LINT_LIB_VERSION.as_ptr().cast()
+        }
+    };
+}
+
+/// Paths to items in the standard library and common crates, see
+/// [`match_def_path`](fn.match_def_path.html).
+pub mod paths {
+    pub use clippy_lints::utils::paths::*;
+}
+
+/// Helpers to recognize desugared and macro-generated code.
+pub mod higher {
+    pub use clippy_lints::utils::higher::*;
+}
+
+/// Recognizing common closure shapes like `|x| x` or `|x| Some(x.foo)`.
+pub mod closures {
+    pub use clippy_lints::utils::closures::*;
+}
+
+/// Building code suggestions from expressions.
+pub mod sugg {
+    pub use clippy_lints::utils::sugg::*;
+}
+
+/// Checks whether variables are used or mutated.
+pub mod usage {
+    pub use clippy_lints::utils::usage::*;
+}
+
+/// Reading `clippy.toml`.
+pub mod conf {
+    pub use clippy_lints::utils::conf::{Conf, Error};
+}
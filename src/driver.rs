@@ -11,15 +11,21 @@ extern crate rustc_errors;
 #[allow(unused_extern_crates)]
 extern crate rustc_interface;
 #[allow(unused_extern_crates)]
+extern crate rustc_metadata;
+#[allow(unused_extern_crates)]
 extern crate rustc_middle;
+#[allow(unused_extern_crates)]
+extern crate rustc_session;
 
 use rustc_interface::interface;
 use rustc_middle::ty::TyCtxt;
+use rustc_session::Session;
 use rustc_tools_util::VersionInfo;
 
 use lazy_static::lazy_static;
 use std::borrow::Cow;
 use std::env;
+use std::mem;
 use std::ops::Deref;
 use std::panic;
 use std::path::{Path, PathBuf};
@@ -63,13 +69,101 @@ fn test_arg_value() {
     assert_eq!(arg_value(args, "--foo", |_| true), None);
 }
 
+/// Removes all `--lint-lib <path>` and `--lint-lib=<path>` arguments from `args`, returning the
+/// paths. rustc doesn't know this option, so it must not be passed on.
+fn take_lint_libs(args: &mut Vec<String>) -> Vec<PathBuf> {
+    let mut lint_libs = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        if let Some(path) = args[i].strip_prefix("--lint-lib=") {
+            lint_libs.push(PathBuf::from(path));
+            args.remove(i);
+        } else if args[i] == "--lint-lib" && i + 1 < args.len() {
+            lint_libs.push(PathBuf::from(args.remove(i + 1)));
+            args.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    lint_libs
+}
+
+#[test]
+fn test_take_lint_libs() {
+    let mut args: Vec<String> = ["rustc", "--lint-lib", "a.so", "-W", "x", "--lint-lib=b.so", "main.rs"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert_eq!(
+        take_lint_libs(&mut args),
+        vec![PathBuf::from("a.so"), PathBuf::from("b.so")]
+    );
+    assert_eq!(args, vec!["rustc", "-W", "x", "main.rs"]);
+    assert!(take_lint_libs(&mut args).is_empty());
+}
+
+/// Loads the lint library at `path` and returns its `clippy_register_lints` function.
+///
+/// Relative paths are resolved by `cargo-clippy` against the directory it was started in, since
+/// cargo runs the driver in the directory of the package being checked.
+fn load_lint_lib(sess: &Session, path: &Path) -> clippy_lints::RegisterLintsFn {
+    use rustc_metadata::dynamic_lib::DynamicLibrary;
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+
+    let lib = DynamicLibrary::open(path)
+        .unwrap_or_else(|e| sess.fatal(&format!("could not load lint library `{}`: {}", path.display(), e)));
+    let symbol = |name: &str| {
+        // SAFETY: the symbol is only read if it has the expected type, see below.
+        unsafe { lib.symbol::<u8>(name) }.unwrap_or_else(|e| {
+            sess.fatal(&format!(
+                "lint library `{}` doesn't export `{}`: {}",
+                path.display(),
+                name,
+                e
+            ))
+        })
+    };
+
+    // SAFETY: `clippy_utils::export_lint_lib_version!` exports an `extern "C"` function returning a
+    // NUL-terminated string. Its ABI doesn't depend on the compiler version, unlike the one of
+    // `RegisterLintsFn`.
+    let version = unsafe {
+        let version = mem::transmute::<*mut u8, extern "C" fn() -> *const c_char>(symbol(
+            clippy_lints::LINT_LIB_VERSION_SYMBOL,
+        ));
+        CStr::from_ptr(version()).to_string_lossy().into_owned()
+    };
+    let expected = clippy_lints::LINT_LIB_VERSION.trim_end_matches('\0');
+    if version != expected {
+        sess.fatal(&format!(
+            "lint library `{}` was built against clippy_utils {}, but this is Clippy {}",
+            path.display(),
+            version,
+            expected
+        ));
+    }
+
+    let register = symbol(clippy_lints::REGISTER_LINTS_SYMBOL);
+    // The lint passes of the library are used until the compiler exits, so it is never unloaded.
+    mem::forget(lib);
+    // SAFETY: the library was built with the same compiler and `clippy_utils` version, so the
+    // exported function has the signature of `RegisterLintsFn`.
+    unsafe { mem::transmute::<*mut u8, clippy_lints::RegisterLintsFn>(register) }
+}
+
 struct DefaultCallbacks;
 impl rustc_driver::Callbacks for DefaultCallbacks {}
 
-struct ClippyCallbacks;
+struct ClippyCallbacks {
+    /// Paths of the lint libraries passed with `--lint-lib`
+    lint_libs: Vec<PathBuf>,
+}
 impl rustc_driver::Callbacks for ClippyCallbacks {
     fn config(&mut self, config: &mut interface::Config) {
         let previous = config.register_lints.take();
+        let lint_libs = mem::take(&mut self.lint_libs);
         config.register_lints = Some(Box::new(move |sess, mut lint_store| {
             // technically we're ~guaranteed that this is none but might as well call anything that
             // is there already. Certainly it can't hurt.
@@ -81,6 +175,11 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
             clippy_lints::register_plugins(&mut lint_store, &sess, &conf);
             clippy_lints::register_pre_expansion_lints(&mut lint_store, &conf);
             clippy_lints::register_renamed(&mut lint_store);
+
+            for path in &lint_libs {
+                let register = load_lint_lib(sess, path);
+                register(&mut lint_store, &sess, &conf);
+            }
         }));
 
         // FIXME: #4825; This is required, because Clippy lints that are based on MIR have to be
@@ -220,6 +319,9 @@ with:
     -D --deny OPT       Set lint denied
     -F --forbid OPT     Set lint forbidden
 
+Additional lints can be loaded from a lint library built against `clippy_utils`
+with `cargo clippy -- --lint-lib PATH`.
+
 You can use tool lints to allow or deny lints from your code, eg.:
 
     #[allow(clippy::needless_lifetimes)]
@@ -366,13 +468,8 @@ pub fn main() {
 
             let should_describe_lints = || {
                 let args: Vec<_> = env::args().collect();
-                args.windows(2).any(|args| {
-                    args[1] == "help"
-                        && match args[0].as_str() {
-                            "-W" | "-A" | "-D" | "-F" => true,
-                            _ => false,
-                        }
-                })
+                args.windows(2)
+                    .any(|args| args[1] == "help" && matches!(args[0].as_str(), "-W" | "-A" | "-D" | "-F"))
             };
 
             if !wrapper_mode && should_describe_lints() {
@@ -405,7 +502,8 @@ pub fn main() {
                     }));
                 }
             }
-            let mut clippy = ClippyCallbacks;
+            let lint_libs = take_lint_libs(&mut args);
+            let mut clippy = ClippyCallbacks { lint_libs };
             let mut default = DefaultCallbacks;
             let callbacks: &mut (dyn rustc_driver::Callbacks + Send) =
                 if clippy_enabled { &mut clippy } else { &mut default };
@@ -0,0 +1,250 @@
+//! `cargo clippy --explain` and `cargo clippy --list-lints`.
+//!
+//! The documentation of the lints is embedded at build time, so both work without access to the
+//! lint list website. The build script parses it from the lint declarations and `define_Conf!`
+//! with the same code `cargo dev update_lints` uses.
+
+use std::fmt::Write;
+
+pub struct LintDoc {
+    pub name: &'static str,
+    pub group: &'static str,
+    pub desc: &'static str,
+    pub deprecation: Option<&'static str>,
+    /// The doc comment of the lint declaration, in markdown
+    pub docs: &'static str,
+    /// The version the lint was added in, or for deprecated lints, the version it was deprecated in
+    pub version: Option<&'static str>,
+    pub config: &'static [ConfigKey],
+}
+
+pub struct ConfigKey {
+    pub name: &'static str,
+    pub ty: &'static str,
+    pub default: &'static str,
+    pub doc: &'static str,
+}
+
+pub static LINTS: &[LintDoc] = include!(concat!(env!("OUT_DIR"), "/lint_docs.rs"));
+
+/// Runs `--explain` or `--list-lints` if one of them is in `args`, which are the arguments before
+/// a `--`.
+pub fn run(args: &[String]) -> Option<Result<(), i32>> {
+    if let Some(pos) = args.iter().position(|arg| arg == "--explain") {
+        return Some(match args.get(pos + 1) {
+            Some(name) => explain(name),
+            None => Err(error("`--explain` requires the name of a lint")),
+        });
+    }
+    if args.iter().any(|arg| arg == "--list-lints") {
+        return Some(list_lints(args));
+    }
+    None
+}
+
+fn error(msg: &str) -> i32 {
+    eprintln!("error: {}", msg);
+    1
+}
+
+/// Finds a lint by its name, with or without the `clippy::` prefix and with `-` or `_`.
+fn find_lint(name: &str) -> Option<&'static LintDoc> {
+    let name = name.trim_start_matches("clippy::").replace('-', "_").to_lowercase();
+    LINTS.iter().find(|lint| lint.name == name)
+}
+
+fn default_level(lint: &LintDoc) -> &'static str {
+    match lint.group {
+        _ if lint.deprecation.is_some() => "removed",
+        "correctness" => "deny",
+        "style" | "complexity" | "perf" => "warn",
+        _ => "allow",
+    }
+}
+
+fn explain(name: &str) -> Result<(), i32> {
+    if LINTS.is_empty() {
+        return Err(error("cargo-clippy was built without the lint docs"));
+    }
+    let lint = find_lint(name).ok_or_else(|| error(&format!("unknown lint `{}`", name)))?;
+    print!("{}", explanation(lint));
+    Ok(())
+}
+
+fn explanation(lint: &LintDoc) -> String {
+    let mut out = String::new();
+    if let Some(reason) = lint.deprecation {
+        let _ = writeln!(out, "`clippy::{}` is deprecated: {}\n", lint.name, reason);
+    } else {
+        let _ = writeln!(
+            out,
+            "`clippy::{}` ({}, {} by default): {}\n",
+            lint.name,
+            lint.group,
+            default_level(lint),
+            lint.desc
+        );
+    }
+    out.push_str(&without_hidden_lines(lint.docs));
+    out.push('\n');
+    if let Some(version) = lint.version {
+        let event = if lint.deprecation.is_some() {
+            "Deprecated"
+        } else {
+            "Added"
+        };
+        let _ = writeln!(out, "\n**Version:** {} in {}.", event, version);
+    }
+    if !lint.config.is_empty() {
+        out.push_str("\n**Configuration:** This lint has the following keys in `clippy.toml`:\n\n");
+        for key in lint.config {
+            let _ = writeln!(
+                out,
+                "* `{}: {}`: {} (defaults to `{}`).",
+                key.name, key.ty, key.doc, key.default
+            );
+        }
+    }
+    out
+}
+
+/// Removes the lines of Rust code examples that rustdoc hides, i.e. the ones starting with `#`.
+fn without_hidden_lines(docs: &str) -> String {
+    let mut in_rust_code = false;
+    let mut lines = Vec::new();
+    for line in docs.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_rust_code = !in_rust_code
+                && trimmed[3..].split(',').all(|attr| {
+                    ["", "rust", "ignore", "no_run", "should_panic", "compile_fail"].contains(&attr.trim())
+                });
+        } else if in_rust_code && (trimmed == "#" || trimmed.starts_with("# ")) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn list_lints(args: &[String]) -> Result<(), i32> {
+    let group = option_value(args, "--group")?;
+    let json = match option_value(args, "--format")? {
+        None | Some("text") => false,
+        Some("json") => true,
+        Some(format) => {
+            return Err(error(&format!(
+                "unknown format `{}`, expected `text` or `json`",
+                format
+            )))
+        },
+    };
+    let lints = LINTS
+        .iter()
+        .filter(|lint| match group {
+            Some(group) => lint.group.eq_ignore_ascii_case(group),
+            None => lint.deprecation.is_none(),
+        })
+        .collect::<Vec<_>>();
+    if lints.is_empty() {
+        return Err(error(&format!("unknown lint group `{}`", group.unwrap_or_default())));
+    }
+
+    if json {
+        println!("{}", lints_json(&lints));
+    } else {
+        let name_width = lints.iter().map(|lint| lint.name.len()).max().unwrap_or(0) + "clippy::".len();
+        let group_width = lints.iter().map(|lint| lint.group.len()).max().unwrap_or(0);
+        for lint in lints {
+            println!(
+                "{:name_width$}  {:group_width$}  {:7}  {}",
+                format!("clippy::{}", lint.name),
+                lint.group,
+                default_level(lint),
+                lint.desc,
+                name_width = name_width,
+                group_width = group_width,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the value of an option given as `--name value` or `--name=value`.
+fn option_value<'a>(args: &'a [String], name: &str) -> Result<Option<&'a str>, i32> {
+    for (i, arg) in args.iter().enumerate() {
+        if arg == name {
+            return match args.get(i + 1) {
+                Some(value) => Ok(Some(value)),
+                None => Err(error(&format!("`{}` requires a value", name))),
+            };
+        }
+        if arg.starts_with(name) && arg[name.len()..].starts_with('=') {
+            return Ok(Some(&arg[name.len() + 1..]));
+        }
+    }
+    Ok(None)
+}
+
+fn lints_json(lints: &[&LintDoc]) -> String {
+    let entries = lints
+        .iter()
+        .map(|lint| {
+            format!(
+                "  {{\"name\": {}, \"group\": {}, \"level\": {}, \"desc\": {}, \"config\": [{}]}}",
+                json_string(lint.name),
+                json_string(lint.group),
+                json_string(default_level(lint)),
+                json_string(lint.desc),
+                lint.config
+                    .iter()
+                    .map(|key| json_string(key.name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]", entries.join(",\n"))
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            },
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_lint, json_string, without_hidden_lines};
+
+    #[test]
+    fn lint_names() {
+        assert!(find_lint("needless_return").is_some());
+        assert!(find_lint("clippy::needless-return").is_some());
+        assert!(find_lint("needless").is_none());
+    }
+
+    #[test]
+    fn hidden_lines() {
+        let docs = "**Example:**\n```rust\n# let x = 1;\nlet y = x;\n```\n```toml\n# comment\n```";
+        let expected = "**Example:**\n```rust\nlet y = x;\n```\n```toml\n# comment\n```";
+        assert_eq!(expected, without_hidden_lines(docs));
+    }
+
+    #[test]
+    fn json_escapes() {
+        assert_eq!(r#""a \"b\"\n\\""#, json_string("a \"b\"\n\\"));
+    }
+}
@@ -87,6 +87,13 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "block_in_if_condition",
     },
+    Lint {
+        name: "blocking_in_async",
+        group: "pedantic",
+        desc: "blocking calls inside of `async` bodies",
+        deprecation: None,
+        module: "async_bodies",
+    },
     Lint {
         name: "bool_comparison",
         group: "complexity",
@@ -416,6 +423,20 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "drop_forget_ref",
     },
+    Lint {
+        name: "dropped_must_use",
+        group: "pedantic",
+        desc: "a value that must be used goes out of scope without being used",
+        deprecation: None,
+        module: "dropped_must_use",
+    },
+    Lint {
+        name: "duplicate_code",
+        group: "pedantic",
+        desc: "functions or blocks that are copies of each other up to the names of local bindings",
+        deprecation: None,
+        module: "duplicate_code",
+    },
     Lint {
         name: "duplicate_underscore_argument",
         group: "style",
@@ -997,6 +1018,13 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "large_enum_variant",
     },
+    Lint {
+        name: "large_futures",
+        group: "pedantic",
+        desc: "`async fn`s and `async` blocks with large futures",
+        deprecation: None,
+        module: "async_bodies",
+    },
     Lint {
         name: "large_stack_arrays",
         group: "pedantic",
@@ -1081,6 +1109,41 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "main_recursion",
     },
+    Lint {
+        name: "manual_async_fn",
+        group: "style",
+        desc: "manual implementations of `async` functions can be simplified using the dedicated syntax",
+        deprecation: None,
+        module: "async_bodies",
+    },
+    Lint {
+        name: "manual_filter",
+        group: "complexity",
+        desc: "reimplementation of `Option::filter`",
+        deprecation: None,
+        module: "manual_combinators",
+    },
+    Lint {
+        name: "manual_flatten",
+        group: "complexity",
+        desc: "reimplementation of `Option::flatten`",
+        deprecation: None,
+        module: "manual_combinators",
+    },
+    Lint {
+        name: "manual_iterator_loop",
+        group: "complexity",
+        desc: "loops that could be a call to an iterator method like `sum`, `any` or `position`",
+        deprecation: None,
+        module: "loops",
+    },
+    Lint {
+        name: "manual_map",
+        group: "complexity",
+        desc: "reimplementation of `Option::map`",
+        deprecation: None,
+        module: "manual_combinators",
+    },
     Lint {
         name: "manual_memcpy",
         group: "perf",
@@ -1088,6 +1151,20 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "loops",
     },
+    Lint {
+        name: "manual_ok",
+        group: "complexity",
+        desc: "reimplementation of `Result::ok`",
+        deprecation: None,
+        module: "manual_combinators",
+    },
+    Lint {
+        name: "manual_ok_or",
+        group: "complexity",
+        desc: "reimplementation of `Option::ok_or` or `Option::ok_or_else`",
+        deprecation: None,
+        module: "manual_combinators",
+    },
     Lint {
         name: "manual_saturating_arithmetic",
         group: "style",
@@ -1095,6 +1172,13 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "methods",
     },
+    Lint {
+        name: "manual_strip",
+        group: "complexity",
+        desc: "reimplementation of `str::strip_prefix`",
+        deprecation: None,
+        module: "manual_combinators",
+    },
     Lint {
         name: "manual_swap",
         group: "complexity",
@@ -1102,6 +1186,20 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "swap",
     },
+    Lint {
+        name: "manual_unwrap_or",
+        group: "complexity",
+        desc: "reimplementation of `unwrap_or` or `unwrap_or_else`",
+        deprecation: None,
+        module: "manual_combinators",
+    },
+    Lint {
+        name: "manual_unwrap_or_default",
+        group: "complexity",
+        desc: "reimplementation of `unwrap_or_default`",
+        deprecation: None,
+        module: "manual_combinators",
+    },
     Lint {
         name: "many_single_char_names",
         group: "style",
@@ -1144,6 +1242,20 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "matches",
     },
+    Lint {
+        name: "match_collapsible_arms",
+        group: "complexity",
+        desc: "consecutive `match` arms that can be collapsed by ignoring one tuple element",
+        deprecation: None,
+        module: "matches",
+    },
+    Lint {
+        name: "match_like_matches_macro",
+        group: "pedantic",
+        desc: "a `match` or `if let` with `true` and `false` arms that could be `matches!`",
+        deprecation: None,
+        module: "redundant_pattern_matching",
+    },
     Lint {
         name: "match_on_vec_items",
         group: "correctness",
@@ -1179,6 +1291,13 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "matches",
     },
+    Lint {
+        name: "match_unreachable_arm",
+        group: "style",
+        desc: "a `match` arm that is covered by an earlier arm with an always true guard",
+        deprecation: None,
+        module: "matches",
+    },
     Lint {
         name: "match_wild_err_arm",
         group: "style",
@@ -1823,6 +1942,13 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "redundant_field_names",
     },
+    Lint {
+        name: "redundant_guard",
+        group: "complexity",
+        desc: "a `match` guard that is always true because of the pattern of the arm",
+        deprecation: None,
+        module: "matches",
+    },
     Lint {
         name: "redundant_pattern",
         group: "style",
@@ -2348,6 +2474,20 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "misc_early",
     },
+    Lint {
+        name: "unnested_or_patterns",
+        group: "pedantic",
+        desc: "patterns that only differ in a nested literal and can use a nested or-pattern",
+        deprecation: None,
+        module: "matches",
+    },
+    Lint {
+        name: "unpolled_async_block",
+        group: "correctness",
+        desc: "`async` blocks that are dropped without being polled",
+        deprecation: None,
+        module: "async_bodies",
+    },
     Lint {
         name: "unreachable",
         group: "restriction",
@@ -2376,6 +2516,13 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "unsafe_removed_from_name",
     },
+    Lint {
+        name: "unsafe_sync_static",
+        group: "pedantic",
+        desc: "`static` item of an interior mutable type that is only `Sync` through an `unsafe impl`",
+        deprecation: None,
+        module: "non_copy_const",
+    },
     Lint {
         name: "unseparated_literal_suffix",
         group: "pedantic",
@@ -2390,6 +2537,20 @@ pub static ref ALL_LINTS: Vec<Lint> = vec![
         deprecation: None,
         module: "transmute",
     },
+    Lint {
+        name: "unused_async",
+        group: "pedantic",
+        desc: "`async fn`s that never `.await`",
+        deprecation: None,
+        module: "async_bodies",
+    },
+    Lint {
+        name: "unused_clippy_allow",
+        group: "restriction",
+        desc: "`allow` attributes for Clippy lints that don\'t suppress any warning",
+        deprecation: None,
+        module: "unused_clippy_allow",
+    },
     Lint {
         name: "unused_io_amount",
         group: "correctness",
@@ -3,9 +3,14 @@
 use rustc_tools_util::VersionInfo;
 use std::env;
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command};
 
+mod explain;
+mod migrate;
+#[path = "../clippy_lints/src/renamed_lints.rs"]
+mod renamed_lints;
+
 const CARGO_CLIPPY_HELP: &str = r#"Checks a package to catch common mistakes and improve your Rust code.
 
 Usage:
@@ -14,6 +19,12 @@ Usage:
 Common options:
     -h, --help               Print this message
     -V, --version            Print version info and exit
+    --explain LINT           Print the documentation of a lint and exit
+    --list-lints             Print all lints with their group and default level and exit
+        --group GROUP        Only list the lints of a group, e.g. `pedantic` or `deprecated`
+        --format FORMAT      Print the list as `text` (default) or `json`
+    --migrate-lint-names     Replace the old names of renamed lints in the lint attributes and
+                             `clippy.toml` files of the workspace and exit
 
 Other options are the same as `cargo check`.
 
@@ -51,6 +62,14 @@ pub fn main() {
         return;
     }
 
+    let clippy_args = env::args().skip(2).take_while(|a| a != "--").collect::<Vec<_>>();
+    if let Some(result) = explain::run(&clippy_args).or_else(|| migrate::run(&clippy_args)) {
+        if let Err(code) = result {
+            process::exit(code);
+        }
+        return;
+    }
+
     if let Err(code) = process(env::args().skip(2)) {
         process::exit(code);
     }
@@ -97,7 +116,14 @@ impl ClippyCmd {
             args.insert(0, "+nightly".to_string());
         }
 
-        let clippy_args: String = old_args.map(|arg| format!("{}__CLIPPY_HACKERY__", arg)).collect();
+        let mut clippy_args: Vec<String> = old_args.collect();
+        if let Ok(current_dir) = env::current_dir() {
+            absolute_lint_lib_paths(&mut clippy_args, &current_dir);
+        }
+        let clippy_args: String = clippy_args
+            .iter()
+            .map(|arg| format!("{}__CLIPPY_HACKERY__", arg))
+            .collect();
 
         ClippyCmd {
             unstable_options,
@@ -177,9 +203,28 @@ where
     }
 }
 
+/// Makes the paths of `--lint-lib` arguments absolute. cargo runs the driver in the directory of
+/// the package being checked, so relative paths would be resolved against that instead of the
+/// directory `cargo clippy` was started in.
+fn absolute_lint_lib_paths(args: &mut [String], current_dir: &Path) {
+    let mut is_path = false;
+    for arg in args {
+        if is_path {
+            *arg = current_dir.join(&arg).display().to_string();
+            is_path = false;
+        } else if arg.starts_with("--lint-lib=") {
+            let path = current_dir.join(&arg["--lint-lib=".len()..]);
+            *arg = format!("--lint-lib={}", path.display());
+        } else {
+            is_path = arg == "--lint-lib";
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ClippyCmd;
+    use super::{absolute_lint_lib_paths, ClippyCmd};
+    use std::path::Path;
 
     #[test]
     #[should_panic]
@@ -216,4 +261,18 @@ mod tests {
         assert_eq!("check", cmd.cargo_subcommand);
         assert_eq!("RUSTC_WORKSPACE_WRAPPER", cmd.path_env());
     }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn lint_lib_paths() {
+        let mut args: Vec<String> = ["--lint-lib", "a.so", "--lint-lib=lib/b.so", "--lint-lib=/c.so", "-Wx"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        absolute_lint_lib_paths(&mut args, Path::new("/work"));
+        assert_eq!(
+            args,
+            vec!["--lint-lib", "/work/a.so", "--lint-lib=/work/lib/b.so", "--lint-lib=/c.so", "-Wx"]
+        );
+    }
 }
@@ -0,0 +1,528 @@
+//! `cargo clippy --migrate-lint-names`: replaces the old names of renamed lints in the lint
+//! attributes and `clippy.toml` files of the workspace members.
+//!
+//! The renames are taken from `clippy_lints/src/renamed_lints.rs`, the same table that registers
+//! them with rustc.
+
+use crate::renamed_lints::RENAMED_LINTS;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+lazy_static! {
+    /// The key of a `key = value` line in a TOML file, possibly dotted or quoted
+    static ref TOML_KEY_RE: Regex = Regex::new(r#"(?m)^[ \t]*((?:[\w.-]+|"[^"\n]*")+)[ \t]*="#).unwrap();
+    static ref TOML_STRING_RE: Regex = Regex::new(r#""(?:clippy::)?\w+""#).unwrap();
+}
+
+/// The attributes that set the level of the lints in their argument list.
+const LEVEL_ATTRS: &[&str] = &["allow", "warn", "deny", "forbid", "expect"];
+
+/// Runs `--migrate-lint-names` if it is in `args`, which are the arguments before a `--`.
+pub fn run(args: &[String]) -> Option<Result<(), i32>> {
+    if args.iter().any(|arg| arg == "--migrate-lint-names") {
+        Some(migrate(args))
+    } else {
+        None
+    }
+}
+
+fn error(msg: &str) -> i32 {
+    eprintln!("error: {}", msg);
+    1
+}
+
+fn migrate(args: &[String]) -> Result<(), i32> {
+    let (root, members) = workspace(args)?;
+    let mut migrated_files = 0;
+    for path in files_to_migrate(&root, &members) {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            // Not UTF-8, so not something rustc or Clippy reads either
+            Err(_) => continue,
+        };
+        let (migrated, count) = if path.extension().map_or(false, |ext| ext == "rs") {
+            migrate_attributes(&content)
+        } else {
+            migrate_toml(&content)
+        };
+        if count == 0 {
+            continue;
+        }
+        fs::write(&path, migrated).map_err(|e| error(&format!("cannot write {}: {}", path.display(), e)))?;
+        let path = path.strip_prefix(&root).unwrap_or(&path);
+        println!(
+            "Migrated {} lint name{} in {}",
+            count,
+            if count == 1 { "" } else { "s" },
+            path.display()
+        );
+        migrated_files += 1;
+    }
+    if migrated_files == 0 {
+        println!("No renamed lints found");
+    }
+    Ok(())
+}
+
+/// Asks cargo for the workspace root and the directories of the workspace members, so that
+/// `--manifest-path` works as usual.
+fn workspace(args: &[String]) -> Result<(PathBuf, Vec<PathBuf>), i32> {
+    let mut cmd = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cmd.args(&["metadata", "--no-deps", "--format-version", "1"]);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--manifest-path" {
+            cmd.arg(arg).args(args.next());
+        } else if arg.starts_with("--manifest-path=") {
+            cmd.arg(arg);
+        }
+    }
+    let output = cmd
+        .output()
+        .map_err(|e| error(&format!("cannot run `cargo metadata`: {}", e)))?;
+    if !output.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        return Err(error("`cargo metadata` failed"));
+    }
+    parse_metadata(&output.stdout).map_err(|msg| error(&msg))
+}
+
+/// Returns the workspace root and the directories of the workspace members from the output of
+/// `cargo metadata`.
+fn parse_metadata(metadata: &[u8]) -> Result<(PathBuf, Vec<PathBuf>), String> {
+    let metadata: serde_json::Value = serde_json::from_slice(metadata)
+        .map_err(|e| format!("cannot parse the output of `cargo metadata`: {}", e))?;
+    let root = metadata["workspace_root"]
+        .as_str()
+        .ok_or_else(|| "`cargo metadata` didn't print the workspace root".to_string())?;
+    let member_ids = metadata["workspace_members"]
+        .as_array()
+        .ok_or_else(|| "`cargo metadata` didn't print the workspace members".to_string())?;
+    let members = metadata["packages"]
+        .as_array()
+        .map_or(&[][..], Vec::as_slice)
+        .iter()
+        .filter(|package| member_ids.contains(&package["id"]))
+        .filter_map(|package| package["manifest_path"].as_str())
+        .filter_map(|manifest| Path::new(manifest).parent().map(Path::to_path_buf))
+        .collect();
+    Ok((PathBuf::from(root), members))
+}
+
+/// Returns the Rust files and Clippy configuration files of the workspace members, and the
+/// configuration file in the workspace root.
+fn files_to_migrate(root: &Path, members: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = members
+        .iter()
+        .flat_map(|member| package_files(member))
+        .chain(
+            ["clippy.toml", ".clippy.toml"]
+                .iter()
+                .map(|name| root.join(name))
+                .filter(|path| path.is_file()),
+        )
+        .collect::<Vec<_>>();
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Returns the Rust files and Clippy configuration files in the package directory `dir`, except
+/// for the ones in `target`, hidden directories and other packages, e.g. vendored crates.
+fn package_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut entries = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>(),
+        Err(_) => return files,
+    };
+    entries.sort();
+    for path in entries {
+        let name = path
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+        if path.is_dir() {
+            if name != "target" && !name.starts_with('.') && !path.join("Cargo.toml").exists() {
+                files.extend(package_files(&path));
+            }
+        } else if name.ends_with(".rs") || name == "clippy.toml" || name == ".clippy.toml" {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Returns the new name of `name`, keeping the `clippy::` prefix if it has one.
+fn new_name(name: &str) -> Option<&'static str> {
+    RENAMED_LINTS.iter().find_map(|(old_name, new_name)| {
+        if *old_name == name {
+            Some(*new_name)
+        } else if old_name.trim_start_matches("clippy::") == name {
+            Some(new_name.trim_start_matches("clippy::"))
+        } else {
+            None
+        }
+    })
+}
+
+/// Renames the lints in the `allow`, `warn`, `deny`, `forbid` and `expect` attributes of `src`,
+/// also inside of `cfg_attr`. Comments and string literals are left alone. Returns the new source
+/// and the number of renamed lints.
+fn migrate_attributes(src: &str) -> (String, usize) {
+    let mut migrated = String::with_capacity(src.len());
+    let mut count = 0;
+    let mut copied = 0;
+    for attr in attributes(src) {
+        migrated.push_str(&src[copied..attr.start]);
+        migrated.push_str(&migrate_attribute(&src[attr.clone()], &mut count));
+        copied = attr.end;
+    }
+    migrated.push_str(&src[copied..]);
+    (migrated, count)
+}
+
+/// Returns the ranges of the attributes in `src`, from the `#` to the closing `]`.
+fn attributes(src: &str) -> Vec<Range<usize>> {
+    let bytes = src.as_bytes();
+    let mut attrs = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'#' {
+            let mut open = i + 1;
+            if bytes.get(open) == Some(&b'!') {
+                open += 1;
+            }
+            while bytes.get(open).map_or(false, u8::is_ascii_whitespace) {
+                open += 1;
+            }
+            if bytes.get(open) == Some(&b'[') {
+                if let Some(close) = closing_bracket(src, open) {
+                    attrs.push(i..close + 1);
+                    i = close + 1;
+                    continue;
+                }
+            }
+        }
+        i = skip_token(src, i);
+    }
+    attrs
+}
+
+/// Returns the position of the `]` matching the `[` at `open`.
+fn closing_bracket(src: &str, open: usize) -> Option<usize> {
+    let bytes = src.as_bytes();
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            },
+            _ => {
+                i = skip_token(src, i);
+                continue;
+            },
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Renames the lints of an attribute. Only the paths directly inside of a lint level attribute
+/// are renamed, which may be nested in `cfg_attr`s.
+fn migrate_attribute(attr: &str, count: &mut usize) -> String {
+    let bytes = attr.as_bytes();
+    let mut migrated = String::with_capacity(attr.len());
+    // the names of the attributes whose argument lists we are in
+    let mut attr_names: Vec<&str> = Vec::new();
+    let mut last_path = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        if bytes[i] == b'_' || bytes[i].is_ascii_alphabetic() {
+            while i < bytes.len() && (bytes[i] == b'_' || bytes[i].is_ascii_alphanumeric() || bytes[i] == b':') {
+                i += 1;
+            }
+            let path = &attr[start..i];
+            let is_level_arg = attr_names.last().map_or(false, |name| LEVEL_ATTRS.contains(name))
+                && attr_names[..attr_names.len() - 1].iter().all(|name| *name == "cfg_attr");
+            match new_name(path) {
+                Some(new_name) if is_level_arg && path.starts_with("clippy::") => {
+                    *count += 1;
+                    migrated.push_str(new_name);
+                },
+                _ => migrated.push_str(path),
+            }
+            last_path = Some(path);
+            continue;
+        }
+        match bytes[i] {
+            b'(' => {
+                attr_names.push(last_path.take().unwrap_or_default());
+                i += 1;
+            },
+            b')' => {
+                attr_names.pop();
+                i += 1;
+            },
+            b if b.is_ascii_whitespace() => i += 1,
+            _ => {
+                last_path = None;
+                i = skip_token(attr, i);
+            },
+        }
+        migrated.push_str(&attr[start..i]);
+    }
+    migrated
+}
+
+/// Returns the position after the comment or literal starting at `i`, or after the character at
+/// `i` if there is none.
+fn skip_token(src: &str, i: usize) -> usize {
+    let bytes = src.as_bytes();
+    let is_ident = |b: u8| b == b'_' || b.is_ascii_alphanumeric();
+    match bytes[i] {
+        b'/' if bytes.get(i + 1) == Some(&b'/') => src[i..].find('\n').map_or(src.len(), |n| i + n),
+        b'/' if bytes.get(i + 1) == Some(&b'*') => {
+            let mut depth = 0;
+            let mut j = i;
+            while j < bytes.len() {
+                if bytes[j..].starts_with(b"/*") {
+                    depth += 1;
+                    j += 2;
+                } else if bytes[j..].starts_with(b"*/") {
+                    depth -= 1;
+                    j += 2;
+                    if depth == 0 {
+                        return j;
+                    }
+                } else {
+                    j += 1;
+                }
+            }
+            j
+        },
+        b'"' => string_end(bytes, i + 1),
+        b'b' | b'r' if i == 0 || !is_ident(bytes[i - 1]) => {
+            let mut j = i;
+            if bytes[j] == b'b' {
+                j += 1;
+            }
+            match bytes.get(j) {
+                Some(b'"') => string_end(bytes, j + 1),
+                Some(b'\'') if j > i => skip_token(src, j),
+                Some(b'r') => {
+                    let hashes = bytes[j + 1..].iter().take_while(|&&b| b == b'#').count();
+                    if bytes.get(j + 1 + hashes) != Some(&b'"') {
+                        return i + 1;
+                    }
+                    let terminator = format!("\"{}", "#".repeat(hashes));
+                    src[j + 2 + hashes..]
+                        .find(&terminator)
+                        .map_or(src.len(), |n| j + 2 + hashes + n + terminator.len())
+                },
+                _ => i + 1,
+            }
+        },
+        // a character literal, or a lifetime
+        b'\'' => {
+            if bytes.get(i + 1) == Some(&b'\\') {
+                return src[i + 3..].find('\'').map_or(src.len(), |n| i + 3 + n + 1);
+            }
+            match src[i + 1..].chars().next() {
+                Some(c) if bytes.get(i + 1 + c.len_utf8()) == Some(&b'\'') => i + 2 + c.len_utf8(),
+                _ => i + 1,
+            }
+        },
+        _ => i + src[i..].chars().next().map_or(1, char::len_utf8),
+    }
+}
+
+/// Returns the position after the `"` that closes the string whose content starts at `i`.
+fn string_end(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Is `key` a list of lints in `clippy.toml`? By convention, such keys are named `lints` or end
+/// in `-lints`, e.g. `allowed-lints` in the table of a `--lint-lib`.
+fn is_lint_list_key(key: &str) -> bool {
+    let key = key.rsplit('.').next().unwrap_or(key).trim_matches('"');
+    key == "lints" || key.ends_with("-lints") || key.ends_with("_lints")
+}
+
+/// Renames the lints in the lint lists of a `clippy.toml` file, with or without the `clippy::`
+/// prefix. Other values, e.g. `blacklisted-names`, are left alone. Returns the new content and
+/// the number of renamed lints.
+fn migrate_toml(src: &str) -> (String, usize) {
+    let mut migrated = String::with_capacity(src.len());
+    let mut count = 0;
+    let mut copied = 0;
+    for key in TOML_KEY_RE.captures_iter(src) {
+        let (key, value_start) = match (key.get(1), key.get(0)) {
+            (Some(name), Some(line)) => (name.as_str(), line.end()),
+            _ => continue,
+        };
+        if value_start < copied || !is_lint_list_key(key) {
+            continue;
+        }
+        let value = value_start..toml_value_end(src, value_start);
+        migrated.push_str(&src[copied..value.start]);
+        let value_src = TOML_STRING_RE.replace_all(&src[value.clone()], |string: &Captures<'_>| {
+            match new_name(string[0].trim_matches('"')) {
+                Some(new_name) => {
+                    count += 1;
+                    format!("\"{}\"", new_name)
+                },
+                None => string[0].to_string(),
+            }
+        });
+        migrated.push_str(&value_src);
+        copied = value.end;
+    }
+    migrated.push_str(&src[copied..]);
+    (migrated, count)
+}
+
+/// Returns the end of the TOML value starting at `start`: the end of the line, or the `]` that
+/// closes an array, which may span several lines.
+fn toml_value_end(src: &str, start: usize) -> usize {
+    let line_end = src[start..].find('\n').map_or(src.len(), |n| start + n);
+    if !src[start..line_end].trim_start().starts_with('[') {
+        return line_end;
+    }
+    let bytes = src.as_bytes();
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            },
+            b'"' => {
+                i = string_end(bytes, i + 1);
+                continue;
+            },
+            b'\'' => {
+                i = src[i + 1..].find('\'').map_or(src.len(), |n| i + n + 2);
+                continue;
+            },
+            b'#' => {
+                i = src[i..].find('\n').map_or(src.len(), |n| i + n);
+                continue;
+            },
+            _ => {},
+        }
+        i += 1;
+    }
+    src.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{migrate_attributes, migrate_toml, package_files, parse_metadata};
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn attributes() {
+        let src = "#![allow(clippy::stutter, clippy::stutter_free)]\n\
+                   #[cfg_attr(feature = \"cargo-clippy\", deny(clippy::cyclomatic_complexity))]\n\
+                   #[expect(clippy::const_static_lifetime, reason = \"clippy::stutter\")]\n\
+                   fn f() {}\n";
+        let expected = "#![allow(clippy::module_name_repetitions, clippy::stutter_free)]\n\
+                        #[cfg_attr(feature = \"cargo-clippy\", deny(clippy::cognitive_complexity))]\n\
+                        #[expect(clippy::redundant_static_lifetimes, reason = \"clippy::stutter\")]\n\
+                        fn f() {}\n";
+        assert_eq!((expected.to_string(), 3), migrate_attributes(src));
+    }
+
+    #[test]
+    fn attributes_skip_literals_and_comments() {
+        let src = "// #[allow(clippy::stutter)]\n\
+                   /* #[allow(clippy::stutter)] */\n\
+                   #[doc = \"#[allow(clippy::stutter)]\"]\n\
+                   #[my_attr(allow(clippy::stutter))]\n\
+                   fn f<'a>(_: &'a str) -> char {\n    \
+                       let _ = \"#[allow(clippy::stutter)]\";\n    \
+                       let _ = r#\"#[allow(clippy::stutter)]\"#;\n    \
+                       '#'\n\
+                   }\n";
+        assert_eq!((src.to_string(), 0), migrate_attributes(src));
+    }
+
+    #[test]
+    fn toml() {
+        let src = "blacklisted-names = [\"stutter\"]\n\
+                   doc-valid-idents = [\"const_static_lifetime\"]\n\
+                   [third-party.my-lints]\n\
+                   allowed-lints = [\n    \"clippy::stutter\", # the old name\n    \"const_static_lifetime\",\n    \
+                   \"stutter2\",\n]\n\
+                   names = [\"stutter\"]\n";
+        let expected = "blacklisted-names = [\"stutter\"]\n\
+                        doc-valid-idents = [\"const_static_lifetime\"]\n\
+                        [third-party.my-lints]\n\
+                        allowed-lints = [\n    \"clippy::module_name_repetitions\", # the old name\n    \
+                        \"redundant_static_lifetimes\",\n    \"stutter2\",\n]\n\
+                        names = [\"stutter\"]\n";
+        assert_eq!((expected.to_string(), 2), migrate_toml(src));
+    }
+
+    #[test]
+    fn metadata_members() {
+        let metadata = br#"{
+            "packages": [
+                {"id": "a 0.1.0 (path+file:///ws/a)", "manifest_path": "/ws/a/Cargo.toml"},
+                {"id": "b 0.1.0 (path+file:///ws/b)", "manifest_path": "/ws/b/Cargo.toml"}
+            ],
+            "workspace_members": ["a 0.1.0 (path+file:///ws/a)"],
+            "workspace_root": "/ws"
+        }"#;
+        assert_eq!(
+            Ok((PathBuf::from("/ws"), vec![PathBuf::from("/ws/a")])),
+            parse_metadata(metadata)
+        );
+        assert!(parse_metadata(b"not json").is_err());
+    }
+
+    #[test]
+    fn package_files_skip_other_packages() {
+        let dir = std::env::temp_dir().join(format!("clippy-migrate-{}", std::process::id()));
+        for file in &[
+            "Cargo.toml",
+            "clippy.toml",
+            "src/lib.rs",
+            "target/debug/build/out.rs",
+            ".git/hook.rs",
+            "vendor/dep/Cargo.toml",
+            "vendor/dep/src/lib.rs",
+        ] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let files = package_files(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(vec![dir.join("clippy.toml"), dir.join("src/lib.rs")], files);
+    }
+}
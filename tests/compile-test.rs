@@ -3,11 +3,13 @@
 use compiletest_rs as compiletest;
 use compiletest_rs::common::Mode as TestMode;
 
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::env::{self, set_var};
 use std::ffi::OsStr;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 mod cargo;
 
@@ -19,6 +21,10 @@ fn host_lib() -> PathBuf {
     }
 }
 
+fn cargo_clippy_path() -> PathBuf {
+    cargo::TARGET_LIB.join("cargo-clippy")
+}
+
 fn clippy_driver_path() -> PathBuf {
     if let Some(path) = option_env!("CLIPPY_DRIVER_PATH") {
         PathBuf::from(path)
@@ -153,6 +159,342 @@ fn run_ui_toml(config: &mut compiletest::Config) {
     }
 }
 
+/// Runs `cargo clippy` on the fixture workspaces in `tests/ui-cargo/<lint>/<case>`. The fixtures
+/// are copied to the build directory first, so that Cargo doesn't write into the source tree.
+fn run_ui_cargo(config: &compiletest::Config) {
+    if cargo::is_rustc_test_suite() {
+        return;
+    }
+    let src_base = Path::new("tests").join("ui-cargo");
+    let build_base = config.build_base.join("ui-cargo");
+
+    let mut failed = Vec::new();
+    for case in ui_cargo_cases(&src_base).unwrap() {
+        let name = case.strip_prefix(&src_base).unwrap().to_path_buf();
+        if let Some(filter) = &config.filter {
+            if !name.to_string_lossy().contains(filter.as_str()) {
+                continue;
+            }
+        }
+        match run_ui_cargo_case(&case, &build_base.join(&name)) {
+            Ok(true) => println!("test [ui-cargo] {} ... ok", name.display()),
+            Ok(false) => {
+                println!("test [ui-cargo] {} ... FAILED", name.display());
+                failed.push(name);
+            },
+            Err(e) => panic!("I/O failure in ui-cargo test {}: {:?}", name.display(), e),
+        }
+    }
+    if !failed.is_empty() {
+        panic!(
+            "Some ui-cargo tests failed:\n{}\n\nRun `tests/ui-cargo/update-all-references.sh` to update the \
+             expected output",
+            failed
+                .iter()
+                .map(|name| format!("    {}", name.display()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
+
+/// The directories that contain a `Cargo.toml`, two levels below `tests/ui-cargo`.
+fn ui_cargo_cases(src_base: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    let mut cases = Vec::new();
+    for lint in fs::read_dir(src_base)? {
+        let lint = lint?;
+        if !lint.file_type()?.is_dir() {
+            continue;
+        }
+        for case in fs::read_dir(lint.path())? {
+            let case = case?.path();
+            if case.join("Cargo.toml").is_file() {
+                cases.push(case);
+            }
+        }
+    }
+    cases.sort();
+    Ok(cases)
+}
+
+/// Runs `cargo clippy` on a copy of the fixture in `case` and compares its stderr to
+/// `cargo-clippy.stderr`. The actual output is written to the copy for `update-references.sh`.
+fn run_ui_cargo_case(case: &Path, build_dir: &Path) -> Result<bool, io::Error> {
+    if build_dir.exists() {
+        fs::remove_dir_all(build_dir)?;
+    }
+    copy_fixture(case, build_dir)?;
+
+    let output = Command::new(cargo_clippy_path())
+        .current_dir(build_dir)
+        .env("CARGO_TARGET_DIR", build_dir.join("target"))
+        .env("CARGO_INCREMENTAL", "0")
+        // Don't lint the dependencies that Cargo runs with `--cap-lints allow`
+        .env_remove("CLIPPY_TESTS")
+        .env_remove("CARGO_MANIFEST_DIR")
+        .args(&["clippy", "--quiet", "--offline", "-j1", "--"])
+        .args(&["-D", "warnings", "-Zui-testing"])
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let actual = stderr
+        .replace(&build_dir.display().to_string(), "$DIR")
+        .replace('\\', "/");
+    let expected_path = case.join("cargo-clippy.stderr");
+    let expected = fs::read_to_string(&expected_path).unwrap_or_default();
+
+    let actual_path = build_dir.join("cargo-clippy.stderr");
+    if actual.is_empty() {
+        if actual_path.exists() {
+            fs::remove_file(&actual_path)?;
+        }
+    } else {
+        fs::write(&actual_path, &actual)?;
+    }
+
+    if actual == expected {
+        return Ok(true);
+    }
+    println!("\nExpected stderr ({}):\n{}", expected_path.display(), expected);
+    println!("Actual stderr ({}):\n{}", actual_path.display(), actual);
+    Ok(false)
+}
+
+/// Copies the fixture without the expected output and any build artifacts.
+fn copy_fixture(src: &Path, dst: &Path) -> Result<(), io::Error> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_name = entry.file_name();
+        if entry.file_type()?.is_dir() {
+            if file_name != "target" {
+                copy_fixture(&path, &dst.join(&file_name))?;
+            }
+        } else if path.extension() != Some(OsStr::new("stderr")) && file_name != "Cargo.lock" {
+            fs::copy(&path, dst.join(&file_name))?;
+        }
+    }
+    Ok(())
+}
+
+/// How often the suggestions of a lint are applied before the fix is considered not to converge.
+const AUTOFIX_MAX_ROUNDS: usize = 3;
+
+/// Applies the machine-applicable suggestions of every lint in `tests/ui` and checks that the fixed
+/// code compiles and that the lint doesn't suggest anything for it anymore. Lints whose fixes are
+/// known to be broken are listed in `tests/autofix-known-failures.txt`.
+fn run_autofix(config: &compiletest::Config) {
+    if cargo::is_rustc_test_suite() {
+        return;
+    }
+    let src_base = Path::new("tests").join("ui");
+    let build_base = config.build_base.join("autofix");
+    fs::create_dir_all(&build_base).unwrap();
+    let flags = config
+        .target_rustcflags
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter(|flag| *flag != "-Dwarnings" && *flag != "-Zui-testing")
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    let mut failures = BTreeMap::<String, Vec<String>>::new();
+    for file in autofix_test_files(&src_base).unwrap() {
+        let name = file.strip_prefix(&src_base).unwrap().display().to_string();
+        if let Some(filter) = &config.filter {
+            if !name.contains(filter.as_str()) {
+                continue;
+            }
+        }
+        let code = fs::read_to_string(&file).unwrap();
+        let mut args = flags.clone();
+        if !autofix_header_args(&code, &mut args) {
+            continue;
+        }
+
+        let json = run_for_json(&file, &args, &build_base);
+        // Tests that don't compile on purpose can't be checked
+        if first_compile_error(&json).is_some() {
+            continue;
+        }
+        for lint in machine_applicable_lints(&json) {
+            let stem = format!(
+                "{}__{}",
+                name.trim_end_matches(".rs").replace('/', "__"),
+                lint.replace("::", "__")
+            );
+            if let Err(e) = check_autofix(&file, &json, &lint, &args, &build_base.join(stem).with_extension("rs")) {
+                failures.entry(lint).or_default().push(format!("{}: {}", name, e));
+            }
+        }
+    }
+
+    let known_failures = fs::read_to_string(Path::new("tests").join("autofix-known-failures.txt")).unwrap();
+    let known_failures = known_failures
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .map(|lint| format!("clippy::{}", lint))
+        .collect::<BTreeSet<_>>();
+
+    let mut report = String::new();
+    for (lint, tests) in &failures {
+        if known_failures.contains(lint) {
+            continue;
+        }
+        report.push_str(&format!("\n{}:\n", lint));
+        for test in tests {
+            report.push_str(&format!("    {}\n", test));
+        }
+    }
+    if config.filter.is_none() {
+        for lint in known_failures.iter().filter(|lint| !failures.contains_key(*lint)) {
+            report.push_str(&format!(
+                "\n{}: the fixes work now, remove the lint from `tests/autofix-known-failures.txt`\n",
+                lint
+            ));
+        }
+    }
+    if !report.is_empty() {
+        panic!("Lints with broken machine-applicable suggestions:\n{}", report);
+    }
+}
+
+/// All tests in `tests/ui` and its subdirectories, except for auxiliary crates.
+fn autofix_test_files(dir: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if !path.ends_with("auxiliary") {
+                files.extend(autofix_test_files(&path)?);
+            }
+        } else if path.extension() == Some(OsStr::new("rs")) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Adds the compiler arguments of the compiletest headers of a test to `args`. Returns `false` for
+/// tests that can't be compiled on their own.
+fn autofix_header_args(code: &str, args: &mut Vec<String>) -> bool {
+    for line in code
+        .lines()
+        .take_while(|line| line.starts_with("//") || line.trim().is_empty())
+    {
+        let header = line.trim_start_matches('/').trim();
+        if header.starts_with("aux-build") || header.starts_with("ignore-") {
+            return false;
+        } else if header.starts_with("edition:") {
+            args.push(format!("--edition={}", header["edition:".len()..].trim()));
+        } else if header.starts_with("compile-flags:") {
+            args.extend(
+                header["compile-flags:".len()..]
+                    .split_whitespace()
+                    .map(ToString::to_string),
+            );
+        }
+    }
+    true
+}
+
+/// Runs Clippy on `file` and returns its diagnostics as JSON, one per line.
+fn run_for_json(file: &Path, args: &[String], out_dir: &Path) -> String {
+    let output = Command::new(clippy_driver_path())
+        .env("CARGO_MANIFEST_DIR", env::current_dir().unwrap())
+        .arg(file)
+        .args(args)
+        .args(&["--error-format=json", "--emit=metadata", "--out-dir"])
+        .arg(out_dir)
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn diagnostics(json: &str) -> impl Iterator<Item = serde_json::Value> + '_ {
+    json.lines().filter_map(|line| serde_json::from_str(line).ok())
+}
+
+/// Returns the message of the first error that doesn't come from a lint.
+fn first_compile_error(json: &str) -> Option<String> {
+    diagnostics(json).find_map(|diag| {
+        let code = diag["code"]["code"].as_str();
+        let message = diag["message"].as_str().unwrap_or_default();
+        let is_error = diag["level"] == "error"
+            && code.map_or(true, |code| {
+                code.starts_with('E') && code[1..].chars().all(|c| c.is_ascii_digit())
+            })
+            && !message.starts_with("aborting due to");
+        if is_error {
+            Some(message.to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the lints that emit at least one machine-applicable suggestion.
+fn machine_applicable_lints(json: &str) -> BTreeSet<String> {
+    fn has_machine_applicable(diag: &serde_json::Value) -> bool {
+        diag["spans"].as_array().map_or(false, |spans| {
+            spans
+                .iter()
+                .any(|span| span["suggestion_applicability"] == "MachineApplicable")
+        }) || diag["children"]
+            .as_array()
+            .map_or(false, |children| children.iter().any(has_machine_applicable))
+    }
+
+    diagnostics(json)
+        .filter(has_machine_applicable)
+        .filter_map(|diag| diag["code"]["code"].as_str().map(ToString::to_string))
+        .filter(|code| code.starts_with("clippy::"))
+        .collect()
+}
+
+/// Applies the machine-applicable suggestions of `lint` to the test `file` until the lint doesn't
+/// suggest anything anymore. The fixed code is written to `fixed_path`.
+fn check_autofix(file: &Path, json: &str, lint: &str, args: &[String], fixed_path: &Path) -> Result<(), String> {
+    let only = std::iter::once(lint.to_string()).collect::<HashSet<_>>();
+    let mut code = fs::read_to_string(file).map_err(|e| e.to_string())?;
+    let mut json = json.to_string();
+    let mut path = file;
+    for _ in 0..AUTOFIX_MAX_ROUNDS {
+        let mut suggestions = rustfix::get_suggestions_from_json(&json, &only, rustfix::Filter::MachineApplicableOnly)
+            .map_err(|e| format!("can't read the suggestions: {}", e))?;
+        // Suggestions for other files, like the modules of a test, aren't applied
+        suggestions.retain(|sugg| {
+            sugg.snippets
+                .iter()
+                .all(|snippet| Path::new(&snippet.file_name) == path)
+        });
+        if suggestions.is_empty() {
+            return Ok(());
+        }
+        code = rustfix::apply_suggestions(&code, &suggestions)
+            .map_err(|e| format!("can't apply the suggestions: {}", e))?;
+        fs::write(fixed_path, &code).map_err(|e| e.to_string())?;
+        path = fixed_path;
+        json = run_for_json(path, args, fixed_path.parent().unwrap());
+        if let Some(error) = first_compile_error(&json) {
+            return Err(format!(
+                "the fixed code doesn't compile: {} (see {})",
+                error,
+                fixed_path.display()
+            ));
+        }
+    }
+    Err(format!(
+        "the lint still suggests a fix after applying its suggestions {} times (see {})",
+        AUTOFIX_MAX_ROUNDS,
+        fixed_path.display()
+    ))
+}
+
 fn prepare_env() {
     set_var("CLIPPY_DISABLE_DOCS_LINKS", "true");
     set_var("CLIPPY_TESTS", "true");
@@ -165,4 +507,11 @@ fn compile_test() {
     let mut config = default_config();
     run_mode(&mut config);
     run_ui_toml(&mut config);
+    run_ui_cargo(&config);
+}
+
+#[test]
+fn autofix_test() {
+    prepare_env();
+    run_autofix(&default_config());
 }
@@ -54,6 +54,7 @@ fn dogfood_subprojects() {
         "clippy_workspace_tests/subcrate",
         "clippy_workspace_tests/subcrate/src",
         "clippy_dev",
+        "clippy_utils",
         "rustc_tools_util",
     ] {
         let output = Command::new(&*CLIPPY_PATH)
@@ -0,0 +1,52 @@
+// Running `cargo-clippy` directly doesn't work on Windows, see `dogfood.rs`
+#![cfg(not(windows))]
+
+use lazy_static::lazy_static;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+mod cargo;
+
+lazy_static! {
+    static ref CLIPPY_PATH: PathBuf = cargo::TARGET_LIB.join("cargo-clippy");
+}
+
+fn cargo_clippy(args: &[&str]) -> Output {
+    Command::new(&*CLIPPY_PATH).arg("clippy").args(args).output().unwrap()
+}
+
+#[test]
+fn explain() {
+    if cargo::is_rustc_test_suite() {
+        return;
+    }
+    let output = cargo_clippy(&["--explain", "clippy::too-many-lines"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("`clippy::too_many_lines` (pedantic, allow by default)"));
+    assert!(stdout.contains("**What it does:**"));
+    assert!(stdout.contains("* `too-many-lines-threshold: u64`"));
+
+    let output = cargo_clippy(&["--explain", "not_a_lint"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn list_lints() {
+    if cargo::is_rustc_test_suite() {
+        return;
+    }
+    let output = cargo_clippy(&["--list-lints", "--group", "correctness"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.lines().all(|line| line.contains(" correctness  deny ")));
+
+    let output = cargo_clippy(&["--list-lints", "--format=json"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with('['));
+    assert!(stdout.contains(r#"{"name": "needless_return", "group": "style", "level": "warn""#));
+
+    let output = cargo_clippy(&["--list-lints", "--group", "not_a_group"]);
+    assert!(!output.status.success());
+}
@@ -0,0 +1,50 @@
+// Dynamic libraries are only tested on Linux, like the driver in CI
+#![cfg(target_os = "linux")]
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+mod cargo;
+
+#[test]
+fn load_lint_lib() {
+    // build the sample lint library in `tests/lint-lib` and run the driver with it
+    if cargo::is_rustc_test_suite() {
+        return;
+    }
+    let lib_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("lint-lib");
+    let target_dir = cargo::CARGO_TARGET_DIR.join("lint-lib");
+
+    let output = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .current_dir(&lib_dir)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .env("CARGO_INCREMENTAL", "0")
+        .arg("build")
+        .output()
+        .unwrap();
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    let lib = target_dir.join("debug").join("liblint_lib_test.so");
+    let output = Command::new(cargo::TARGET_LIB.join("clippy-driver"))
+        .current_dir(&lib_dir)
+        .arg("--lint-lib")
+        .arg(&lib)
+        .args(&["--edition=2018", "--crate-type=lib", "--emit=metadata", "-o"])
+        .arg(target_dir.join("input.rmeta"))
+        .arg("input.rs")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    println!("stderr: {}", stderr);
+
+    assert!(output.status.success());
+    assert!(stderr.contains("warning: function named `forbidden`"));
+    assert!(stderr.contains("#[warn(clippy::forbidden_name)]"));
+    assert_eq!(stderr.matches("function named").count(), 1);
+
+    assert_eq!(stderr.matches("warning: call to `mem::drop`").count(), 2);
+    assert_eq!(stderr.matches("warning: call to `String::new`").count(), 1);
+    assert_eq!(stderr.matches("warning: call that returns a `Vec`").count(), 1);
+}
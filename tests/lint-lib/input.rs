@@ -0,0 +1,9 @@
+pub fn forbidden() {}
+
+pub fn allowed() {}
+
+pub fn calls() {
+    drop(String::new());
+    drop(Vec::<u8>::new());
+    let _ = std::mem::replace(&mut 0, 1);
+}
@@ -0,0 +1,78 @@
+//! A lint library that is built and loaded by `tests/lint-lib.rs`.
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use clippy_utils::{declare_clippy_lint, is_def_path, is_type_diag_item_or_path, span_lint, Conf};
+use rustc_hir::def::Res;
+use rustc_hir::{Expr, ExprKind, Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_session::{declare_lint_pass, declare_tool_lint, Session};
+use rustc_span::Symbol;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for functions named `forbidden`.
+    #[clippy::version = "1.45.0"]
+    pub FORBIDDEN_NAME,
+    style,
+    "functions named `forbidden`"
+}
+
+declare_lint_pass!(ForbiddenName => [FORBIDDEN_NAME]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ForbiddenName {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Fn(..) = item.kind {
+            if item.ident.as_str() == "forbidden" {
+                span_lint(cx, FORBIDDEN_NAME, item.span, "function named `forbidden`");
+            }
+        }
+    }
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for calls to `mem::drop` and `String::new` and for calls that
+    /// return a `Vec`.
+    #[clippy::version = "1.45.0"]
+    pub DEF_PATHS,
+    style,
+    "calls to some items of `std`"
+}
+
+declare_lint_pass!(DefPaths => [DEF_PATHS]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for DefPaths {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Call(func, _) = expr.kind {
+            if let ExprKind::Path(ref qpath) = func.kind {
+                if let Res::Def(_, did) = cx.tables.qpath_res(qpath, func.hir_id) {
+                    // resolved through the exports of `core`
+                    if is_def_path(cx, did, &["core", "mem", "drop"]) {
+                        span_lint(cx, DEF_PATHS, expr.span, "call to `mem::drop`");
+                    }
+                    // inherent methods can't be resolved, their def path is compared instead
+                    if is_def_path(cx, did, &["alloc", "string", "String", "new"]) {
+                        span_lint(cx, DEF_PATHS, expr.span, "call to `String::new`");
+                    }
+                }
+            }
+            let ty = cx.tables.expr_ty(expr);
+            if is_type_diag_item_or_path(cx, ty, Symbol::intern("vec_type"), &["alloc", "vec", "Vec"]) {
+                span_lint(cx, DEF_PATHS, expr.span, "call that returns a `Vec`");
+            }
+        }
+    }
+}
+
+#[no_mangle]
+pub fn clippy_register_lints(store: &mut LintStore, _: &Session, _: &Conf) {
+    store.register_lints(&[&FORBIDDEN_NAME, &DEF_PATHS]);
+    store.register_late_pass(|| Box::new(ForbiddenName));
+    store.register_late_pass(|| Box::new(DefPaths));
+}
+
+clippy_utils::export_lint_lib_version!();
@@ -0,0 +1,7 @@
+#![warn(clippy::module_inception)]
+
+mod foo;
+
+fn main() {
+    foo::foo::bar();
+}
@@ -0,0 +1 @@
+pub(crate) fn helper() {}
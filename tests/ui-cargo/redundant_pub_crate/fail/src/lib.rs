@@ -0,0 +1,7 @@
+#![warn(clippy::redundant_pub_crate)]
+
+mod internal;
+
+pub fn api() {
+    internal::helper();
+}
@@ -0,0 +1,5 @@
+#![warn(clippy::unused_clippy_allow)]
+// `multiple_crate_versions` only checks whether it is allowed, the `allow` must count as used
+#![allow(clippy::multiple_crate_versions)]
+
+fn main() {}
@@ -0,0 +1,19 @@
+#!/bin/bash
+#
+# A script to update the references for all tests. The idea is that
+# you do a run, which will generate files in the build directory
+# containing the (normalized) actual output of `cargo clippy`. You
+# then run this script, which will copy those files over. If you find
+# yourself manually editing a cargo-clippy.stderr file, you're doing
+# it wrong.
+#
+# See all `update-references.sh`, if you just want to update a single test.
+
+if [[ "$1" == "--help" || "$1" == "-h" ]]; then
+    echo "usage: $0"
+fi
+
+BUILD_DIR=$PWD/target/debug/test_build_base/ui-cargo
+MY_DIR=$(dirname "$0")
+cd "$MY_DIR" || exit
+find . -mindepth 3 -maxdepth 3 -name Cargo.toml -exec dirname {} \; | xargs ./update-references.sh "$BUILD_DIR"
@@ -0,0 +1,37 @@
+#!/bin/bash
+
+# A script to update the references for particular tests. The idea is
+# that you do a run, which will generate files in the build directory
+# containing the (normalized) actual output of `cargo clippy`. This
+# script will then copy that output and replace the "expected output"
+# files. You can then commit the changes.
+#
+# If you find yourself manually editing a cargo-clippy.stderr file,
+# you're doing it wrong.
+
+if [[ "$1" == "--help" || "$1" == "-h" || "$1" == "" || "$2" == "" ]]; then
+    echo "usage: $0 <build-directory> <relative-path-to-test-case-dirs>"
+    echo ""
+    echo "For example:"
+    echo "   $0 ../../target/debug/test_build_base/ui-cargo cargo_common_metadata/fail"
+fi
+
+MYDIR=$(dirname "$0")
+
+BUILD_DIR="$1"
+shift
+
+while [[ "$1" != "" ]]; do
+    STDERR_NAME="$1/cargo-clippy.stderr"
+    shift
+    if [[ -f "$BUILD_DIR"/"$STDERR_NAME" ]]; then
+        if ! (cmp -s -- "$BUILD_DIR"/"$STDERR_NAME" "$MYDIR"/"$STDERR_NAME"); then
+            echo updating "$MYDIR"/"$STDERR_NAME"
+            cp "$BUILD_DIR"/"$STDERR_NAME" "$MYDIR"/"$STDERR_NAME"
+        fi
+    elif [[ -f "$MYDIR"/"$STDERR_NAME" ]]; then
+        # `cargo clippy` didn't emit anything for this test
+        echo removing "$MYDIR"/"$STDERR_NAME"
+        rm "$MYDIR"/"$STDERR_NAME"
+    fi
+done
@@ -0,0 +1,3 @@
+#![warn(clippy::wildcard_dependencies)]
+
+fn main() {}
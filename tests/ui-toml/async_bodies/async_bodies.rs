@@ -0,0 +1,28 @@
+// edition:2018
+#![warn(clippy::blocking_in_async, clippy::large_futures)]
+
+mod db {
+    pub fn query(_: &str) -> u32 {
+        0
+    }
+
+    pub fn prepare(_: &str) {}
+}
+
+async fn nop() {}
+
+async fn run() -> u32 {
+    db::prepare("SELECT 1");
+    db::query("SELECT 1")
+}
+
+async fn buffered() {
+    let buffer = [0_u8; 2048];
+    nop().await;
+    drop(buffer);
+}
+
+fn main() {
+    let _ = run();
+    let _ = buffered();
+}
@@ -0,0 +1,65 @@
+#![warn(clippy::cognitive_complexity)]
+#![allow(dead_code)]
+
+enum Shape {
+    Circle(f64),
+    Square(f64),
+    Empty,
+}
+
+enum Unit {
+    Metric,
+    Imperial,
+}
+
+// The arms of a `match` on an enum don't count as nesting
+fn area(shape: &Shape, unit: &Unit) -> f64 {
+    match shape {
+        Shape::Circle(r) => match unit {
+            Unit::Metric => 3.0 * r * r,
+            Unit::Imperial => 3.0 * r * r * 0.155,
+        },
+        Shape::Square(side) => side * side,
+        Shape::Empty => 0.0,
+    }
+}
+
+fn describe(n: u32) -> &'static str {
+    match n {
+        0 => "zero",
+        _ => match n % 2 {
+            0 => "even",
+            _ => "odd",
+        },
+    }
+}
+
+fn sign(x: i32) -> i32 {
+    if x > 0 {
+        1
+    } else if x < 0 {
+        -1
+    } else {
+        0
+    }
+}
+
+fn count(v: &[u32]) -> u32 {
+    let mut n = 0;
+    for x in v {
+        if *x > 10 {
+            n += 1;
+        }
+    }
+    n
+}
+
+fn fib(n: u64) -> u64 {
+    if n < 2 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+fn main() {}
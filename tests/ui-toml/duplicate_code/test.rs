@@ -0,0 +1,85 @@
+#![warn(clippy::duplicate_code)]
+#![allow(dead_code)]
+
+fn area(width: u32, height: u32) -> u32 {
+    let w = width.max(1);
+    let h = height.max(1);
+    let a = w * h;
+    if a > 100 { a - 100 } else { a + w + h }
+}
+
+// Same as `area`, up to the names of the bindings
+fn surface(x: u32, y: u32) -> u32 {
+    let first = x.max(1);
+    let second = y.max(1);
+    let product = first * second;
+    if product > 100 { product - 100 } else { product + first + second }
+}
+
+fn volume(a: u32, b: u32) -> u32 {
+    let c = a.max(1);
+    let d = b.max(1);
+    let e = c * d;
+    if e > 100 { e - 100 } else { e + c + d }
+}
+
+// Different operator, not a copy
+fn not_a_copy(width: u32, height: u32) -> u32 {
+    let w = width.max(1);
+    let h = height.max(1);
+    let a = w * h;
+    if a > 100 { a / 100 } else { a + w + h }
+}
+
+// Below the threshold
+fn small_a(x: u32) -> u32 {
+    x + 1
+}
+
+fn small_b(y: u32) -> u32 {
+    y + 1
+}
+
+fn with_block_a(v: &[u32]) -> usize {
+    let len = {
+        let first = v.first().copied().unwrap_or(0);
+        let last = v.last().copied().unwrap_or(0);
+        (first + last) as usize
+    };
+    len + 1
+}
+
+fn with_block_b(items: &[u32]) -> usize {
+    let n = items.len();
+    let len = {
+        let head = items.first().copied().unwrap_or(0);
+        let tail = items.last().copied().unwrap_or(0);
+        (head + tail) as usize
+    };
+    len * n
+}
+
+// Same as `area` up to the types, not a copy
+fn area_u64(width: u64, height: u64) -> u64 {
+    let w = width.max(1);
+    let h = height.max(1);
+    let a = w * h;
+    if a > 100 { a - 100 } else { a + w + h }
+}
+
+// Same hash, since type arguments are not hashed, but not a copy
+fn collision_a(n: usize) -> usize {
+    let mut v: Vec<u32> = Vec::with_capacity(n);
+    v.push(1);
+    v.push(2);
+    v.len() + n + v.capacity()
+}
+
+fn collision_b(n: usize) -> usize {
+    let mut v: Vec<u16> = Vec::with_capacity(n);
+    v.push(1);
+    v.push(2);
+    v.len() + n + v.capacity()
+}
+
+fn main() {}
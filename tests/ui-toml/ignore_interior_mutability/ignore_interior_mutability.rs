@@ -0,0 +1,39 @@
+#![warn(clippy::declare_interior_mutable_const)]
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+/// A key whose hash only depends on the immutable id.
+struct Counter {
+    id: u32,
+    hits: AtomicUsize,
+}
+
+impl PartialEq for Counter {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Counter {}
+
+impl Hash for Counter {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.id.hash(h);
+    }
+}
+
+struct Keyed(Counter);
+
+const COUNTER: Counter = Counter {
+    id: 0,
+    hits: AtomicUsize::new(0),
+};
+const ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+fn ignored(_set: &HashSet<Counter>, _arcs: &HashSet<Arc<Counter>>, _keyed: &HashSet<Keyed>) {}
+
+fn main() {}
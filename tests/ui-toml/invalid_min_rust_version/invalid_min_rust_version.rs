@@ -0,0 +1,3 @@
+// error-pattern: `invalid.version` is not a valid Rust version
+
+fn main() {}
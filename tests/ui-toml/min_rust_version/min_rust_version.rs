@@ -0,0 +1,11 @@
+#![warn(clippy::manual_strip)]
+
+// `str::strip_prefix` requires Rust 1.45, so `manual_strip` doesn't lint with `msrv = "1.44"`
+fn main() {
+    let s = "--verbose";
+    let _ = if s.starts_with("--") {
+        Some(&s["--".len()..])
+    } else {
+        None
+    };
+}
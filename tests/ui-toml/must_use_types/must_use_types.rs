@@ -0,0 +1,47 @@
+#![warn(clippy::dropped_must_use)]
+
+#[derive(Default)]
+struct Builder {
+    verbose: bool,
+    level: u8,
+}
+
+impl Builder {
+    fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    fn level(mut self, level: u8) -> Self {
+        self.level = level;
+        self
+    }
+
+    fn build(self) -> u8 {
+        if self.verbose {
+            self.level
+        } else {
+            0
+        }
+    }
+}
+
+fn configure(builder: Builder, verbose: bool) -> Option<u8> {
+    let builder = builder.level(3);
+    if verbose {
+        return None;
+    }
+    Some(builder.verbose(verbose).build())
+}
+
+fn main() {
+    let builder = Builder::default();
+    builder.verbose(true).level(2);
+
+    let _unused = Builder::default().level(1);
+
+    // used
+    let builder = Builder::default().level(1);
+    let _ = configure(builder, true);
+    let _ = Builder::default();
+}
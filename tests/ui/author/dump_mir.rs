@@ -0,0 +1,11 @@
+#![allow(dead_code)]
+
+#[clippy::dump = "mir"]
+fn id(x: u8) -> u8 {
+    x
+}
+
+#[clippy::dump = "mir"]
+struct NoBody;
+
+fn main() {}
@@ -0,0 +1,4 @@
+fn main() {
+    #[clippy::author = "early"]
+    let _ = ::std::cmp::min(3, 4);
+}
@@ -0,0 +1,6 @@
+// normalize-stdout-test: "\d+\.\d+\.\d+" -> "<version>"
+
+fn main() {
+    #[clippy::author = "skeleton"]
+    let _ = 42;
+}
@@ -1,5 +1,6 @@
 // edition:2018
 #![warn(clippy::await_holding_lock)]
+#![allow(clippy::manual_async_fn)]
 
 use std::sync::Mutex;
 
@@ -0,0 +1,44 @@
+// edition:2018
+#![warn(clippy::blocking_in_async)]
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+async fn bad(m: &Mutex<u32>) -> std::io::Result<String> {
+    let config = fs::read_to_string("config.toml")?;
+    let mut file = File::open("log.txt")?;
+    thread::sleep(Duration::from_millis(10));
+    *m.lock().unwrap() += 1;
+    let mut contents = String::new();
+    // trait methods are not detected
+    file.read_to_string(&mut contents)?;
+    Ok(config + &contents)
+}
+
+fn spawn<F: std::future::Future>(_: F) {}
+
+fn in_block() {
+    spawn(async {
+        std::thread::sleep(Duration::from_secs(1));
+    });
+}
+
+async fn in_closure() -> Option<Vec<u8>> {
+    // the closure may be run on another thread
+    Some("data.bin").map(|path| fs::read(path).unwrap())
+}
+
+fn not_async() -> std::io::Result<String> {
+    fs::read_to_string("config.toml")
+}
+
+fn main() {
+    let m = Mutex::new(0);
+    let _ = bad(&m);
+    in_block();
+    let _ = in_closure();
+    let _ = not_async();
+}
@@ -0,0 +1,98 @@
+#![deny(clippy::internal)]
+#![feature(rustc_private)]
+
+#[macro_use]
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_session;
+extern crate rustc_lint;
+
+// A reduced version of Clippy's `declare_clippy_lint!`
+macro_rules! declare_clippy_lint {
+    { $(#[$attr:meta])* pub $name:tt, $category:ident, $description:tt } => {
+        declare_tool_lint! {
+            $(#[$attr])* pub clippy::$name, Warn, $description, report_in_external_macro: true
+        }
+    };
+}
+
+declare_clippy_lint! {
+    /// Some docs
+    #[clippy::version = "1.45.0"]
+    pub VALID_VERSION,
+    style,
+    "valid version"
+}
+
+declare_clippy_lint! {
+    #[clippy::version = "pre 1.29.0"]
+    pub VALID_OLD_VERSION,
+    style,
+    "valid old version"
+}
+
+declare_clippy_lint! {
+    #[clippy::version = "1.45"]
+    pub INVALID_VERSION,
+    style,
+    "invalid version"
+}
+
+declare_clippy_lint! {
+    #[clippy::version = "unreleased"]
+    pub INVALID_VERSION_TEXT,
+    style,
+    "invalid version text"
+}
+
+declare_clippy_lint! {
+    /// Some docs
+    pub MISSING_VERSION,
+    style,
+    "missing version"
+}
+
+// A reduced version of Clippy's `declare_deprecated_lint!`
+macro_rules! declare_deprecated_lint {
+    { $(#[$attr:meta])* pub $name: ident, $_reason: expr } => {
+        declare_lint! {
+            $(#[$attr])*
+            pub $name,
+            Allow,
+            "deprecated lint"
+        }
+    }
+}
+
+declare_deprecated_lint! {
+    #[clippy::version = "1.45.0"]
+    pub VALID_DEPRECATED,
+    "valid deprecated"
+}
+
+declare_deprecated_lint! {
+    /// Some docs
+    pub MISSING_DEPRECATED_VERSION,
+    "missing deprecated version"
+}
+
+// Only lints declared with `declare_clippy_lint!` or `declare_deprecated_lint!` need a version
+declare_tool_lint! {
+    pub clippy::OTHER_LINT,
+    Warn,
+    "other lint",
+    report_in_external_macro: true
+}
+
+declare_lint_pass!(Pass => [
+    VALID_VERSION,
+    VALID_OLD_VERSION,
+    INVALID_VERSION,
+    INVALID_VERSION_TEXT,
+    MISSING_VERSION,
+    VALID_DEPRECATED,
+    MISSING_DEPRECATED_VERSION,
+    OTHER_LINT
+]);
+
+fn main() {}
@@ -133,8 +133,7 @@ fn bloo() {
     }
 }
 
-// Short circuiting operations don't increase the complexity of a function.
-// Note that the minimum complexity of a function is 1.
+// A sequence of the same short circuiting operator only adds 1 to the complexity.
 #[clippy::cognitive_complexity = "1"]
 fn lots_of_short_circuits() -> bool {
     true && false && true && false && true && false && true
@@ -393,3 +392,55 @@ impl Moo {
         }
     }
 }
+
+#[clippy::cognitive_complexity = "1"]
+fn mixed_short_circuits(a: bool, b: bool, c: bool) -> bool {
+    a && b || c && !(a || b)
+}
+
+#[clippy::cognitive_complexity = "1"]
+fn factorial(n: u64) -> u64 {
+    if n == 0 {
+        1
+    } else {
+        n * factorial(n - 1)
+    }
+}
+
+struct Tree(Vec<Tree>);
+
+impl Tree {
+    #[clippy::cognitive_complexity = "0"]
+    fn depth(&self) -> usize {
+        self.0.iter().map(|tree| tree.depth()).max().unwrap_or(0) + 1
+    }
+}
+
+#[clippy::cognitive_complexity = "1"]
+fn else_if_chain(x: u32) -> u32 {
+    if x == 0 {
+        0
+    } else if x == 1 {
+        for _ in 0..x {
+            if x > 2 {
+                return 2;
+            }
+        }
+        1
+    } else {
+        2
+    }
+}
+
+#[clippy::cognitive_complexity = "1"]
+fn loops(mut x: u32) {
+    while x > 0 {
+        x -= 1;
+    }
+    loop {
+        if x == 3 {
+            break;
+        }
+        x += 1;
+    }
+}
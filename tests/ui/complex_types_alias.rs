@@ -0,0 +1,38 @@
+#![warn(clippy::type_complexity)]
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+struct VecBoxPair;
+
+// The largest part that occurs more than once is factored out
+fn repeated(_: HashMap<Vec<Box<(u8, u8)>>, Vec<Box<(u8, u8)>>>) {}
+
+struct Registry {
+    callbacks: Arc<Mutex<HashMap<String, Vec<Box<dyn Fn(u32) -> u32>>>>>,
+}
+
+struct Cache<'a, T> {
+    entries: Vec<Vec<Box<(&'a T, &'a T, T, T)>>>,
+}
+
+trait Node {
+    fn children(&self) -> Vec<Vec<Box<(Self, Self, u32, u32)>>>
+    where
+        Self: Sized;
+}
+
+fn elided_lifetimes() {
+    let _: Vec<Vec<Box<(&str, &str, u32, u32)>>> = Vec::new();
+}
+
+fn main() {}
+
+mod inner {
+    pub struct S {
+        pub f: Vec<Vec<Box<(u32, u32, u32, u32)>>>,
+    }
+
+    pub fn f(_: Vec<Vec<Box<(u32, u32, u32, u32)>>>) {}
+}
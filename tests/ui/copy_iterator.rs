@@ -1,4 +1,5 @@
 #![warn(clippy::copy_iterator)]
+#![allow(clippy::needless_collect)]
 
 #[derive(Copy, Clone)]
 struct Countdown(u8);
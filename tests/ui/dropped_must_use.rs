@@ -0,0 +1,133 @@
+#![warn(clippy::dropped_must_use)]
+#![allow(dead_code, unused_assignments)]
+
+use std::sync::Mutex;
+
+fn fallible() -> Result<u32, ()> {
+    Ok(1)
+}
+
+#[must_use]
+fn pure() -> u32 {
+    1
+}
+
+fn check() -> Result<(), ()> {
+    Ok(())
+}
+
+fn never_used() {
+    let _res = fallible();
+    let _sum = pure();
+}
+
+fn overwritten() -> Result<u32, ()> {
+    let mut _res = fallible();
+    _res = fallible();
+    _res
+}
+
+fn early_exit(flag: bool) -> Result<u32, ()> {
+    let res = fallible();
+    check()?;
+    if flag {
+        return Err(());
+    }
+    res
+}
+
+fn in_loop() {
+    for _ in 0..2 {
+        let res = fallible();
+        if res.is_err() {
+            break;
+        }
+        let other = fallible();
+        if pure() == 1 {
+            continue;
+        }
+        other.unwrap();
+    }
+}
+
+fn should_not_lint(m: &Mutex<u32>, flag: bool) -> Result<u32, ()> {
+    // used
+    let res = fallible();
+    let _ = res?;
+    // dropping a pure value on an early exit is fine
+    let sum = pure();
+    if flag {
+        return Ok(0);
+    }
+    // held for its `Drop` impl
+    let _guard = m.lock().unwrap();
+    let _lock = m.lock();
+    // lazy
+    let iter = vec![1, 2].into_iter().map(|x| x + 1);
+    check()?;
+    // the `break` doesn't leave the block
+    let res = fallible();
+    loop {
+        break;
+    }
+    // the `return` is in a closure
+    let _f = || -> Result<(), ()> { Err(()) };
+    let _g = || {
+        return;
+    };
+    let total = res? + sum + iter.sum::<u32>();
+    Ok(total)
+}
+
+fn branches(flag: bool) -> Result<u32, ()> {
+    let res = fallible();
+    if flag {
+        res?;
+    } else {
+        return Err(());
+    }
+    let res = fallible();
+    match check() {
+        Ok(()) => res?,
+        Err(()) => return Err(()),
+    };
+    Ok(0)
+}
+
+fn labeled_break() {
+    'outer: for _ in 0..2 {
+        let res = fallible();
+        for _ in 0..2 {
+            if pure() == 1 {
+                break 'outer;
+            }
+        }
+        res.unwrap();
+    }
+}
+
+fn should_not_lint_paths(flag: bool) -> Result<u32, ()> {
+    // the labeled `break` doesn't leave the block
+    let res = fallible();
+    'inner: for _ in 0..2 {
+        for _ in 0..2 {
+            break 'inner;
+        }
+    }
+    // read on both paths
+    let other = fallible();
+    if flag {
+        other?;
+    } else {
+        other.unwrap();
+    }
+    // read before the `return`
+    let last = fallible();
+    if flag {
+        last?;
+        return Ok(0);
+    }
+    res
+}
+
+fn main() {}
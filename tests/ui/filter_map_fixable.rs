@@ -0,0 +1,18 @@
+// run-rustfix
+
+#![warn(clippy::filter_map)]
+#![allow(unused)]
+
+struct Item {
+    ok: bool,
+    value: u32,
+}
+
+fn main() {
+    let _: Vec<_> = (0..4).filter(|&x| x > 1).map(|x| x * 2).collect();
+    let items = vec![Item { ok: true, value: 1 }];
+    let _: Vec<_> = items.into_iter().filter(|x| x.ok).map(|x| x.value).collect();
+    // the method may be implemented for the reference only
+    let names = vec![String::new()];
+    let _: Vec<_> = names.iter().filter(|s| s.is_empty()).map(|s| s.len()).collect();
+}
@@ -21,4 +21,9 @@ fn main() {
         .filter_map(|x| x.checked_mul(2))
         .map(|x| x.checked_mul(2))
         .collect();
+
+    // the closures bind different names
+    let _: Vec<_> = vec![5; 6].into_iter().filter(|&x| x == 0).map(|y| y * 2).collect();
+    // the condition dereferences the parameter
+    let _: Vec<_> = [5; 6].iter().filter(|x| **x == 0).map(|x| x * 2).collect();
 }
@@ -1,4 +1,5 @@
 #![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::match_like_matches_macro)]
 
 #[derive(Debug, Copy, Clone)]
 enum Flavor {
@@ -1,5 +1,6 @@
 // edition:2018
 #![warn(clippy::future_not_send)]
+#![allow(clippy::manual_async_fn)]
 
 use std::cell::Cell;
 use std::rc::Rc;
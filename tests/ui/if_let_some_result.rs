@@ -1,6 +1,7 @@
 // run-rustfix
 
 #![warn(clippy::if_let_some_result)]
+#![allow(clippy::manual_unwrap_or)]
 
 fn str_to_int(x: &str) -> i32 {
     if let Some(y) = x.parse().ok() {
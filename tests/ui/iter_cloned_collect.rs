@@ -23,3 +23,9 @@ fn main() {
             .collect();
     }
 }
+
+#[allow(clippy::map_clone)]
+fn clone_closure() {
+    let v = [1, 2, 3];
+    let _: Vec<isize> = v.iter().map(|x| x.clone()).collect();
+}
@@ -0,0 +1,31 @@
+// edition:2018
+#![warn(clippy::large_futures)]
+
+async fn nop() {}
+
+async fn big() {
+    let buffer = [0_u8; 20000];
+    nop().await;
+    drop(buffer);
+}
+
+async fn small() {
+    let buffer = [0_u8; 200];
+    nop().await;
+    drop(buffer);
+}
+
+// not held across an `.await`
+async fn not_held() {
+    {
+        let buffer = [0_u8; 20000];
+        drop(buffer);
+    }
+    nop().await;
+}
+
+fn main() {
+    let _ = big();
+    let _ = small();
+    let _ = not_held();
+}
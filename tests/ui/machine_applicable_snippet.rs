@@ -0,0 +1,105 @@
+#![deny(clippy::internal)]
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_lint;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::ast::Expr;
+use rustc_errors::Applicability;
+use rustc_lint::{EarlyContext, EarlyLintPass, Lint, LintContext};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::source_map::Span;
+
+#[allow(unused_variables)]
+fn span_lint_and_sugg<'a, T: LintContext>(
+    cx: &'a T,
+    lint: &'static Lint,
+    sp: Span,
+    msg: &str,
+    help: &str,
+    sugg: String,
+    applicability: Applicability,
+) {
+}
+
+#[allow(unused_variables)]
+fn snippet<'a, T: LintContext>(cx: &T, span: Span, default: &'a str) -> &'a str {
+    default
+}
+
+#[allow(unused_variables)]
+fn snippet_with_applicability<'a, T: LintContext>(
+    cx: &T,
+    span: Span,
+    default: &'a str,
+    applicability: &mut Applicability,
+) -> &'a str {
+    default
+}
+
+declare_tool_lint! {
+    pub clippy::TEST_LINT,
+    Warn,
+    "",
+    report_in_external_macro: true
+}
+
+declare_lint_pass!(Pass => [TEST_LINT]);
+
+impl EarlyLintPass for Pass {
+    fn check_expr(&mut self, cx: &EarlyContext, expr: &Expr) {
+        let lint_msg = "lint message";
+        let help_msg = "help message";
+
+        span_lint_and_sugg(
+            cx,
+            TEST_LINT,
+            expr.span,
+            lint_msg,
+            help_msg,
+            format!("&*{}", snippet(cx, expr.span, "..")),
+            Applicability::MachineApplicable,
+        );
+
+        let applicability = Applicability::MachineApplicable;
+        span_lint_and_sugg(
+            cx,
+            TEST_LINT,
+            expr.span,
+            lint_msg,
+            help_msg,
+            snippet(cx, expr.span, "..").to_string(),
+            applicability,
+        );
+
+        // These shouldn't trigger the lint.
+        span_lint_and_sugg(
+            cx,
+            TEST_LINT,
+            expr.span,
+            lint_msg,
+            help_msg,
+            format!("&*{}", snippet(cx, expr.span, "..")),
+            Applicability::MaybeIncorrect,
+        );
+
+        let mut applicability = Applicability::MachineApplicable;
+        span_lint_and_sugg(
+            cx,
+            TEST_LINT,
+            expr.span,
+            lint_msg,
+            help_msg,
+            format!(
+                "&*{}",
+                snippet_with_applicability(cx, expr.span, "..", &mut applicability)
+            ),
+            applicability,
+        );
+    }
+}
+
+fn main() {}
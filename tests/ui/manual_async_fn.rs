@@ -0,0 +1,60 @@
+// edition:2018
+// run-rustfix
+#![warn(clippy::manual_async_fn)]
+#![allow(unused)]
+
+use std::future::Future;
+
+fn fut() -> impl Future<Output = i32> {
+    async { 42 }
+}
+
+fn empty_fut() -> impl Future<Output = ()> {
+    async {}
+}
+
+fn core_fut() -> impl core::future::Future<Output = i32> {
+    async move { 42 }
+}
+
+fn with_block() -> impl Future<Output = i32> {
+    async {
+        let a = 42;
+        let b = 21;
+        a - b
+    }
+}
+
+fn elided(x: &i32) -> impl Future<Output = i32> + '_ {
+    async move { *x }
+}
+
+struct S;
+
+impl S {
+    pub fn method(&self, x: i32) -> impl Future<Output = i32> {
+        async move { x }
+    }
+}
+
+// has other statements
+fn setup() -> impl Future<Output = i32> {
+    let x = 42;
+    async move { x }
+}
+
+// the future must be `Send`
+fn send_fut() -> impl Future<Output = i32> + Send {
+    async { 42 }
+}
+
+// not an `async` block
+fn ready() -> impl Future<Output = i32> {
+    already_async()
+}
+
+async fn already_async() -> i32 {
+    42
+}
+
+fn main() {}
@@ -0,0 +1,107 @@
+// run-rustfix
+
+#![warn(clippy::all)]
+#![allow(dead_code, unused_mut, clippy::redundant_closure)]
+
+fn side_effect() -> i32 {
+    42
+}
+
+fn option() {
+    let x = Some(1);
+    let _ = match x {
+        Some(v) => Some(v + 1),
+        None => None,
+    };
+    let _ = if let Some(v) = x { v } else { 0 };
+    let _ = if let Some(v) = x { v } else { side_effect() };
+    let _ = match x {
+        Some(v) => {
+            if v > 0 {
+                Some(v)
+            } else {
+                None
+            }
+        },
+        None => None,
+    };
+    let _: Result<i32, &str> = match x {
+        Some(v) => Ok(v),
+        None => Err("none"),
+    };
+    let _ = match Some(Some(1)) {
+        Some(v) => v,
+        None => None,
+    };
+    let s: Option<String> = None;
+    let _ = match s {
+        Some(v) => v,
+        None => String::new(),
+    };
+}
+
+fn result() {
+    let x: Result<i32, ()> = Ok(1);
+    let _ = match x {
+        Ok(v) => Some(v),
+        Err(_) => None,
+    };
+    let _ = if let Ok(v) = x { v } else { 2 };
+    let _ = match x {
+        Ok(v) => v,
+        Err(_) => side_effect(),
+    };
+    let _ = if let Ok(v) = x { v } else { side_effect() };
+}
+
+fn binding_modes() {
+    let x = Some(1);
+    let _ = match x {
+        Some(mut v) => {
+            if v > 0 {
+                Some(v)
+            } else {
+                None
+            }
+        },
+        None => None,
+    };
+    let s = Some(String::new());
+    let _ = match s {
+        Some(_) => Some(1),
+        None => None,
+    };
+    let _ = s;
+}
+
+fn should_not_lint() {
+    let x = Some(1);
+    // can't be moved into a closure
+    let _ = match x {
+        Some(v) => v,
+        None => return,
+    };
+    // diverges
+    let _ = match x {
+        Some(v) => v,
+        None => panic!("no value"),
+    };
+    // has a guard
+    let _ = match x {
+        Some(v) if v > 0 => v,
+        _ => 0,
+    };
+    // binds by reference
+    let _ = match &x {
+        Some(v) => Some(*v + 1),
+        None => None,
+    };
+    // uses the error
+    let y: Result<i32, i32> = Ok(1);
+    let _ = match y {
+        Ok(v) => v,
+        Err(e) => e,
+    };
+}
+
+fn main() {}
@@ -0,0 +1,162 @@
+#![warn(clippy::manual_iterator_loop)]
+#![allow(clippy::needless_range_loop, clippy::explicit_counter_loop)]
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn sum(v: Vec<i32>, points: Vec<Point>) {
+    let mut total = 0;
+    for x in &v {
+        total += x;
+    }
+    let _ = total;
+
+    let mut total: i64 = 0;
+    for p in &points {
+        total += i64::from(p.x * p.y);
+    }
+    let _ = total;
+
+    let mut total = 0;
+    for x in &v {
+        total += x;
+    }
+    total *= 2;
+    let _ = total;
+
+    // ok, the accumulator is used in the loop
+    let mut total = 1;
+    for x in &v {
+        total += total * x;
+    }
+    let _ = total;
+
+    // ok, not a sum
+    let mut total = 0;
+    for x in &v {
+        total -= x;
+    }
+    let _ = total;
+}
+
+fn any(v: Vec<i32>) {
+    let mut found = false;
+    for x in &v {
+        if *x > 10 {
+            found = true;
+            break;
+        }
+    }
+    let _ = found;
+
+    // ok, the loop does something else too
+    let mut found = false;
+    for x in &v {
+        if *x > 10 {
+            found = true;
+            println!("found");
+            break;
+        }
+    }
+    let _ = found;
+}
+
+fn position(v: Vec<i32>) {
+    let mut index = None;
+    for (i, x) in v.iter().enumerate() {
+        if *x == 3 {
+            index = Some(i);
+            break;
+        }
+    }
+    let _ = index;
+
+    // ok, the index is used in the condition
+    let mut index = None;
+    for (i, x) in v.iter().enumerate() {
+        if *x == i as i32 {
+            index = Some(i);
+            break;
+        }
+    }
+    let _ = index;
+}
+
+fn max_by_key(words: Vec<&str>) {
+    let mut longest = None;
+    for word in &words {
+        if longest.map_or(true, |l: &&str| word.len() >= l.len()) {
+            longest = Some(word);
+        }
+    }
+    let _ = longest;
+
+    let mut shortest = None;
+    for word in &words {
+        if shortest.map_or(true, |s: &&str| word.len() < s.len()) {
+            shortest = Some(word);
+        }
+    }
+    let _ = shortest;
+
+    // ok, `>` keeps the first maximum, unlike `max_by_key`
+    let mut longest = None;
+    for word in &words {
+        if longest.map_or(true, |l: &&str| word.len() > l.len()) {
+            longest = Some(word);
+        }
+    }
+    let _ = longest;
+}
+
+fn extend(v: Vec<i32>, w: &mut Vec<i32>) {
+    let mut squares = Vec::new();
+    for x in &v {
+        squares.push(x * x);
+    }
+
+    for x in v.iter().copied() {
+        w.push(x);
+    }
+
+    // ok, the vector is used while pushing
+    let mut sums = vec![0];
+    for x in &v {
+        sums.push(sums[sums.len() - 1] + x);
+    }
+}
+
+fn retain(v: &mut Vec<i32>) {
+    let mut i = 0;
+    while i < v.len() {
+        if v[i] % 2 == 0 {
+            v.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+
+    let mut i = 0;
+    while i < v.len() {
+        if !v[i].is_positive() {
+            v.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+
+    // ok, the index is needed after the loop
+    let mut i = 0;
+    while i < v.len() {
+        if v[i] == 0 {
+            v.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    let _ = i;
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#![warn(clippy::manual_strip)]
+
+fn main() {
+    let s = "--verbose";
+    let prefix = "--";
+    let _ = if s.starts_with(prefix) {
+        Some(&s[prefix.len()..])
+    } else {
+        None
+    };
+    let _ = if s.starts_with("--") {
+        Some(&s["--".len()..])
+    } else {
+        None
+    };
+
+    // slices off a different length
+    let _ = if s.starts_with(prefix) { Some(&s[1..]) } else { None };
+}
@@ -0,0 +1,54 @@
+// run-rustfix
+
+#![warn(clippy::match_collapsible_arms)]
+#![allow(clippy::single_match, dead_code, unused_variables)]
+
+enum Side {
+    Left,
+    Right,
+}
+
+fn main() {
+    let (a, b) = (true, Some(1));
+    let _ = match (a, b) {
+        (true, Some(_)) => 1,
+        (true, None) => 1,
+        (false, _) => 2,
+    };
+    let _ = match (b, a, 3) {
+        (Some(x), true, 3) => x,
+        (Some(x), false, 3) => x,
+        _ => 0,
+    };
+    let side = Side::Left;
+    match (1, side) {
+        (1, Side::Left) => {},
+        (1, Side::Right) => {},
+        _ => {},
+    }
+
+    // the bodies differ
+    let _ = match (a, b) {
+        (true, Some(_)) => 1,
+        (true, None) => 2,
+        (false, _) => 3,
+    };
+    // the patterns don't cover all values
+    let _ = match (a, b) {
+        (true, Some(1)) => 1,
+        (true, None) => 1,
+        _ => 3,
+    };
+    // the patterns differ in two elements
+    let _ = match (a, b) {
+        (true, Some(_)) => 1,
+        (false, None) => 1,
+        _ => 3,
+    };
+    // the element that differs binds a variable
+    let _ = match (a, b) {
+        (true, Some(x)) => 1,
+        (true, None) => 1,
+        _ => 3,
+    };
+}
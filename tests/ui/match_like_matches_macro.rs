@@ -0,0 +1,57 @@
+// run-rustfix
+
+#![warn(clippy::match_like_matches_macro)]
+#![allow(clippy::match_bool, dead_code, unreachable_patterns)]
+
+enum Shape {
+    Circle(f32),
+    Square(f32),
+    Line,
+}
+
+fn main() {
+    let c = 'a';
+    let _ = match c {
+        '0'..='9' => true,
+        _ => false,
+    };
+    let _ = if let 'a'..='z' = c { false } else { true };
+
+    let x = Some(5);
+    let _ = match x {
+        Some(0) => true,
+        _ => false,
+    };
+    let _ = match x {
+        Some(n) if n > 3 => false,
+        _ => true,
+    };
+    let _ = if let Some(1..=3) = x { true } else { false };
+
+    let shape = Shape::Line;
+    let _ = match (&shape, x) {
+        (Shape::Line, None) => true,
+        _ => false,
+    };
+
+    // no lint
+    let b = true;
+    let _ = match b {
+        true => false,
+        false => true,
+    };
+    let _ = match x {
+        Some(_) => true,
+        None => true,
+    };
+    let _ = match x {
+        _y => true,
+        _ => false,
+    };
+    // exhaustive matches have to be revisited when a variant is added
+    let _ = match shape {
+        Shape::Circle(_) | Shape::Square(_) => true,
+        Shape::Line => false,
+    };
+    let _ = matches!(x, Some(1));
+}
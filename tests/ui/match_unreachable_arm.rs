@@ -0,0 +1,52 @@
+#![warn(clippy::match_unreachable_arm)]
+#![allow(dead_code, unreachable_patterns)]
+
+enum E {
+    A(u8),
+    B,
+    C,
+}
+
+const YES: bool = true;
+
+fn main() {
+    let x = Some(3);
+    match x {
+        Some(_) if true => {},
+        Some(0) => {},
+        Some(1..=5) => {},
+        None => {},
+    }
+
+    let e = E::B;
+    match e {
+        E::A(0..=10) if true => {},
+        E::A(3) => {},
+        E::A(10..=20) => {},
+        E::A(_) => {},
+        E::B | E::C => {},
+    }
+
+    let t = (1, true);
+    match t {
+        (_, true) if true => {},
+        (1, true) | (2, true) => {},
+        (1, _) => {},
+        _ => {},
+    }
+
+    // the guard may be false
+    let flag = x.is_some();
+    match x {
+        Some(_) if flag => {},
+        Some(0) => {},
+        _ => {},
+    }
+
+    // constants are not evaluated
+    match x {
+        Some(_) if YES => {},
+        Some(0) => {},
+        _ => {},
+    }
+}
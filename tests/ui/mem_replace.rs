@@ -24,7 +24,44 @@ fn replace_with_default() {
     let _ = std::mem::replace(s, Default::default());
 }
 
+fn replace_with_default_module_path() {
+    let mut s = String::from("foo");
+    let _ = mem::replace(&mut s, String::default());
+}
+
+mod imported_replace {
+    use std::mem::replace;
+
+    pub fn replace_with_default() {
+        let mut s = String::from("foo");
+        let _ = replace(&mut s, String::default());
+    }
+}
+
+mod imported_replace_and_take {
+    use std::mem::{replace, take};
+
+    pub fn replace_with_default() {
+        let mut s = String::from("foo");
+        let _ = replace(&mut s, String::default());
+        let _ = take(&mut s);
+    }
+}
+
+mod glob_import {
+    use std::mem::*;
+
+    pub fn replace_with_default() {
+        let mut s = String::from("foo");
+        let _ = replace(&mut s, String::default());
+    }
+}
+
 fn main() {
     replace_option_with_none();
     replace_with_default();
+    replace_with_default_module_path();
+    imported_replace::replace_with_default();
+    imported_replace_and_take::replace_with_default();
+    glob_import::replace_with_default();
 }
@@ -46,6 +46,10 @@ fn tuples<U>(_m: &mut HashMap<((), U), ()>) {}
 
 fn tuples_bad<U>(_m: &mut HashMap<(Key, U), bool>) {}
 
+fn through_generics(_m: &HashSet<std::rc::Rc<std::cell::RefCell<usize>>>, _a: &HashMap<std::sync::Arc<Key>, u8>) {}
+
+fn through_generics_ok(_m: &HashSet<std::rc::Rc<String>>, _v: &HashMap<Vec<usize>, Key>) {}
+
 fn main() {
     let _ = should_not_take_this_arg(&mut HashMap::new(), 1);
     this_is_ok(&mut HashMap::new());
@@ -0,0 +1,60 @@
+#![warn(clippy::needless_collect)]
+#![allow(clippy::iter_cloned_collect)]
+
+use std::collections::{HashSet, LinkedList, VecDeque};
+
+fn count(sample: &[u32]) -> usize {
+    let indirect: Vec<_> = sample.iter().filter(|x| **x > 1).collect();
+    indirect.len()
+}
+
+fn for_loop(sample: &[u32]) {
+    let indirect: VecDeque<_> = sample.iter().map(|x| x + 1).collect();
+    for x in indirect {
+        println!("{}", x);
+    }
+}
+
+fn any(sample: &[u32]) -> bool {
+    let indirect = sample.iter().cloned().collect::<LinkedList<_>>();
+    indirect.iter().any(|x| *x == 1)
+}
+
+fn into_iterator(sample: &[u32], other: &mut Vec<u32>) {
+    let indirect: Vec<u32> = sample.iter().map(|x| x * 2).collect();
+    other.extend(indirect);
+}
+
+fn should_not_lint(sample: &[u32]) {
+    // used twice
+    let twice: Vec<_> = sample.iter().collect();
+    println!("{}", twice.len());
+    for x in twice {
+        println!("{}", x);
+    }
+
+    // used in a loop, the iterator would be consumed on the first iteration
+    let in_loop: Vec<_> = sample.iter().collect();
+    for _ in 0..3 {
+        println!("{}", in_loop.len());
+    }
+
+    // mutated before the use
+    let mut mutated: Vec<_> = sample.iter().collect();
+    mutated.push(&0);
+    for x in mutated {
+        println!("{}", x);
+    }
+
+    // not a sequence, the length can be different
+    let set: HashSet<_> = sample.iter().collect();
+    println!("{}", set.len());
+
+    // `iter` yields references, unlike the original iterator in a `for` loop
+    let by_ref: Vec<u32> = sample.iter().cloned().collect();
+    for x in by_ref.iter() {
+        println!("{}", x);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+// run-rustfix
+
+#![warn(clippy::redundant_guard)]
+#![allow(clippy::single_match, unused_variables)]
+
+fn main() {
+    let x = Some(1);
+    match x {
+        y @ Some(_) if y.is_some() => {},
+        _ => {},
+    }
+    match x {
+        Some(_) if x.is_some() => {},
+        None => {},
+    }
+    let r: Result<u8, ()> = Ok(1);
+    match r {
+        ok @ Ok(1..=3) if ok.is_ok() => {},
+        _ => {},
+    }
+    match (x, r) {
+        (a @ Some(1), b @ Err(_)) if matches!(a, Some(_)) => {},
+        _ => {},
+    }
+
+    // the guard tests something else
+    let z = Some(2);
+    match x {
+        Some(_) if z.is_some() => {},
+        _ => {},
+    }
+    // the guard tests a binding without subpattern
+    match x {
+        Some(y) if y == 1 => {},
+        _ => {},
+    }
+    // the guard can be false
+    match x {
+        y @ Some(_) if matches!(y, Some(1)) => {},
+        _ => {},
+    }
+    // `x` is shadowed in the arm
+    let x = Some(Some(1));
+    match x {
+        Some(x) if x.is_some() => {},
+        _ => {},
+    }
+}
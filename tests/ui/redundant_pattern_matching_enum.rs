@@ -0,0 +1,100 @@
+#![warn(clippy::redundant_pattern_matching)]
+#![allow(dead_code)]
+
+enum Shape {
+    Circle(f32),
+    Square { side: f32 },
+    Line,
+}
+
+impl Shape {
+    fn is_circle(&self) -> bool {
+        matches!(self, Self::Circle(_))
+    }
+
+    fn is_square(&self) -> bool {
+        matches!(self, Self::Square { .. })
+    }
+
+    fn is_line(self) -> bool {
+        matches!(self, Self::Line)
+    }
+}
+
+mod light {
+    pub enum Light {
+        Red,
+        Green,
+    }
+
+    impl Light {
+        fn is_red(&self) -> bool {
+            matches!(self, Self::Red)
+        }
+    }
+}
+
+fn main() {
+    let shape = Shape::Line;
+    if let Shape::Circle(_) = shape {}
+    if let Shape::Square { .. } = shape {}
+    let _ = match shape {
+        Shape::Circle(..) => true,
+        _ => false,
+    };
+    let _ = match shape {
+        Shape::Square { .. } => false,
+        _ => true,
+    };
+    if let Either::Left(_) = Either::<u8>::Right {}
+
+    // `is_line` takes `self` by value
+    if let Shape::Line = shape {}
+    // the pattern binds the radius
+    if let Shape::Circle(radius) = shape {
+        let _ = radius;
+    }
+    // `is_red` is private
+    let light = light::Light::Green;
+    if let light::Light::Red = light {}
+    // `is_open` takes `&mut self`
+    if let Door::Open = Door::Closed {}
+    // `is_closed` returns an `Option`
+    if let Door::Closed = Door::Open {}
+    // `is_left` is only implemented for `Either<u8>`
+    if let Either::Left(_) = Either::<u16>::Right {}
+    // `is_right` requires `T: Copy`
+    if let Either::Right = Either::<String>::Right {}
+}
+
+enum Door {
+    Open,
+    Closed,
+}
+
+impl Door {
+    fn is_open(&mut self) -> bool {
+        matches!(self, Self::Open)
+    }
+
+    fn is_closed(&self) -> Option<bool> {
+        Some(matches!(self, Self::Closed))
+    }
+}
+
+enum Either<T> {
+    Left(T),
+    Right,
+}
+
+impl Either<u8> {
+    fn is_left(&self) -> bool {
+        matches!(self, Self::Left(_))
+    }
+}
+
+impl<T: Copy> Either<T> {
+    fn is_right(&self) -> bool {
+        matches!(self, Self::Right)
+    }
+}
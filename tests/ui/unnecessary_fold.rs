@@ -49,4 +49,10 @@ fn unnecessary_fold_over_multiple_lines() {
         .fold(false, |acc, x| acc || x > 2);
 }
 
+/// Folds over floats and over references to numbers
+fn unnecessary_fold_numeric() {
+    let _: f64 = [1.0, 2.0].iter().fold(0.0, |acc, x| acc + x);
+    let _: f64 = [1.0, 2.0].iter().fold(1.0, |acc, x| acc * *x);
+}
+
 fn main() {}
@@ -0,0 +1,50 @@
+// run-rustfix
+
+#![feature(or_patterns)]
+#![warn(clippy::unnested_or_patterns)]
+
+fn f(x: u8) -> u8 {
+    x
+}
+
+fn main() {
+    let t = (0u8, 5u8);
+    let _ = match t {
+        (0, y) => f(y),
+        (1, y) => f(y),
+        (2..=4, y) => f(y),
+        _ => 0,
+    };
+    let x = Some(3);
+    let _ = match x {
+        Some(1) | Some(2) => 1,
+        _ => 0,
+    };
+    let e: Result<(u8, char), ()> = Ok((1, 'a'));
+    let _ = match e {
+        Ok((1, 'a')) | Ok((1, 'b')) | Ok((1, 'c')) => 1,
+        _ => 0,
+    };
+
+    // literals at the top level
+    let _ = match 3 {
+        1 | 2 => 1,
+        _ => 0,
+    };
+    // the patterns differ in two places
+    let _ = match t {
+        (0, 1) | (1, 0) => 1,
+        _ => 0,
+    };
+    // the bodies differ
+    let _ = match t {
+        (0, y) => f(y),
+        (1, y) => f(y + 1),
+        _ => 0,
+    };
+    // `&0 | 1` would not mean `&(0 | 1)`
+    let _ = match Some(&3) {
+        Some(&0) | Some(&1) => 1,
+        _ => 0,
+    };
+}
@@ -0,0 +1,32 @@
+// edition:2018
+#![warn(clippy::unpolled_async_block)]
+
+async fn report() {}
+
+async fn bad() {
+    let _ = async {
+        report().await;
+    };
+    let _unused = async move { 42 };
+    drop(async {
+        report().await;
+    });
+    std::mem::forget(async {});
+}
+
+async fn good() {
+    let task = async {
+        report().await;
+    };
+    task.await;
+    let _task = async { 1 };
+    let _ = _task.await;
+    // closures returning futures are not futures themselves
+    let _ = || async {};
+    async {}.await;
+}
+
+fn main() {
+    let _ = bad();
+    let _ = good();
+}
@@ -0,0 +1,32 @@
+#![warn(clippy::unsafe_sync_static)]
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Mutex;
+
+struct Counter(Cell<u32>);
+
+unsafe impl Sync for Counter {}
+
+struct Wrapper {
+    counter: Counter,
+    name: &'static str,
+}
+
+struct Handle(*const u8);
+
+unsafe impl Sync for Handle {}
+
+static COUNTER: Counter = Counter(Cell::new(0));
+static WRAPPED: Option<Wrapper> = None;
+
+// no interior mutability
+static HANDLE: Handle = Handle(std::ptr::null());
+// `Sync` through the std types
+static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+static LOCKED: Option<Mutex<Counter>> = None;
+// `static mut` doesn't need to be `Sync`
+static mut MUTABLE: Cell<u32> = Cell::new(0);
+
+fn main() {}
@@ -0,0 +1,36 @@
+// edition:2018
+#![warn(clippy::unused_async)]
+
+async fn foo() -> i32 {
+    4
+}
+
+async fn bar() -> i32 {
+    foo().await
+}
+
+struct S;
+
+impl S {
+    async fn method(&self) -> usize {
+        0
+    }
+
+    async fn awaiting(&self) -> usize {
+        self.method().await
+    }
+}
+
+// the `.await` is in a nested block, which is still polled by the function
+async fn nested() -> i32 {
+    let x = async { 1 };
+    x.await
+}
+
+fn main() {
+    let _ = foo();
+    let _ = bar();
+    let _ = S.method();
+    let _ = S.awaiting();
+    let _ = nested();
+}
@@ -0,0 +1,52 @@
+// run-rustfix
+
+#![feature(lint_reasons)]
+#![warn(clippy::unused_clippy_allow)]
+#![allow(dead_code)]
+
+struct S;
+
+impl S {
+    #[allow(clippy::needless_return)]
+    fn used() -> u32 {
+        return 1;
+    }
+
+    #[allow(clippy::needless_return)]
+    fn unused() -> u32 {
+        1
+    }
+
+    #[allow(clippy::needless_return, reason = "no early returns left")]
+    fn unused_with_reason() -> u32 {
+        1
+    }
+
+    #[allow(clippy::needless_return, reason = "the early return is clearer here")]
+    fn used_with_reason() -> u32 {
+        return 1;
+    }
+
+    #[allow(clippy::let_and_return, clippy::needless_return)]
+    fn partly_used() -> u32 {
+        return 1;
+    }
+
+    #[allow(clippy::needless_return, clippy::let_and_return)]
+    fn partly_used_last() -> u32 {
+        return 1;
+    }
+
+    #[allow(clippy::let_and_return, unused_variables, clippy::needless_return)]
+    fn rustc_lint_used() -> u32 {
+        let x = 1;
+        1
+    }
+}
+
+#[allow(clippy::unused_clippy_allow, clippy::needless_return)]
+fn allowed() -> u32 {
+    1
+}
+
+fn main() {}
@@ -1,10 +1,11 @@
 #!/usr/bin/env python
 
 import json
+import logging as log
 import os
 import sys
 
-from lintlib import log
+log.basicConfig(level=log.INFO, format='%(levelname)s: %(message)s')
 
 
 def key(v):